@@ -6,11 +6,14 @@ use log::{debug, info, warn};
 use reqwest::Client;
 use serde::Deserialize;
 use serde_json::Value;
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
 use tauri_plugin_notification::NotificationExt;
 use tokio::{io::AsyncBufReadExt, sync::Mutex};
 use tokio_util::io::StreamReader;
 
+use crate::commands::notifications::{
+    admit_notification, admit_prompt_notification, should_notify,
+};
 use crate::path_utils::expand_tilde_path;
 use crate::DesktopRuntime;
 
@@ -121,7 +124,9 @@ async fn run_once(
             data_lines.clear();
 
             match parse_event_envelope(&raw) {
-                Ok(event) => handle_event(app, event, notified_messages, notified_questions).await,
+                Ok(event) => {
+                    handle_event(app, runtime, event, notified_messages, notified_questions).await
+                }
                 Err(err) => {
                     warn!("[desktop:notify] Failed to parse SSE data: {err}; raw={raw}");
                 }
@@ -244,19 +249,20 @@ async fn try_connect_sse(
 
 async fn handle_event(
     app: &AppHandle,
+    runtime: &DesktopRuntime,
     event: EventEnvelope,
     notified_messages: &Mutex<HashSet<String>>,
     notified_questions: &Mutex<HashSet<String>>,
 ) {
     match event.event_type.as_str() {
         "message.updated" => {
-            handle_message_updated(app, &event.properties, notified_messages).await;
+            handle_message_updated(app, runtime, &event.properties, notified_messages).await;
         }
         "question.asked" => {
-            handle_question_asked(app, &event.properties, notified_questions).await;
+            handle_question_asked(app, runtime, &event.properties, notified_questions).await;
         }
         "permission.asked" => {
-            handle_permission_asked(app, &event.properties, notified_questions).await;
+            handle_permission_asked(app, runtime, &event.properties, notified_questions).await;
         }
         _ => {}
     }
@@ -264,6 +270,7 @@ async fn handle_event(
 
 async fn handle_question_asked(
     app: &AppHandle,
+    runtime: &DesktopRuntime,
     properties: &Value,
     notified_questions: &Mutex<HashSet<String>>,
 ) {
@@ -284,16 +291,7 @@ async fn handle_question_asked(
         notified.insert(key);
     }
 
-    let should_notify = app
-        .get_webview_window("main")
-        .map(|window| {
-            let focused = window.is_focused().unwrap_or(false);
-            let minimized = window.is_minimized().unwrap_or(false);
-            !focused || minimized
-        })
-        .unwrap_or(true);
-
-    if should_notify {
+    if should_notify(app, runtime).await {
         let (title, body) = properties
             .get("questions")
             .and_then(Value::as_array)
@@ -336,6 +334,8 @@ async fn handle_question_asked(
                 )
             });
 
+        admit_prompt_notification();
+
         let _ = app
             .notification()
             .builder()
@@ -348,6 +348,7 @@ async fn handle_question_asked(
 
 async fn handle_permission_asked(
     app: &AppHandle,
+    runtime: &DesktopRuntime,
     properties: &Value,
     notified_requests: &Mutex<HashSet<String>>,
 ) {
@@ -374,16 +375,9 @@ async fn handle_permission_asked(
         .filter(|s| !s.is_empty())
         .unwrap_or("Agent requested permission");
 
-    let should_notify = app
-        .get_webview_window("main")
-        .map(|window| {
-            let focused = window.is_focused().unwrap_or(false);
-            let minimized = window.is_minimized().unwrap_or(false);
-            !focused || minimized
-        })
-        .unwrap_or(true);
+    if should_notify(app, runtime).await {
+        admit_prompt_notification();
 
-    if should_notify {
         let _ = app
             .notification()
             .builder()
@@ -396,6 +390,7 @@ async fn handle_permission_asked(
 
 async fn handle_message_updated(
     app: &AppHandle,
+    runtime: &DesktopRuntime,
     properties: &Value,
     notified_messages: &Mutex<HashSet<String>>,
 ) {
@@ -440,17 +435,13 @@ async fn handle_message_updated(
     let title = format!("{} agent is ready", format_mode(raw_mode));
     let body = format!("{} completed the task", format_model_id(raw_model));
 
-    let should_notify = app
-        .get_webview_window("main")
-        .map(|window| {
-            let focused = window.is_focused().unwrap_or(false);
-            let minimized = window.is_minimized().unwrap_or(false);
-            // Only notify when the app is not in the foreground or is minimized
-            !focused || minimized
-        })
-        .unwrap_or(true);
-
-    if should_notify {
+    // By default only notify when the app is not in the foreground or is minimized
+    if should_notify(app, runtime).await {
+        let session_id = info.get("sessionID").and_then(Value::as_str);
+        let Some(body) = admit_notification(session_id, &body) else {
+            return;
+        };
+
         let _ = app
             .notification()
             .builder()
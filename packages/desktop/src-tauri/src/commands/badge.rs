@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{Emitter, Manager, Runtime, State, Window};
+
+use crate::DesktopRuntime;
+
+const BADGE_COUNTS_EVENT: &str = "openchamber:badge-counts";
+// Counts that are not attributed to a project land in this bucket.
+const UNASSIGNED_PROJECT_KEY: &str = "";
+const DEFAULT_BADGE_TRIGGERS: &[&str] = &["completed-runs"];
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BadgeCountsPayload {
+    pub total: u32,
+    pub projects: HashMap<String, u32>,
+}
+
+/// Dock badge counts per project. All updates, including the clear on focus, go through the
+/// same lock and apply the badge while holding it so a clear can't interleave with an increment.
+#[derive(Default)]
+pub struct BadgeState {
+    projects: Mutex<HashMap<String, u32>>,
+}
+
+impl BadgeState {
+    pub fn clear<R: Runtime>(&self, window: &Window<R>) {
+        let mut projects = self.projects.lock();
+        projects.clear();
+        apply_badge(window, &projects);
+    }
+
+    fn set<R: Runtime>(
+        &self,
+        window: &Window<R>,
+        project_id: Option<&str>,
+        count: Option<u32>,
+    ) -> BadgeCountsPayload {
+        let mut projects = self.projects.lock();
+        let key = project_id.unwrap_or(UNASSIGNED_PROJECT_KEY).to_string();
+        match count.filter(|value| *value > 0) {
+            Some(value) => {
+                projects.insert(key, value);
+            }
+            None => {
+                projects.remove(&key);
+            }
+        }
+        apply_badge(window, &projects)
+    }
+
+    fn increment<R: Runtime>(
+        &self,
+        window: &Window<R>,
+        project_id: Option<&str>,
+    ) -> BadgeCountsPayload {
+        let mut projects = self.projects.lock();
+        let key = project_id.unwrap_or(UNASSIGNED_PROJECT_KEY).to_string();
+        let entry = projects.entry(key).or_insert(0);
+        *entry = entry.saturating_add(1);
+        apply_badge(window, &projects)
+    }
+}
+
+fn apply_badge<R: Runtime>(
+    window: &Window<R>,
+    projects: &HashMap<String, u32>,
+) -> BadgeCountsPayload {
+    let total = projects
+        .values()
+        .fold(0u32, |acc, value| acc.saturating_add(*value));
+    let badge = if total > 0 { Some(total as i64) } else { None };
+    let _ = window.set_badge_count(badge);
+    crate::tray::set_badge_count(window.app_handle(), total);
+
+    let payload = BadgeCountsPayload {
+        total,
+        projects: projects.clone(),
+    };
+    let _ = window.emit(BADGE_COUNTS_EVENT, payload.clone());
+    payload
+}
+
+/// Set the badge count for a project (or the unassigned bucket). `None` or 0 clears it.
+#[tauri::command]
+pub async fn set_badge_count(
+    count: Option<u32>,
+    project_id: Option<String>,
+    window: Window,
+    badge: State<'_, BadgeState>,
+) -> Result<BadgeCountsPayload, String> {
+    Ok(badge.set(&window, project_id.as_deref(), count))
+}
+
+/// Increment the badge for `trigger` ("unread-messages", "completed-runs" or "errors") if the
+/// `badgeTriggers` setting enables it. Returns the updated counts, or `None` when ignored.
+#[tauri::command]
+pub async fn increment_badge_count(
+    trigger: String,
+    project_id: Option<String>,
+    window: Window,
+    badge: State<'_, BadgeState>,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Option<BadgeCountsPayload>, String> {
+    let settings = state.settings().load().await.unwrap_or(Value::Null);
+    let enabled = match settings.get("badgeTriggers").and_then(Value::as_array) {
+        Some(triggers) => triggers
+            .iter()
+            .filter_map(Value::as_str)
+            .any(|value| value == trigger),
+        None => DEFAULT_BADGE_TRIGGERS.contains(&trigger.as_str()),
+    };
+
+    if !enabled {
+        return Ok(None);
+    }
+
+    Ok(Some(badge.increment(&window, project_id.as_deref())))
+}
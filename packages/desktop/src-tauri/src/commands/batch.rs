@@ -0,0 +1,125 @@
+use futures_util::future::join_all;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::error::CommandError;
+use crate::commands::{diagnostics, git, github, sessions, settings, terminal, window};
+
+// Large enough for the startup burst; anything bigger should be separate calls.
+const MAX_BATCH_SIZE: usize = 16;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchRequest {
+    pub command: String,
+    /// Arguments keyed the same way as when invoking the command directly (camelCase).
+    #[serde(default)]
+    pub args: Value,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchResponse {
+    pub command: String,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<CommandError>,
+}
+
+/// Read a named argument; a missing key reads as `null`, so optional arguments may be omitted.
+fn arg<T: DeserializeOwned>(args: &Value, key: &str) -> Result<T, CommandError> {
+    let value = args.get(key).cloned().unwrap_or(Value::Null);
+    serde_json::from_value(value)
+        .map_err(|e| CommandError::invalid_argument(format!("Invalid argument '{}': {}", key, e)))
+}
+
+fn to_result<T: Serialize, E: Into<CommandError>>(
+    result: Result<T, E>,
+) -> Result<Value, CommandError> {
+    let value = result.map_err(Into::into)?;
+    serde_json::to_value(value).map_err(|e| format!("Failed to serialize result: {}", e).into())
+}
+
+/// Run one batched command. Only read-only commands are listed here; anything that changes
+/// state must be invoked on its own.
+async fn dispatch(app: &AppHandle, command: &str, args: &Value) -> Result<Value, CommandError> {
+    match command {
+        "desktop_server_info" => to_result(crate::desktop_server_info(app.state()).await),
+        "get_active_workspace" => to_result(settings::get_active_workspace(app.state()).await),
+        "github_auth_status" => to_result(github::github_auth_status(app.state()).await),
+        "check_is_git_repository" => {
+            to_result(git::check_is_git_repository(arg(args, "directory")?, app.state()).await)
+        }
+        "get_git_status" => {
+            to_result(git::get_git_status(arg(args, "directory")?, app.state()).await)
+        }
+        "get_staged_diff_stat" => {
+            to_result(git::get_staged_diff_stat(arg(args, "directory")?, app.state()).await)
+        }
+        "get_repo_context" => {
+            to_result(git::get_repo_context(arg(args, "directory")?, app.state()).await)
+        }
+        "get_git_branches" => {
+            to_result(git::get_git_branches(arg(args, "directory")?, app.state()).await)
+        }
+        "git_operation_state" => {
+            to_result(git::git_operation_state(arg(args, "directory")?, app.state()).await)
+        }
+        "list_git_remotes" => {
+            to_result(git::list_git_remotes(arg(args, "directory")?, app.state()).await)
+        }
+        "get_current_git_identity" => {
+            to_result(git::get_current_git_identity(arg(args, "directory")?, app.state()).await)
+        }
+        "get_git_identities" => to_result(git::get_git_identities().await),
+        "list_available_shells" => to_result(terminal::list_available_shells().await),
+        "get_session_activity" => to_result(sessions::get_session_activity(app.state()).await),
+        "get_window_state" => to_result(window::get_window_state(app.state()).await),
+        "get_connectivity_status" => {
+            to_result(diagnostics::get_connectivity_status(app.clone(), app.state()).await)
+        }
+        _ => Err(CommandError::invalid_argument(format!(
+            "Command '{}' is not allowed in a batch",
+            command
+        ))),
+    }
+}
+
+/// Run several read-only commands concurrently in one IPC round-trip. Each entry gets its
+/// own result or error, in request order; one failing entry does not fail the batch.
+#[tauri::command]
+pub async fn batch(
+    app: AppHandle,
+    requests: Vec<BatchRequest>,
+) -> Result<Vec<BatchResponse>, CommandError> {
+    if requests.len() > MAX_BATCH_SIZE {
+        return Err(CommandError::invalid_argument(format!(
+            "Batch has {} requests (limit {})",
+            requests.len(),
+            MAX_BATCH_SIZE
+        )));
+    }
+
+    let app = &app;
+    let responses = join_all(requests.into_iter().map(|request| async move {
+        match dispatch(app, &request.command, &request.args).await {
+            Ok(result) => BatchResponse {
+                command: request.command,
+                ok: true,
+                result: Some(result),
+                error: None,
+            },
+            Err(error) => BatchResponse {
+                command: request.command,
+                ok: false,
+                result: None,
+                error: Some(error),
+            },
+        }
+    }))
+    .await;
+    Ok(responses)
+}
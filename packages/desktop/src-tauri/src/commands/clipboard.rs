@@ -0,0 +1,45 @@
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+// Generous enough for full diffs; beyond this the paste target is unlikely to cope anyway.
+const CLIPBOARD_MAX_BYTES: usize = 32 * 1024 * 1024;
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardCopyResult {
+    pub copied: bool,
+    /// Size of the text actually written, in bytes.
+    pub bytes: usize,
+}
+
+/// Write `text` to the system clipboard, bypassing the webview clipboard API.
+/// With `trim_trailing_whitespace`, trailing whitespace is dropped before copying.
+#[tauri::command]
+pub async fn copy_to_clipboard(
+    text: String,
+    trim_trailing_whitespace: Option<bool>,
+    app: AppHandle,
+) -> Result<ClipboardCopyResult, String> {
+    let text = if trim_trailing_whitespace.unwrap_or(false) {
+        text.trim_end().to_string()
+    } else {
+        text
+    };
+    if text.len() > CLIPBOARD_MAX_BYTES {
+        return Err(format!(
+            "Text is too large to copy ({} bytes, limit {} bytes)",
+            text.len(),
+            CLIPBOARD_MAX_BYTES
+        ));
+    }
+
+    let bytes = text.len();
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
+    Ok(ClipboardCopyResult {
+        copied: true,
+        bytes,
+    })
+}
@@ -0,0 +1,347 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, State};
+use tokio::{fs, net::TcpListener, process::Command};
+
+use crate::commands::github::resolve_auth_status;
+use crate::commands::settings::models_metadata_sources;
+use crate::connectivity::{refresh_connectivity, ConnectivityStatus};
+use crate::opencode_manager::check_cli_exists;
+use crate::{DesktopRuntime, MODELS_DEV_API_URL};
+
+const DIAGNOSTICS_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticCheck {
+    pub id: &'static str,
+    pub label: &'static str,
+    pub status: DiagnosticStatus,
+    pub message: String,
+    /// What the user can do about a warn/fail result.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl DiagnosticCheck {
+    fn pass(id: &'static str, label: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            id,
+            label,
+            status: DiagnosticStatus::Pass,
+            message: message.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(
+        id: &'static str,
+        label: &'static str,
+        message: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            id,
+            label,
+            status: DiagnosticStatus::Warn,
+            message: message.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(
+        id: &'static str,
+        label: &'static str,
+        message: impl Into<String>,
+        hint: impl Into<String>,
+    ) -> Self {
+        Self {
+            status: DiagnosticStatus::Fail,
+            ..Self::warn(id, label, message, hint)
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsReport {
+    /// Worst status across all checks.
+    pub status: DiagnosticStatus,
+    pub checks: Vec<DiagnosticCheck>,
+    /// Unix timestamp in milliseconds.
+    pub generated_at: i64,
+}
+
+async fn check_opencode_cli(state: &DesktopRuntime) -> DiagnosticCheck {
+    const ID: &str = "opencodeCli";
+    const LABEL: &str = "OpenCode CLI";
+
+    if !check_cli_exists() {
+        return DiagnosticCheck::fail(
+            ID,
+            LABEL,
+            "The opencode binary was not found",
+            "Install OpenCode, or set OPENCODE_BINARY to the full path of the CLI, then restart OpenChamber.",
+        );
+    }
+
+    let manager = state.opencode_manager();
+    let Some(version) = manager.cli_version().await else {
+        return DiagnosticCheck::warn(
+            ID,
+            LABEL,
+            "The opencode binary was found but `opencode --version` did not answer",
+            "Reinstall or update the OpenCode CLI.",
+        );
+    };
+
+    if manager.is_ready() {
+        return DiagnosticCheck::pass(ID, LABEL, format!("OpenCode {version} is running"));
+    }
+    match manager.last_error() {
+        Some(error) => DiagnosticCheck::fail(
+            ID,
+            LABEL,
+            format!("OpenCode {version} failed to start: {}", error.message),
+            "Restart OpenCode from the app menu and check the OpenCode logs for details.",
+        ),
+        None => DiagnosticCheck::warn(
+            ID,
+            LABEL,
+            format!("OpenCode {version} is installed but not running yet"),
+            "Wait for startup to finish, or restart OpenCode from the app menu.",
+        ),
+    }
+}
+
+async fn check_port_binding(state: &DesktopRuntime) -> DiagnosticCheck {
+    const ID: &str = "portBinding";
+    const LABEL: &str = "Local port binding";
+
+    if let Err(err) = TcpListener::bind(("127.0.0.1", 0)).await {
+        return DiagnosticCheck::fail(
+            ID,
+            LABEL,
+            format!("Could not open a port on 127.0.0.1: {err}"),
+            "Check that firewall or security software allows local (127.0.0.1) listeners.",
+        );
+    }
+
+    // A pinned port only matters while OpenCode isn't already listening on it.
+    let manager = state.opencode_manager();
+    if let Some(port) = manager.desired_port() {
+        if manager.current_port() != Some(port) {
+            if let Err(err) = TcpListener::bind(("127.0.0.1", port)).await {
+                return DiagnosticCheck::warn(
+                    ID,
+                    LABEL,
+                    format!("Port {port} from OPENCHAMBER_OPENCODE_PORT is unavailable: {err}"),
+                    format!("Free port {port} or unset OPENCHAMBER_OPENCODE_PORT."),
+                );
+            }
+        }
+    }
+
+    DiagnosticCheck::pass(ID, LABEL, "Local ports can be bound")
+}
+
+async fn check_models_metadata(settings: &Value) -> DiagnosticCheck {
+    const ID: &str = "modelsMetadata";
+    const LABEL: &str = "models.dev reachability";
+
+    let client = match reqwest::Client::builder()
+        .timeout(DIAGNOSTICS_PROBE_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(err) => {
+            return DiagnosticCheck::fail(
+                ID,
+                LABEL,
+                format!("Failed to create HTTP client: {err}"),
+                "Restart OpenChamber.",
+            )
+        }
+    };
+
+    let sources = models_metadata_sources(settings, MODELS_DEV_API_URL);
+    let mut errors = Vec::new();
+    for (index, source) in sources.iter().enumerate() {
+        // Only the status matters; the body is dropped unread.
+        let result = client
+            .get(source)
+            .header(reqwest::header::ACCEPT, "application/json")
+            .send()
+            .await;
+        match result {
+            Ok(response) if response.status().is_success() => {
+                if index == 0 {
+                    return DiagnosticCheck::pass(ID, LABEL, format!("{source} is reachable"));
+                }
+                return DiagnosticCheck::warn(
+                    ID,
+                    LABEL,
+                    format!("Primary source failed, mirror {source} is reachable"),
+                    "Check network or proxy settings for the primary models metadata URL.",
+                );
+            }
+            Ok(response) => errors.push(format!("{source}: status {}", response.status())),
+            Err(err) => errors.push(format!("{source}: {err}")),
+        }
+    }
+
+    DiagnosticCheck::warn(
+        ID,
+        LABEL,
+        format!("No models metadata source is reachable ({})", errors.join("; ")),
+        "Check your network or proxy settings, or configure modelsMetadataMirrors. Cached model data is used meanwhile.",
+    )
+}
+
+async fn check_config_dir(state: &DesktopRuntime) -> DiagnosticCheck {
+    const ID: &str = "configDir";
+    const LABEL: &str = "Config directory";
+
+    let Some(dir) = state.settings().config_dir() else {
+        return DiagnosticCheck::fail(
+            ID,
+            LABEL,
+            "Could not determine the config directory",
+            "Make sure your home directory is set and accessible.",
+        );
+    };
+    let hint = format!(
+        "Make sure {} exists and is writable by your user.",
+        dir.display()
+    );
+
+    if let Err(err) = fs::create_dir_all(dir).await {
+        return DiagnosticCheck::fail(
+            ID,
+            LABEL,
+            format!("Could not create {}: {err}", dir.display()),
+            hint,
+        );
+    }
+    let probe = dir.join(format!(".write-test-{}", uuid::Uuid::new_v4()));
+    if let Err(err) = fs::write(&probe, b"ok").await {
+        return DiagnosticCheck::fail(
+            ID,
+            LABEL,
+            format!("{} is not writable: {err}", dir.display()),
+            hint,
+        );
+    }
+    let _ = fs::remove_file(&probe).await;
+
+    DiagnosticCheck::pass(ID, LABEL, format!("{} is writable", dir.display()))
+}
+
+async fn check_git() -> DiagnosticCheck {
+    const ID: &str = "git";
+    const LABEL: &str = "Git";
+
+    let hint = if cfg!(target_os = "macos") {
+        "Install Git (for example with `xcode-select --install`) and make sure it is on your PATH."
+    } else {
+        "Install Git and make sure it is on your PATH."
+    };
+
+    let output = Command::new("git")
+        .arg("--version")
+        .stdin(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    match tokio::time::timeout(DIAGNOSTICS_PROBE_TIMEOUT, output).await {
+        Ok(Ok(output)) if output.status.success() => {
+            let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            DiagnosticCheck::pass(ID, LABEL, version)
+        }
+        Ok(Ok(output)) => DiagnosticCheck::fail(
+            ID,
+            LABEL,
+            format!(
+                "`git --version` failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            hint,
+        ),
+        Ok(Err(err)) => {
+            DiagnosticCheck::fail(ID, LABEL, format!("Git is not available: {err}"), hint)
+        }
+        Err(_) => DiagnosticCheck::fail(ID, LABEL, "`git --version` timed out", hint),
+    }
+}
+
+async fn check_github_auth() -> DiagnosticCheck {
+    const ID: &str = "githubAuth";
+    const LABEL: &str = "GitHub";
+
+    match resolve_auth_status().await {
+        Ok(status) if status.connected => {
+            let login = status.user.map(|user| user.login).unwrap_or_default();
+            DiagnosticCheck::pass(ID, LABEL, format!("Connected as {login}"))
+        }
+        Ok(_) => DiagnosticCheck::warn(
+            ID,
+            LABEL,
+            "Not connected",
+            "Connect a GitHub account in Settings to use pull request and issue features.",
+        ),
+        Err(err) => DiagnosticCheck::warn(
+            ID,
+            LABEL,
+            format!("Could not verify GitHub authentication: {err}"),
+            "Check that api.github.com is reachable from this machine.",
+        ),
+    }
+}
+
+/// Run the troubleshooting checks concurrently and return a pass/warn/fail report.
+#[tauri::command]
+pub async fn run_diagnostics(
+    state: State<'_, DesktopRuntime>,
+) -> Result<DiagnosticsReport, String> {
+    let settings = state.settings().load().await.unwrap_or(Value::Null);
+    let (opencode, port, models, config_dir, git, github) = tokio::join!(
+        check_opencode_cli(&state),
+        check_port_binding(&state),
+        check_models_metadata(&settings),
+        check_config_dir(&state),
+        check_git(),
+        check_github_auth(),
+    );
+
+    let checks = vec![opencode, port, models, config_dir, git, github];
+    let status = checks
+        .iter()
+        .map(|check| check.status)
+        .max()
+        .unwrap_or(DiagnosticStatus::Pass);
+
+    Ok(DiagnosticsReport {
+        status,
+        checks,
+        generated_at: chrono::Utc::now().timestamp_millis(),
+    })
+}
+
+/// OpenCode, models.dev, GitHub and network health in one report, for the offline banner.
+/// Changes are also pushed as `openchamber:connectivity-changed`.
+#[tauri::command]
+pub async fn get_connectivity_status(
+    app: AppHandle,
+    state: State<'_, DesktopRuntime>,
+) -> Result<ConnectivityStatus, String> {
+    Ok(refresh_connectivity(&app, &state).await)
+}
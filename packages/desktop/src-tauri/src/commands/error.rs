@@ -0,0 +1,92 @@
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Stable, machine-readable error kinds. The UI branches on these; the wording of
+/// `CommandError::message` is for people and may change.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// No GitHub account is connected.
+    NotConnected,
+    /// The stored credentials were rejected (expired or revoked).
+    Unauthorized,
+    /// Authenticated, but not allowed to do this.
+    Forbidden,
+    RateLimited,
+    /// The directory is not a git repository, or its remote is not a GitHub repository
+    /// we can access.
+    RepoNotFound,
+    NotFound,
+    InvalidArgument,
+    /// The operation conflicts with the current state (existing tag, unmerged files, ...).
+    Conflict,
+    Network,
+    /// git ran and failed; `message` carries its stderr.
+    GitFailed,
+    Internal,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandError {
+    pub code: ErrorCode,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+impl CommandError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn with_details(mut self, details: Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    pub fn invalid_argument(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidArgument, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// Errors that were never classified surface as `internal`.
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+}
+
+/// Keeps the code of a `CommandError` that was passed through `anyhow`.
+impl From<anyhow::Error> for CommandError {
+    fn from(err: anyhow::Error) -> Self {
+        match err.downcast::<CommandError>() {
+            Ok(err) => err,
+            Err(err) => Self::new(ErrorCode::Internal, err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_opener::OpenerExt;
+use url::Url;
+
+// Anything else (file://, custom app schemes) could launch local handlers.
+const ALLOWED_EXTERNAL_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+fn validate_external_url(raw: &str) -> Result<Url, String> {
+    let url = Url::parse(raw.trim()).map_err(|e| format!("Invalid URL: {}", e))?;
+    if !ALLOWED_EXTERNAL_SCHEMES.contains(&url.scheme()) {
+        return Err(format!(
+            "URL scheme \"{}\" is not allowed (expected http, https or mailto)",
+            url.scheme()
+        ));
+    }
+    if url.scheme() != "mailto" && url.host_str().unwrap_or_default().is_empty() {
+        return Err("URL is missing a host".to_string());
+    }
+    Ok(url)
+}
+
+/// Open `url` in the system browser or mail client after checking its scheme.
+pub(crate) fn open_external_url<R: Runtime>(app: &AppHandle<R>, url: &str) -> Result<(), String> {
+    let url = validate_external_url(url)?;
+    app.opener()
+        .open_url(url.as_str(), None::<&str>)
+        .map_err(|e| format!("Failed to open URL: {}", e))
+}
+
+/// Open an http, https or mailto link outside the app. Other schemes are rejected.
+#[tauri::command]
+pub async fn open_external(url: String, app: AppHandle) -> Result<(), String> {
+    open_external_url(&app, &url)
+}
@@ -1,18 +1,33 @@
+use crate::commands::error::{CommandError, ErrorCode};
 use crate::path_utils::expand_tilde_path;
 use crate::{DesktopRuntime, SettingsStore};
+use futures_util::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet, VecDeque},
     path::{Path, PathBuf},
     process::Command,
-    sync::OnceLock,
-    time::UNIX_EPOCH,
+    sync::{atomic::Ordering, OnceLock},
+    time::{Duration, Instant, UNIX_EPOCH},
+};
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
 };
-use tokio::fs;
 
 const DEFAULT_FILE_SEARCH_LIMIT: usize = 60;
 const MAX_FILE_SEARCH_LIMIT: usize = 400;
 const FILE_SEARCH_MAX_CONCURRENCY: usize = 5;
+const LIST_DIRECTORY_MAX_CONCURRENCY: usize = 32;
+const HASH_FILES_MAX_CONCURRENCY: usize = 8;
+const HASH_FILES_MAX_PATHS: usize = 1000;
+const HASH_FILE_BUFFER_BYTES: usize = 64 * 1024;
+// Format detection only looks at the start of large files.
+const FILE_FORMAT_SAMPLE_BYTES: usize = 1024 * 1024;
+const DEFAULT_DISK_USAGE_TOP: usize = 10;
+const MAX_DISK_USAGE_TOP: usize = 50;
+const DISK_USAGE_MAX_CONCURRENCY: usize = 8;
+const DISK_USAGE_TIME_BUDGET: Duration = Duration::from_secs(10);
 const FILE_SEARCH_EXCLUDED_DIRS: &[&str] = &[
     "node_modules",
     ".git",
@@ -34,6 +49,9 @@ pub struct FileListEntry {
     is_directory: bool,
     is_file: bool,
     is_symbolic_link: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symlink_target: Option<String>,
+    is_broken_symlink: bool,
     size: Option<u64>,
     modified_time: Option<i64>,
 }
@@ -81,6 +99,7 @@ pub struct SearchFilesResponse {
     root: String,
     count: usize,
     files: Vec<FileSearchHit>,
+    cancelled: bool,
 }
 
 #[derive(Debug)]
@@ -178,8 +197,10 @@ impl From<std::io::Error> for FsCommandError {
 pub async fn list_directory(
     path: Option<String>,
     respect_gitignore: Option<bool>,
+    follow_symlinks: Option<bool>,
     state: tauri::State<'_, DesktopRuntime>,
 ) -> Result<DirectoryListResult, String> {
+    let follow_symlinks = follow_symlinks.unwrap_or(true);
     let (workspace_roots, default_root) = resolve_workspace_roots(state.settings()).await;
     let resolved_path = resolve_sandboxed_path(path, &workspace_roots, default_root.as_ref())
         .await
@@ -210,7 +231,6 @@ pub async fn list_directory(
         return Err(FsCommandError::OutsideWorkspace.to_list_message());
     }
 
-    let mut entries = Vec::new();
     let mut dir_entries = fs::read_dir(&resolved_path)
         .await
         .map_err(|err| FsCommandError::from(err).to_list_message())?;
@@ -257,70 +277,254 @@ pub async fn list_directory(
         HashSet::new()
     };
 
-    for (entry, name) in all_entries {
-        // Skip gitignored entries
-        if !ignored_names.is_empty() && ignored_names.contains(&name) {
-            continue;
-        }
+    // Skip gitignored entries
+    let visible_entries: Vec<(tokio::fs::DirEntry, String)> = all_entries
+        .into_iter()
+        .filter(|(_, name)| ignored_names.is_empty() || !ignored_names.contains(name))
+        .collect();
 
-        let file_type = entry
-            .file_type()
-            .await
-            .map_err(|err| FsCommandError::from(err).to_list_message())?;
+    let entries = build_list_entries(visible_entries, follow_symlinks).await?;
 
-        let entry_path = entry.path();
+    Ok(DirectoryListResult {
+        directory: normalize_path(&resolved_path),
+        path: normalize_path(&resolved_path),
+        entries,
+    })
+}
 
-        let mut is_directory = file_type.is_dir();
-        let is_symlink = file_type.is_symlink();
+/// Stat entries concurrently and return them sorted by name.
+async fn build_list_entries(
+    entries: Vec<(tokio::fs::DirEntry, String)>,
+    follow_symlinks: bool,
+) -> Result<Vec<FileListEntry>, String> {
+    // `buffered` keeps results in input order.
+    let results: Vec<Result<FileListEntry, String>> = stream::iter(entries)
+        .map(|(entry, name)| async move {
+            let file_type = entry
+                .file_type()
+                .await
+                .map_err(|err| FsCommandError::from(err).to_list_message())?;
+            Ok(build_list_entry(entry.path(), name, file_type, follow_symlinks).await)
+        })
+        .buffered(LIST_DIRECTORY_MAX_CONCURRENCY)
+        .collect()
+        .await;
 
-        if !is_directory && is_symlink {
-            if let Ok(link_meta) = fs::metadata(&entry_path).await {
-                is_directory = link_meta.is_dir();
-            }
+    let mut entries = results.into_iter().collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
+
+async fn build_list_entry(
+    entry_path: PathBuf,
+    name: String,
+    file_type: std::fs::FileType,
+    follow_symlinks: bool,
+) -> FileListEntry {
+    let mut is_directory = file_type.is_dir();
+    let is_symlink = file_type.is_symlink();
+    let mut symlink_target = None;
+    let mut is_broken_symlink = false;
+
+    // Metadata through the link; a failure here means the link is dangling or cyclic.
+    let followed = if is_symlink {
+        if let Ok(target) = fs::read_link(&entry_path).await {
+            let resolved = if target.is_absolute() {
+                target
+            } else {
+                entry_path
+                    .parent()
+                    .map(|parent| parent.join(&target))
+                    .unwrap_or(target)
+            };
+            symlink_target = Some(normalize_path(&resolved));
         }
+        let followed = fs::metadata(&entry_path).await.ok();
+        is_broken_symlink = followed.is_none();
+        followed
+    } else {
+        None
+    };
 
-        let metadata = fs::metadata(&entry_path).await.ok();
-        let size = metadata
-            .as_ref()
-            .filter(|meta| meta.is_file())
-            .map(|meta| meta.len());
-        let modified_time = metadata
-            .and_then(|meta| meta.modified().ok())
-            .and_then(|mtime| mtime.duration_since(UNIX_EPOCH).ok())
-            .map(|duration| duration.as_millis() as i64);
-
-        entries.push(FileListEntry {
-            name,
-            path: normalize_path(&entry_path),
-            is_directory,
-            is_file: file_type.is_file(),
-            is_symbolic_link: is_symlink,
-            size,
-            modified_time,
-        });
+    if is_symlink && follow_symlinks {
+        if let Some(link_meta) = followed.as_ref() {
+            is_directory = link_meta.is_dir();
+        }
     }
 
-    Ok(DirectoryListResult {
-        directory: normalize_path(&resolved_path),
+    let metadata = if is_symlink && follow_symlinks {
+        followed
+    } else if is_symlink {
+        fs::symlink_metadata(&entry_path).await.ok()
+    } else {
+        fs::metadata(&entry_path).await.ok()
+    };
+    let size = metadata
+        .as_ref()
+        .filter(|meta| meta.is_file())
+        .map(|meta| meta.len());
+    let modified_time = metadata
+        .and_then(|meta| meta.modified().ok())
+        .and_then(|mtime| mtime.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as i64);
+
+    FileListEntry {
+        name,
+        path: normalize_path(&entry_path),
+        is_directory,
+        is_file: file_type.is_file(),
+        is_symbolic_link: is_symlink,
+        symlink_target,
+        is_broken_symlink,
+        size,
+        modified_time,
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchDirectoryResponse {
+    success: bool,
+    path: String,
+    already_watching: bool,
+}
+
+/// Watch a directory inside the workspace and emit `openchamber:fs-changed` events.
+#[tauri::command]
+pub async fn watch_directory(
+    path: String,
+    recursive: Option<bool>,
+    app: tauri::AppHandle,
+    state: tauri::State<'_, DesktopRuntime>,
+) -> Result<WatchDirectoryResponse, String> {
+    let (workspace_roots, default_root) = resolve_workspace_roots(state.settings()).await;
+    let resolved_path = resolve_sandboxed_path(Some(path), &workspace_roots, default_root.as_ref())
+        .await
+        .map_err(|err| err.to_list_message())?;
+
+    let metadata = fs::metadata(&resolved_path)
+        .await
+        .map_err(|err| FsCommandError::from(err).to_list_message())?;
+    if !metadata.is_dir() {
+        return Err(FsCommandError::NotDirectory.to_list_message());
+    }
+
+    let started =
+        crate::fs_watcher::watch_directory(app, resolved_path.clone(), recursive.unwrap_or(false))?;
+
+    Ok(WatchDirectoryResponse {
+        success: true,
         path: normalize_path(&resolved_path),
-        entries,
+        already_watching: !started,
+    })
+}
+
+#[tauri::command]
+pub async fn unwatch_directory(path: String) -> Result<bool, String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("Path is required".to_string());
+    }
+
+    let expanded = expand_tilde_path(trimmed);
+    let resolved = fs::canonicalize(&expanded).await.unwrap_or(expanded);
+    Ok(crate::fs_watcher::unwatch_directory(&resolved))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TailFileResponse {
+    id: u64,
+    path: String,
+    /// Size of the file when the tail started; only bytes after it are streamed.
+    offset: u64,
+}
+
+/// Follow a file inside the workspace like `tail -f`, emitting appended lines as
+/// `openchamber:file-tail` events until `stop_tail` is called or the window reloads or closes.
+#[tauri::command]
+pub async fn tail_file(
+    path: String,
+    app: tauri::AppHandle,
+    window: tauri::WebviewWindow,
+    state: tauri::State<'_, DesktopRuntime>,
+) -> Result<TailFileResponse, String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("Path is required".to_string());
+    }
+
+    let (workspace_roots, default_root) = resolve_workspace_roots(state.settings()).await;
+    let resolved_path = resolve_sandboxed_path(
+        Some(trimmed.to_string()),
+        &workspace_roots,
+        default_root.as_ref(),
+    )
+    .await
+    .map_err(|_| "File not found or access denied".to_string())?;
+
+    let metadata = fs::metadata(&resolved_path)
+        .await
+        .map_err(|_| "File not found".to_string())?;
+    if !metadata.is_file() {
+        return Err("Specified path is not a file".to_string());
+    }
+
+    let offset = metadata.len();
+    let id = crate::file_tail::start_tail(
+        app,
+        window.label().to_string(),
+        resolved_path.clone(),
+        offset,
+    )?;
+    Ok(TailFileResponse {
+        id,
+        path: normalize_path(&resolved_path),
+        offset,
     })
 }
 
+#[tauri::command]
+pub async fn stop_tail(id: u64) -> Result<bool, String> {
+    Ok(crate::file_tail::stop_tail(id))
+}
+
 struct ScoredFileHit {
     hit: FileSearchHit,
     score: i32,
 }
 
+/// Optional knobs of `search_files`; every field may be omitted.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchFilesOptions {
+    max_results: Option<usize>,
+    include_hidden: Option<bool>,
+    respect_gitignore: Option<bool>,
+    max_depth: Option<usize>,
+    follow_symlinks: Option<bool>,
+    extensions: Option<Vec<String>>,
+    glob: Option<String>,
+    search_id: Option<String>,
+}
+
 #[tauri::command]
 pub async fn search_files(
     directory: Option<String>,
     query: Option<String>,
-    max_results: Option<usize>,
-    include_hidden: Option<bool>,
-    respect_gitignore: Option<bool>,
+    options: Option<SearchFilesOptions>,
     state: tauri::State<'_, DesktopRuntime>,
 ) -> Result<SearchFilesResponse, String> {
+    let SearchFilesOptions {
+        max_results,
+        include_hidden,
+        respect_gitignore,
+        max_depth,
+        follow_symlinks,
+        extensions,
+        glob,
+        search_id,
+    } = options.unwrap_or_default();
     let (workspace_roots, default_root) = resolve_workspace_roots(state.settings()).await;
     let resolved_root = resolve_sandboxed_path(directory, &workspace_roots, default_root.as_ref())
         .await
@@ -331,6 +535,29 @@ pub async fn search_files(
     let match_all = normalized_query.is_empty();
     let include_hidden = include_hidden.unwrap_or(false);
     let respect_gitignore = respect_gitignore.unwrap_or(true);
+    // Depth is counted from the search root (0); unbounded unless the caller asks otherwise.
+    let max_depth = max_depth.unwrap_or(usize::MAX);
+    // Symlinked directories can form cycles or point outside the workspace, so skip them
+    // unless explicitly requested.
+    let follow_symlinks = follow_symlinks.unwrap_or(false);
+    // Extension and glob filters narrow the candidate set before scoring; when a query is also
+    // given it only ranks the files that passed the filters.
+    let extension_filter = normalize_extension_filter(extensions);
+    let glob_filter = match glob
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        Some(pattern) => {
+            Some(glob::Pattern::new(pattern).map_err(|e| format!("Invalid glob pattern: {}", e))?)
+        }
+        None => None,
+    };
+    let glob_options = glob::MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
 
     // Collect more candidates for fuzzy matching, then sort and trim
     let collect_limit = if match_all {
@@ -339,16 +566,36 @@ pub async fn search_files(
         (limit * 3).max(200)
     };
 
+    // A search started with an id can be stopped via `cancel_search`; it then returns the
+    // candidates collected so far.
+    let search_id = search_id.filter(|id| !id.trim().is_empty());
+    let cancel_token = search_id.as_deref().map(|id| state.begin_search(id));
+    let is_cancelled = || {
+        cancel_token
+            .as_ref()
+            .is_some_and(|token| token.load(Ordering::Relaxed))
+    };
+    let mut cancelled = false;
+
     let mut candidates: Vec<ScoredFileHit> = Vec::new();
-    let mut queue = VecDeque::new();
+    let mut queue: VecDeque<(PathBuf, usize)> = VecDeque::new();
     let mut visited = HashSet::new();
 
-    queue.push_back(resolved_root.clone());
-    visited.insert(resolved_root.clone());
+    queue.push_back((resolved_root.clone(), 0));
+    visited.insert(
+        fs::canonicalize(&resolved_root)
+            .await
+            .unwrap_or_else(|_| resolved_root.clone()),
+    );
 
-    while !queue.is_empty() && candidates.len() < collect_limit {
+    'search: while !queue.is_empty() && candidates.len() < collect_limit {
         for _ in 0..FILE_SEARCH_MAX_CONCURRENCY {
-            let Some(dir) = queue.pop_front() else {
+            if is_cancelled() {
+                cancelled = true;
+                break 'search;
+            }
+
+            let Some((dir, depth)) = queue.pop_front() else {
                 break;
             };
 
@@ -408,17 +655,44 @@ pub async fn search_files(
                 }
 
                 let entry_path = entry.path();
-                if file_type.is_dir() {
-                    if should_skip_directory(name_str, include_hidden) {
+                let is_symlink = file_type.is_symlink();
+                let (is_dir, is_file) = if is_symlink {
+                    if !follow_symlinks {
+                        continue;
+                    }
+                    match fs::metadata(&entry_path).await {
+                        Ok(metadata) => (metadata.is_dir(), metadata.is_file()),
+                        Err(_) => continue,
+                    }
+                } else {
+                    (file_type.is_dir(), file_type.is_file())
+                };
+
+                if is_dir {
+                    if should_skip_directory(name_str, include_hidden)
+                        || depth >= max_depth
+                        || candidates.len() >= collect_limit
+                    {
+                        continue;
+                    }
+                    let Ok(canonical) = fs::canonicalize(&entry_path).await else {
+                        continue;
+                    };
+                    if is_symlink
+                        && !workspace_roots.is_empty()
+                        && !workspace_roots
+                            .iter()
+                            .any(|root| canonical.starts_with(root))
+                    {
                         continue;
                     }
-                    if visited.insert(entry_path.clone()) && candidates.len() < collect_limit {
-                        queue.push_back(entry_path);
+                    if visited.insert(canonical) {
+                        queue.push_back((entry_path, depth + 1));
                     }
                     continue;
                 }
 
-                if !file_type.is_file() {
+                if !is_file {
                     continue;
                 }
 
@@ -428,10 +702,26 @@ pub async fn search_files(
                     .and_then(|ext| ext.to_str())
                     .map(|ext| ext.to_lowercase());
 
+                if let Some(allowed) = extension_filter.as_ref() {
+                    if !extension
+                        .as_ref()
+                        .is_some_and(|ext| allowed.contains(ext.as_str()))
+                    {
+                        continue;
+                    }
+                }
+
+                let relative_path = relative_path.replace('\\', "/");
+                if let Some(pattern) = glob_filter.as_ref() {
+                    if !pattern.matches_with(&relative_path, glob_options) {
+                        continue;
+                    }
+                }
+
                 let hit = FileSearchHit {
                     name: name_str.to_string(),
                     path: normalize_path(&entry_path),
-                    relative_path: relative_path.replace('\\', "/"),
+                    relative_path: relative_path.clone(),
                     extension,
                 };
 
@@ -451,6 +741,10 @@ pub async fn search_files(
         }
     }
 
+    if let (Some(id), Some(token)) = (search_id.as_deref(), cancel_token.as_ref()) {
+        state.end_search(id, token);
+    }
+
     // Sort by score descending, then by path length, then alphabetically
     if !match_all {
         candidates.sort_by(|a, b| match b.score.cmp(&a.score) {
@@ -474,9 +768,20 @@ pub async fn search_files(
         root: normalize_path(&resolved_root),
         count: files.len(),
         files,
+        cancelled,
     })
 }
 
+/// Signal a running `search_files` call started with `search_id` to stop early. Returns
+/// `false` when no such search is active.
+#[tauri::command]
+pub async fn cancel_search(
+    search_id: String,
+    state: tauri::State<'_, DesktopRuntime>,
+) -> Result<bool, String> {
+    Ok(state.cancel_search(search_id.trim()))
+}
+
 #[tauri::command]
 pub async fn create_directory(
     path: String,
@@ -605,7 +910,7 @@ async fn resolve_sandboxed_path(
         .await
         .map_err(FsCommandError::from)?;
 
-    // Allow OpenChamber per-project config under ~/.config/openchamber.
+    // Allow OpenChamber per-project config under the OpenChamber config directory.
     if is_within_openchamber_user_config(&canonicalized) {
         return Ok(canonicalized);
     }
@@ -642,7 +947,7 @@ async fn resolve_creatable_path(
         fallback_root.join(candidate)
     };
 
-    // Allow OpenChamber per-project config under ~/.config/openchamber.
+    // Allow OpenChamber per-project config under the OpenChamber config directory.
     // Needed because Desktop FS commands are sandboxed to workspace roots.
     if is_within_openchamber_user_config(&absolute) {
         return Ok(absolute);
@@ -663,6 +968,28 @@ async fn resolve_creatable_path(
     Ok(absolute)
 }
 
+/// Where a file may be created at `path`: inside a workspace root or the OpenChamber config
+/// directory. For commands outside this module that write files.
+pub(crate) async fn resolve_workspace_creatable_path(
+    path: &str,
+    settings: &SettingsStore,
+) -> Result<PathBuf, CommandError> {
+    let (workspace_roots, default_root) = resolve_workspace_roots(settings).await;
+    resolve_creatable_path(path, &workspace_roots, default_root.as_ref())
+        .await
+        .map_err(|err| {
+            let code = match err {
+                FsCommandError::AccessDenied | FsCommandError::OutsideWorkspace => {
+                    ErrorCode::Forbidden
+                }
+                FsCommandError::NotFound => ErrorCode::NotFound,
+                FsCommandError::NotDirectory => ErrorCode::InvalidArgument,
+                FsCommandError::Other(_) => ErrorCode::Internal,
+            };
+            CommandError::new(code, err.to_create_message())
+        })
+}
+
 async fn resolve_workspace_roots(settings: &SettingsStore) -> (Vec<PathBuf>, Option<PathBuf>) {
     let mut roots: Vec<PathBuf> = Vec::new();
     let mut default_root: Option<PathBuf> = None;
@@ -732,7 +1059,8 @@ fn default_home_directory() -> PathBuf {
 }
 
 fn openchamber_user_config_root() -> PathBuf {
-    default_home_directory().join(".config").join("openchamber")
+    crate::path_utils::openchamber_config_dir()
+        .unwrap_or_else(|| default_home_directory().join(".config").join("openchamber"))
 }
 
 fn is_within_openchamber_user_config(path: &PathBuf) -> bool {
@@ -760,6 +1088,22 @@ fn clamp_search_limit(value: Option<usize>) -> usize {
     limit.clamp(1, MAX_FILE_SEARCH_LIMIT)
 }
 
+/// Lowercase and strip leading dots (`.rs` and `rs` are equivalent). `None` when no usable
+/// extensions were given, so the filter is skipped entirely.
+fn normalize_extension_filter(extensions: Option<Vec<String>>) -> Option<HashSet<String>> {
+    let normalized: HashSet<String> = extensions
+        .unwrap_or_default()
+        .iter()
+        .map(|ext| ext.trim().trim_start_matches('.').to_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .collect();
+    if normalized.is_empty() {
+        None
+    } else {
+        Some(normalized)
+    }
+}
+
 fn should_skip_directory(name: &str, include_hidden: bool) -> bool {
     if !include_hidden && name.starts_with('.') {
         return true;
@@ -769,6 +1113,26 @@ fn should_skip_directory(name: &str, include_hidden: bool) -> bool {
         .any(|dir| dir.eq_ignore_ascii_case(name))
 }
 
+/// Lowercase `value` one char per char, so indices line up with `value.chars()`.
+fn lowercase_chars(value: &str) -> Vec<char> {
+    // Lowercase per char (keeping the first char of multi-char mappings) so indices stay
+    // aligned with the original-case chars used for boundary detection.
+    value
+        .chars()
+        .map(|ch| ch.to_lowercase().next().unwrap_or(ch))
+        .collect()
+}
+
+fn is_word_boundary(original: &[char], idx: usize) -> bool {
+    if idx == 0 {
+        return true;
+    }
+    let (Some(prev), Some(current)) = (original.get(idx - 1), original.get(idx)) else {
+        return false;
+    };
+    matches!(*prev, '/' | '_' | '-' | '.' | ' ') || (current.is_uppercase() && prev.is_lowercase())
+}
+
 /// Fuzzy match scoring function.
 /// Returns Some(score) if the query fuzzy-matches the candidate, None otherwise.
 /// Higher scores indicate better matches.
@@ -777,21 +1141,20 @@ fn fuzzy_match_score(query: &str, candidate: &str) -> Option<i32> {
         return Some(0);
     }
 
-    let q: Vec<char> = query.to_lowercase().chars().collect();
-    let c: Vec<char> = candidate.to_lowercase().chars().collect();
-    let c_str = candidate.to_lowercase();
+    let q = lowercase_chars(query);
+    let original: Vec<char> = candidate.chars().collect();
+    let c = lowercase_chars(candidate);
 
     // Fast path: exact substring match gets high score
-    if c_str.contains(query) {
-        if let Some(idx) = c_str.find(query) {
-            let mut bonus: i32 = 0;
-            if idx == 0 {
-                bonus = 20;
-            } else if let Some(prev) = c.get(idx.saturating_sub(1)) {
-                if *prev == '/' || *prev == '_' || *prev == '-' || *prev == '.' || *prev == ' ' {
-                    bonus = 15;
-                }
-            }
+    if q.len() <= c.len() {
+        if let Some(idx) = c.windows(q.len()).position(|window| window == q.as_slice()) {
+            let bonus: i32 = if idx == 0 {
+                20
+            } else if is_word_boundary(&original, idx) {
+                15
+            } else {
+                0
+            };
             return Some(100 + bonus - (idx.min(20) as i32) - (c.len() as i32 / 5));
         }
     }
@@ -829,14 +1192,11 @@ fn fuzzy_match_score(query: &str, candidate: &str) -> Option<i32> {
                 score += (18 - idx as i32).max(0); // Prefer matches near start
                 score -= gap.min(10); // Penalize gaps
 
-                // Bonus for word boundary matches
+                // Bonus for word boundary matches (delimiters and camelCase humps)
                 if idx == 0 {
                     score += 12;
-                } else if let Some(prev) = c.get(idx - 1) {
-                    if *prev == '/' || *prev == '_' || *prev == '-' || *prev == '.' || *prev == ' '
-                    {
-                        score += 10;
-                    }
+                } else if is_word_boundary(&original, idx) {
+                    score += 10;
                 }
 
                 score += if consecutive > 0 { 12 } else { 0 }; // Bonus for consecutive matches
@@ -867,6 +1227,11 @@ fn relative_path(root: &Path, target: &Path) -> String {
 pub struct ReadFileResponse {
     content: String,
     path: String,
+    /// Set when the file was not UTF-8 and had to be decoded; pass it back to `write_file`
+    /// together with `has_bom` to save the file in the same format.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encoding: Option<&'static str>,
+    has_bom: bool,
 }
 
 #[derive(Serialize)]
@@ -913,6 +1278,14 @@ pub struct WriteFileResponse {
     path: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateFileResponse {
+    success: bool,
+    path: String,
+    size: u64,
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CommandResult {
@@ -958,13 +1331,31 @@ pub async fn read_file(
         return Err("Specified path is not a file".to_string());
     }
 
-    let content = fs::read_to_string(&resolved_path)
+    let bytes = fs::read(&resolved_path)
         .await
         .map_err(|err| format!("Failed to read file: {}", err))?;
+    // A UTF-8 BOM stays in the content, so it is written back as-is.
+    let (content, encoding, has_bom) = match String::from_utf8(bytes) {
+        Ok(content) => (content, None, false),
+        Err(err) => {
+            let bytes = err.into_bytes();
+            let (encoding, bom_len) = detect_text_encoding(&bytes);
+            if encoding == TextEncoding::Binary {
+                return Err("File is not a text file".to_string());
+            }
+            (
+                decode_text(&bytes[bom_len..], encoding),
+                Some(encoding.name()),
+                bom_len > 0,
+            )
+        }
+    };
 
     Ok(ReadFileResponse {
         content,
         path: normalize_path(&resolved_path),
+        encoding,
+        has_bom,
     })
 }
 
@@ -1016,16 +1407,26 @@ pub async fn read_file_binary(
     })
 }
 
+/// Write `content` to a file. `encoding` and `hasBom` as reported by `read_file` keep a
+/// non-UTF-8 file in its original encoding; without them the file is written as UTF-8.
 #[tauri::command]
 pub async fn write_file(
     path: String,
     content: String,
+    encoding: Option<String>,
+    has_bom: Option<bool>,
     state: tauri::State<'_, DesktopRuntime>,
 ) -> Result<WriteFileResponse, String> {
     let trimmed = path.trim();
     if trimmed.is_empty() {
         return Err("Path is required".to_string());
     }
+    let encoding = match encoding.as_deref().map(str::trim) {
+        None | Some("") => TextEncoding::Utf8,
+        Some(name) => TextEncoding::from_name(name)
+            .ok_or_else(|| format!("Unsupported encoding: {}", name))?,
+    };
+    let data = encode_text(&content, encoding, has_bom.unwrap_or(false))?;
 
     let (workspace_roots, default_root) = resolve_workspace_roots(state.settings()).await;
     let resolved_path = resolve_creatable_path(trimmed, &workspace_roots, default_root.as_ref())
@@ -1039,7 +1440,7 @@ pub async fn write_file(
             .map_err(|err| format!("Failed to create parent directory: {}", err))?;
     }
 
-    fs::write(&resolved_path, content)
+    fs::write(&resolved_path, data)
         .await
         .map_err(|err| format!("Failed to write file: {}", err))?;
 
@@ -1049,6 +1450,604 @@ pub async fn write_file(
     })
 }
 
+/// Create a file (and any missing parent directories). Existing files are left untouched
+/// unless `overwrite` is set.
+#[tauri::command]
+pub async fn create_file(
+    path: String,
+    contents: Option<String>,
+    overwrite: Option<bool>,
+    state: tauri::State<'_, DesktopRuntime>,
+) -> Result<CreateFileResponse, String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("Path is required".to_string());
+    }
+
+    let (workspace_roots, default_root) = resolve_workspace_roots(state.settings()).await;
+    let resolved_path = resolve_creatable_path(trimmed, &workspace_roots, default_root.as_ref())
+        .await
+        .map_err(|err| err.to_create_message())?;
+
+    if let Some(parent) = resolved_path.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .map_err(|err| format!("Failed to create parent directory: {}", err))?;
+    }
+
+    if let Ok(metadata) = fs::symlink_metadata(&resolved_path).await {
+        if metadata.is_dir() {
+            return Err("A directory already exists at this path".to_string());
+        }
+    }
+
+    let overwrite = overwrite.unwrap_or(false);
+    let mut options = fs::OpenOptions::new();
+    options.write(true);
+    if overwrite {
+        options.create(true).truncate(true);
+    } else {
+        // create_new fails atomically if something appeared since the check above.
+        options.create_new(true);
+    }
+
+    let mut file = options.open(&resolved_path).await.map_err(|err| {
+        if err.kind() == std::io::ErrorKind::AlreadyExists {
+            "File already exists".to_string()
+        } else {
+            format!("Failed to create file: {}", err)
+        }
+    })?;
+
+    let contents = contents.unwrap_or_default();
+    file.write_all(contents.as_bytes())
+        .await
+        .map_err(|err| format!("Failed to write file: {}", err))?;
+    file.flush()
+        .await
+        .map_err(|err| format!("Failed to write file: {}", err))?;
+
+    Ok(CreateFileResponse {
+        success: true,
+        path: normalize_path(&resolved_path),
+        size: contents.len() as u64,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+    Binary,
+}
+
+impl TextEncoding {
+    fn name(self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "utf-8",
+            TextEncoding::Utf16Le => "utf-16le",
+            TextEncoding::Utf16Be => "utf-16be",
+            TextEncoding::Latin1 => "latin-1",
+            TextEncoding::Binary => "binary",
+        }
+    }
+
+    /// Encodings `write_file` can produce.
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(TextEncoding::Utf8),
+            "utf-16le" => Some(TextEncoding::Utf16Le),
+            "utf-16be" => Some(TextEncoding::Utf16Be),
+            "latin-1" | "latin1" | "iso-8859-1" => Some(TextEncoding::Latin1),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileFormatResult {
+    path: String,
+    /// "utf-8", "utf-16le", "utf-16be", "latin-1" or "binary".
+    encoding: &'static str,
+    has_bom: bool,
+    /// Dominant style: "lf", "crlf", "cr", or "none" for files without line breaks.
+    line_ending: &'static str,
+    /// More than one line-ending style occurs.
+    mixed: bool,
+    has_trailing_newline: bool,
+    size: u64,
+    /// Only the first part of the file was inspected.
+    sampled: bool,
+}
+
+/// Best-effort encoding from the BOM, then UTF-8 validity, then NUL-byte patterns. Returns the
+/// encoding and the BOM length to skip.
+fn detect_text_encoding(bytes: &[u8]) -> (TextEncoding, usize) {
+    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return (TextEncoding::Utf8, 3);
+    }
+    if bytes.starts_with(&[0xFF, 0xFE]) {
+        return (TextEncoding::Utf16Le, 2);
+    }
+    if bytes.starts_with(&[0xFE, 0xFF]) {
+        return (TextEncoding::Utf16Be, 2);
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(_) => return (TextEncoding::Utf8, 0),
+        // A sample can end in the middle of a multi-byte character.
+        Err(err) if err.error_len().is_none() => return (TextEncoding::Utf8, 0),
+        Err(_) => {}
+    }
+
+    // BOM-less UTF-16 text is mostly ASCII, so every other byte is NUL.
+    let pairs = bytes.len() / 2;
+    if pairs > 0 {
+        let even_nuls = bytes.iter().step_by(2).filter(|b| **b == 0).count();
+        let odd_nuls = bytes.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+        if odd_nuls * 10 >= pairs * 7 && even_nuls * 10 < pairs {
+            return (TextEncoding::Utf16Le, 0);
+        }
+        if even_nuls * 10 >= pairs * 7 && odd_nuls * 10 < pairs {
+            return (TextEncoding::Utf16Be, 0);
+        }
+    }
+    if bytes.contains(&0) {
+        return (TextEncoding::Binary, 0);
+    }
+    (TextEncoding::Latin1, 0)
+}
+
+/// Decode `bytes` (without BOM), replacing invalid sequences.
+fn decode_text(bytes: &[u8], encoding: TextEncoding) -> String {
+    match encoding {
+        TextEncoding::Utf8 | TextEncoding::Binary => String::from_utf8_lossy(bytes).into_owned(),
+        TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|pair| {
+                    if encoding == TextEncoding::Utf16Le {
+                        u16::from_le_bytes([pair[0], pair[1]])
+                    } else {
+                        u16::from_be_bytes([pair[0], pair[1]])
+                    }
+                })
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        TextEncoding::Latin1 => bytes.iter().map(|byte| *byte as char).collect(),
+    }
+}
+
+/// Inverse of `decode_text`, prefixing the BOM for UTF-16 when `bom` is set. Fails when the
+/// text has characters the encoding cannot represent.
+fn encode_text(text: &str, encoding: TextEncoding, bom: bool) -> Result<Vec<u8>, String> {
+    match encoding {
+        TextEncoding::Utf8 | TextEncoding::Binary => Ok(text.as_bytes().to_vec()),
+        TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+            let mut bytes = Vec::with_capacity(text.len() * 2 + 2);
+            let units = bom.then_some(0xFEFF).into_iter().chain(text.encode_utf16());
+            for unit in units {
+                if encoding == TextEncoding::Utf16Le {
+                    bytes.extend_from_slice(&unit.to_le_bytes());
+                } else {
+                    bytes.extend_from_slice(&unit.to_be_bytes());
+                }
+            }
+            Ok(bytes)
+        }
+        TextEncoding::Latin1 => text
+            .chars()
+            .map(|c| u8::try_from(u32::from(c)).ok())
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| {
+                "The file is Latin-1 encoded and the content has characters it cannot store"
+                    .to_string()
+            }),
+    }
+}
+
+/// Dominant line-ending style and whether several styles are mixed.
+fn detect_line_endings(text: &str) -> (&'static str, bool) {
+    let (mut lf, mut crlf, mut cr) = (0usize, 0usize, 0usize);
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' if chars.peek() == Some(&'\n') => {
+                chars.next();
+                crlf += 1;
+            }
+            '\r' => cr += 1,
+            '\n' => lf += 1,
+            _ => {}
+        }
+    }
+
+    let styles = [(lf, "lf"), (crlf, "crlf"), (cr, "cr")];
+    let used = styles.iter().filter(|(count, _)| *count > 0).count();
+    let dominant = styles
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .max_by_key(|(count, _)| *count)
+        .map_or("none", |(_, name)| *name);
+    (dominant, used > 1)
+}
+
+/// Encoding, BOM, line-ending style and trailing newline of a file inside the workspace.
+#[tauri::command]
+pub async fn detect_file_format(
+    path: String,
+    state: tauri::State<'_, DesktopRuntime>,
+) -> Result<FileFormatResult, String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("Path is required".to_string());
+    }
+
+    let (workspace_roots, default_root) = resolve_workspace_roots(state.settings()).await;
+    let resolved_path = resolve_sandboxed_path(
+        Some(trimmed.to_string()),
+        &workspace_roots,
+        default_root.as_ref(),
+    )
+    .await
+    .map_err(|_| "File not found or access denied".to_string())?;
+
+    let file = fs::File::open(&resolved_path)
+        .await
+        .map_err(|_| "File not found".to_string())?;
+    let metadata = file
+        .metadata()
+        .await
+        .map_err(|err| format!("Failed to read file metadata: {}", err))?;
+    if !metadata.is_file() {
+        return Err("Specified path is not a file".to_string());
+    }
+
+    let mut sample = Vec::new();
+    file.take(FILE_FORMAT_SAMPLE_BYTES as u64)
+        .read_to_end(&mut sample)
+        .await
+        .map_err(|err| format!("Failed to read file: {}", err))?;
+    let sampled = metadata.len() > sample.len() as u64;
+
+    let (encoding, bom_len) = detect_text_encoding(&sample);
+    let text = decode_text(&sample[bom_len..], encoding);
+    let (line_ending, mixed) = detect_line_endings(&text);
+    // The real end of the file is only known when it was read completely.
+    let has_trailing_newline = if sampled {
+        let mut file = fs::File::open(&resolved_path)
+            .await
+            .map_err(|err| format!("Failed to read file: {}", err))?;
+        let mut tail = [0u8; 2];
+        file.seek(std::io::SeekFrom::End(-2))
+            .await
+            .map_err(|err| format!("Failed to read file: {}", err))?;
+        file.read_exact(&mut tail)
+            .await
+            .map_err(|err| format!("Failed to read file: {}", err))?;
+        match encoding {
+            TextEncoding::Utf16Le => tail == [b'\n', 0] || tail == [b'\r', 0],
+            TextEncoding::Utf16Be => tail == [0, b'\n'] || tail == [0, b'\r'],
+            _ => tail[1] == b'\n' || tail[1] == b'\r',
+        }
+    } else {
+        text.ends_with('\n') || text.ends_with('\r')
+    };
+
+    Ok(FileFormatResult {
+        path: normalize_path(&resolved_path),
+        encoding: encoding.name(),
+        has_bom: bom_len > 0,
+        line_ending,
+        mixed,
+        has_trailing_newline,
+        size: metadata.len(),
+        sampled,
+    })
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHashResult {
+    path: String,
+    /// Hex-encoded BLAKE3 digest of the file contents.
+    hash: String,
+    size: u64,
+    modified_time: Option<i64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHashEntry {
+    /// The path as requested, so results can be matched back to inputs.
+    requested_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<FileHashResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+async fn hash_sandboxed_file(
+    path: &str,
+    workspace_roots: &[PathBuf],
+    default_root: Option<&PathBuf>,
+) -> Result<FileHashResult, String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err("Path is required".to_string());
+    }
+
+    let resolved_path =
+        resolve_sandboxed_path(Some(trimmed.to_string()), workspace_roots, default_root)
+            .await
+            .map_err(|_| "File not found or access denied".to_string())?;
+
+    let mut file = fs::File::open(&resolved_path)
+        .await
+        .map_err(|_| "File not found".to_string())?;
+    let metadata = file
+        .metadata()
+        .await
+        .map_err(|err| format!("Failed to read file metadata: {}", err))?;
+    if !metadata.is_file() {
+        return Err("Specified path is not a file".to_string());
+    }
+
+    let mut hasher = blake3::Hasher::new();
+    let mut buffer = vec![0u8; HASH_FILE_BUFFER_BYTES];
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .await
+            .map_err(|err| format!("Failed to read file: {}", err))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    let modified_time = metadata
+        .modified()
+        .ok()
+        .and_then(|mtime| mtime.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as i64);
+
+    Ok(FileHashResult {
+        path: normalize_path(&resolved_path),
+        hash: hasher.finalize().to_hex().to_string(),
+        size: metadata.len(),
+        modified_time,
+    })
+}
+
+/// Content hash, size and mtime of a file inside the workspace, read in chunks so large
+/// files are never held in memory.
+#[tauri::command]
+pub async fn hash_file(
+    path: String,
+    state: tauri::State<'_, DesktopRuntime>,
+) -> Result<FileHashResult, String> {
+    let (workspace_roots, default_root) = resolve_workspace_roots(state.settings()).await;
+    hash_sandboxed_file(&path, &workspace_roots, default_root.as_ref()).await
+}
+
+/// Batch `hash_file`. Failures are reported per path; results keep the input order.
+#[tauri::command]
+pub async fn hash_files(
+    paths: Vec<String>,
+    state: tauri::State<'_, DesktopRuntime>,
+) -> Result<Vec<FileHashEntry>, String> {
+    if paths.len() > HASH_FILES_MAX_PATHS {
+        return Err(format!("Too many paths (limit {})", HASH_FILES_MAX_PATHS));
+    }
+
+    let (workspace_roots, default_root) = resolve_workspace_roots(state.settings()).await;
+    let workspace_roots = &workspace_roots;
+    let default_root = default_root.as_ref();
+    let entries = stream::iter(paths)
+        .map(|path| async move {
+            match hash_sandboxed_file(&path, workspace_roots, default_root).await {
+                Ok(result) => FileHashEntry {
+                    requested_path: path,
+                    result: Some(result),
+                    error: None,
+                },
+                Err(error) => FileHashEntry {
+                    requested_path: path,
+                    result: None,
+                    error: Some(error),
+                },
+            }
+        })
+        .buffered(HASH_FILES_MAX_CONCURRENCY)
+        .collect()
+        .await;
+
+    Ok(entries)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsageEntry {
+    name: String,
+    path: String,
+    bytes: u64,
+    file_count: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskUsageResult {
+    path: String,
+    total_bytes: u64,
+    file_count: u64,
+    /// Largest immediate subdirectories, biggest first.
+    largest: Vec<DiskUsageEntry>,
+    /// Excluded directories (node_modules, .git, ...) that were not measured.
+    skipped_directories: Vec<String>,
+    /// The time budget ran out; sizes only cover what was walked.
+    truncated: bool,
+}
+
+/// Recursive size of `dir` without following symlinks. Stops at `deadline` and reports
+/// whether it did.
+async fn measure_directory(
+    dir: PathBuf,
+    skip_excluded: bool,
+    deadline: Instant,
+) -> (u64, u64, bool) {
+    let mut bytes = 0;
+    let mut files = 0;
+    let mut stack = vec![dir];
+
+    while let Some(current) = stack.pop() {
+        if Instant::now() >= deadline {
+            return (bytes, files, true);
+        }
+        let Ok(mut entries) = fs::read_dir(&current).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let Ok(file_type) = entry.file_type().await else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let name = entry.file_name();
+                if skip_excluded && should_skip_directory(&name.to_string_lossy(), true) {
+                    continue;
+                }
+                stack.push(entry.path());
+            } else if file_type.is_file() {
+                if let Ok(metadata) = entry.metadata().await {
+                    bytes += metadata.len();
+                    files += 1;
+                }
+            }
+        }
+    }
+
+    (bytes, files, false)
+}
+
+async fn compute_disk_usage(
+    root: &Path,
+    skip_excluded: bool,
+    top: Option<usize>,
+) -> DiskUsageResult {
+    let deadline = Instant::now() + DISK_USAGE_TIME_BUDGET;
+    let top = top
+        .unwrap_or(DEFAULT_DISK_USAGE_TOP)
+        .clamp(1, MAX_DISK_USAGE_TOP);
+
+    let mut total_bytes = 0;
+    let mut file_count = 0;
+    let mut subdirectories = Vec::new();
+    let mut skipped_directories = Vec::new();
+    if let Ok(mut entries) = fs::read_dir(root).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let Ok(file_type) = entry.file_type().await else {
+                continue;
+            };
+            let name = entry.file_name().to_string_lossy().to_string();
+            if file_type.is_dir() {
+                if skip_excluded && should_skip_directory(&name, true) {
+                    skipped_directories.push(name);
+                } else {
+                    subdirectories.push((name, entry.path()));
+                }
+            } else if file_type.is_file() {
+                if let Ok(metadata) = entry.metadata().await {
+                    total_bytes += metadata.len();
+                    file_count += 1;
+                }
+            }
+        }
+    }
+
+    let mut truncated = false;
+    let mut largest: Vec<DiskUsageEntry> = stream::iter(subdirectories)
+        .map(|(name, path)| async move {
+            let measured = measure_directory(path.clone(), skip_excluded, deadline).await;
+            (name, path, measured)
+        })
+        .buffer_unordered(DISK_USAGE_MAX_CONCURRENCY)
+        .map(|(name, path, (bytes, files, cut_short))| {
+            truncated |= cut_short;
+            total_bytes += bytes;
+            file_count += files;
+            DiskUsageEntry {
+                name,
+                path: normalize_path(&path),
+                bytes,
+                file_count: files,
+            }
+        })
+        .collect()
+        .await;
+
+    largest.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+    largest.truncate(top);
+    skipped_directories.sort();
+
+    DiskUsageResult {
+        path: normalize_path(root),
+        total_bytes,
+        file_count,
+        largest,
+        skipped_directories,
+        truncated,
+    }
+}
+
+/// Recursive size of a workspace directory plus its `top` largest subdirectories. Excluded
+/// directories are skipped, and walking stops after a few seconds with `truncated` set.
+#[tauri::command]
+pub async fn get_disk_usage(
+    path: Option<String>,
+    top: Option<usize>,
+    state: tauri::State<'_, DesktopRuntime>,
+) -> Result<DiskUsageResult, String> {
+    let (workspace_roots, default_root) = resolve_workspace_roots(state.settings()).await;
+    let resolved = resolve_sandboxed_path(path, &workspace_roots, default_root.as_ref())
+        .await
+        .map_err(|err| err.to_list_message())?;
+    let metadata = fs::metadata(&resolved)
+        .await
+        .map_err(|err| FsCommandError::from(err).to_list_message())?;
+    if !metadata.is_dir() {
+        return Err(FsCommandError::NotDirectory.to_list_message());
+    }
+
+    Ok(compute_disk_usage(&resolved, true, top).await)
+}
+
+/// Size of the OpenChamber config directory (settings, auth, caches, ...), with nothing
+/// skipped. Logs are counted on Linux and Windows, where they live in its `logs` directory;
+/// on macOS they are under `~/Library/Logs/OpenChamber` and not part of the total.
+#[tauri::command]
+pub async fn get_config_disk_usage(top: Option<usize>) -> Result<DiskUsageResult, String> {
+    let root = openchamber_user_config_root();
+    if !fs::metadata(&root)
+        .await
+        .is_ok_and(|metadata| metadata.is_dir())
+    {
+        return Ok(DiskUsageResult {
+            path: normalize_path(&root),
+            total_bytes: 0,
+            file_count: 0,
+            largest: Vec::new(),
+            skipped_directories: Vec::new(),
+            truncated: false,
+        });
+    }
+
+    Ok(compute_disk_usage(&root, false, top).await)
+}
+
 static CACHED_LOGIN_SHELL_PATH: OnceLock<Option<String>> = OnceLock::new();
 
 #[cfg(target_os = "macos")]
@@ -1275,3 +2274,77 @@ pub async fn exec_commands(
         results,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_match_score_rewards_camel_case_humps() {
+        let camel = fuzzy_match_score("sf", "SearchFiles").unwrap();
+        let inner = fuzzy_match_score("sf", "Searchfiles").unwrap();
+        assert!(camel > inner, "camel {camel} <= inner {inner}");
+    }
+
+    #[test]
+    fn fuzzy_match_score_rewards_delimiters() {
+        let delimited = fuzzy_match_score("fs", "file_search").unwrap();
+        let inner = fuzzy_match_score("fs", "fileasearch").unwrap();
+        assert!(delimited > inner, "delimited {delimited} <= inner {inner}");
+        assert!(fuzzy_match_score("sf", "src/files.rs").is_some());
+        assert_eq!(fuzzy_match_score("zz", "src/files.rs"), None);
+    }
+
+    #[test]
+    fn fuzzy_match_score_handles_multibyte_names() {
+        assert!(fuzzy_match_score("über", "Überblick.md").is_some());
+        assert!(fuzzy_match_score("日本", "docs/日本語.md").is_some());
+        // Boundary detection must stay aligned after multi-byte chars.
+        let hump = fuzzy_match_score("éb", "ÉtatBase").unwrap();
+        let inner = fuzzy_match_score("éb", "Étatbase").unwrap();
+        assert!(hump > inner, "hump {hump} <= inner {inner}");
+    }
+
+    #[test]
+    fn encode_text_round_trips_detected_encodings() {
+        for (bytes, encoding) in [
+            (b"\xff\xfeh\x00\xe9\x00".to_vec(), TextEncoding::Utf16Le),
+            (b"\xfe\xff\x00h\x00\xe9".to_vec(), TextEncoding::Utf16Be),
+            (b"caf\xe9 noir".to_vec(), TextEncoding::Latin1),
+        ] {
+            let (detected, bom_len) = detect_text_encoding(&bytes);
+            assert_eq!(detected, encoding);
+            let text = decode_text(&bytes[bom_len..], detected);
+            assert_eq!(encode_text(&text, detected, bom_len > 0).unwrap(), bytes);
+        }
+        assert!(encode_text("日本", TextEncoding::Latin1, false).is_err());
+    }
+
+    #[tokio::test]
+    async fn build_list_entries_handles_large_directories() {
+        let dir = std::env::temp_dir().join(format!("openchamber-list-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for index in 0..5000 {
+            std::fs::write(dir.join(format!("file-{index:04}.txt")), b"x").unwrap();
+        }
+
+        let started = std::time::Instant::now();
+        let mut read_dir = fs::read_dir(&dir).await.unwrap();
+        let mut raw_entries = Vec::new();
+        while let Some(entry) = read_dir.next_entry().await.unwrap() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            raw_entries.push((entry, name));
+        }
+        let entries = build_list_entries(raw_entries, true).await.unwrap();
+        let elapsed = started.elapsed();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(entries.len(), 5000);
+        assert!(entries.windows(2).all(|pair| pair[0].name < pair[1].name));
+        assert_eq!(entries[0].name, "file-0000.txt");
+        assert!(
+            elapsed < std::time::Duration::from_secs(10),
+            "listing took {elapsed:?}"
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::commands::error::{CommandError, ErrorCode};
 use crate::path_utils::expand_tilde_path;
 use crate::{DesktopRuntime, SettingsStore};
 use anyhow::{anyhow, Context, Result};
@@ -10,7 +11,7 @@ use std::collections::{HashMap, HashSet};
 use std::path::{Component, Path, PathBuf};
 use std::process::Stdio;
 use std::sync::LazyLock;
-use tauri::State;
+use tauri::{AppHandle, Emitter, State};
 use tokio::fs;
 use tokio::io::AsyncReadExt;
 use tokio::process::Command;
@@ -56,11 +57,17 @@ fn extract_json_object(value: &str) -> Option<String> {
 const GIT_IDENTITY_STORAGE_FILE: &str = "git-identities.json";
 const GIT_FILE_DIFF_TIMEOUT_MS: u64 = 15_000;
 const GIT_LS_REMOTE_TIMEOUT_MS: u64 = 5_000;
+const GIT_PROGRESS_EVENT: &str = "openchamber:git-progress";
+const SIGNING_PROBE_TIMEOUT_MS: u64 = 10_000;
+const SIGNING_PROBE_PAYLOAD: &[u8] = b"openchamber signing key check\n";
 const GIT_FILE_TEXT_MAX_BYTES: u64 = 2_000_000;
 const GIT_FILE_IMAGE_MAX_BYTES: u64 = 10_000_000;
 // Tauri invoke payloads can become unstable with very large strings (e.g. huge blobs or base64 data URLs).
 // Keep a conservative upper bound to ensure the diff IPC response always returns.
 const GIT_FILE_IPC_MAX_CHARS: usize = 600_000;
+const COMMIT_MESSAGE_FILE_DIFF_MAX_CHARS: usize = 4_000;
+const COMMIT_MESSAGE_DIFF_MAX_BYTES: usize = 48_000;
+const COMMIT_MESSAGE_DEFAULT_MODEL: &str = "gpt-5-nano";
 
 // --- Structs mirroring TypeScript types ---
 
@@ -68,8 +75,13 @@ const GIT_FILE_IPC_MAX_CHARS: usize = 600_000;
 #[serde(rename_all = "camelCase")]
 pub struct GitStatusFile {
     pub path: String,
+    /// Index (staged) status code; empty when unchanged.
     pub index: String,
+    /// Worktree (unstaged) status code; empty when unchanged.
     pub working_dir: String,
+    /// Source path of a rename or copy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_path: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -80,8 +92,56 @@ pub struct GitStatus {
     pub ahead: i32,
     pub behind: i32,
     pub files: Vec<GitStatusFile>,
+    /// Files with index changes. Unmerged files are only listed in `conflicts`.
+    #[serde(default)]
+    pub staged: Vec<GitStatusFile>,
+    /// Tracked files with worktree changes; a partially staged file is in both groups.
+    #[serde(default)]
+    pub unstaged: Vec<GitStatusFile>,
+    #[serde(default)]
+    pub untracked: Vec<GitStatusFile>,
+    #[serde(default)]
+    pub has_changes: bool,
     pub is_clean: bool,
     pub diff_stats: Option<HashMap<String, DiffStat>>,
+    #[serde(default)]
+    pub conflicts: Vec<GitConflictFile>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConflictFile {
+    pub path: String,
+    /// Two-letter unmerged status code from porcelain output (e.g. "UU", "AA").
+    pub code: String,
+    /// Human-readable conflict kind (e.g. "both-modified", "added-by-us").
+    pub kind: String,
+    pub is_binary: bool,
+    /// False when the conflict cannot be resolved in-app (binary content or a deleted side).
+    pub resolvable: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConflictRegion {
+    /// 1-based line numbers of the `<<<<<<<` and `>>>>>>>` markers.
+    pub start_line: usize,
+    pub end_line: usize,
+    pub ours_label: String,
+    pub theirs_label: String,
+    pub ours: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    pub theirs: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitConflictMarkersResponse {
+    pub path: String,
+    pub is_binary: bool,
+    pub resolvable: bool,
+    pub regions: Vec<GitConflictRegion>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -98,8 +158,17 @@ pub struct GitBranchDetails {
     pub commit: String,
     pub label: String,
     pub tracking: Option<String>,
+    /// Full upstream ref, e.g. `refs/remotes/origin/main`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upstream_ref: Option<String>,
+    /// The configured upstream no longer exists on the remote.
+    #[serde(default)]
+    pub upstream_gone: bool,
     pub ahead: Option<i32>,
     pub behind: Option<i32>,
+    /// Committer date of the branch tip, as a Unix timestamp in seconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_commit_date: Option<i64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -127,6 +196,18 @@ pub struct GitCommitResult {
     pub summary: GitCommitSummary,
 }
 
+/// Outcome of a cherry-pick or commit revert.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitCommitOperationResult {
+    pub success: bool,
+    /// `applied`, `staged` (applied with no commit), `empty` (already applied / nothing to
+    /// commit) or `conflict`.
+    pub status: &'static str,
+    pub commit: Option<String>,
+    pub conflicts: Vec<GitConflictFile>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GitPushResult {
@@ -135,6 +216,19 @@ pub struct GitPushResult {
     pub repo: String,
     #[serde(rename = "ref")]
     pub ref_: Option<String>, // "ref" is a keyword in Rust
+    pub operation_id: String,
+    /// Set when the remote refused the push without it being a hard failure, e.g. a
+    /// `--force-with-lease` push against a remote that moved since the last fetch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<GitPushRejection>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitPushRejection {
+    /// `stale_lease`: the remote branch has commits the local tracking ref hasn't seen.
+    pub reason: String,
+    pub message: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -152,6 +246,43 @@ pub struct GitPullResult {
     pub files: Vec<String>,
     pub insertions: i32,
     pub deletions: i32,
+    pub operation_id: String,
+}
+
+/// A remote-tracking ref or tag touched by `git fetch`.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFetchedRef {
+    /// "new", "fastForward", "forced", "pruned", "tagUpdated", "rejected" or "upToDate".
+    pub status: &'static str,
+    /// Git's summary column, e.g. "a1b2c3d..e4f5a6b" or "[new branch]".
+    pub summary: String,
+    pub from: String,
+    pub to: String,
+    /// Parenthesized note, e.g. "forced update" or "non-fast-forward".
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFetchResult {
+    pub remote: String,
+    pub refs: Vec<GitFetchedRef>,
+    pub operation_id: String,
+}
+
+/// Progress for a long-running fetch/pull/push, emitted as `openchamber:git-progress`.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitProgressPayload {
+    pub operation_id: String,
+    pub operation: &'static str,
+    /// Git's phase label, e.g. "Receiving objects".
+    pub phase: Option<String>,
+    pub percent: Option<u8>,
+    pub current: Option<u64>,
+    pub total: Option<u64>,
+    pub done: bool,
 }
 
 fn parse_shortstat(output: &str) -> GitCommitSummary {
@@ -230,6 +361,36 @@ pub struct GitIdentityProfile {
     pub host: Option<String>,
     pub color: Option<String>,
     pub icon: Option<String>,
+    /// GPG key id/fingerprint, or for SSH signing a key path or `key::` literal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
+    /// `openpgp` (default) or `ssh`, mirroring git's `gpg.format`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_format: Option<String>,
+}
+
+impl GitIdentityProfile {
+    fn signing(&self) -> Option<(&'static str, &str)> {
+        let key = self
+            .signing_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|key| !key.is_empty())?;
+        let format = match self.signing_format.as_deref().map(str::trim) {
+            Some("ssh") => "ssh",
+            _ => "openpgp",
+        };
+        Some((format, key))
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitIdentityValidation {
+    pub valid: bool,
+    pub signing_configured: bool,
+    pub signing_format: Option<String>,
+    pub error: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -253,6 +414,71 @@ pub struct GitIdentitySummary {
     pub ssh_command: Option<String>,
 }
 
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitIdentityImportResult {
+    pub identity: GitIdentityProfile,
+    /// False when an identity with the same name and email already existed.
+    pub created: bool,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRepoContext {
+    pub is_git_repo: bool,
+    /// Inside a linked worktree (`git worktree add`) rather than the main checkout.
+    pub is_worktree: bool,
+    pub is_submodule: bool,
+    /// Working tree of the repository this one is a submodule of.
+    pub superproject: Option<String>,
+    pub toplevel: Option<String>,
+    pub git_dir: Option<String>,
+    pub common_dir: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GitOperationState {
+    /// `merge`, `rebase`, `cherry-pick` or `revert`; `None` when nothing is in progress.
+    pub operation: Option<&'static str>,
+    pub conflicts: Vec<GitConflictFile>,
+    /// Rebase progress: the step being applied and the total number of steps.
+    pub step: Option<u32>,
+    pub total_steps: Option<u32>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitOperationResult {
+    /// False when `continue` stopped on new conflicts.
+    pub success: bool,
+    pub operation: &'static str,
+    /// State of the repository after the command ran.
+    pub state: GitOperationState,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitReflogEntry {
+    pub hash: String,
+    /// Reflog selector such as `HEAD@{3}`.
+    pub selector: String,
+    /// What moved HEAD, e.g. `commit`, `checkout`, `rebase (finish)`, `reset`.
+    pub action: String,
+    pub message: String,
+    /// Committer date of the commit the entry points at (ISO 8601).
+    pub date: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitResetResult {
+    pub mode: &'static str,
+    /// HEAD before the reset, so the UI can offer to undo it.
+    pub previous: Option<String>,
+    pub head: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GitLogEntry {
@@ -278,6 +504,56 @@ pub struct GitLogResponse {
     pub total: i32,
 }
 
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitGraphRef {
+    pub name: String,
+    /// `branch`, `remote`, `tag` or `head` (detached HEAD).
+    pub kind: &'static str,
+    /// True for the branch HEAD currently points at.
+    pub is_head: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitGraphCommit {
+    pub hash: String,
+    pub parents: Vec<String>,
+    pub author_name: String,
+    pub author_email: String,
+    pub date: String,
+    pub message: String,
+    pub refs: Vec<GitGraphRef>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitLogGraphResponse {
+    /// Commits in topological order (children before parents).
+    pub commits: Vec<GitGraphCommit>,
+    pub head: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitTagInfo {
+    pub name: String,
+    /// Commit the tag points at (peeled through annotated tag objects).
+    pub target: String,
+    pub annotated: bool,
+    /// Annotation message; `None` for lightweight tags.
+    pub message: Option<String>,
+    pub date: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRemoteInfo {
+    pub name: String,
+    pub fetch_url: Option<String>,
+    pub push_url: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GitWorktreeInfo {
@@ -286,6 +562,16 @@ pub struct GitWorktreeInfo {
     pub branch: Option<String>,
 }
 
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitWorktreeMaintenanceResult {
+    /// Stale entries removed by a prune.
+    pub removed: usize,
+    /// Git's own report of what it pruned or repaired.
+    pub messages: Vec<String>,
+    pub worktrees: Vec<GitWorktreeInfo>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GeneratedCommitMessage {
@@ -308,9 +594,59 @@ pub struct GitCommitFilesResponse {
     pub files: Vec<CommitFileEntry>,
 }
 
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRefDiffResponse {
+    pub from: String,
+    pub to: String,
+    pub diff: String,
+    /// True when `diff` was cut to fit the IPC size cap; `files` is always complete.
+    pub truncated: bool,
+    pub files: Vec<CommitFileEntry>,
+    pub summary: GitCommitSummary,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StagedFileStat {
+    pub path: String,
+    /// Path before a rename or copy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+    pub insertions: i32,
+    pub deletions: i32,
+    /// Git reports no line counts for binary files; both counts are 0.
+    pub is_binary: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StagedDiffStat {
+    pub files: Vec<StagedFileStat>,
+    pub summary: GitCommitSummary,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitFileAtRevision {
+    pub path: String,
+    pub sha: String,
+    /// False when the path did not exist at `sha` (e.g. it was added later).
+    pub exists: bool,
+    /// Text content, or a data URL for images. `None` for other binary files.
+    pub content: Option<String>,
+    pub is_binary: bool,
+    pub size: u64,
+    pub truncated: bool,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct CommitMessageResponse {
     pub message: GeneratedCommitMessage,
+    /// True when part of the diff was replaced by file-level stats to stay under the size cap.
+    #[serde(default)]
+    pub truncated: bool,
 }
 
 // --- Constants & Regexes ---
@@ -324,6 +660,14 @@ static INSERTIONS_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(\d+)\s+insertions?\(\+\)").unwrap());
 static DELETIONS_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(\d+)\s+deletions?\(-\)").unwrap());
+// " <flag> <summary> <from> -> <to> [(<reason>)]", one line per ref in `git fetch` stderr.
+static FETCH_REF_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^ ([ +\-t*!=]) (\[[^\]]+\]|\S+)\s+(\S+)\s+-> (\S+)(?:\s+\((.+)\))?$").unwrap()
+});
+static PROGRESS_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:remote:\s*)?([A-Za-z][A-Za-z ]*?):\s+(\d{1,3})%(?:\s+\((\d+)/(\d+)\))?")
+        .unwrap()
+});
 
 // --- Helpers ---
 
@@ -331,12 +675,17 @@ async fn run_git(args: &[&str], cwd: &Path) -> Result<String> {
     run_git_with_allowed_exit(args, cwd, &[]).await
 }
 
-async fn run_git_with_allowed_exit(
+async fn git_output(args: &[&str], cwd: &Path) -> Result<std::process::Output> {
+    git_output_with_env(args, cwd, &[]).await
+}
+
+/// Like `git_output`, with `extra_env` set on top of the usual non-interactive environment.
+async fn git_output_with_env(
     args: &[&str],
     cwd: &Path,
-    allowed_codes: &[i32],
-) -> Result<String> {
-    let output = Command::new("git")
+    extra_env: &[(&str, &str)],
+) -> Result<std::process::Output> {
+    Command::new("git")
         .args(args)
         .current_dir(cwd)
         .stdin(Stdio::null())
@@ -345,9 +694,18 @@ async fn run_git_with_allowed_exit(
         .env("GIT_TERMINAL_PROMPT", "0")
         .env("GCM_INTERACTIVE", "Never")
         .env("LC_ALL", "C")
+        .envs(extra_env.iter().copied())
         .output()
         .await
-        .context("Failed to execute git command")?;
+        .context("Failed to execute git command")
+}
+
+async fn run_git_with_allowed_exit(
+    args: &[&str],
+    cwd: &Path,
+    allowed_codes: &[i32],
+) -> Result<String> {
+    let output = git_output(args, cwd).await?;
 
     if !output.status.success() {
         if let Some(code) = output.status.code() {
@@ -362,6 +720,81 @@ async fn run_git_with_allowed_exit(
     Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
 }
 
+/// Like `run_git`, for commands that would open an editor. `GIT_EDITOR` takes precedence over
+/// any editor the user configured, so prepared messages are accepted as they are.
+async fn run_git_without_editor(args: &[&str], cwd: &Path) -> Result<String> {
+    let output = git_output_with_env(args, cwd, &[("GIT_EDITOR", "true")]).await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(anyhow!("{}", stderr));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Like `run_git`, but also returns stderr, where commands such as `git worktree prune -v`
+/// report what they did.
+async fn run_git_with_stderr(args: &[&str], cwd: &Path) -> Result<(String, String)> {
+    let output = git_output(args, cwd).await?;
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if !output.status.success() {
+        return Err(anyhow!("{}", stderr));
+    }
+    Ok((
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        stderr,
+    ))
+}
+
+/// Read at most `max_bytes` of a git command's stdout. The flag is true when the output was
+/// cut off, in which case the command is killed instead of being read to the end.
+async fn run_git_bytes_limited(
+    args: &[&str],
+    cwd: &Path,
+    max_bytes: u64,
+    timeout_ms: u64,
+) -> Result<(Vec<u8>, bool)> {
+    let mut child = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GCM_INTERACTIVE", "Never")
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .context("Failed to execute git command")?;
+    let stdout = child
+        .stdout
+        .take()
+        .context("Failed to capture git stdout")?;
+
+    let mut bytes = Vec::new();
+    tokio::time::timeout(
+        std::time::Duration::from_millis(timeout_ms),
+        stdout.take(max_bytes + 1).read_to_end(&mut bytes),
+    )
+    .await
+    .map_err(|_| anyhow!("Git command timed out after {}ms", timeout_ms))?
+    .context("Failed to read git output")?;
+
+    if bytes.len() as u64 > max_bytes {
+        bytes.truncate(max_bytes as usize);
+        let _ = child.kill().await;
+        return Ok((bytes, true));
+    }
+    let status = child
+        .wait()
+        .await
+        .context("Failed to execute git command")?;
+    if !status.success() {
+        return Err(anyhow!("git {} failed with {}", args.join(" "), status));
+    }
+    Ok((bytes, false))
+}
+
 async fn run_git_bytes_with_allowed_exit_timeout(
     args: &[&str],
     cwd: &Path,
@@ -398,6 +831,183 @@ async fn run_git_bytes_with_allowed_exit_timeout(
     Ok(output.stdout)
 }
 
+fn resolve_operation_id(operation_id: Option<String>) -> String {
+    operation_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// Run a network git command with `--progress` already in `args`, emitting a progress event
+/// whenever git reports a new phase or percentage. Git redraws progress with `\r`, so stderr
+/// is split on both carriage returns and newlines. Returns stdout and the raw stderr.
+async fn run_git_with_progress(
+    args: &[&str],
+    cwd: &Path,
+    app: &AppHandle,
+    operation_id: &str,
+    operation: &'static str,
+) -> Result<(String, String)> {
+    let mut child = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GCM_INTERACTIVE", "Never")
+        .env("LC_ALL", "C")
+        .spawn()
+        .context("Failed to execute git command")?;
+
+    let mut stdout = child
+        .stdout
+        .take()
+        .context("Failed to capture git stdout")?;
+    let mut stderr = child
+        .stderr
+        .take()
+        .context("Failed to capture git stderr")?;
+
+    let emit = |phase: Option<String>, percent, current, total, done| {
+        let _ = app.emit(
+            GIT_PROGRESS_EVENT,
+            GitProgressPayload {
+                operation_id: operation_id.to_string(),
+                operation,
+                phase,
+                percent,
+                current,
+                total,
+                done,
+            },
+        );
+    };
+
+    let read_stdout = async {
+        let mut buf = Vec::new();
+        let _ = stdout.read_to_end(&mut buf).await;
+        buf
+    };
+    let read_stderr = async {
+        let mut collected = String::new();
+        let mut pending = String::new();
+        let mut last: Option<(String, u8)> = None;
+        let mut chunk = [0u8; 4096];
+        loop {
+            let read = match stderr.read(&mut chunk).await {
+                Ok(0) | Err(_) => break,
+                Ok(read) => read,
+            };
+            let text = String::from_utf8_lossy(&chunk[..read]);
+            collected.push_str(&text);
+            pending.push_str(&text);
+
+            while let Some(idx) = pending.find(['\r', '\n']) {
+                let line: String = pending.drain(..=idx).collect();
+                let Some(caps) = PROGRESS_REGEX.captures(line.trim()) else {
+                    continue;
+                };
+                let phase = caps[1].trim().to_string();
+                let percent = caps[2].parse::<u8>().unwrap_or(0).min(100);
+                if last.as_ref() == Some(&(phase.clone(), percent)) {
+                    continue;
+                }
+                last = Some((phase.clone(), percent));
+                let current = caps.get(3).and_then(|m| m.as_str().parse().ok());
+                let total = caps.get(4).and_then(|m| m.as_str().parse().ok());
+                emit(Some(phase), Some(percent), current, total, false);
+            }
+        }
+        collected
+    };
+    let (stdout_bytes, stderr_text) = tokio::join!(read_stdout, read_stderr);
+
+    let status = child
+        .wait()
+        .await
+        .context("Failed to execute git command")?;
+    emit(None, None, None, None, true);
+
+    if !status.success() {
+        // Keep only the final state of each redrawn progress line in the error text.
+        let message = stderr_text
+            .lines()
+            .map(|line| line.rsplit('\r').next().unwrap_or(line).trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        return Err(anyhow!("{}", message));
+    }
+
+    Ok((
+        String::from_utf8_lossy(&stdout_bytes).trim().to_string(),
+        stderr_text,
+    ))
+}
+
+/// Ref update lines from `git fetch` stderr; progress output is skipped.
+fn parse_fetch_output(stderr: &str) -> Vec<GitFetchedRef> {
+    stderr
+        .split(['\r', '\n'])
+        .filter_map(|line| FETCH_REF_REGEX.captures(line.trim_end()))
+        .map(|caps| GitFetchedRef {
+            status: match &caps[1] {
+                "+" => "forced",
+                "-" => "pruned",
+                "t" => "tagUpdated",
+                "*" => "new",
+                "!" => "rejected",
+                "=" => "upToDate",
+                _ => "fastForward",
+            },
+            summary: caps[2].to_string(),
+            from: caps[3].to_string(),
+            to: caps[4].to_string(),
+            reason: caps.get(5).map(|m| m.as_str().to_string()),
+        })
+        .collect()
+}
+
+async fn run_git_with_stdin(args: &[&str], cwd: &Path, input: &[u8]) -> Result<String> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut child = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .env("GIT_OPTIONAL_LOCKS", "0")
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env("GCM_INTERACTIVE", "Never")
+        .env("LC_ALL", "C")
+        .spawn()
+        .context("Failed to execute git command")?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input)
+            .await
+            .context("Failed to write to git stdin")?;
+        // Dropping stdin closes the pipe so git sees EOF.
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .context("Failed to execute git command")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(anyhow!("{}", stderr));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
 async fn read_file_bytes_limited(path: &Path, max_bytes: u64) -> Result<(Vec<u8>, bool)> {
     let file = tokio::fs::File::open(path).await?;
     let mut buf = Vec::new();
@@ -594,22 +1204,52 @@ fn append_git_option_map(args: &mut Vec<String>, map: &serde_json::Map<String, V
 async fn validate_git_path(path: &str, _settings: &SettingsStore) -> Result<PathBuf> {
     let path_buf = expand_tilde_path(path);
     if !path_buf.exists() {
-        return Err(anyhow!("Directory does not exist: {}", path));
+        return Err(CommandError::not_found(format!("Directory does not exist: {}", path)).into());
     }
 
     if !path_buf.is_absolute() {
-        return Err(anyhow!("Path must be absolute"));
+        return Err(CommandError::invalid_argument("Path must be absolute").into());
     }
 
     Ok(path_buf)
 }
 
+/// Turn a failed git invocation into a `CommandError`, classified from git's stderr
+/// (always in English, as git runs with `LC_ALL=C`). Codes set earlier, e.g. by
+/// `validate_git_path`, are kept.
+fn git_error(err: anyhow::Error) -> CommandError {
+    let err = match err.downcast::<CommandError>() {
+        Ok(err) => return err,
+        Err(err) => err,
+    };
+    let message = err.to_string();
+    let lower = message.to_lowercase();
+    let code = if lower.contains("not a git repository") {
+        ErrorCode::RepoNotFound
+    } else if lower.contains("authentication failed")
+        || lower.contains("permission denied (publickey")
+        || lower.contains("could not read username")
+    {
+        ErrorCode::Unauthorized
+    } else if lower.contains("could not resolve host")
+        || lower.contains("failed to connect")
+        || lower.contains("connection timed out")
+        || lower.contains("network is unreachable")
+    {
+        ErrorCode::Network
+    } else if lower.contains("conflict") || lower.contains("would be overwritten") {
+        ErrorCode::Conflict
+    } else {
+        ErrorCode::GitFailed
+    };
+    CommandError::new(code, message)
+}
+
 // --- Identity Storage ---
 
 async fn get_identity_storage_path() -> Result<PathBuf> {
-    let mut path = dirs::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))?;
-    path.push(".config");
-    path.push("openchamber");
+    let mut path = crate::path_utils::openchamber_config_dir()
+        .ok_or_else(|| anyhow!("Could not find home directory"))?;
     fs::create_dir_all(&path).await?;
     path.push(GIT_IDENTITY_STORAGE_FILE);
     Ok(path)
@@ -665,112 +1305,52 @@ async fn save_identities(profiles: Vec<GitIdentityProfile>) -> Result<()> {
 
 // --- Commands ---
 
+pub(crate) async fn is_git_repository(path: &Path) -> bool {
+    match run_git(&["rev-parse", "--is-inside-work-tree"], path).await {
+        Ok(output) => output.trim().eq_ignore_ascii_case("true"),
+        Err(_) => false,
+    }
+}
+
 #[tauri::command]
 pub async fn check_is_git_repository(
     directory: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     let path = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
-    match run_git(&["rev-parse", "--is-inside-work-tree"], &path).await {
-        Ok(output) => Ok(output.trim().eq_ignore_ascii_case("true")),
-        Err(_) => Ok(false),
-    }
+        .map_err(git_error)?;
+    Ok(is_git_repository(&path).await)
 }
 
 #[tauri::command]
 pub async fn get_git_status(
     directory: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<GitStatus, String> {
+) -> Result<GitStatus, CommandError> {
     let path = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
 
-    // 1. Get porcelain status
+    // 1. Get porcelain v2 status, which carries the original path of renames and copies.
     // Use -uall to show all untracked files individually, not just directories
-    let status_output = run_git(&["status", "--porcelain", "-b", "-z", "-uall"], &path)
-        .await
-        .map_err(|e| e.to_string())?;
-
-    // Parse status output
-    let mut files = Vec::new();
-    let mut current = String::new();
-    let mut tracking = None;
-    let mut ahead = 0;
-    let mut behind = 0;
+    let status_output = run_git(
+        &["status", "--porcelain=v2", "--branch", "-z", "-uall"],
+        &path,
+    )
+    .await
+    .map_err(git_error)?;
 
-    let entries: Vec<&str> = status_output.split('\0').collect();
-    let mut i = 0usize;
+    let PorcelainV2Status {
+        current,
+        tracking,
+        mut ahead,
+        mut behind,
+        files,
+    } = parse_status_porcelain_v2(&status_output);
 
-    while i < entries.len() {
-        let entry = entries[i];
-        i += 1;
-
-        if entry.is_empty() {
-            continue;
-        }
-
-        if entry.starts_with("## ") {
-            // Branch info: ## main...origin/main [ahead 1, behind 2]
-            let branch_line = &entry[3..];
-            if let Some((local, remote_part)) = branch_line.split_once("...") {
-                current = local.to_string();
-                // Parse remote part for ahead/behind
-                // Format: origin/main [ahead 1, behind 2] or origin/main
-                if let Some(bracket_start) = remote_part.find('[') {
-                    tracking = Some(remote_part[..bracket_start].trim().to_string());
-                    let stats = &remote_part[bracket_start + 1..remote_part.len() - 1]; // inside brackets
-                    for part in stats.split(", ") {
-                        if let Some(val) = part.strip_prefix("ahead ") {
-                            ahead = val.parse().unwrap_or(0);
-                        } else if let Some(val) = part.strip_prefix("behind ") {
-                            behind = val.parse().unwrap_or(0);
-                        }
-                    }
-                } else {
-                    tracking = Some(remote_part.trim().to_string());
-                }
-            } else {
-                // No remote or initial commit
-                current = branch_line.to_string();
-            }
-            continue;
-        }
-
-        // File entries (porcelain v1, -z):
-        // - Normal: XY<space>path
-        // - Rename/Copy: XY<space>old_path<null>new_path
-        if entry.len() >= 4 {
-            let index_status = &entry[0..1];
-            let working_status = &entry[1..2];
-            let mut file_path = &entry[3..];
-
-            // Handle rename/copy by consuming the next NUL-terminated token as the new path.
-            let is_rename_or_copy = index_status == "R"
-                || working_status == "R"
-                || index_status == "C"
-                || working_status == "C";
-            if is_rename_or_copy && i < entries.len() {
-                let next_path = entries[i];
-                if !next_path.is_empty() {
-                    file_path = next_path;
-                    i += 1;
-                }
-            }
-
-            // Simple-git parsing logic approximation
-            files.push(GitStatusFile {
-                path: file_path.to_string(),
-                index: index_status.trim().to_string(),
-                working_dir: working_status.trim().to_string(),
-            });
-        }
-    }
-
-    // 2. Get diff stats (staged and unstaged)
-    let mut diff_stats = HashMap::new();
+    // 2. Get diff stats (staged and unstaged)
+    let mut diff_stats = HashMap::new();
 
     let collect_stats = |output: String| {
         let mut stats = HashMap::new();
@@ -904,14 +1484,212 @@ pub async fn get_git_status(
         }
     }
 
+    let conflicts = collect_conflicts(&path).await;
+
+    let is_unmerged =
+        |file: &GitStatusFile| conflicts.iter().any(|conflict| conflict.path == file.path);
+    let staged: Vec<GitStatusFile> = files
+        .iter()
+        .filter(|file| !file.index.is_empty() && file.index != "?" && !is_unmerged(file))
+        .cloned()
+        .collect();
+    let unstaged: Vec<GitStatusFile> = files
+        .iter()
+        .filter(|file| {
+            !file.working_dir.is_empty() && file.working_dir != "?" && !is_unmerged(file)
+        })
+        .cloned()
+        .collect();
+    let untracked: Vec<GitStatusFile> = files
+        .iter()
+        .filter(|file| file.index == "?")
+        .cloned()
+        .collect();
+
     Ok(GitStatus {
         current,
         tracking,
         ahead,
         behind,
         is_clean: files.is_empty(),
+        has_changes: !files.is_empty(),
+        staged,
+        unstaged,
+        untracked,
         files,
         diff_stats: Some(diff_stats),
+        conflicts,
+    })
+}
+
+fn describe_conflict_code(code: &str) -> &'static str {
+    match code {
+        "DD" => "both-deleted",
+        "AU" => "added-by-us",
+        "UD" => "deleted-by-them",
+        "UA" => "added-by-them",
+        "DU" => "deleted-by-us",
+        "AA" => "both-added",
+        "UU" => "both-modified",
+        _ => "unknown",
+    }
+}
+
+// Same heuristic git uses: a NUL byte within the first 8000 bytes marks the file as binary.
+async fn is_probably_binary_file(path: &Path) -> bool {
+    match read_file_bytes_limited(path, 8000).await {
+        Ok((bytes, _)) => bytes.contains(&0),
+        Err(_) => false,
+    }
+}
+
+async fn collect_conflicts(root: &Path) -> Vec<GitConflictFile> {
+    // Porcelain v2 reports unmerged entries as:
+    // u <XY> <sub> <m1> <m2> <m3> <mW> <h1> <h2> <h3> <path>
+    let output = match run_git(&["status", "--porcelain=v2", "-z", "-uno"], root).await {
+        Ok(output) => output,
+        Err(_) => return Vec::new(),
+    };
+
+    // Porcelain paths are relative to the repository root, not to `root`.
+    let repo_root = resolve_repo_root(root).await;
+    let mut conflicts = Vec::new();
+    for entry in output.split('\0') {
+        if !entry.starts_with("u ") {
+            continue;
+        }
+        let fields: Vec<&str> = entry.splitn(11, ' ').collect();
+        if fields.len() < 11 {
+            continue;
+        }
+
+        let code = fields[1].to_string();
+        let path = fields[10].to_string();
+        let is_binary = is_probably_binary_file(&repo_root.join(&path)).await;
+        let one_side_deleted = code.contains('D');
+
+        conflicts.push(GitConflictFile {
+            kind: describe_conflict_code(&code).to_string(),
+            resolvable: !is_binary && !one_side_deleted,
+            path,
+            code,
+            is_binary,
+        });
+    }
+
+    conflicts
+}
+
+fn parse_conflict_regions(content: &str) -> Vec<GitConflictRegion> {
+    enum Section {
+        Ours,
+        Base,
+        Theirs,
+    }
+
+    let mut regions = Vec::new();
+    let mut current: Option<(GitConflictRegion, Section)> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+
+        if let Some(label) = line.strip_prefix("<<<<<<<") {
+            current = Some((
+                GitConflictRegion {
+                    start_line: line_number,
+                    end_line: line_number,
+                    ours_label: label.trim().to_string(),
+                    theirs_label: String::new(),
+                    ours: String::new(),
+                    base: None,
+                    theirs: String::new(),
+                },
+                Section::Ours,
+            ));
+            continue;
+        }
+
+        let Some((region, section)) = current.as_mut() else {
+            continue;
+        };
+
+        if line.starts_with("|||||||") && matches!(section, Section::Ours) {
+            region.base = Some(String::new());
+            *section = Section::Base;
+        } else if line == "=======" && !matches!(section, Section::Theirs) {
+            *section = Section::Theirs;
+        } else if let Some(label) = line.strip_prefix(">>>>>>>") {
+            if matches!(section, Section::Theirs) {
+                region.theirs_label = label.trim().to_string();
+                region.end_line = line_number;
+                if let Some((region, _)) = current.take() {
+                    regions.push(region);
+                }
+            }
+        } else {
+            let target = match section {
+                Section::Ours => &mut region.ours,
+                Section::Base => region.base.get_or_insert_with(String::new),
+                Section::Theirs => &mut region.theirs,
+            };
+            target.push_str(line);
+            target.push('\n');
+        }
+    }
+
+    regions
+}
+
+#[tauri::command]
+pub async fn get_conflict_markers(
+    directory: String,
+    file_path: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitConflictMarkersResponse, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+
+    let (_, full_path, relative_path) = resolve_git_paths(&root, &file_path).await;
+
+    let metadata = metadata_with_timeout(&full_path, GIT_FILE_DIFF_TIMEOUT_MS)
+        .await
+        .map_err(|_| {
+            CommandError::not_found(format!("Conflicted file not found: {}", relative_path))
+        })?;
+    if !metadata.is_file() {
+        return Err(CommandError::invalid_argument(format!(
+            "Not a file: {}",
+            relative_path
+        )));
+    }
+
+    if is_probably_binary_file(&full_path).await {
+        return Ok(GitConflictMarkersResponse {
+            path: relative_path,
+            is_binary: true,
+            resolvable: false,
+            regions: Vec::new(),
+        });
+    }
+
+    let (bytes, truncated) = read_file_bytes_limited_with_timeout(
+        &full_path,
+        GIT_FILE_TEXT_MAX_BYTES,
+        GIT_FILE_DIFF_TIMEOUT_MS,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    if truncated {
+        return Err(format!("File is too large to resolve in-app: {}", relative_path).into());
+    }
+
+    let content = String::from_utf8_lossy(&bytes);
+    Ok(GitConflictMarkersResponse {
+        path: relative_path,
+        is_binary: false,
+        resolvable: true,
+        regions: parse_conflict_regions(&content),
     })
 }
 
@@ -922,10 +1700,10 @@ pub async fn get_git_diff(
     staged: Option<bool>,
     context_lines: Option<u32>,
     state: State<'_, DesktopRuntime>,
-) -> Result<String, String> {
+) -> Result<String, CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
 
     let mut args = vec!["diff", "--no-color"];
     let context = format!("-U{}", context_lines.unwrap_or(3));
@@ -956,13 +1734,95 @@ pub async fn get_git_diff(
             ];
             return run_git_with_allowed_exit(&args_no_index, &root, &[1])
                 .await
-                .map_err(|e| e.to_string());
+                .map_err(git_error);
         }
     }
 
     Ok(output)
 }
 
+/// Line counts of what is staged, from `git diff --cached --numstat`; much cheaper than
+/// `get_git_diff` for a commit header. Nothing staged gives an empty summary.
+#[tauri::command]
+pub async fn get_staged_diff_stat(
+    directory: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<StagedDiffStat, CommandError> {
+    let path = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+
+    let output = run_git(&["diff", "--cached", "--numstat", "-z", "-M"], &path)
+        .await
+        .map_err(git_error)?;
+    let files = parse_numstat_z(&output);
+
+    let summary = GitCommitSummary {
+        changes: files.len() as i32,
+        insertions: files.iter().map(|file| file.insertions).sum(),
+        deletions: files.iter().map(|file| file.deletions).sum(),
+    };
+    Ok(StagedDiffStat { files, summary })
+}
+
+/// Diff between two refs (`from..to`), optionally limited to one path. With `merge_base` the
+/// diff is taken from the merge base (`from...to`), i.e. what a PR from `to` into `from` adds.
+#[tauri::command]
+pub async fn get_git_ref_diff(
+    directory: String,
+    from_ref: String,
+    to_ref: String,
+    file_path: Option<String>,
+    merge_base: Option<bool>,
+    context_lines: Option<u32>,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitRefDiffResponse, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+
+    let from = resolve_commit_sha(&root, &from_ref).await?;
+    let to = resolve_commit_sha(&root, &to_ref).await?;
+    let separator = if merge_base.unwrap_or(false) {
+        "..."
+    } else {
+        ".."
+    };
+    let range = format!("{}{}{}", from, separator, to);
+    let context = format!("-U{}", context_lines.unwrap_or(3));
+
+    let mut pathspec: Vec<&str> = vec!["--"];
+    if let Some(path) = file_path.as_deref().filter(|p| !p.trim().is_empty()) {
+        pathspec.push(path);
+    }
+
+    let diff_args = [&["diff", "--no-color", &context, &range][..], &pathspec].concat();
+    let diff = run_git(&diff_args, &root).await.map_err(git_error)?;
+
+    let numstat_args = [&["diff", "--numstat", &range][..], &pathspec].concat();
+    let numstat_output = run_git(&numstat_args, &root).await.map_err(git_error)?;
+    let name_status_args = [&["diff", "--name-status", &range][..], &pathspec].concat();
+    let name_status_output = run_git(&name_status_args, &root).await.unwrap_or_default();
+    let files = parse_changed_files(&numstat_output, &name_status_output);
+
+    let summary = GitCommitSummary {
+        changes: files.len() as i32,
+        insertions: files.iter().map(|file| file.insertions).sum(),
+        deletions: files.iter().map(|file| file.deletions).sum(),
+    };
+    let original_len = diff.len();
+    let diff = cap_ipc_payload(diff);
+
+    Ok(GitRefDiffResponse {
+        from: from_ref,
+        to: to_ref,
+        truncated: diff.len() < original_len,
+        diff,
+        files,
+        summary,
+    })
+}
+
 const IMAGE_EXTENSIONS: &[&str] = &[
     "png", "jpg", "jpeg", "gif", "svg", "webp", "ico", "bmp", "avif",
 ];
@@ -1035,13 +1895,13 @@ pub async fn get_git_file_diff(
     directory: String,
     path_str: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<(String, String), String> {
+) -> Result<(String, String), CommandError> {
     use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
     use tokio::fs;
 
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
 
     let (repo_root, full_path, relative_path) = resolve_path_for_git_show(&root, &path_str).await;
     let is_image = is_image_file(&relative_path);
@@ -1140,15 +2000,95 @@ pub async fn get_git_file_diff(
     Ok((cap_ipc_payload(original), cap_ipc_payload(modified)))
 }
 
+/// Full content of a file as of commit `sha`, for side-by-side views.
+#[tauri::command]
+pub async fn get_file_at_revision(
+    directory: String,
+    sha: String,
+    file_path: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitFileAtRevision, CommandError> {
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    let sha = resolve_commit_sha(&root, &sha).await?;
+    let (repo_root, _, relative_path) = resolve_path_for_git_show(&root, &file_path).await;
+    let spec = format!("{}:{}", sha, relative_path);
+
+    let mut result = GitFileAtRevision {
+        path: relative_path.clone(),
+        sha,
+        exists: false,
+        content: None,
+        is_binary: false,
+        size: 0,
+        truncated: false,
+    };
+
+    let Ok(size) = run_git(&["cat-file", "-s", &spec], &repo_root).await else {
+        return Ok(result);
+    };
+    result.exists = true;
+    result.size = size.trim().parse().unwrap_or(0);
+
+    let is_image = is_image_file(&relative_path);
+    let max_bytes = if is_image {
+        GIT_FILE_IMAGE_MAX_BYTES
+    } else {
+        GIT_FILE_TEXT_MAX_BYTES
+    };
+    if is_image && result.size > max_bytes {
+        result.is_binary = true;
+        result.truncated = true;
+        return Ok(result);
+    }
+
+    // Blobs can be far larger than what is shown, so stop reading at the cap.
+    let (bytes, truncated) = run_git_bytes_limited(
+        &["show", &spec],
+        &repo_root,
+        max_bytes,
+        GIT_FILE_DIFF_TIMEOUT_MS,
+    )
+    .await
+    .map_err(git_error)?;
+
+    if is_image {
+        result.is_binary = true;
+        let mime_type = get_image_mime_type(&relative_path);
+        result.content = Some(format!(
+            "data:{};base64,{}",
+            mime_type,
+            BASE64.encode(&bytes)
+        ));
+        return Ok(result);
+    }
+
+    if bytes[..bytes.len().min(8000)].contains(&0) {
+        result.is_binary = true;
+        return Ok(result);
+    }
+
+    result.truncated = truncated;
+    let text = String::from_utf8_lossy(&bytes).to_string();
+    let original_len = text.len();
+    let text = cap_ipc_payload(text);
+    result.truncated |= text.len() < original_len;
+    result.content = Some(text);
+    Ok(result)
+}
+
 #[tauri::command]
 pub async fn revert_git_file(
     directory: String,
     file_path: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
 
     // Check if tracked
     let is_tracked = run_git(&["ls-files", "--error-unmatch", &file_path], &root)
@@ -1177,31 +2117,175 @@ pub async fn revert_git_file(
     Ok(())
 }
 
+/// Build a patch `git apply` accepts from a hunk fragment sent by the diff view.
+/// Fragments that start at `@@` get file headers for `relative_path` prepended.
+fn build_hunk_patch(relative_path: &str, patch: &str) -> Result<String> {
+    let trimmed = patch.trim_start_matches(['\r', '\n']);
+    if trimmed.trim().is_empty() {
+        return Err(anyhow!("Patch is empty"));
+    }
+
+    let has_headers = trimmed.starts_with("diff --git ") || trimmed.starts_with("--- ");
+    if !has_headers && !trimmed.starts_with("@@") {
+        return Err(anyhow!(
+            "Patch must start with a diff header or a hunk header (@@)"
+        ));
+    }
+
+    let mut result = String::with_capacity(trimmed.len() + relative_path.len() * 2 + 32);
+    if !has_headers {
+        result.push_str(&format!("--- a/{relative_path}\n+++ b/{relative_path}\n"));
+    }
+    result.push_str(trimmed);
+    if !result.ends_with('\n') {
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+async fn apply_hunk_to_index(
+    directory: &str,
+    file_path: &str,
+    patch: &str,
+    reverse: bool,
+    settings: &SettingsStore,
+) -> Result<()> {
+    let root = validate_git_path(directory, settings).await?;
+    let (repo_root, _, relative_path) = resolve_path_for_git_show(&root, file_path).await;
+    let patch = build_hunk_patch(&relative_path, patch)?;
+
+    let mut args = vec!["apply", "--cached", "--recount", "--whitespace=nowarn"];
+    if reverse {
+        args.push("--reverse");
+    }
+
+    let mut check_args = args.clone();
+    check_args.push("--check");
+    check_args.push("-");
+    if let Err(err) = run_git_with_stdin(&check_args, &repo_root, patch.as_bytes()).await {
+        let action = if reverse { "unstaged" } else { "staged" };
+        return Err(anyhow!(
+            "Hunk cannot be {} cleanly; {} may have changed since the diff was generated. Refresh the diff and try again. ({})",
+            action,
+            relative_path,
+            err
+        ));
+    }
+
+    args.push("-");
+    run_git_with_stdin(&args, &repo_root, patch.as_bytes()).await?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stage_git_hunk(
+    directory: String,
+    file_path: String,
+    patch: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<(), CommandError> {
+    apply_hunk_to_index(&directory, &file_path, &patch, false, state.settings())
+        .await
+        .map_err(git_error)
+}
+
+#[tauri::command]
+pub async fn unstage_git_hunk(
+    directory: String,
+    file_path: String,
+    patch: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<(), CommandError> {
+    apply_hunk_to_index(&directory, &file_path, &patch, true, state.settings())
+        .await
+        .map_err(git_error)
+}
+
+/// Git dir and common dir of the repository containing `root`, both absolute. They differ
+/// inside a linked worktree.
+async fn resolve_git_dirs(root: &Path) -> Option<(PathBuf, PathBuf)> {
+    let output = run_git(
+        &["rev-parse", "--absolute-git-dir", "--git-common-dir"],
+        root,
+    )
+    .await
+    .ok()?;
+    let mut lines = output.lines();
+    let git_dir = PathBuf::from(lines.next()?.trim());
+    // --git-common-dir may be relative to the working directory.
+    let common_dir = root.join(lines.next()?.trim());
+    let canonical = |path: PathBuf| std::fs::canonicalize(&path).unwrap_or(path);
+    Some((canonical(git_dir), canonical(common_dir)))
+}
+
+async fn resolve_repo_context(root: &Path) -> GitRepoContext {
+    let Some((git_dir, common_dir)) = resolve_git_dirs(root).await else {
+        return GitRepoContext::default();
+    };
+
+    // The superproject line is only printed for submodules.
+    let toplevel_output = run_git(
+        &[
+            "rev-parse",
+            "--show-toplevel",
+            "--show-superproject-working-tree",
+        ],
+        root,
+    )
+    .await
+    .unwrap_or_default();
+    let mut lines = toplevel_output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    let toplevel = lines.next().map(str::to_string);
+    let superproject = lines.next().map(str::to_string);
+
+    GitRepoContext {
+        is_git_repo: is_git_repository(root).await,
+        is_worktree: git_dir != common_dir,
+        is_submodule: superproject.is_some(),
+        superproject,
+        toplevel,
+        git_dir: Some(git_dir.to_string_lossy().to_string()),
+        common_dir: Some(common_dir.to_string_lossy().to_string()),
+    }
+}
+
 #[tauri::command]
 pub async fn is_linked_worktree(
     directory: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
-    let git_dir = run_git(&["rev-parse", "--git-dir"], &root)
+        .map_err(git_error)?;
+    Ok(resolve_git_dirs(&root)
         .await
-        .unwrap_or_default();
-    let common_dir = run_git(&["rev-parse", "--git-common-dir"], &root)
+        .is_some_and(|(git_dir, common_dir)| git_dir != common_dir))
+}
+
+/// Everything the git panel needs to know about where `directory` sits: repository,
+/// linked worktree and/or submodule, with the relevant paths.
+#[tauri::command]
+pub async fn get_repo_context(
+    directory: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitRepoContext, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
         .await
-        .unwrap_or_default();
-    Ok(git_dir.trim() != common_dir.trim())
+        .map_err(git_error)?;
+    Ok(resolve_repo_context(&root).await)
 }
 
 #[tauri::command]
 pub async fn get_git_branches(
     directory: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<GitBranch, String> {
+) -> Result<GitBranch, CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
 
     // Discover actual remote heads so we can drop stale remote-tracking refs
     let allowed_remote_heads: Option<HashSet<String>> =
@@ -1235,14 +2319,14 @@ pub async fn get_git_branches(
     let output = run_git(
         &[
             "for-each-ref",
-            "--format=%(refname)|%(refname:short)|%(objectname)|%(upstream:short)|%(HEAD)|%(upstream:track)",
+            "--format=%(refname)|%(refname:short)|%(objectname)|%(upstream:short)|%(HEAD)|%(upstream:track)|%(upstream)|%(committerdate:unix)",
             "refs/heads",
             "refs/remotes",
         ],
         &root,
     )
     .await
-    .map_err(|e| e.to_string())?;
+    .map_err(git_error)?;
 
     let mut all = Vec::new();
     let mut current_branch = String::new();
@@ -1250,7 +2334,7 @@ pub async fn get_git_branches(
 
     for line in output.lines() {
         let parts: Vec<&str> = line.split('|').collect();
-        if parts.len() < 6 {
+        if parts.len() < 8 {
             continue;
         }
 
@@ -1260,6 +2344,8 @@ pub async fn get_git_branches(
         let upstream = parts[3].trim();
         let is_current = parts[4] == "*";
         let track_info = parts[5];
+        let upstream_full = parts[6].trim();
+        let last_commit_date = parts[7].trim().parse::<i64>().ok();
 
         let is_remote = full_ref.starts_with("refs/remotes/");
 
@@ -1297,18 +2383,26 @@ pub async fn get_git_branches(
 
         let mut ahead = None;
         let mut behind = None;
+        let mut upstream_gone = false;
 
-        // Parse track info like "[ahead 1, behind 2]"
+        // Parse track info like "[ahead 1, behind 2]" or "[gone]"; an empty value with an
+        // upstream set means the branch is in sync.
         if !track_info.is_empty() {
             let content = track_info.trim_matches(|c| c == '[' || c == ']');
             for part in content.split(", ") {
-                if let Some(val) = part.strip_prefix("ahead ") {
+                if part == "gone" {
+                    upstream_gone = true;
+                } else if let Some(val) = part.strip_prefix("ahead ") {
                     ahead = val.parse().ok();
                 } else if let Some(val) = part.strip_prefix("behind ") {
                     behind = val.parse().ok();
                 }
             }
         }
+        if tracking.is_some() && !upstream_gone {
+            ahead = ahead.or(Some(0));
+            behind = behind.or(Some(0));
+        }
 
         branches.insert(
             normalized_name.clone(),
@@ -1318,8 +2412,15 @@ pub async fn get_git_branches(
                 commit,
                 label: short_name.to_string(),
                 tracking,
+                upstream_ref: if upstream_full.is_empty() {
+                    None
+                } else {
+                    Some(upstream_full.to_string())
+                },
+                upstream_gone,
                 ahead,
                 behind,
+                last_commit_date,
             },
         );
     }
@@ -1337,14 +2438,14 @@ pub async fn delete_git_branch(
     branch: String,
     force: Option<bool>,
     state: State<'_, DesktopRuntime>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
     let flag = if force.unwrap_or(false) { "-D" } else { "-d" };
     run_git(&["branch", flag, &branch], &root)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
     Ok(())
 }
 
@@ -1354,10 +2455,10 @@ pub async fn delete_remote_branch(
     branch: String,
     remote: Option<String>,
     state: State<'_, DesktopRuntime>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
     let remote_name = remote.unwrap_or_else(|| "origin".to_string());
 
     // branch might be refs/heads/foo or just foo
@@ -1365,74 +2466,270 @@ pub async fn delete_remote_branch(
 
     run_git(&["push", &remote_name, "--delete", clean_branch], &root)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
     Ok(())
 }
 
-#[tauri::command]
-pub async fn list_git_worktrees(
-    directory: String,
-    state: State<'_, DesktopRuntime>,
-) -> Result<Vec<GitWorktreeInfo>, String> {
-    let root = validate_git_path(&directory, state.settings())
+async fn validate_tag_name(root: &Path, name: &str) -> Result<(), CommandError> {
+    if name.is_empty() || name.starts_with('-') {
+        return Err(CommandError::invalid_argument(format!(
+            "Invalid tag name: {}",
+            name
+        )));
+    }
+    let ref_name = format!("refs/tags/{}", name);
+    run_git(&["check-ref-format", &ref_name], root)
         .await
-        .map_err(|e| e.to_string())?;
-    let output = run_git(&["worktree", "list", "--porcelain"], &root)
+        .map_err(|_| CommandError::invalid_argument(format!("Invalid tag name: {}", name)))?;
+    Ok(())
+}
+
+async fn tag_exists(root: &Path, name: &str) -> bool {
+    let ref_name = format!("refs/tags/{}", name);
+    run_git(&["show-ref", "--verify", "--quiet", &ref_name], root)
         .await
-        .map_err(|e| e.to_string())?;
+        .is_ok()
+}
 
-    let mut worktrees = Vec::new();
-    let mut current = GitWorktreeInfo {
-        worktree: String::new(),
-        head: None,
-        branch: None,
-    };
+async fn collect_git_tags(root: &Path) -> Result<Vec<GitTagInfo>> {
+    let output = run_git(
+        &[
+            "for-each-ref",
+            "refs/tags",
+            "--sort=-creatordate",
+            "--format=%(refname:short)%1f%(objecttype)%1f%(objectname)%1f%(*objectname)%1f%(creatordate:iso)%1f%(contents)%1e",
+        ],
+        root,
+    )
+    .await?;
 
-    for line in output.lines() {
-        if let Some(cap) = WORKTREE_REGEX.captures(line) {
-            if !current.worktree.is_empty() {
-                worktrees.push(current.clone());
-                current = GitWorktreeInfo {
-                    worktree: String::new(),
-                    head: None,
-                    branch: None,
-                };
-            }
-            current.worktree = cap[1].to_string();
-        } else if let Some(cap) = HEAD_REGEX.captures(line) {
-            current.head = Some(cap[1].to_string());
-        } else if let Some(cap) = BRANCH_REGEX.captures(line) {
-            current.branch = Some(cap[1].trim_start_matches("refs/heads/").to_string());
-        } else if line.is_empty() {
-            if !current.worktree.is_empty() {
-                worktrees.push(current.clone());
-                current = GitWorktreeInfo {
-                    worktree: String::new(),
-                    head: None,
-                    branch: None,
-                };
+    Ok(output
+        .split('\x1e')
+        .map(|record| record.trim_start_matches('\n'))
+        .filter(|record| !record.trim().is_empty())
+        .filter_map(|record| {
+            let parts: Vec<&str> = record.splitn(6, '\x1f').collect();
+            if parts.len() < 6 {
+                return None;
             }
-        }
-    }
-    if !current.worktree.is_empty() {
-        worktrees.push(current);
-    }
-
-    Ok(worktrees)
+            let annotated = parts[1] == "tag";
+            let target = if annotated && !parts[3].is_empty() {
+                parts[3]
+            } else {
+                parts[2]
+            };
+            let message = Some(parts[5].trim())
+                .filter(|message| annotated && !message.is_empty())
+                .map(str::to_string);
+            Some(GitTagInfo {
+                name: parts[0].to_string(),
+                target: target.to_string(),
+                annotated,
+                message,
+                date: Some(parts[4]).filter(|d| !d.is_empty()).map(str::to_string),
+            })
+        })
+        .collect())
 }
 
 #[tauri::command]
-pub async fn add_git_worktree(
+pub async fn get_git_tags(
+    directory: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Vec<GitTagInfo>, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    collect_git_tags(&root).await.map_err(git_error)
+}
+
+/// Create a tag at `sha` (default HEAD). A non-empty `message` makes it an annotated tag.
+#[tauri::command]
+pub async fn create_git_tag(
+    directory: String,
+    name: String,
+    message: Option<String>,
+    sha: Option<String>,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Vec<GitTagInfo>, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    let name = name.trim();
+    validate_tag_name(&root, name).await?;
+    if tag_exists(&root, name).await {
+        return Err(CommandError::new(
+            ErrorCode::Conflict,
+            format!("Tag {} already exists", name),
+        ));
+    }
+
+    let mut args = vec!["tag"];
+    let message = message.as_deref().map(str::trim).filter(|m| !m.is_empty());
+    if let Some(message) = message {
+        args.extend(["-a", name, "-m", message]);
+    } else {
+        args.push(name);
+    }
+    if let Some(sha) = sha.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        if sha.starts_with('-') {
+            return Err(CommandError::invalid_argument(format!(
+                "Invalid commit: {}",
+                sha
+            )));
+        }
+        args.push(sha);
+    }
+
+    run_git(&args, &root).await.map_err(git_error)?;
+    collect_git_tags(&root).await.map_err(git_error)
+}
+
+#[tauri::command]
+pub async fn delete_git_tag(
+    directory: String,
+    name: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Vec<GitTagInfo>, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    let name = name.trim();
+    validate_tag_name(&root, name).await?;
+    if !tag_exists(&root, name).await {
+        return Err(CommandError::not_found(format!("Tag {} not found", name)));
+    }
+
+    run_git(&["tag", "-d", name], &root)
+        .await
+        .map_err(git_error)?;
+    collect_git_tags(&root).await.map_err(git_error)
+}
+
+#[tauri::command]
+pub async fn list_git_worktrees(
+    directory: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Vec<GitWorktreeInfo>, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    collect_git_worktrees(&root).await.map_err(git_error)
+}
+
+async fn collect_git_worktrees(root: &Path) -> Result<Vec<GitWorktreeInfo>> {
+    let output = run_git(&["worktree", "list", "--porcelain"], root).await?;
+
+    let mut worktrees = Vec::new();
+    let mut current = GitWorktreeInfo {
+        worktree: String::new(),
+        head: None,
+        branch: None,
+    };
+
+    for line in output.lines() {
+        if let Some(cap) = WORKTREE_REGEX.captures(line) {
+            if !current.worktree.is_empty() {
+                worktrees.push(current.clone());
+                current = GitWorktreeInfo {
+                    worktree: String::new(),
+                    head: None,
+                    branch: None,
+                };
+            }
+            current.worktree = cap[1].to_string();
+        } else if let Some(cap) = HEAD_REGEX.captures(line) {
+            current.head = Some(cap[1].to_string());
+        } else if let Some(cap) = BRANCH_REGEX.captures(line) {
+            current.branch = Some(cap[1].trim_start_matches("refs/heads/").to_string());
+        } else if line.is_empty() {
+            if !current.worktree.is_empty() {
+                worktrees.push(current.clone());
+                current = GitWorktreeInfo {
+                    worktree: String::new(),
+                    head: None,
+                    branch: None,
+                };
+            }
+        }
+    }
+    if !current.worktree.is_empty() {
+        worktrees.push(current);
+    }
+
+    Ok(worktrees)
+}
+
+/// Drop administrative entries for worktrees whose directories no longer exist.
+#[tauri::command]
+pub async fn prune_git_worktrees(
+    directory: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitWorktreeMaintenanceResult, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+
+    let before = collect_git_worktrees(&root).await.map_err(git_error)?.len();
+    let (stdout, stderr) = run_git_with_stderr(&["worktree", "prune", "--verbose"], &root)
+        .await
+        .map_err(git_error)?;
+    let worktrees = collect_git_worktrees(&root).await.map_err(git_error)?;
+
+    let mut messages = output_lines(&stdout);
+    messages.extend(output_lines(&stderr));
+    Ok(GitWorktreeMaintenanceResult {
+        removed: before.saturating_sub(worktrees.len()),
+        messages,
+        worktrees,
+    })
+}
+
+/// Reconnect worktrees and the main repository after either was moved on disk.
+#[tauri::command]
+pub async fn repair_git_worktrees(
+    directory: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitWorktreeMaintenanceResult, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+
+    let (stdout, stderr) = run_git_with_stderr(&["worktree", "repair"], &root)
+        .await
+        .map_err(git_error)?;
+    let worktrees = collect_git_worktrees(&root).await.map_err(git_error)?;
+
+    let mut messages = output_lines(&stdout);
+    messages.extend(output_lines(&stderr));
+    Ok(GitWorktreeMaintenanceResult {
+        removed: 0,
+        messages,
+        worktrees,
+    })
+}
+
+fn output_lines(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[tauri::command]
+pub async fn add_git_worktree(
     directory: String,
     path_str: String,
     branch: String,
     create_branch: Option<bool>,
     start_point: Option<String>,
     state: State<'_, DesktopRuntime>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
 
     let mut args = vec!["worktree", "add"];
     if create_branch.unwrap_or(false) {
@@ -1450,7 +2747,7 @@ pub async fn add_git_worktree(
         }
     }
 
-    run_git(&args, &root).await.map_err(|e| e.to_string())?;
+    run_git(&args, &root).await.map_err(git_error)?;
     Ok(())
 }
 
@@ -1460,15 +2757,15 @@ pub async fn remove_git_worktree(
     path_str: String,
     force: Option<bool>,
     state: State<'_, DesktopRuntime>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
     let mut args = vec!["worktree", "remove", &path_str];
     if force.unwrap_or(false) {
         args.push("--force");
     }
-    run_git(&args, &root).await.map_err(|e| e.to_string())?;
+    run_git(&args, &root).await.map_err(git_error)?;
     Ok(())
 }
 
@@ -1477,10 +2774,10 @@ pub async fn ensure_openchamber_ignored(
     // LEGACY_WORKTREES: only needed for <project>/.openchamber era. Safe to remove after legacy support dropped.
     directory: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
     let exclude_path = root.join(".git/info/exclude");
 
     if let Some(parent) = exclude_path.parent() {
@@ -1504,35 +2801,56 @@ pub async fn ensure_openchamber_ignored(
     Ok(())
 }
 
+/// Create a commit, or amend HEAD when `amend` is set (an empty message keeps the existing one).
+/// `sign` forces signing on or off; `None` defers to the repository config.
 #[tauri::command]
 pub async fn create_git_commit(
     directory: String,
     message: String,
     add_all: Option<bool>,
     files: Option<Vec<String>>,
+    amend: Option<bool>,
+    sign: Option<bool>,
     state: State<'_, DesktopRuntime>,
-) -> Result<GitCommitResult, String> {
+) -> Result<GitCommitResult, CommandError> {
+    let amend = amend.unwrap_or(false);
+    if !amend && message.trim().is_empty() {
+        return Err(CommandError::invalid_argument("Commit message is required"));
+    }
+
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
 
     if add_all.unwrap_or(false) {
-        run_git(&["add", "."], &root)
-            .await
-            .map_err(|e| e.to_string())?;
+        run_git(&["add", "."], &root).await.map_err(git_error)?;
     } else if let Some(file_list) = files {
         if !file_list.is_empty() {
             let mut args = vec!["add"];
             args.extend(file_list.iter().map(|s| s.as_str()));
-            run_git(&args, &root).await.map_err(|e| e.to_string())?;
+            run_git(&args, &root).await.map_err(git_error)?;
         }
     }
 
-    run_git(&["commit", "-m", &message], &root)
-        .await
-        .map_err(|e| e.to_string())?;
+    let mut args = vec!["commit"];
+    if amend {
+        // The original author is kept; the committer is the identity set for this repo.
+        args.push("--amend");
+    }
+    if amend && message.trim().is_empty() {
+        args.push("--no-edit");
+    } else {
+        args.extend(["-m", message.as_str()]);
+    }
+    match sign {
+        Some(true) => args.push("-S"),
+        Some(false) => args.push("--no-gpg-sign"),
+        None => {}
+    }
+
+    run_git(&args, &root).await.map_err(git_error)?;
 
-    let commit_hash = get_head_hash(&root).await.map_err(|e| e.to_string())?;
+    let commit_hash = get_head_hash(&root).await.map_err(git_error)?;
     let branch_name = get_current_branch_name(&root)
         .await
         .unwrap_or_else(|_| "HEAD".to_string());
@@ -1550,25 +2868,80 @@ pub async fn create_git_commit(
     })
 }
 
+/// Map the `force` argument of `git_push` to whether `--force-with-lease` is used. A bare
+/// `--force` is deliberately not offered.
+fn parse_push_force(force: Option<&str>) -> Result<bool, CommandError> {
+    match force.map(str::trim).unwrap_or_default() {
+        "" | "none" => Ok(false),
+        "lease" => Ok(true),
+        "force" => Err(CommandError::invalid_argument(
+            "Plain force push is not supported; use force \"lease\" instead",
+        )),
+        other => Err(CommandError::invalid_argument(format!(
+            "Unsupported force mode: {}",
+            other
+        ))),
+    }
+}
+
+fn is_force_push_flag(arg: &str) -> bool {
+    arg == "-f"
+        || arg == "--force"
+        || arg.starts_with("--force-with-lease")
+        || arg == "--force-if-includes"
+        || arg == "--mirror"
+}
+
+/// How `git_push` pushes, beyond the remote, branch and extra git options.
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GitPushOptions {
+    /// Also push all tags.
+    pub push_tags: Option<bool>,
+    /// `"none"` (default) or `"lease"` for `--force-with-lease`.
+    pub force: Option<String>,
+    /// Id of the progress events; generated when omitted.
+    pub operation_id: Option<String>,
+}
+
 #[tauri::command]
 pub async fn git_push(
     directory: String,
     remote: Option<String>,
     branch: Option<String>,
     options: Option<Value>,
+    push_options: Option<GitPushOptions>,
+    app: AppHandle,
     state: State<'_, DesktopRuntime>,
-) -> Result<GitPushResult, String> {
+) -> Result<GitPushResult, CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
+    let GitPushOptions {
+        push_tags,
+        force,
+        operation_id,
+    } = push_options.unwrap_or_default();
+    let force_with_lease = parse_push_force(force.as_deref())?;
+    let operation_id = resolve_operation_id(operation_id);
     let remote_name = remote.unwrap_or_else(|| "origin".to_string());
     let explicit_branch = branch
         .as_deref()
         .map(|value| !value.trim().is_empty())
         .unwrap_or(false);
     let mut branch_name = branch.unwrap_or_default();
+    // A leading "+" in the refspec force-pushes just like --force.
+    if branch_name.trim_start().starts_with('+') {
+        return Err(CommandError::invalid_argument(
+            "Branch name must not start with '+'",
+        ));
+    }
 
-    let mut args = vec!["push".to_string(), remote_name.clone()];
+    let mut args = vec![
+        "push".to_string(),
+        "--progress".to_string(),
+        remote_name.clone(),
+    ];
     if branch_name.is_empty() {
         branch_name = get_current_branch_name(&root).await.unwrap_or_default();
     }
@@ -1598,17 +2971,51 @@ pub async fn git_push(
         args.push(branch_name.clone());
     }
 
+    if push_tags.unwrap_or(false) {
+        args.push("--tags".to_string());
+    }
+
     if let Some(extra) = options.as_ref() {
-        append_git_option(&mut args, extra);
+        let mut extra_args = Vec::new();
+        append_git_option(&mut extra_args, extra);
+        if extra_args.iter().any(|arg| is_force_push_flag(arg)) {
+            return Err(CommandError::invalid_argument(
+                "Force flags are not accepted in push options; use force \"lease\"",
+            ));
+        }
+        args.extend(extra_args);
     }
 
-    let arg_refs: Vec<&str> = args.iter().map(|value| value.as_str()).collect();
+    if force_with_lease {
+        // Without an explicit expected value git compares against our remote-tracking ref,
+        // so the push fails if the remote has commits we haven't fetched.
+        args.insert(1, "--force-with-lease".to_string());
+    }
 
-    // TODO: Streaming? Frontend types.ts defines GitPushResult, but doesn't mention streaming response for this call,
-    // but Stage 2 plan says "streaming progress events for long operations".
-    // Implementing simple await for now as `simple-git` wrapper does in `git-service.js`.
+    let arg_refs: Vec<&str> = args.iter().map(|value| value.as_str()).collect();
 
-    run_git(&arg_refs, &root).await.map_err(|e| e.to_string())?;
+    if let Err(err) = run_git_with_progress(&arg_refs, &root, &app, &operation_id, "push").await {
+        if force_with_lease && err.to_string().contains("(stale info)") {
+            return Ok(GitPushResult {
+                success: false,
+                pushed: vec![],
+                repo: remote_name,
+                ref_: if branch_name.is_empty() {
+                    None
+                } else {
+                    Some(branch_name)
+                },
+                operation_id,
+                rejection: Some(GitPushRejection {
+                    reason: "stale_lease".to_string(),
+                    message: "The remote branch has commits that have not been fetched yet. \
+                              Fetch and review them before force pushing again."
+                        .to_string(),
+                }),
+            });
+        }
+        return Err(git_error(err));
+    }
 
     Ok(GitPushResult {
         success: true,
@@ -1626,6 +3033,8 @@ pub async fn git_push(
         } else {
             Some(branch_name)
         },
+        operation_id,
+        rejection: None,
     })
 }
 
@@ -1634,22 +3043,27 @@ pub async fn git_pull(
     directory: String,
     remote: Option<String>,
     branch: Option<String>,
+    operation_id: Option<String>,
+    app: AppHandle,
     state: State<'_, DesktopRuntime>,
-) -> Result<GitPullResult, String> {
+) -> Result<GitPullResult, CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
+    let operation_id = resolve_operation_id(operation_id);
     let r = remote.unwrap_or_else(|| "origin".to_string());
     let b = branch.unwrap_or_default();
 
-    let mut args = vec!["pull", &r];
+    let mut args = vec!["pull", "--progress", &r];
     if !b.is_empty() {
         args.push(&b);
     }
 
     let previous_head = get_head_hash(&root).await.ok();
 
-    run_git(&args, &root).await.map_err(|e| e.to_string())?;
+    run_git_with_progress(&args, &root, &app, &operation_id, "pull")
+        .await
+        .map_err(git_error)?;
 
     let (summary, files) = if let Some(previous) = previous_head {
         let new_head = get_head_hash(&root).await.unwrap_or(previous.clone());
@@ -1693,6 +3107,7 @@ pub async fn git_pull(
         files,
         insertions: summary.insertions,
         deletions: summary.deletions,
+        operation_id,
     })
 }
 
@@ -1700,16 +3115,366 @@ pub async fn git_pull(
 pub async fn git_fetch(
     directory: String,
     remote: Option<String>,
+    operation_id: Option<String>,
+    app: AppHandle,
     state: State<'_, DesktopRuntime>,
-) -> Result<(), String> {
+) -> Result<GitFetchResult, CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
+    let operation_id = resolve_operation_id(operation_id);
     let r = remote.unwrap_or_else(|| "origin".to_string());
-    run_git(&["fetch", &r], &root)
+    let (_, stderr) = run_git_with_progress(
+        &["fetch", "--progress", &r],
+        &root,
+        &app,
+        &operation_id,
+        "fetch",
+    )
+    .await
+    .map_err(git_error)?;
+    Ok(GitFetchResult {
+        remote: r,
+        refs: parse_fetch_output(&stderr),
+        operation_id,
+    })
+}
+
+async fn resolve_commit_sha(root: &Path, sha: &str) -> Result<String, CommandError> {
+    let sha = sha.trim();
+    if sha.is_empty() || sha.starts_with('-') {
+        return Err(CommandError::invalid_argument(format!(
+            "Invalid commit: {}",
+            sha
+        )));
+    }
+    let spec = format!("{}^{{commit}}", sha);
+    run_git(&["rev-parse", "--verify", "--quiet", &spec], root)
         .await
-        .map_err(|e| e.to_string())?;
-    Ok(())
+        .ok()
+        .filter(|resolved| !resolved.is_empty())
+        .ok_or_else(|| CommandError::not_found(format!("Commit not found: {}", sha)))
+}
+
+async fn has_staged_changes(root: &Path) -> bool {
+    // `diff --quiet` exits 1 when there are differences.
+    run_git(&["diff", "--cached", "--quiet"], root)
+        .await
+        .is_err()
+}
+
+/// Run a cherry-pick/revert and classify failures: conflicts are left in place for the user
+/// to resolve, an empty result is aborted and reported as `empty`, anything else is an error.
+async fn run_commit_operation(
+    root: &Path,
+    args: &[&str],
+    operation: &str,
+    pending_ref: &str,
+    no_commit: bool,
+) -> Result<GitCommitOperationResult, CommandError> {
+    if let Err(err) = run_git(args, root).await {
+        let conflicts = collect_conflicts(root).await;
+        if !conflicts.is_empty() {
+            return Ok(GitCommitOperationResult {
+                success: false,
+                status: "conflict",
+                commit: None,
+                conflicts,
+            });
+        }
+
+        let in_progress = run_git(&["rev-parse", "--verify", "--quiet", pending_ref], root)
+            .await
+            .is_ok();
+        if in_progress && !has_staged_changes(root).await {
+            let _ = run_git(&[operation, "--abort"], root).await;
+            return Ok(GitCommitOperationResult {
+                success: true,
+                status: "empty",
+                commit: None,
+                conflicts: Vec::new(),
+            });
+        }
+
+        return Err(git_error(err));
+    }
+
+    if no_commit {
+        let staged = has_staged_changes(root).await;
+        return Ok(GitCommitOperationResult {
+            success: true,
+            status: if staged { "staged" } else { "empty" },
+            commit: None,
+            conflicts: Vec::new(),
+        });
+    }
+
+    Ok(GitCommitOperationResult {
+        success: true,
+        status: "applied",
+        commit: get_head_hash(root).await.ok(),
+        conflicts: Vec::new(),
+    })
+}
+
+#[tauri::command]
+pub async fn cherry_pick_commit(
+    directory: String,
+    sha: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitCommitOperationResult, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    let sha = resolve_commit_sha(&root, &sha).await?;
+
+    run_commit_operation(
+        &root,
+        &["cherry-pick", &sha],
+        "cherry-pick",
+        "CHERRY_PICK_HEAD",
+        false,
+    )
+    .await
+}
+
+/// Revert a commit. With `no_commit` the inverse changes are only staged.
+#[tauri::command]
+pub async fn revert_commit(
+    directory: String,
+    sha: String,
+    no_commit: Option<bool>,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitCommitOperationResult, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    let sha = resolve_commit_sha(&root, &sha).await?;
+    let no_commit = no_commit.unwrap_or(false);
+
+    let mut args = vec!["revert", "--no-edit"];
+    if no_commit {
+        args.push("--no-commit");
+    }
+    args.push(&sha);
+
+    run_commit_operation(&root, &args, "revert", "REVERT_HEAD", no_commit).await
+}
+
+async fn read_step_file(path: PathBuf) -> Option<u32> {
+    fs::read_to_string(path).await.ok()?.trim().parse().ok()
+}
+
+/// Detect an interrupted merge, rebase, cherry-pick or revert from the marker files git
+/// leaves in the (per-worktree) git dir.
+async fn detect_git_operation(root: &Path) -> GitOperationState {
+    let Some((git_dir, _)) = resolve_git_dirs(root).await else {
+        return GitOperationState::default();
+    };
+
+    let mut state = GitOperationState::default();
+    let rebase_merge = git_dir.join("rebase-merge");
+    let rebase_apply = git_dir.join("rebase-apply");
+    if rebase_merge.is_dir() {
+        state.operation = Some("rebase");
+        state.step = read_step_file(rebase_merge.join("msgnum")).await;
+        state.total_steps = read_step_file(rebase_merge.join("end")).await;
+    } else if rebase_apply.is_dir() && !rebase_apply.join("applying").exists() {
+        // rebase-apply with an `applying` marker belongs to `git am`, not rebase.
+        state.operation = Some("rebase");
+        state.step = read_step_file(rebase_apply.join("next")).await;
+        state.total_steps = read_step_file(rebase_apply.join("last")).await;
+    } else if git_dir.join("CHERRY_PICK_HEAD").exists() {
+        state.operation = Some("cherry-pick");
+    } else if git_dir.join("REVERT_HEAD").exists() {
+        state.operation = Some("revert");
+    } else if git_dir.join("MERGE_HEAD").exists() {
+        state.operation = Some("merge");
+    }
+
+    if state.operation.is_some() {
+        state.conflicts = collect_conflicts(root).await;
+    }
+    state
+}
+
+/// Run `git <operation> --abort` or `--continue` for whatever is in progress.
+async fn run_git_operation_step(
+    root: &Path,
+    step: &str,
+) -> Result<GitOperationResult, CommandError> {
+    let current = detect_git_operation(root).await;
+    let Some(operation) = current.operation else {
+        return Err(CommandError::new(
+            ErrorCode::Conflict,
+            "No merge, rebase, cherry-pick or revert is in progress",
+        ));
+    };
+    if step == "--continue" && !current.conflicts.is_empty() {
+        return Err(CommandError::new(
+            ErrorCode::Conflict,
+            format!(
+                "Resolve {} conflicted file(s) before continuing the {}",
+                current.conflicts.len(),
+                operation
+            ),
+        )
+        .with_details(serde_json::json!({ "conflicts": current.conflicts })));
+    }
+
+    // Accept the prepared commit messages instead of waiting for an editor.
+    let result = run_git_without_editor(&[operation, step], root).await;
+    let state = detect_git_operation(root).await;
+    match result {
+        Ok(_) => Ok(GitOperationResult {
+            success: true,
+            operation,
+            state,
+        }),
+        // A rebase continues onto the next commit, which may conflict in turn.
+        Err(_) if step == "--continue" && !state.conflicts.is_empty() => Ok(GitOperationResult {
+            success: false,
+            operation,
+            state,
+        }),
+        Err(err) => Err(git_error(err)),
+    }
+}
+
+#[tauri::command]
+pub async fn git_operation_state(
+    directory: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitOperationState, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    Ok(detect_git_operation(&root).await)
+}
+
+/// Abort the in-progress merge, rebase, cherry-pick or revert.
+#[tauri::command]
+pub async fn git_operation_abort(
+    directory: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitOperationResult, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    run_git_operation_step(&root, "--abort").await
+}
+
+/// Continue the in-progress operation once its conflicts are resolved and staged.
+#[tauri::command]
+pub async fn git_operation_continue(
+    directory: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitOperationResult, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    run_git_operation_step(&root, "--continue").await
+}
+
+/// Recent HEAD reflog entries, newest first, for recovering from a bad rebase or reset.
+#[tauri::command]
+pub async fn get_git_reflog(
+    directory: String,
+    max_count: Option<u32>,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Vec<GitReflogEntry>, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    let max = max_count.unwrap_or(50).clamp(1, 1000).to_string();
+
+    // A repository without commits has no reflog yet.
+    let output = run_git_with_allowed_exit(
+        &[
+            "reflog",
+            "show",
+            "--format=%H%x1f%gd%x1f%gs%x1f%cI",
+            "-n",
+            &max,
+            "HEAD",
+            "--",
+        ],
+        &root,
+        &[128],
+    )
+    .await
+    .map_err(git_error)?;
+
+    let entries = output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split('\x1f');
+            let hash = parts.next()?.trim();
+            let selector = parts.next()?.trim();
+            let subject = parts.next()?.trim();
+            let date = parts.next().unwrap_or_default().trim();
+            if hash.is_empty() {
+                return None;
+            }
+            let (action, message) = match subject.split_once(": ") {
+                Some((action, message)) => (action, message),
+                None => (subject, ""),
+            };
+            Some(GitReflogEntry {
+                hash: hash.to_string(),
+                selector: selector.to_string(),
+                action: action.to_string(),
+                message: message.to_string(),
+                date: date.to_string(),
+            })
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// Move the current branch to `sha`. `hard` discards uncommitted changes, so it only runs
+/// with `confirm_hard` set.
+#[tauri::command]
+pub async fn reset_to(
+    directory: String,
+    sha: String,
+    mode: Option<String>,
+    confirm_hard: Option<bool>,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitResetResult, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    let mode = match mode.as_deref().map(str::trim).unwrap_or("mixed") {
+        "soft" => "soft",
+        "" | "mixed" => "mixed",
+        "hard" => "hard",
+        other => {
+            return Err(CommandError::invalid_argument(format!(
+                "Unsupported reset mode: {}",
+                other
+            )))
+        }
+    };
+    if mode == "hard" && !confirm_hard.unwrap_or(false) {
+        return Err(CommandError::invalid_argument(
+            "Hard reset discards uncommitted changes and must be confirmed",
+        ));
+    }
+    let sha = resolve_commit_sha(&root, &sha).await?;
+    let previous = get_head_hash(&root).await.ok();
+
+    let flag = format!("--{}", mode);
+    run_git(&["reset", &flag, &sha, "--"], &root)
+        .await
+        .map_err(git_error)?;
+    info!("Reset {} to {} ({})", root.display(), sha, mode);
+
+    Ok(GitResetResult {
+        mode,
+        previous,
+        head: get_head_hash(&root).await.map_err(git_error)?,
+    })
 }
 
 #[tauri::command]
@@ -1717,13 +3482,13 @@ pub async fn checkout_branch(
     directory: String,
     branch: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
     run_git(&["checkout", &branch], &root)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
     Ok(())
 }
 
@@ -1733,14 +3498,14 @@ pub async fn create_branch(
     name: String,
     start_point: Option<String>,
     state: State<'_, DesktopRuntime>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
     let start = start_point.unwrap_or_else(|| "HEAD".to_string());
     run_git(&["checkout", "-b", &name, &start], &root)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
     Ok(())
 }
 
@@ -1750,13 +3515,13 @@ pub async fn rename_branch(
     old_name: String,
     new_name: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
     run_git(&["branch", "-m", &old_name, &new_name], &root)
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
     Ok(())
 }
 
@@ -1768,10 +3533,10 @@ pub async fn get_git_log(
     to: Option<String>,
     file: Option<String>,
     state: State<'_, DesktopRuntime>,
-) -> Result<GitLogResponse, String> {
+) -> Result<GitLogResponse, CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
 
     let max = max_count.unwrap_or(50).to_string();
     let mut args = vec![
@@ -1799,7 +3564,7 @@ pub async fn get_git_log(
         args.push(f);
     }
 
-    let output = run_git(&args, &root).await.map_err(|e| e.to_string())?;
+    let output = run_git(&args, &root).await.map_err(git_error)?;
 
     let mut entries = Vec::new();
     let entries_raw: Vec<&str> = output.split('\x1e').collect();
@@ -1888,29 +3653,265 @@ pub async fn get_git_log(
             }
         }
     }
-
-    Ok(GitLogResponse {
-        all: entries.clone(),
-        latest: entries.first().cloned(),
-        total: entries.len() as i32,
-    })
+
+    Ok(GitLogResponse {
+        all: entries.clone(),
+        latest: entries.first().cloned(),
+        total: entries.len() as i32,
+    })
+}
+
+/// Commit topology for drawing a branch graph: each commit with its parents and the refs that
+/// point at it. Walks `branch` (default HEAD), or every ref when `all` is set.
+#[tauri::command]
+pub async fn get_git_log_graph(
+    directory: String,
+    max_count: Option<i32>,
+    branch: Option<String>,
+    all: Option<bool>,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitLogGraphResponse, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+
+    let max = max_count.unwrap_or(200).max(1).to_string();
+    let mut args = vec![
+        "log",
+        "--max-count",
+        &max,
+        "--topo-order",
+        "--date=iso",
+        "--decorate=full",
+        "--pretty=format:%H%x1f%P%x1f%an%x1f%ae%x1f%ad%x1f%s%x1f%D%x1e",
+    ];
+    if all.unwrap_or(false) {
+        args.push("--all");
+    } else if let Some(name) = branch.as_deref().map(str::trim).filter(|b| !b.is_empty()) {
+        if name.starts_with('-') {
+            return Err(CommandError::invalid_argument("Invalid branch name"));
+        }
+        args.push(name);
+    }
+    args.push("--");
+
+    let output = run_git(&args, &root).await.map_err(git_error)?;
+
+    let commits: Vec<GitGraphCommit> = output
+        .split('\x1e')
+        .map(str::trim)
+        .filter(|record| !record.is_empty())
+        .filter_map(|record| {
+            let parts: Vec<&str> = record.split('\x1f').collect();
+            if parts.len() < 7 {
+                return None;
+            }
+            Some(GitGraphCommit {
+                hash: parts[0].to_string(),
+                parents: parts[1].split_whitespace().map(str::to_string).collect(),
+                author_name: parts[2].to_string(),
+                author_email: parts[3].to_string(),
+                date: parts[4].to_string(),
+                message: parts[5].to_string(),
+                refs: parse_graph_decorations(parts[6]),
+            })
+        })
+        .collect();
+
+    let head = run_git(&["rev-parse", "--verify", "--quiet", "HEAD"], &root)
+        .await
+        .ok()
+        .filter(|hash| !hash.is_empty());
+
+    Ok(GitLogGraphResponse { commits, head })
+}
+
+/// Parse a `%D` decoration list produced with `--decorate=full`.
+fn parse_graph_decorations(decorations: &str) -> Vec<GitGraphRef> {
+    let mut refs = Vec::new();
+    for decoration in decorations.split(", ").map(str::trim) {
+        if decoration.is_empty() {
+            continue;
+        }
+        if decoration == "HEAD" {
+            refs.push(GitGraphRef {
+                name: "HEAD".to_string(),
+                kind: "head",
+                is_head: true,
+            });
+            continue;
+        }
+
+        let (is_head, full_name) = match decoration.strip_prefix("HEAD -> ") {
+            Some(target) => (true, target),
+            None => (false, decoration),
+        };
+        let full_name = full_name.strip_prefix("tag: ").unwrap_or(full_name);
+
+        let (kind, name) = if let Some(name) = full_name.strip_prefix("refs/heads/") {
+            ("branch", name)
+        } else if let Some(name) = full_name.strip_prefix("refs/remotes/") {
+            // Skip the symbolic origin/HEAD pointer; it duplicates the remote default branch.
+            if name.ends_with("/HEAD") {
+                continue;
+            }
+            ("remote", name)
+        } else if let Some(name) = full_name.strip_prefix("refs/tags/") {
+            ("tag", name)
+        } else {
+            continue;
+        };
+
+        refs.push(GitGraphRef {
+            name: name.to_string(),
+            kind,
+            is_head,
+        });
+    }
+    refs
+}
+
+/// Branch headers and file records of `git status --porcelain=v2 --branch -z`.
+#[derive(Default)]
+struct PorcelainV2Status {
+    current: String,
+    tracking: Option<String>,
+    ahead: i32,
+    behind: i32,
+    files: Vec<GitStatusFile>,
+}
+
+fn parse_status_porcelain_v2(output: &str) -> PorcelainV2Status {
+    let mut status = PorcelainV2Status::default();
+    let entries: Vec<&str> = output.split('\0').collect();
+    let mut i = 0usize;
+
+    while i < entries.len() {
+        let entry = entries[i];
+        i += 1;
+
+        if entry.is_empty() {
+            continue;
+        }
+
+        // Branch headers: "# branch.head main", "# branch.upstream origin/main", "# branch.ab +1 -2"
+        if let Some(header) = entry.strip_prefix("# ") {
+            if let Some(head) = header.strip_prefix("branch.head ") {
+                // Keep reporting a detached HEAD the way porcelain v1 did.
+                status.current = if head == "(detached)" {
+                    "HEAD (no branch)".to_string()
+                } else {
+                    head.to_string()
+                };
+            } else if let Some(upstream) = header.strip_prefix("branch.upstream ") {
+                status.tracking = Some(upstream.to_string());
+            } else if let Some(ab) = header.strip_prefix("branch.ab ") {
+                for part in ab.split_whitespace() {
+                    if let Some(val) = part.strip_prefix('+') {
+                        status.ahead = val.parse().unwrap_or(0);
+                    } else if let Some(val) = part.strip_prefix('-') {
+                        status.behind = val.parse().unwrap_or(0);
+                    }
+                }
+            }
+            continue;
+        }
+
+        // File entries (porcelain v2, -z):
+        // - Changed:   1 XY sub mH mI mW hH hI path
+        // - Renamed:   2 XY sub mH mI mW hH hI Xscore path<null>origPath
+        // - Unmerged:  u XY sub m1 m2 m3 mW h1 h2 h3 path
+        // - Untracked: ? path
+        let (kind, rest) = entry.split_at(1);
+        let Some(rest) = rest.strip_prefix(' ') else {
+            continue;
+        };
+        let file = match kind {
+            "?" => GitStatusFile {
+                path: rest.to_string(),
+                index: "?".to_string(),
+                working_dir: "?".to_string(),
+                original_path: None,
+            },
+            "1" | "2" | "u" => {
+                let field_count = match kind {
+                    "1" => 8,
+                    "2" => 9,
+                    _ => 10,
+                };
+                let mut fields = rest.splitn(field_count, ' ');
+                let xy = fields.next().unwrap_or("..");
+                let Some(file_path) = fields.nth(field_count - 2) else {
+                    continue;
+                };
+                let original_path = if kind == "2" && i < entries.len() {
+                    let original = entries[i];
+                    i += 1;
+                    Some(original.to_string())
+                } else {
+                    None
+                };
+                // "." marks an unchanged side.
+                let mut codes = xy.chars().map(|c| {
+                    if c == '.' {
+                        String::new()
+                    } else {
+                        c.to_string()
+                    }
+                });
+                GitStatusFile {
+                    path: file_path.to_string(),
+                    index: codes.next().unwrap_or_default(),
+                    working_dir: codes.next().unwrap_or_default(),
+                    original_path,
+                }
+            }
+            _ => continue,
+        };
+        status.files.push(file);
+    }
+    status
 }
 
-#[tauri::command]
-pub async fn get_commit_files(
-    directory: String,
-    hash: String,
-    state: State<'_, DesktopRuntime>,
-) -> Result<GitCommitFilesResponse, String> {
-    let root = validate_git_path(&directory, state.settings())
-        .await
-        .map_err(|e| e.to_string())?;
+/// Parse `git diff --numstat -z`. Renames and copies come as an empty path field followed
+/// by the old and new paths as separate NUL-terminated entries.
+fn parse_numstat_z(output: &str) -> Vec<StagedFileStat> {
+    let mut files = Vec::new();
+    let mut entries = output.split('\0');
 
-    // Get numstat for insertions/deletions per file
-    let numstat_output = run_git(&["show", "--numstat", "--format=", &hash], &root)
-        .await
-        .map_err(|e| e.to_string())?;
+    while let Some(entry) = entries.next() {
+        let mut fields = entry.splitn(3, '\t');
+        let (Some(insertions_raw), Some(deletions_raw), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+
+        let (old_path, path) = if path.is_empty() {
+            let (Some(old_path), Some(new_path)) = (entries.next(), entries.next()) else {
+                break;
+            };
+            (Some(old_path.to_string()), new_path.to_string())
+        } else {
+            (None, path.to_string())
+        };
+
+        // Binary files show '-' for stats
+        let is_binary = insertions_raw == "-" && deletions_raw == "-";
+        files.push(StagedFileStat {
+            path,
+            old_path,
+            insertions: insertions_raw.parse().unwrap_or(0),
+            deletions: deletions_raw.parse().unwrap_or(0),
+            is_binary,
+        });
+    }
+
+    files
+}
 
+/// Combine `--numstat` and `--name-status` output into per-file entries.
+fn parse_changed_files(numstat_output: &str, name_status_output: &str) -> Vec<CommitFileEntry> {
     let mut files = Vec::new();
 
     for line in numstat_output.lines() {
@@ -1949,11 +3950,6 @@ pub async fn get_commit_files(
         });
     }
 
-    // Get accurate change types using --name-status
-    let name_status_output = run_git(&["show", "--name-status", "--format=", &hash], &root)
-        .await
-        .unwrap_or_default();
-
     let mut status_map: HashMap<String, String> = HashMap::new();
     for line in name_status_output.lines() {
         let parts: Vec<&str> = line.split('\t').collect();
@@ -1984,63 +3980,319 @@ pub async fn get_commit_files(
         }
     }
 
-    Ok(GitCommitFilesResponse { files })
+    files
 }
 
 #[tauri::command]
-pub async fn get_git_identities() -> Result<Vec<GitIdentityProfile>, String> {
-    load_identities().await.map_err(|e| e.to_string())
+pub async fn get_commit_files(
+    directory: String,
+    hash: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitCommitFilesResponse, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+
+    // Get numstat for insertions/deletions per file
+    let numstat_output = run_git(&["show", "--numstat", "--format=", &hash], &root)
+        .await
+        .map_err(git_error)?;
+
+    // Get accurate change types using --name-status
+    let name_status_output = run_git(&["show", "--name-status", "--format=", &hash], &root)
+        .await
+        .unwrap_or_default();
+
+    let files = parse_changed_files(&numstat_output, &name_status_output);
+
+    Ok(GitCommitFilesResponse { files })
 }
 
 #[tauri::command]
-pub async fn create_git_identity(
-    profile: GitIdentityProfile,
-) -> Result<GitIdentityProfile, String> {
-    let mut profiles = load_identities().await.map_err(|e| e.to_string())?;
+pub async fn get_git_identities() -> Result<Vec<GitIdentityProfile>, CommandError> {
+    load_identities().await.map_err(CommandError::from)
+}
+
+async fn insert_identity(profile: GitIdentityProfile) -> Result<GitIdentityProfile, CommandError> {
+    let mut profiles = load_identities().await?;
     if profiles.iter().any(|p| p.id == profile.id) {
-        return Err(format!("Profile with ID {} already exists", profile.id));
+        return Err(CommandError::new(
+            ErrorCode::Conflict,
+            format!("Profile with ID {} already exists", profile.id),
+        ));
     }
     profiles.push(profile.clone());
-    save_identities(profiles).await.map_err(|e| e.to_string())?;
+    save_identities(profiles).await?;
     Ok(profile)
 }
 
+#[tauri::command]
+pub async fn create_git_identity(
+    profile: GitIdentityProfile,
+) -> Result<GitIdentityProfile, CommandError> {
+    insert_identity(profile).await
+}
+
+/// Save the repository's `user.name`/`user.email` (and signing key, if any) as a managed
+/// identity. Only the repo-local config is read unless `include_global` is set, in which case
+/// the effective value (local, then global/system) is used. An identity with the same name and
+/// email is returned instead of creating a duplicate.
+#[tauri::command]
+pub async fn import_git_identity_from_repo(
+    directory: String,
+    label: Option<String>,
+    include_global: Option<bool>,
+    state: State<'_, DesktopRuntime>,
+) -> Result<GitIdentityImportResult, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    let include_global = include_global.unwrap_or(false);
+
+    let read_config = |key: &'static str| {
+        let root = root.clone();
+        async move {
+            let mut args = vec!["config"];
+            if !include_global {
+                args.push("--local");
+            }
+            args.extend(["--get", key]);
+            run_git_with_allowed_exit(&args, &root, &[1])
+                .await
+                .ok()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        }
+    };
+
+    let (Some(user_name), Some(user_email)) = (
+        read_config("user.name").await,
+        read_config("user.email").await,
+    ) else {
+        return Err(CommandError::not_found(if include_global {
+            "No user.name and user.email are configured for this repository"
+        } else {
+            "This repository has no local user.name and user.email"
+        }));
+    };
+
+    let profiles = load_identities().await?;
+    if let Some(existing) = profiles.into_iter().find(|profile| {
+        profile.user_name.trim() == user_name
+            && profile.user_email.trim().eq_ignore_ascii_case(&user_email)
+    }) {
+        return Ok(GitIdentityImportResult {
+            identity: existing,
+            created: false,
+        });
+    }
+
+    let signing_key = read_config("user.signingkey").await;
+    let signing_format = match signing_key {
+        Some(_) => read_config("gpg.format").await,
+        None => None,
+    };
+    let name = label
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| user_name.clone());
+    // Same id shape and defaults as profiles created from the identities settings page.
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let profile = GitIdentityProfile {
+        id: format!(
+            "profile-{}-{}",
+            chrono::Utc::now().timestamp_millis(),
+            &suffix[..7]
+        ),
+        name,
+        user_name,
+        user_email,
+        auth_type: None,
+        ssh_key: None,
+        host: None,
+        color: Some("keyword".to_string()),
+        icon: Some("branch".to_string()),
+        signing_key,
+        signing_format,
+    };
+
+    let identity = insert_identity(profile).await?;
+    info!("Imported git identity {} from {:?}", identity.id, root);
+    Ok(GitIdentityImportResult {
+        identity,
+        created: true,
+    })
+}
+
 #[tauri::command]
 pub async fn update_git_identity(
     id: String,
     updates: GitIdentityProfile,
-) -> Result<GitIdentityProfile, String> {
-    let mut profiles = load_identities().await.map_err(|e| e.to_string())?;
+) -> Result<GitIdentityProfile, CommandError> {
+    let mut profiles = load_identities().await?;
     if let Some(idx) = profiles.iter().position(|p| p.id == id) {
         profiles[idx] = updates.clone();
-        save_identities(profiles).await.map_err(|e| e.to_string())?;
+        save_identities(profiles).await?;
         Ok(updates)
     } else {
-        Err(format!("Profile with ID {} not found", id))
+        Err(CommandError::not_found(format!(
+            "Profile with ID {} not found",
+            id
+        )))
     }
 }
 
 #[tauri::command]
-pub async fn delete_git_identity(id: String) -> Result<(), String> {
-    let mut profiles = load_identities().await.map_err(|e| e.to_string())?;
+pub async fn delete_git_identity(id: String) -> Result<(), CommandError> {
+    let mut profiles = load_identities().await?;
     let len = profiles.len();
     profiles.retain(|p| p.id != id);
     if profiles.len() == len {
-        return Err(format!("Profile with ID {} not found", id));
+        return Err(CommandError::not_found(format!(
+            "Profile with ID {} not found",
+            id
+        )));
     }
-    save_identities(profiles).await.map_err(|e| e.to_string())?;
+    save_identities(profiles).await?;
     Ok(())
 }
 
+/// Check that an identity's signing key exists and can produce a signature. Identities without
+/// signing configured are always valid.
+#[tauri::command]
+pub async fn validate_git_identity(id: String) -> Result<GitIdentityValidation, CommandError> {
+    let profiles = load_identities().await?;
+    let profile = profiles
+        .into_iter()
+        .find(|p| p.id == id)
+        .ok_or_else(|| CommandError::not_found(format!("Profile {} not found", id)))?;
+
+    let Some((format, key)) = profile.signing() else {
+        return Ok(GitIdentityValidation {
+            valid: true,
+            signing_configured: false,
+            signing_format: None,
+            error: None,
+        });
+    };
+
+    let result = if key.starts_with('-') {
+        Err("Signing key must not start with '-'".to_string())
+    } else if format == "ssh" {
+        validate_ssh_signing_key(key).await
+    } else {
+        validate_gpg_signing_key(key).await
+    };
+
+    Ok(GitIdentityValidation {
+        valid: result.is_ok(),
+        signing_configured: true,
+        signing_format: Some(format.to_string()),
+        error: result.err(),
+    })
+}
+
+async fn validate_gpg_signing_key(key: &str) -> Result<(), String> {
+    run_signing_probe("gpg", &["--batch", "--list-secret-keys", key], None)
+        .await
+        .map_err(|e| format!("Secret key {} not found: {}", key, e))?;
+    run_signing_probe(
+        "gpg",
+        &["--batch", "--local-user", key, "--detach-sign", "--armor"],
+        Some(SIGNING_PROBE_PAYLOAD),
+    )
+    .await
+    .map_err(|e| format!("Test signature with {} failed: {}", key, e))
+}
+
+async fn validate_ssh_signing_key(key: &str) -> Result<(), String> {
+    // Literal public keys are only usable through the agent, so check it holds the key.
+    let literal = key
+        .strip_prefix("key::")
+        .or_else(|| (key.starts_with("ssh-") || key.starts_with("ecdsa-")).then_some(key));
+    if let Some(literal) = literal {
+        let key_body = literal.split_whitespace().nth(1).unwrap_or(literal);
+        let agent_keys = run_signing_probe("ssh-add", &["-L"], None)
+            .await
+            .map_err(|e| format!("Could not query ssh-agent: {}", e))?;
+        if !agent_keys.contains(key_body) {
+            return Err("Signing key is not loaded in ssh-agent".to_string());
+        }
+        return Ok(());
+    }
+
+    let key_path = expand_tilde_path(key);
+    if !fs::try_exists(&key_path).await.unwrap_or(false) {
+        return Err(format!("SSH key file not found: {}", key_path.display()));
+    }
+    let key_path = key_path.to_string_lossy().to_string();
+    run_signing_probe(
+        "ssh-keygen",
+        &["-Y", "sign", "-n", "git", "-f", &key_path],
+        Some(SIGNING_PROBE_PAYLOAD),
+    )
+    .await
+    .map(|_| ())
+    .map_err(|e| format!("Test signature with {} failed: {}", key_path, e))
+}
+
+async fn run_signing_probe(
+    program: &str,
+    args: &[&str],
+    input: Option<&[u8]>,
+) -> Result<String, String> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .env("LC_ALL", "C")
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if let (Some(data), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin
+            .write_all(data)
+            .await
+            .map_err(|e| format!("Failed to write to {}: {}", program, e))?;
+    }
+
+    let output = tokio::time::timeout(
+        std::time::Duration::from_millis(SIGNING_PROBE_TIMEOUT_MS),
+        child.wait_with_output(),
+    )
+    .await
+    .map_err(|_| format!("{} timed out (is a passphrase prompt waiting?)", program))?
+    .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(if stderr.is_empty() {
+            format!("{} exited with {}", program, output.status)
+        } else {
+            stderr
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
 #[tauri::command]
 pub async fn get_remote_url(
     directory: String,
     remote: Option<String>,
     state: State<'_, DesktopRuntime>,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
 
     let remote_name = remote.unwrap_or_else(|| "origin".to_string());
     let url = run_git(&["remote", "get-url", &remote_name], &root)
@@ -2050,14 +4302,202 @@ pub async fn get_remote_url(
     Ok(url.filter(|s| !s.is_empty()))
 }
 
+fn validate_remote_name(name: &str) -> Result<(), CommandError> {
+    let valid = !name.is_empty()
+        && !name.starts_with(['-', '.', '/'])
+        && !name.ends_with(['.', '/'])
+        && !name.ends_with(".lock")
+        && !name.contains("..")
+        && !name.contains("//")
+        && name
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.' | '/'));
+    if valid {
+        Ok(())
+    } else {
+        Err(CommandError::invalid_argument(format!(
+            "Invalid remote name: {}",
+            name
+        )))
+    }
+}
+
+/// Accepts URLs with a scheme (`https://`, `ssh://`, `git://`, `file://`), scp-style
+/// `user@host:path`, or an absolute local path.
+fn validate_remote_url(url: &str) -> Result<(), CommandError> {
+    let invalid = || {
+        Err(CommandError::invalid_argument(format!(
+            "Invalid remote URL: {}",
+            url
+        )))
+    };
+    if url.is_empty() || url.starts_with('-') || url.chars().any(|ch| ch.is_control()) {
+        return invalid();
+    }
+
+    if let Some((scheme, rest)) = url.split_once("://") {
+        let known = matches!(scheme, "https" | "http" | "ssh" | "git" | "file");
+        return if known && !rest.is_empty() && !url.contains(char::is_whitespace) {
+            Ok(())
+        } else {
+            invalid()
+        };
+    }
+    if Path::new(url).is_absolute() {
+        return Ok(());
+    }
+    match url.split_once(':') {
+        Some((host, path))
+            if !host.is_empty()
+                && !path.is_empty()
+                && !host.contains('/')
+                && !url.contains(char::is_whitespace) =>
+        {
+            Ok(())
+        }
+        _ => invalid(),
+    }
+}
+
+async fn collect_git_remotes(root: &Path) -> Result<Vec<GitRemoteInfo>> {
+    let output = run_git(&["remote", "-v"], root).await?;
+    let mut remotes: Vec<GitRemoteInfo> = Vec::new();
+
+    for line in output.lines() {
+        let Some((name, rest)) = line.split_once('\t') else {
+            continue;
+        };
+        let Some((url, kind)) = rest.trim().rsplit_once(' ') else {
+            continue;
+        };
+
+        let index = match remotes.iter().position(|remote| remote.name == name) {
+            Some(index) => index,
+            None => {
+                remotes.push(GitRemoteInfo {
+                    name: name.to_string(),
+                    fetch_url: None,
+                    push_url: None,
+                });
+                remotes.len() - 1
+            }
+        };
+        match kind {
+            "(fetch)" => remotes[index].fetch_url = Some(url.to_string()),
+            "(push)" => remotes[index].push_url = Some(url.to_string()),
+            _ => {}
+        }
+    }
+
+    Ok(remotes)
+}
+
+#[tauri::command]
+pub async fn list_git_remotes(
+    directory: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Vec<GitRemoteInfo>, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    collect_git_remotes(&root).await.map_err(git_error)
+}
+
+#[tauri::command]
+pub async fn add_git_remote(
+    directory: String,
+    name: String,
+    url: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Vec<GitRemoteInfo>, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    let name = name.trim();
+    let url = url.trim();
+    validate_remote_name(name)?;
+    validate_remote_url(url)?;
+
+    run_git(&["remote", "add", name, url], &root)
+        .await
+        .map_err(git_error)?;
+    collect_git_remotes(&root).await.map_err(git_error)
+}
+
+#[tauri::command]
+pub async fn remove_git_remote(
+    directory: String,
+    name: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Vec<GitRemoteInfo>, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    let name = name.trim();
+    validate_remote_name(name)?;
+
+    run_git(&["remote", "remove", name], &root)
+        .await
+        .map_err(git_error)?;
+    collect_git_remotes(&root).await.map_err(git_error)
+}
+
+#[tauri::command]
+pub async fn rename_git_remote(
+    directory: String,
+    name: String,
+    new_name: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Vec<GitRemoteInfo>, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    let name = name.trim();
+    let new_name = new_name.trim();
+    validate_remote_name(name)?;
+    validate_remote_name(new_name)?;
+
+    run_git(&["remote", "rename", name, new_name], &root)
+        .await
+        .map_err(git_error)?;
+    collect_git_remotes(&root).await.map_err(git_error)
+}
+
+/// Update a remote's URL. With `push` set only the push URL changes, which is how a fork
+/// workflow fetches from upstream but pushes to the fork.
+#[tauri::command]
+pub async fn set_git_remote_url(
+    directory: String,
+    name: String,
+    url: String,
+    push: Option<bool>,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Vec<GitRemoteInfo>, CommandError> {
+    let root = validate_git_path(&directory, state.settings())
+        .await
+        .map_err(git_error)?;
+    let name = name.trim();
+    let url = url.trim();
+    validate_remote_name(name)?;
+    validate_remote_url(url)?;
+
+    let mut args = vec!["remote", "set-url"];
+    if push.unwrap_or(false) {
+        args.push("--push");
+    }
+    args.extend([name, url]);
+    run_git(&args, &root).await.map_err(git_error)?;
+    collect_git_remotes(&root).await.map_err(git_error)
+}
+
 #[tauri::command]
 pub async fn get_current_git_identity(
     directory: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<GitIdentitySummary, String> {
+) -> Result<GitIdentitySummary, CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
 
     let user_name = run_git(&["config", "user.name"], &root).await.ok();
     let user_email = run_git(&["config", "user.email"], &root).await.ok();
@@ -2074,10 +4514,10 @@ pub async fn get_current_git_identity(
 pub async fn has_local_identity(
     directory: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<bool, String> {
+) -> Result<bool, CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
 
     let user_name = run_git(&["config", "--local", "--get", "user.name"], &root)
         .await
@@ -2092,7 +4532,7 @@ pub async fn has_local_identity(
 }
 
 #[tauri::command]
-pub async fn get_global_git_identity() -> Result<GitIdentitySummary, String> {
+pub async fn get_global_git_identity() -> Result<GitIdentitySummary, CommandError> {
     let user_name = tokio::process::Command::new("git")
         .args(["config", "--global", "user.name"])
         .output()
@@ -2132,29 +4572,29 @@ pub async fn set_git_identity(
     directory: String,
     profile_id: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<GitIdentityProfile, String> {
+) -> Result<GitIdentityProfile, CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
-    let profiles = load_identities().await.map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
+    let profiles = load_identities().await?;
 
     let profile = profiles
         .into_iter()
         .find(|p| p.id == profile_id)
-        .ok_or_else(|| format!("Profile {} not found", profile_id))?;
+        .ok_or_else(|| CommandError::not_found(format!("Profile {} not found", profile_id)))?;
 
     run_git(
         &["config", "--local", "user.name", &profile.user_name],
         &root,
     )
     .await
-    .map_err(|e| e.to_string())?;
+    .map_err(git_error)?;
     run_git(
         &["config", "--local", "user.email", &profile.user_email],
         &root,
     )
     .await
-    .map_err(|e| e.to_string())?;
+    .map_err(git_error)?;
 
     let auth_type = profile.auth_type.as_deref().unwrap_or("ssh");
 
@@ -2163,7 +4603,7 @@ pub async fn set_git_identity(
             let cmd = format!("ssh -i {}", key);
             run_git(&["config", "--local", "core.sshCommand", &cmd], &root)
                 .await
-                .map_err(|e| e.to_string())?;
+                .map_err(git_error)?;
         }
         let _ = run_git(
             &["config", "--local", "--unset", "credential.helper"],
@@ -2173,17 +4613,30 @@ pub async fn set_git_identity(
     } else if auth_type == "token" && profile.host.is_some() {
         run_git(&["config", "--local", "credential.helper", "store"], &root)
             .await
-            .map_err(|e| e.to_string())?;
+            .map_err(git_error)?;
         let _ = run_git(&["config", "--local", "--unset", "core.sshCommand"], &root).await;
     } else {
         let _ = run_git(&["config", "--local", "--unset", "core.sshCommand"], &root).await;
     }
 
+    if let Some((format, key)) = profile.signing() {
+        run_git(&["config", "--local", "user.signingkey", key], &root)
+            .await
+            .map_err(git_error)?;
+        run_git(&["config", "--local", "gpg.format", format], &root)
+            .await
+            .map_err(git_error)?;
+    } else {
+        // Don't leave the previous identity's key behind for commits to be signed with.
+        let _ = run_git(&["config", "--local", "--unset", "user.signingkey"], &root).await;
+        let _ = run_git(&["config", "--local", "--unset", "gpg.format"], &root).await;
+    }
+
     Ok(profile)
 }
 
 #[tauri::command]
-pub async fn discover_git_credentials() -> Result<Vec<DiscoveredGitCredential>, String> {
+pub async fn discover_git_credentials() -> Result<Vec<DiscoveredGitCredential>, CommandError> {
     let home = dirs::home_dir().ok_or_else(|| "Could not find home directory".to_string())?;
     let credentials_path = home.join(".git-credentials");
 
@@ -2226,36 +4679,110 @@ pub async fn discover_git_credentials() -> Result<Vec<DiscoveredGitCredential>,
     Ok(credentials)
 }
 
+/// Pick the model for commit message generation: explicit request, then the
+/// `defaultModel` setting when it targets OpenCode Zen, then the built-in default.
+fn resolve_commit_message_model(requested: Option<&str>, settings: &Value) -> String {
+    if let Some(requested) = requested.map(str::trim).filter(|value| !value.is_empty()) {
+        return requested
+            .strip_prefix("opencode/")
+            .unwrap_or(requested)
+            .to_string();
+    }
+
+    settings
+        .get("defaultModel")
+        .and_then(Value::as_str)
+        .and_then(|value| value.trim().strip_prefix("opencode/"))
+        .filter(|value| !value.is_empty())
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| COMMIT_MESSAGE_DEFAULT_MODEL.to_string())
+}
+
+fn count_diff_lines(diff: &str) -> (usize, usize) {
+    let mut insertions = 0;
+    let mut deletions = 0;
+    for line in diff.lines() {
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        if line.starts_with('+') {
+            insertions += 1;
+        } else if line.starts_with('-') {
+            deletions += 1;
+        }
+    }
+    (insertions, deletions)
+}
+
 #[tauri::command]
 pub async fn generate_commit_message(
     directory: String,
     files: Vec<String>,
+    max_diff_bytes: Option<usize>,
+    model: Option<String>,
+    gitmoji: Option<bool>,
     state: State<'_, DesktopRuntime>,
-) -> Result<CommitMessageResponse, String> {
+) -> Result<CommitMessageResponse, CommandError> {
     let _root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
-
-    // 1. Collect diffs
+        .map_err(git_error)?;
+
+    let settings = state.settings().load().await.unwrap_or(Value::Null);
+    let model = resolve_commit_message_model(model.as_deref(), &settings);
+    let gitmoji = gitmoji.unwrap_or_else(|| {
+        settings
+            .get("gitmojiEnabled")
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    });
+    let max_diff_bytes = max_diff_bytes
+        .filter(|value| *value > 0)
+        .unwrap_or(COMMIT_MESSAGE_DIFF_MAX_BYTES);
+
+    // 1. Collect diffs, falling back to per-file stats once the byte budget is spent
     let mut diff_summaries = String::new();
+    let mut summarized_files = 0usize;
+    let total_files = files.len();
     for file in files {
         if let Ok(diff) =
             get_git_diff(directory.clone(), file.clone(), None, None, state.clone()).await
         {
-            let trimmed = if diff.len() > 4000 {
-                format!("{}\n...", &diff[..4000])
+            let trimmed =
+                truncate_string_to_char_boundary(diff, COMMIT_MESSAGE_FILE_DIFF_MAX_CHARS, "\n...");
+            let entry = format!("FILE: {}\n{}\n\n", file, trimmed);
+            if diff_summaries.len() + entry.len() <= max_diff_bytes {
+                diff_summaries.push_str(&entry);
             } else {
-                diff
-            };
-            diff_summaries.push_str(&format!("FILE: {}\n{}\n\n", file, trimmed));
+                let (insertions, deletions) = count_diff_lines(&trimmed);
+                diff_summaries.push_str(&format!(
+                    "FILE: {} (+{} -{}, diff omitted)\n\n",
+                    file, insertions, deletions
+                ));
+                summarized_files += 1;
+            }
         }
     }
 
     if diff_summaries.is_empty() {
-        return Err("No diffs available for selected files".to_string());
+        return Err(CommandError::invalid_argument(
+            "No diffs available for selected files",
+        ));
+    }
+
+    let truncated = summarized_files > 0;
+    if truncated {
+        diff_summaries.push_str(&format!(
+            "Note: diff exceeded {} bytes; {} of {} files are summarized by line counts only.\n",
+            max_diff_bytes, summarized_files, total_files
+        ));
     }
 
     // 2. Construct prompt (matching server/index.js)
+    let gitmoji_instruction = if gitmoji {
+        "- prefix the subject with a single matching gitmoji (e.g. \"✨ feat: add diff virtualization\")\n"
+    } else {
+        ""
+    };
     let prompt = format!(
         r#"You are drafting git commit notes for this codebase. Respond in JSON of the shape {{"subject": string, "highlights": string[]}} (ONLY the JSON in response, no markdown wrappers or anything except JSON) with these rules:
 - subject follows our convention: type[optional-scope]: summary (examples: "feat: add diff virtualization", "fix(chat): restore enter key handling")
@@ -2265,14 +4792,12 @@ pub async fn generate_commit_message(
 - focus on the most impactful user-facing change; if multiple capabilities ship together, align the subject with the dominant theme and use highlights to cover the other major outcomes
 - highlights array should contain 2-3 plain sentences (<= 90 chars each) that describe distinct features or UI changes users will notice (e.g. "Add per-file revert action in Changes list"). Avoid subjective benefit statements, marketing tone, repeating the subject, or referencing helper function names. Highlight additions such as new controls/buttons, new actions (e.g. revert), or stored state changes explicitly. Skip highlights if fewer than two meaningful points exist.
 - text must be plain (no markdown bullets); each highlight should start with an uppercase verb
-
+{}
 Diff summary:
 {}"#,
-        diff_summaries
+        gitmoji_instruction, diff_summaries
     );
 
-    let model = "gpt-5-nano";
-
     // 3. Call API
     let client = Client::new();
     let res = client
@@ -2288,10 +4813,10 @@ Diff summary:
         }))
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| CommandError::new(ErrorCode::Network, e.to_string()))?;
 
     if !res.status().is_success() {
-        return Err(format!("API request failed: {}", res.status()));
+        return Err(format!("API request failed: {}", res.status()).into());
     }
 
     let body: serde_json::Value = res.json().await.map_err(|e| e.to_string())?;
@@ -2319,7 +4844,7 @@ Diff summary:
     if let Some(candidate) = extracted.as_deref() {
         if candidate.starts_with('{') || candidate.starts_with('[') {
             match serde_json::from_str::<GeneratedCommitMessage>(candidate) {
-                Ok(message) => return Ok(CommitMessageResponse { message }),
+                Ok(message) => return Ok(CommitMessageResponse { message, truncated }),
                 Err(err) => last_error = Some(err.to_string()),
             }
         }
@@ -2327,7 +4852,7 @@ Diff summary:
 
     if cleaned.starts_with('{') || cleaned.starts_with('[') {
         match serde_json::from_str::<GeneratedCommitMessage>(cleaned) {
-            Ok(message) => return Ok(CommitMessageResponse { message }),
+            Ok(message) => return Ok(CommitMessageResponse { message, truncated }),
             Err(err) => last_error = Some(err.to_string()),
         }
     }
@@ -2335,7 +4860,8 @@ Diff summary:
     Err(format!(
         "Failed to parse AI response: {}",
         last_error.unwrap_or_else(|| "unknown error".to_string())
-    ))
+    )
+    .into())
 }
 
 #[tauri::command]
@@ -2344,13 +4870,13 @@ pub async fn generate_pr_description(
     base: String,
     head: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, CommandError> {
     let root = validate_git_path(&directory, state.settings())
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(git_error)?;
 
     if base.trim().is_empty() || head.trim().is_empty() {
-        return Err("base and head are required".to_string());
+        return Err(CommandError::invalid_argument("base and head are required"));
     }
 
     // 1. Collect PR range diffs (base...head)
@@ -2373,7 +4899,9 @@ pub async fn generate_pr_description(
             .collect::<Vec<String>>()
     };
     if files.is_empty() {
-        return Err("No diffs available for base...head".to_string());
+        return Err(CommandError::invalid_argument(
+            "No diffs available for base...head",
+        ));
     }
     let mut diff_summaries = String::new();
     for file in files.iter() {
@@ -2394,7 +4922,9 @@ pub async fn generate_pr_description(
     }
 
     if diff_summaries.is_empty() {
-        return Err("No diffs available for selected files".to_string());
+        return Err(CommandError::invalid_argument(
+            "No diffs available for selected files",
+        ));
     }
 
     // 2. Construct PR-specific prompt
@@ -2431,10 +4961,10 @@ Diff summary:
         }))
         .send()
         .await
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| CommandError::new(ErrorCode::Network, e.to_string()))?;
 
     if !res.status().is_success() {
-        return Err(format!("API request failed: {}", res.status()));
+        return Err(format!("API request failed: {}", res.status()).into());
     }
 
     let body_json: serde_json::Value = res.json().await.map_err(|e| e.to_string())?;
@@ -2448,7 +4978,7 @@ Diff summary:
         .trim();
 
     if raw_content.is_empty() {
-        return Err("No PR description returned by generator".to_string());
+        return Err("No PR description returned by generator".into());
     }
 
     let cleaned = raw_content
@@ -2477,3 +5007,85 @@ Diff summary:
 
     Ok(serde_json::json!({ "title": "", "body": raw_content }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_fetch_output_reads_ref_updates_and_skips_progress() {
+        let stderr = "remote: Counting objects:  50% (1/2)        \rremote: Counting objects: 100% (2/2), done.        \n\
+                      From github.com:owner/repo\n   \
+                      02f93d0..8dd4f64  main       -> origin/main\n \
+                      * [new branch]      feat       -> origin/feat\n \
+                      + 8dd4f64...1207cd4 next       -> origin/next  (forced update)\n";
+        let refs = parse_fetch_output(stderr);
+        assert_eq!(refs.len(), 3);
+        assert_eq!(refs[0].status, "fastForward");
+        assert_eq!(refs[0].summary, "02f93d0..8dd4f64");
+        assert_eq!(refs[0].to, "origin/main");
+        assert_eq!(refs[1].status, "new");
+        assert_eq!(refs[1].summary, "[new branch]");
+        assert_eq!(refs[1].from, "feat");
+        assert_eq!(refs[2].status, "forced");
+        assert_eq!(refs[2].reason.as_deref(), Some("forced update"));
+    }
+
+    #[test]
+    fn parse_numstat_z_reads_renames_and_binary_files() {
+        let output = "3\t1\tsrc/main.rs\0\
+                      2\t0\t\0src/old.rs\0src/new.rs\0\
+                      -\t-\tassets/logo.png\0";
+        let files = parse_numstat_z(output);
+        assert_eq!(files.len(), 3);
+
+        assert_eq!(files[0].path, "src/main.rs");
+        assert_eq!(files[0].old_path, None);
+        assert_eq!((files[0].insertions, files[0].deletions), (3, 1));
+
+        assert_eq!(files[1].path, "src/new.rs");
+        assert_eq!(files[1].old_path.as_deref(), Some("src/old.rs"));
+        assert_eq!((files[1].insertions, files[1].deletions), (2, 0));
+        assert!(!files[1].is_binary);
+
+        assert_eq!(files[2].path, "assets/logo.png");
+        assert!(files[2].is_binary);
+        assert_eq!((files[2].insertions, files[2].deletions), (0, 0));
+    }
+
+    #[test]
+    fn parse_status_porcelain_v2_reads_headers_and_records() {
+        let output = "# branch.oid 1234567\0\
+                      # branch.head main\0\
+                      # branch.upstream origin/main\0\
+                      # branch.ab +2 -1\0\
+                      1 .M N... 100644 100644 100644 abc123 abc123 src/my file.rs\0\
+                      2 R. N... 100644 100644 100644 abc123 abc123 R100 src/new.rs\0src/old.rs\0\
+                      u UU N... 100644 100644 100644 100644 a1 b2 c3 conflict.txt\0\
+                      ?  notes.md\0";
+        let status = parse_status_porcelain_v2(output);
+        assert_eq!(status.current, "main");
+        assert_eq!(status.tracking.as_deref(), Some("origin/main"));
+        assert_eq!((status.ahead, status.behind), (2, 1));
+        assert_eq!(status.files.len(), 4);
+
+        assert_eq!(status.files[0].path, "src/my file.rs");
+        assert_eq!(status.files[0].index, "");
+        assert_eq!(status.files[0].working_dir, "M");
+        assert_eq!(status.files[0].original_path, None);
+
+        assert_eq!(status.files[1].path, "src/new.rs");
+        assert_eq!(status.files[1].index, "R");
+        assert_eq!(status.files[1].working_dir, "");
+        assert_eq!(status.files[1].original_path.as_deref(), Some("src/old.rs"));
+
+        assert_eq!(status.files[2].path, "conflict.txt");
+        assert_eq!(status.files[2].index, "U");
+        assert_eq!(status.files[2].working_dir, "U");
+
+        // Only the one separator is stripped, so leading spaces in a path survive.
+        assert_eq!(status.files[3].path, " notes.md");
+        assert_eq!(status.files[3].index, "?");
+        assert_eq!(status.files[3].working_dir, "?");
+    }
+}
@@ -1,22 +1,98 @@
+use once_cell::sync::{Lazy, OnceCell};
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::path::PathBuf;
-use tauri::State;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
 use tokio::fs;
 use tokio::process::Command;
 
+use crate::commands::error::{CommandError, ErrorCode};
 use crate::DesktopRuntime;
 
 const DEVICE_CODE_URL: &str = "https://github.com/login/device/code";
 const ACCESS_TOKEN_URL: &str = "https://github.com/login/oauth/access_token";
 const API_USER_URL: &str = "https://api.github.com/user";
 const API_EMAILS_URL: &str = "https://api.github.com/user/emails";
+const API_USER_REPOS_URL: &str = "https://api.github.com/user/repos";
 const API_PULLS_URL_PREFIX: &str = "https://api.github.com/repos";
 const API_GRAPHQL_URL: &str = "https://api.github.com/graphql";
 const DEVICE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
 
 const DEFAULT_GITHUB_CLIENT_ID: &str = "Ov23liNd8TxDcMXtAHHM";
 const DEFAULT_GITHUB_SCOPES: &str = "repo read:org workflow read:user user:email";
+// Scopes GitHub defines for OAuth apps; anything else in `githubScopes` is most likely a typo.
+const KNOWN_GITHUB_SCOPES: &[&str] = &[
+    "repo",
+    "repo:status",
+    "repo_deployment",
+    "public_repo",
+    "repo:invite",
+    "security_events",
+    "admin:repo_hook",
+    "write:repo_hook",
+    "read:repo_hook",
+    "admin:org",
+    "write:org",
+    "read:org",
+    "admin:public_key",
+    "write:public_key",
+    "read:public_key",
+    "admin:org_hook",
+    "gist",
+    "notifications",
+    "user",
+    "read:user",
+    "user:email",
+    "user:follow",
+    "project",
+    "read:project",
+    "delete_repo",
+    "write:packages",
+    "read:packages",
+    "delete:packages",
+    "admin:gpg_key",
+    "write:gpg_key",
+    "read:gpg_key",
+    "admin:ssh_signing_key",
+    "write:ssh_signing_key",
+    "read:ssh_signing_key",
+    "codespace",
+    "workflow",
+    "copilot",
+    "audit_log",
+    "read:audit_log",
+];
+
+// Prefixes of GitHub-issued tokens; older classic tokens are 40 hex characters instead.
+const GITHUB_TOKEN_PREFIXES: &[&str] = &["ghp_", "github_pat_", "gho_", "ghu_"];
+const FINE_GRAINED_TOKEN_PREFIX: &str = "github_pat_";
+
+const GITHUB_MAX_RETRIES: u32 = 3;
+const GITHUB_RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+// A longer Retry-After than this is surfaced as `rate_limited` instead of waited out.
+const GITHUB_MAX_RETRY_WAIT: Duration = Duration::from_secs(30);
+
+const GITHUB_AUTH_LOST_EVENT: &str = "openchamber:github-auth-lost";
+const PR_CHECKS_CHANGED_EVENT: &str = "openchamber:pr-checks-changed";
+// Poll quickly while checks are running, slowly once they settle, and back off on errors.
+const PR_WATCH_PENDING_INTERVAL: Duration = Duration::from_secs(15);
+const PR_WATCH_SETTLED_INTERVAL: Duration = Duration::from_secs(60);
+const PR_WATCH_ERROR_INTERVAL: Duration = Duration::from_secs(120);
+
+const PR_FILES_DEFAULT_PER_PAGE: u32 = 30;
+const PR_FILES_MAX_PER_PAGE: u32 = 100;
+// The files endpoint stops at 3000 files, i.e. 30 pages of 100.
+const PR_FILES_MAX_PAGES: u32 = 30;
+
+// Where GitHub looks for pull request templates, relative to the repo root. The file name is
+// matched case-insensitively; a `PULL_REQUEST_TEMPLATE/` directory holds multiple templates.
+const PR_TEMPLATE_DIRS: &[&str] = &[".github", "", "docs"];
+const PR_TEMPLATE_NAME: &str = "pull_request_template";
+const PR_TEMPLATE_MAX_BYTES: u64 = 64 * 1024;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -191,6 +267,8 @@ pub struct GitHubPullRequestContext {
 pub struct GitHubPullRequestFile {
     filename: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    previous_filename: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     status: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     additions: Option<u64>,
@@ -202,6 +280,20 @@ pub struct GitHubPullRequestFile {
     patch: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHubPullRequestFilesResult {
+    connected: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repo: Option<GitHubRepoRef>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    files: Option<Vec<GitHubPullRequestFile>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    page: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    has_more: Option<bool>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GitHubPullRequestReviewComment {
@@ -302,6 +394,52 @@ pub struct GitHubIssueComment {
     updated_at: Option<String>,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHubPrChecksChanged {
+    directory: String,
+    number: u64,
+    repo: GitHubRepoRef,
+    /// "open", "closed" or "merged". The watch stops once the PR is no longer open.
+    state: String,
+    head_sha: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    checks: Option<GitHubChecksSummary>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct GitHubAuthLostPayload {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    login: Option<String>,
+}
+
+// `false` once the UI has been told the token was lost; re-armed by the next successful login
+// so a burst of failing commands produces a single event.
+static AUTH_KNOWN_GOOD: AtomicBool = AtomicBool::new(true);
+// Serializes lost-token handling so concurrent failures clear the stored account only once.
+static AUTH_LOST_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
+static AUTH_APP_HANDLE: OnceCell<AppHandle> = OnceCell::new();
+
+struct PrWatch {
+    id: u64,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+/// Watched PRs keyed by (owner, repo, number); GitHub names are case-insensitive.
+type PrWatchKey = (String, String, u64);
+
+static PR_WATCHES: Lazy<Mutex<HashMap<PrWatchKey, PrWatch>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_PR_WATCH_ID: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHubPrTemplatesResult {
+    /// Template contents keyed by repo-relative path, e.g. `.github/PULL_REQUEST_TEMPLATE.md`.
+    templates: BTreeMap<String, String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GitHubIssuesListResult {
@@ -339,7 +477,7 @@ pub struct GitHubIssueCommentsResult {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GitHubUserSummary {
-    login: String,
+    pub(crate) login: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     id: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -364,9 +502,9 @@ pub struct GitHubAuthAccount {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GitHubAuthStatus {
-    connected: bool,
+    pub(crate) connected: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
-    user: Option<GitHubUserSummary>,
+    pub(crate) user: Option<GitHubUserSummary>,
     #[serde(skip_serializing_if = "Option::is_none")]
     scope: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -387,6 +525,25 @@ pub struct GitHubDeviceFlowStart {
     scope: Option<String>,
 }
 
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GitHubClientValidation {
+    client_id: String,
+    /// GitHub issued a device code for this client id.
+    valid: bool,
+    /// GitHub's error code when rejected, e.g. `incorrect_client_credentials` or
+    /// `device_flow_disabled`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error_description: Option<String>,
+    /// Scopes the device flow will request. GitHub only settles which are granted when the
+    /// user authorizes.
+    scopes: Vec<String>,
+    /// Requested scopes GitHub does not define.
+    unknown_scopes: Vec<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct GitHubDeviceFlowCompleteSuccess {
@@ -544,6 +701,8 @@ struct IssueCommentResponse {
 struct PullFileResponse {
     filename: String,
     #[serde(default)]
+    previous_filename: Option<String>,
+    #[serde(default)]
     status: Option<String>,
     #[serde(default)]
     additions: Option<u64>,
@@ -669,6 +828,15 @@ struct PermissionResponse {
     permission: String,
 }
 
+#[derive(Debug, Serialize)]
+struct IssueCreateRequest<'a> {
+    title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    labels: Option<&'a [String]>,
+}
+
 #[derive(Debug, Serialize)]
 struct PullCreateRequest<'a> {
     title: &'a str,
@@ -718,10 +886,8 @@ struct ApiEmailEntry {
 }
 
 fn github_auth_path() -> Result<PathBuf, String> {
-    let home = dirs::home_dir().ok_or_else(|| "No home directory".to_string())?;
-    let mut dir = home;
-    dir.push(".config");
-    dir.push("openchamber");
+    let mut dir = crate::path_utils::openchamber_config_dir()
+        .ok_or_else(|| "No home directory".to_string())?;
     dir.push("github-auth.json");
     Ok(dir)
 }
@@ -787,7 +953,7 @@ fn build_auth_accounts(list: &[StoredAuth]) -> Option<Vec<GitHubAuthAccount>> {
     }
 }
 
-async fn resolve_auth_status() -> Result<GitHubAuthStatus, String> {
+pub(crate) async fn resolve_auth_status() -> Result<GitHubAuthStatus, CommandError> {
     let list = read_auth_list().await;
     let accounts = build_auth_accounts(&list);
     let current = list.iter().find(|entry| entry.current).cloned().or_else(|| list.first().cloned());
@@ -801,7 +967,7 @@ async fn resolve_auth_status() -> Result<GitHubAuthStatus, String> {
     };
 
     if stored.access_token.trim().is_empty() {
-        let _ = clear_auth_file().await;
+        handle_auth_lost(&stored.access_token).await;
         return Ok(GitHubAuthStatus {
             connected: false,
             user: None,
@@ -811,14 +977,17 @@ async fn resolve_auth_status() -> Result<GitHubAuthStatus, String> {
     }
 
     match fetch_me(&stored.access_token).await {
-        Ok(user) => Ok(GitHubAuthStatus {
-            connected: true,
-            user: Some(user),
-            scope: stored.scope,
-            accounts,
-        }),
-        Err(err) if err == "unauthorized" => {
-            let _ = clear_auth_file().await;
+        Ok(user) => {
+            AUTH_KNOWN_GOOD.store(true, Ordering::SeqCst);
+            Ok(GitHubAuthStatus {
+                connected: true,
+                user: Some(user),
+                scope: stored.scope,
+                accounts,
+            })
+        }
+        Err(err) if err.code == ErrorCode::Unauthorized => {
+            handle_auth_lost(&stored.access_token).await;
             Ok(GitHubAuthStatus {
                 connected: false,
                 user: None,
@@ -840,42 +1009,108 @@ async fn read_auth_list() -> Vec<StoredAuth> {
         Err(_) => return Vec::new(),
     };
 
-    let mut list = if let Ok(list) = serde_json::from_slice::<Vec<StoredAuth>>(&bytes) {
-        list
-    } else if let Ok(entry) = serde_json::from_slice::<StoredAuth>(&bytes) {
-        vec![entry]
-    } else {
-        Vec::new()
+    let (mut list, recovered) = match parse_auth_list(&bytes) {
+        Some(list) => (list, false),
+        None => (recover_auth_list(&path).await, true),
     };
 
     let changed = normalize_auth_list(&mut list);
-    if changed {
-        let _ = persist_auth_list(&list).await;
+    if changed || (recovered && !list.is_empty()) {
+        let persisted = persist_auth_list(&list).await.is_ok();
+        // The damaged file holds tokens too; drop it once the restored list is in place.
+        if recovered && persisted {
+            let _ = fs::remove_file(auth_corrupt_path(&path)).await;
+        }
     }
     list
 }
 
-async fn persist_auth_list(list: &Vec<StoredAuth>) -> Result<(), String> {
-    let path = github_auth_path()?;
-    if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent).await;
+fn parse_auth_list(bytes: &[u8]) -> Option<Vec<StoredAuth>> {
+    if let Ok(list) = serde_json::from_slice::<Vec<StoredAuth>>(bytes) {
+        return Some(list);
     }
-    let bytes = serde_json::to_vec_pretty(list).map_err(|e| e.to_string())?;
-    fs::write(&path, bytes).await.map_err(|e| e.to_string())?;
+    serde_json::from_slice::<StoredAuth>(bytes)
+        .ok()
+        .map(|entry| vec![entry])
+}
+
+/// Copy of the last successful write, used when `github-auth.json` itself is unreadable.
+fn auth_backup_path(path: &Path) -> PathBuf {
+    path.with_extension("json.bak")
+}
+
+/// Where an unreadable `github-auth.json` is moved aside.
+fn auth_corrupt_path(path: &Path) -> PathBuf {
+    path.with_extension("json.corrupt")
+}
+
+/// `github-auth.json` did not parse (e.g. truncated by a crash mid-write). Restore the
+/// accounts from the backup if possible. The damaged file is kept aside until the restored
+/// list has been written, or for inspection when nothing could be restored.
+async fn recover_auth_list(path: &Path) -> Vec<StoredAuth> {
+    log::warn!("[desktop:github] GitHub auth file is unreadable, attempting recovery");
+    let _ = fs::rename(path, auth_corrupt_path(path)).await;
+
+    let backup = fs::read(auth_backup_path(path)).await.ok();
+    match backup.as_deref().and_then(parse_auth_list) {
+        Some(list) => {
+            log::info!("[desktop:github] Restored GitHub auth from backup");
+            list
+        }
+        None => {
+            log::warn!("[desktop:github] No usable GitHub auth backup, signing out");
+            Vec::new()
+        }
+    }
+}
+
+/// Write through a temp file and rename it into place, so a crash leaves either the old
+/// file or the new one, never a truncated token file.
+async fn write_private_file(path: &Path, bytes: &[u8]) -> Result<(), String> {
+    use tokio::io::AsyncWriteExt;
 
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(&tmp).await.map_err(|e| e.to_string())?;
+    file.write_all(bytes).await.map_err(|e| e.to_string())?;
+    file.sync_all().await.map_err(|e| e.to_string())?;
+    drop(file);
+    fs::rename(&tmp, path).await.map_err(|e| e.to_string())?;
+
+    // `mode` only applies to newly created files; a stale temp file may have kept other bits.
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
-        if let Ok(metadata) = std::fs::metadata(&path) {
+        if let Ok(metadata) = std::fs::metadata(path) {
             let mut perms = metadata.permissions();
             perms.set_mode(0o600);
-            let _ = std::fs::set_permissions(&path, perms);
+            let _ = std::fs::set_permissions(path, perms);
         }
     }
 
     Ok(())
 }
 
+async fn persist_auth_list(list: &Vec<StoredAuth>) -> Result<(), String> {
+    let path = github_auth_path()?;
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent).await;
+    }
+    let bytes = serde_json::to_vec_pretty(list).map_err(|e| e.to_string())?;
+    write_private_file(&path, &bytes).await?;
+    // Refreshed only once the main file is in place, so it always holds a complete list.
+    if let Err(err) = write_private_file(&auth_backup_path(&path), &bytes).await {
+        log::warn!("[desktop:github] Failed to update GitHub auth backup: {err}");
+    }
+    Ok(())
+}
+
 async fn read_auth_file() -> Option<StoredAuth> {
     let list = read_auth_list().await;
     let current = list.iter().find(|entry| entry.current).cloned();
@@ -903,7 +1138,9 @@ async fn write_auth_file(auth: &StoredAuth) -> Result<(), String> {
         entry.current = account_id.is_some() && entry.account_id.as_ref() == account_id.as_ref();
     }
 
-    persist_auth_list(&list).await
+    persist_auth_list(&list).await?;
+    AUTH_KNOWN_GOOD.store(true, Ordering::SeqCst);
+    Ok(())
 }
 
 async fn clear_auth_file() -> bool {
@@ -918,12 +1155,64 @@ async fn clear_auth_file() -> bool {
     }
     list.retain(|entry| !entry.current);
     if list.is_empty() {
+        let _ = fs::remove_file(auth_backup_path(&path)).await;
         return fs::remove_file(&path).await.is_ok() || !path.exists();
     }
     normalize_auth_list(&mut list);
     persist_auth_list(&list).await.is_ok()
 }
 
+/// Lets lost-token handling emit `openchamber:github-auth-lost`.
+pub(crate) fn attach_app_handle(app: AppHandle) {
+    let _ = AUTH_APP_HANDLE.set(app);
+}
+
+/// The current account, or `None` when not connected. An account with an empty token is
+/// treated as lost.
+async fn load_stored_auth() -> Option<StoredAuth> {
+    let stored = read_auth_file().await?;
+    if stored.access_token.trim().is_empty() {
+        handle_auth_lost(&stored.access_token).await;
+        return None;
+    }
+    Some(stored)
+}
+
+/// Called when GitHub rejects `access_token`. Clears the account only if it is still the
+/// current one (another command may have handled it already) and emits the lost event once.
+async fn handle_auth_lost(access_token: &str) {
+    let _guard = AUTH_LOST_LOCK.lock().await;
+    let Some(current) = read_auth_file().await else {
+        return;
+    };
+    if current.access_token != access_token {
+        return;
+    }
+    let _ = clear_auth_file().await;
+
+    if AUTH_KNOWN_GOOD.swap(false, Ordering::SeqCst) {
+        log::warn!("[desktop:github] stored token was rejected, signing out");
+        if let Some(app) = AUTH_APP_HANDLE.get() {
+            let payload = GitHubAuthLostPayload {
+                login: current.user.map(|user| user.login),
+            };
+            let _ = app.emit(GITHUB_AUTH_LOST_EVENT, payload);
+        }
+    }
+}
+
+/// Sign out if `result` is GitHub rejecting `access_token`. Request helpers only report the
+/// rejection; commands pass results through this so the token is handled once, at the boundary.
+async fn check_token<T>(
+    access_token: &str,
+    result: Result<T, CommandError>,
+) -> Result<T, CommandError> {
+    if matches!(&result, Err(err) if err.code == ErrorCode::Unauthorized) {
+        handle_auth_lost(access_token).await;
+    }
+    result
+}
+
 fn read_string_setting(settings: &Value, key: &str) -> Option<String> {
     settings
         .get(key)?
@@ -1001,6 +1290,88 @@ async fn get_origin_remote_url(directory: &str) -> Option<String> {
         .filter(|s| !s.is_empty())
 }
 
+async fn get_repo_toplevel(directory: &str) -> Option<PathBuf> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .arg("rev-parse")
+        .arg("--show-toplevel")
+        .output()
+        .await
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+    let toplevel = String::from_utf8(output.stdout).ok()?;
+    fs::canonicalize(toplevel.trim()).await.ok()
+}
+
+fn is_pr_template_file(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    lower == format!("{PR_TEMPLATE_NAME}.md") || lower == format!("{PR_TEMPLATE_NAME}.txt")
+}
+
+/// Read a template file if it resolves inside `root` and is small enough; symlinks pointing out
+/// of the repo are ignored.
+async fn read_pr_template(root: &Path, path: &Path) -> Option<String> {
+    let resolved = fs::canonicalize(path).await.ok()?;
+    if !resolved.starts_with(root) {
+        return None;
+    }
+    let metadata = fs::metadata(&resolved).await.ok()?;
+    if !metadata.is_file() || metadata.len() > PR_TEMPLATE_MAX_BYTES {
+        return None;
+    }
+    fs::read_to_string(&resolved).await.ok()
+}
+
+fn repo_relative_key(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+async fn collect_pr_templates(root: &Path) -> BTreeMap<String, String> {
+    let mut templates = BTreeMap::new();
+    for dir in PR_TEMPLATE_DIRS {
+        let base = root.join(dir);
+        let Ok(mut entries) = fs::read_dir(&base).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let path = entry.path();
+            if is_pr_template_file(&name) {
+                if let Some(content) = read_pr_template(root, &path).await {
+                    templates.insert(repo_relative_key(root, &path), content);
+                }
+                continue;
+            }
+            if !name.eq_ignore_ascii_case(PR_TEMPLATE_NAME) {
+                continue;
+            }
+            let Ok(mut nested) = fs::read_dir(&path).await else {
+                continue;
+            };
+            while let Ok(Some(template)) = nested.next_entry().await {
+                let template_path = template.path();
+                let is_markdown = template_path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+                if !is_markdown {
+                    continue;
+                }
+                if let Some(content) = read_pr_template(root, &template_path).await {
+                    templates.insert(repo_relative_key(root, &template_path), content);
+                }
+            }
+        }
+    }
+    templates
+}
+
 fn parse_github_remote_url(remote_url: &str) -> Option<GitHubRepoRef> {
     let trimmed = remote_url.trim();
     if trimmed.is_empty() {
@@ -1057,58 +1428,183 @@ async fn resolve_repo_from_directory(directory: &str) -> Option<GitHubRepoRef> {
     parse_github_remote_url(&remote)
 }
 
+/// How long GitHub asks us to back off, if `resp` is a primary or secondary rate-limit response.
+/// A bare 429 without rate-limit headers only counts when `retry_blind` is set.
+fn rate_limit_wait(resp: &reqwest::Response, attempt: u32, retry_blind: bool) -> Option<Duration> {
+    let status = resp.status();
+    let headers = resp.headers();
+    let header_str = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    let retry_after = header_str("retry-after").and_then(|value| value.trim().parse::<u64>().ok());
+    let exhausted = header_str("x-ratelimit-remaining") == Some("0");
+
+    let explicit = retry_after.is_some() || exhausted;
+    let limited = (status == reqwest::StatusCode::TOO_MANY_REQUESTS && (explicit || retry_blind))
+        || (status == reqwest::StatusCode::FORBIDDEN && explicit);
+    if !limited {
+        return None;
+    }
+
+    if let Some(seconds) = retry_after {
+        return Some(Duration::from_secs(seconds));
+    }
+    if exhausted {
+        let reset = header_str("x-ratelimit-reset").and_then(|value| value.parse::<i64>().ok());
+        if let Some(reset) = reset {
+            let seconds = reset.saturating_sub(chrono::Utc::now().timestamp()).max(1);
+            return Some(Duration::from_secs(seconds as u64));
+        }
+    }
+    Some(GITHUB_RETRY_BASE_DELAY * 2u32.pow(attempt))
+}
+
+fn not_connected() -> CommandError {
+    CommandError::new(ErrorCode::NotConnected, "GitHub not connected")
+}
+
+fn token_rejected() -> CommandError {
+    CommandError::new(ErrorCode::Unauthorized, "GitHub token expired or revoked")
+}
+
+fn repo_not_resolved() -> CommandError {
+    CommandError::new(
+        ErrorCode::RepoNotFound,
+        "Unable to resolve GitHub repo from git remote",
+    )
+}
+
+/// Classify an unsuccessful GitHub API status. `body` is GitHub's explanation, if read.
+fn github_status_error(status: reqwest::StatusCode, body: &str) -> CommandError {
+    let code = match status {
+        reqwest::StatusCode::UNAUTHORIZED => return token_rejected(),
+        reqwest::StatusCode::FORBIDDEN => ErrorCode::Forbidden,
+        reqwest::StatusCode::NOT_FOUND => ErrorCode::NotFound,
+        reqwest::StatusCode::TOO_MANY_REQUESTS => ErrorCode::RateLimited,
+        _ => ErrorCode::Internal,
+    };
+    let message = if body.is_empty() {
+        format!("GitHub request failed: {}", status)
+    } else {
+        format!("GitHub request failed: {} {}", status, body)
+    };
+    CommandError::new(code, message).with_details(serde_json::json!({ "status": status.as_u16() }))
+}
+
+/// Send a GitHub API request, waiting out rate limits (bounded) and retrying 5xx and
+/// connection failures with exponential backoff. `build` is called once per attempt.
+///
+/// Only GETs are retried blindly; anything else may already have taken effect, so it is
+/// resent only when GitHub explicitly rate-limited it.
+async fn send_github_request<F>(build: F) -> Result<reqwest::Response, CommandError>
+where
+    F: Fn() -> reqwest::RequestBuilder,
+{
+    let idempotent =
+        matches!(build().build(), Ok(request) if request.method() == reqwest::Method::GET);
+    let mut attempt = 0;
+    loop {
+        let backoff = GITHUB_RETRY_BASE_DELAY * 2u32.pow(attempt);
+        let resp = match build().send().await {
+            Ok(resp) => resp,
+            Err(err)
+                if idempotent
+                    && attempt < GITHUB_MAX_RETRIES
+                    && (err.is_connect() || err.is_timeout()) =>
+            {
+                tokio::time::sleep(backoff).await;
+                attempt += 1;
+                continue;
+            }
+            Err(err) => return Err(CommandError::new(ErrorCode::Network, err.to_string())),
+        };
+
+        if let Some(wait) = rate_limit_wait(&resp, attempt, idempotent) {
+            if attempt >= GITHUB_MAX_RETRIES || wait > GITHUB_MAX_RETRY_WAIT {
+                return Err(CommandError::new(
+                    ErrorCode::RateLimited,
+                    format!("GitHub rate limit exceeded; retry in {}s", wait.as_secs()),
+                )
+                .with_details(serde_json::json!({ "retryAfterSecs": wait.as_secs() })));
+            }
+            log::warn!(
+                "[desktop:github] rate limited, retrying in {}s",
+                wait.as_secs()
+            );
+            tokio::time::sleep(wait).await;
+            attempt += 1;
+            continue;
+        }
+        if idempotent && resp.status().is_server_error() && attempt < GITHUB_MAX_RETRIES {
+            tokio::time::sleep(backoff).await;
+            attempt += 1;
+            continue;
+        }
+        return Ok(resp);
+    }
+}
+
 async fn github_get_json<T: for<'de> Deserialize<'de>>(
     url: &str,
     access_token: &str,
-) -> Result<T, String> {
+) -> Result<T, CommandError> {
     let client = reqwest::Client::new();
-    let resp = client
-        .get(url)
-        .header("Accept", "application/vnd.github+json")
-        .header("Authorization", format!("Bearer {}", access_token))
-        .header("User-Agent", "OpenChamber")
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    let resp = send_github_request(|| {
+        client
+            .get(url)
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("User-Agent", "OpenChamber")
+    })
+    .await?;
 
     if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
-        return Err("unauthorized".to_string());
+        return Err(token_rejected());
     }
     if !resp.status().is_success() {
-        return Err(format!("GitHub request failed: {}", resp.status()));
+        return Err(github_status_error(resp.status(), ""));
     }
-    resp.json::<T>().await.map_err(|e| e.to_string())
+    resp.json::<T>()
+        .await
+        .map_err(|e| CommandError::from(e.to_string()))
 }
 
 async fn github_post_json<T: for<'de> Deserialize<'de>, B: Serialize>(
     url: &str,
     access_token: &str,
     body: &B,
-) -> Result<T, String> {
+) -> Result<T, CommandError> {
     let client = reqwest::Client::new();
-    let resp = client
-        .post(url)
-        .header("Accept", "application/vnd.github+json")
-        .header("Authorization", format!("Bearer {}", access_token))
-        .header("User-Agent", "OpenChamber")
-        .json(body)
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    let resp = send_github_request(|| {
+        client
+            .post(url)
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("User-Agent", "OpenChamber")
+            .json(body)
+    })
+    .await?;
 
     if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
-        return Err("unauthorized".to_string());
+        return Err(token_rejected());
     }
     if !resp.status().is_success() {
         let status = resp.status();
         let text = resp.text().await.unwrap_or_default();
-        return Err(format!("GitHub request failed: {} {}", status, text));
+        return Err(github_status_error(status, &text));
     }
-    resp.json::<T>().await.map_err(|e| e.to_string())
+    resp.json::<T>()
+        .await
+        .map_err(|e| CommandError::from(e.to_string()))
 }
 
 
-async fn fetch_me(access_token: &str) -> Result<GitHubUserSummary, String> {
+async fn fetch_me(access_token: &str) -> Result<GitHubUserSummary, CommandError> {
+    fetch_me_with_scopes(access_token).await.map(|(user, _)| user)
+}
+
+/// `/user` plus the token's `x-oauth-scopes` header, which only classic tokens carry.
+async fn fetch_me_with_scopes(
+    access_token: &str,
+) -> Result<(GitHubUserSummary, Option<String>), CommandError> {
     let client = reqwest::Client::new();
     let resp = client
         .get(API_USER_URL)
@@ -1117,16 +1613,18 @@ async fn fetch_me(access_token: &str) -> Result<GitHubUserSummary, String> {
         .header("User-Agent", "OpenChamber")
         .send()
         .await
-        .map_err(|e| e.to_string())?;
-
-    if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
-        return Err("unauthorized".to_string());
-    }
+        .map_err(|e| CommandError::new(ErrorCode::Network, e.to_string()))?;
 
     if !resp.status().is_success() {
-        return Err(format!("GitHub /user failed: {}", resp.status()));
+        return Err(github_status_error(resp.status(), ""));
     }
 
+    let scopes = resp
+        .headers()
+        .get("x-oauth-scopes")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.trim().to_string());
+
     let payload = resp
         .json::<ApiUserResponse>()
         .await
@@ -1137,13 +1635,14 @@ async fn fetch_me(access_token: &str) -> Result<GitHubUserSummary, String> {
         _ => fetch_primary_email(access_token).await.ok().flatten(),
     };
 
-    Ok(GitHubUserSummary {
+    let user = GitHubUserSummary {
         login: payload.login,
         id: Some(payload.id),
         avatar_url: payload.avatar_url,
         name: payload.name,
         email,
-    })
+    };
+    Ok((user, scopes))
 }
 
 fn map_issue_user(user: &IssueUser) -> GitHubUserSummary {
@@ -1167,17 +1666,49 @@ fn map_issue_labels(labels: Vec<IssueLabel>) -> Vec<GitHubIssueLabel> {
         .collect()
 }
 
+fn map_issue_summary(item: IssueListItem) -> GitHubIssueSummary {
+    GitHubIssueSummary {
+        number: item.number,
+        title: item.title,
+        url: item.html_url,
+        state: item.state,
+        author: item.user.as_ref().map(map_issue_user),
+        labels: Some(map_issue_labels(item.labels)),
+    }
+}
+
+fn normalize_issue_state(state: Option<&str>) -> Result<&'static str, CommandError> {
+    match state.map(str::trim).filter(|value| !value.is_empty()) {
+        None | Some("open") => Ok("open"),
+        Some("closed") => Ok("closed"),
+        Some("all") => Ok("all"),
+        Some(other) => Err(CommandError::invalid_argument(format!(
+            "Invalid issue state '{}': expected open, closed or all",
+            other
+        ))),
+    }
+}
+
+// GitHub answers 410 Gone on the issues endpoints when the repo has issues turned off.
+fn issues_disabled_error(repo: &GitHubRepoRef) -> CommandError {
+    CommandError::not_found(format!(
+        "Issues are disabled for {}/{}",
+        repo.owner, repo.repo
+    ))
+    .with_details(serde_json::json!({ "reason": "issues_disabled" }))
+}
+
 #[tauri::command]
 pub async fn github_auth_status(
     _state: State<'_, DesktopRuntime>,
-) -> Result<GitHubAuthStatus, String> {
+) -> Result<GitHubAuthStatus, CommandError> {
     resolve_auth_status().await
 }
 
 #[tauri::command]
 pub async fn github_auth_start(
     state: State<'_, DesktopRuntime>,
-) -> Result<GitHubDeviceFlowStart, String> {
+) -> Result<GitHubDeviceFlowStart, CommandError> {
     let (client_id, scopes) = resolve_client_config(state.inner()).await;
 
     let client = reqwest::Client::new();
@@ -1194,7 +1725,7 @@ pub async fn github_auth_start(
         .map_err(|e| e.to_string())?;
 
     if !resp.status().is_success() {
-        return Err(format!("GitHub device code failed: {}", resp.status()));
+        return Err(format!("GitHub device code failed: {}", resp.status()).into());
     }
 
     let payload = resp
@@ -1212,15 +1743,78 @@ pub async fn github_auth_start(
     })
 }
 
+/// Check that `clientId` is an OAuth app with device flow enabled by requesting a device
+/// code. The code is discarded unused and simply expires; nothing is stored.
+#[tauri::command]
+pub async fn validate_github_client(
+    #[allow(non_snake_case)]
+    clientId: String,
+    scopes: Option<String>,
+) -> Result<GitHubClientValidation, CommandError> {
+    let client_id = clientId.trim().to_string();
+    if client_id.is_empty() {
+        return Err(CommandError::invalid_argument("clientId is required"));
+    }
+    let scopes = scopes
+        .map(|scopes| scopes.trim().to_string())
+        .filter(|scopes| !scopes.is_empty())
+        .unwrap_or_else(|| DEFAULT_GITHUB_SCOPES.to_string());
+    let scopes: Vec<String> = scopes
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|scope| !scope.is_empty())
+        .map(str::to_string)
+        .collect();
+    let unknown_scopes = scopes
+        .iter()
+        .filter(|scope| !KNOWN_GITHUB_SCOPES.contains(&scope.as_str()))
+        .cloned()
+        .collect();
+
+    let client = reqwest::Client::new();
+    let resp = client
+        .post(DEVICE_CODE_URL)
+        .header("Accept", "application/json")
+        .header("User-Agent", "OpenChamber")
+        .form(&[
+            ("client_id", client_id.as_str()),
+            ("scope", scopes.join(" ").as_str()),
+        ])
+        .send()
+        .await
+        .map_err(|e| {
+            CommandError::new(ErrorCode::Network, format!("Failed to reach GitHub: {}", e))
+        })?;
+
+    // A rejected client id comes back as an `error` in the body, not always with a 4xx.
+    let status = resp.status();
+    let body: Value = resp.json().await.unwrap_or(Value::Null);
+    let read = |key: &str| body.get(key).and_then(Value::as_str).map(str::to_string);
+    let (valid, error) = match read("error") {
+        Some(error) => (false, Some(error)),
+        None if status.is_success() && body.get("device_code").is_some() => (true, None),
+        None if status.is_client_error() => (false, Some(format!("http_{}", status.as_u16()))),
+        None => return Err(format!("GitHub device code failed: {}", status).into()),
+    };
+
+    Ok(GitHubClientValidation {
+        client_id,
+        valid,
+        error,
+        error_description: read("error_description"),
+        scopes,
+        unknown_scopes,
+    })
+}
+
 #[tauri::command]
 pub async fn github_auth_complete(
     #[allow(non_snake_case)]
     deviceCode: String,
     state: State<'_, DesktopRuntime>,
-) -> Result<GitHubDeviceFlowComplete, String> {
+) -> Result<GitHubDeviceFlowComplete, CommandError> {
     let device_code = deviceCode;
     if device_code.trim().is_empty() {
-        return Err("deviceCode is required".to_string());
+        return Err(CommandError::invalid_argument("deviceCode is required"));
     }
 
     let (client_id, _) = resolve_client_config(state.inner()).await;
@@ -1240,7 +1834,7 @@ pub async fn github_auth_complete(
         .map_err(|e| e.to_string())?;
 
     if !resp.status().is_success() {
-        return Err(format!("GitHub token exchange failed: {}", resp.status()));
+        return Err(format!("GitHub token exchange failed: {}", resp.status()).into());
     }
 
     let payload = resp
@@ -1259,12 +1853,12 @@ pub async fn github_auth_complete(
 
     let access_token = payload.access_token.unwrap_or_default();
     if access_token.trim().is_empty() {
-        return Err("Missing access_token from GitHub".to_string());
+        return Err("Missing access_token from GitHub".into());
     }
 
     let user = fetch_me(&access_token).await.map_err(|e| {
-        if e == "unauthorized" {
-            "GitHub token invalid".to_string()
+        if e.code == ErrorCode::Unauthorized {
+            CommandError::new(ErrorCode::Unauthorized, "GitHub token invalid")
         } else {
             e
         }
@@ -1295,26 +1889,128 @@ pub async fn github_auth_complete(
     ))
 }
 
-#[tauri::command]
-pub async fn github_auth_disconnect(
-    _state: State<'_, DesktopRuntime>,
-) -> Result<GitHubDisconnectResult, String> {
-    let removed = clear_auth_file().await;
-    Ok(GitHubDisconnectResult { removed })
+fn validate_personal_access_token(token: &str) -> Result<(), CommandError> {
+    if token.is_empty() {
+        return Err(CommandError::invalid_argument("token is required"));
+    }
+    let well_formed =
+        token.len() <= 255 && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let legacy = token.len() == 40 && token.chars().all(|c| c.is_ascii_hexdigit());
+    let prefixed = GITHUB_TOKEN_PREFIXES
+        .iter()
+        .any(|prefix| token.starts_with(prefix) && token.len() > prefix.len() + 20);
+    if !well_formed || !(legacy || prefixed) {
+        return Err(CommandError::invalid_argument(
+            "This doesn't look like a GitHub personal access token",
+        ));
+    }
+    Ok(())
+}
+
+/// Fine-grained tokens carry no scopes header, so probe whether they can see any repository.
+async fn check_fine_grained_access(access_token: &str) -> Result<(), CommandError> {
+    let url = format!("{}?per_page=1", API_USER_REPOS_URL);
+    let repos = github_get_json::<Vec<Value>>(&url, access_token)
+        .await
+        .map_err(|e| {
+            if e.code == ErrorCode::Forbidden {
+                CommandError::new(ErrorCode::Forbidden, "The fine-grained token is not allowed to list repositories. Grant it the Metadata (read) permission.")
+            } else {
+                e
+            }
+        })?;
+    if repos.is_empty() {
+        return Err(CommandError::new(ErrorCode::Forbidden, "The fine-grained token has no repository access. Select the repositories to use and grant Contents, Pull requests and Issues permissions."));
+    }
+    Ok(())
 }
 
+/// Connect with a personal access token instead of the device flow (e.g. when the OAuth app is
+/// blocked by an organization). The account is stored exactly like a device-flow login.
 #[tauri::command]
-#[allow(non_snake_case)]
-pub async fn github_auth_activate(
-    accountId: String,
+pub async fn github_auth_with_token(
+    token: String,
     _state: State<'_, DesktopRuntime>,
-) -> Result<GitHubAuthStatus, String> {
-    let account_id = accountId.trim().to_string();
-    if account_id.is_empty() {
-        return Err("accountId is required".to_string());
-    }
+) -> Result<GitHubAuthStatus, CommandError> {
+    let token = token.trim().to_string();
+    validate_personal_access_token(&token)?;
 
-    let mut list = read_auth_list().await;
+    let (user, scope) = fetch_me_with_scopes(&token)
+        .await
+        .map_err(|e| match e.code {
+            ErrorCode::Unauthorized => CommandError::new(
+                ErrorCode::Unauthorized,
+                "GitHub rejected the token: it is invalid, expired or revoked",
+            ),
+            ErrorCode::Forbidden => CommandError::new(
+                ErrorCode::Forbidden,
+                "The token is not allowed to read your GitHub profile",
+            ),
+            _ => e,
+        })?;
+
+    if token.starts_with(FINE_GRAINED_TOKEN_PREFIX) {
+        check_fine_grained_access(&token).await?;
+    } else if let Some(scope) = scope.as_deref() {
+        let has_repo_scope = scope
+            .split(',')
+            .map(str::trim)
+            .any(|s| s == "repo" || s == "public_repo");
+        if !has_repo_scope {
+            return Err(CommandError::new(
+                ErrorCode::Forbidden,
+                format!(
+                    "The token is missing the repo scope (granted: {})",
+                    if scope.is_empty() { "none" } else { scope }
+                ),
+            ));
+        }
+    }
+
+    let stored = StoredAuth {
+        access_token: token,
+        scope: scope.clone().filter(|s| !s.is_empty()),
+        token_type: Some("bearer".to_string()),
+        created_at: Some(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+        ),
+        user: Some(user.clone()),
+        account_id: None,
+        current: true,
+    };
+    write_auth_file(&stored).await?;
+
+    Ok(GitHubAuthStatus {
+        connected: true,
+        user: Some(user),
+        scope: stored.scope,
+        accounts: build_auth_accounts(&read_auth_list().await),
+    })
+}
+
+#[tauri::command]
+pub async fn github_auth_disconnect(
+    _state: State<'_, DesktopRuntime>,
+) -> Result<GitHubDisconnectResult, CommandError> {
+    let removed = clear_auth_file().await;
+    Ok(GitHubDisconnectResult { removed })
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+pub async fn github_auth_activate(
+    accountId: String,
+    _state: State<'_, DesktopRuntime>,
+) -> Result<GitHubAuthStatus, CommandError> {
+    let account_id = accountId.trim().to_string();
+    if account_id.is_empty() {
+        return Err(CommandError::invalid_argument("accountId is required"));
+    }
+
+    let mut list = read_auth_list().await;
     if list.is_empty() {
         return Ok(GitHubAuthStatus {
             connected: false,
@@ -1336,7 +2032,7 @@ pub async fn github_auth_activate(
     }
 
     if !found {
-        return Err("GitHub account not found".to_string());
+        return Err(CommandError::not_found("GitHub account not found"));
     }
 
     persist_auth_list(&list).await?;
@@ -1344,34 +2040,155 @@ pub async fn github_auth_activate(
 }
 
 #[tauri::command]
-pub async fn github_me(_state: State<'_, DesktopRuntime>) -> Result<GitHubUserSummary, String> {
-    let stored = read_auth_file().await;
+pub async fn github_me(
+    _state: State<'_, DesktopRuntime>,
+) -> Result<GitHubUserSummary, CommandError> {
+    let stored = load_stored_auth().await;
     let Some(stored) = stored else {
-        return Err("GitHub not connected".to_string());
+        return Err(not_connected());
     };
     match fetch_me(&stored.access_token).await {
         Ok(user) => Ok(user),
-        Err(err) if err == "unauthorized" => {
-            let _ = clear_auth_file().await;
-            Err("GitHub token expired or revoked".to_string())
+        Err(err) if err.code == ErrorCode::Unauthorized => {
+            handle_auth_lost(&stored.access_token).await;
+            Err(token_rejected())
         }
         Err(err) => Err(err),
     }
 }
 
+/// The commit's check runs (Actions); empty if there are none or they couldn't be read.
+async fn fetch_check_runs(
+    repo: &GitHubRepoRef,
+    sha: &str,
+    access_token: &str,
+) -> Vec<CheckRunEntry> {
+    let check_runs_url = format!(
+        "{}/{}/{}/commits/{}/check-runs",
+        API_PULLS_URL_PREFIX, repo.owner, repo.repo, sha
+    );
+    github_get_json::<CheckRunsResponse>(&check_runs_url, access_token)
+        .await
+        .map(|runs| runs.check_runs)
+        .unwrap_or_default()
+}
+
+/// Aggregate CI state for `sha`: check runs (Actions) when present, else classic statuses.
+async fn fetch_checks_summary(
+    repo: &GitHubRepoRef,
+    sha: &str,
+    access_token: &str,
+) -> Option<GitHubChecksSummary> {
+    let check_runs = fetch_check_runs(repo, sha, access_token).await;
+    summarize_checks(repo, sha, &check_runs, access_token).await
+}
+
+/// Like `fetch_checks_summary`, for callers that already fetched the check runs.
+async fn summarize_checks(
+    repo: &GitHubRepoRef,
+    sha: &str,
+    check_runs: &[CheckRunEntry],
+    access_token: &str,
+) -> Option<GitHubChecksSummary> {
+    let mut checks: Option<GitHubChecksSummary> = None;
+
+    if !check_runs.is_empty() {
+        let mut success = 0;
+        let mut failure = 0;
+        let mut pending = 0;
+
+        for run in check_runs.iter() {
+            let status = run.status.as_deref().unwrap_or("");
+            let conclusion = run.conclusion.as_deref().unwrap_or("");
+            if status == "queued" || status == "in_progress" {
+                pending += 1;
+                continue;
+            }
+            if conclusion.is_empty() {
+                pending += 1;
+                continue;
+            }
+            if conclusion == "success" || conclusion == "neutral" || conclusion == "skipped" {
+                success += 1;
+            } else {
+                failure += 1;
+            }
+        }
+
+        let total = success + failure + pending;
+        let state = if failure > 0 {
+            "failure"
+        } else if pending > 0 {
+            "pending"
+        } else if total > 0 {
+            "success"
+        } else {
+            "unknown"
+        };
+        checks = Some(GitHubChecksSummary {
+            state: state.to_string(),
+            total,
+            success,
+            failure,
+            pending,
+        });
+    }
+
+    if checks.is_none() {
+        let status_url = format!(
+            "{}/{}/{}/commits/{}/status",
+            API_PULLS_URL_PREFIX, repo.owner, repo.repo, sha
+        );
+        if let Ok(status) = github_get_json::<CombinedStatusResponse>(&status_url, access_token).await {
+            let mut success = 0;
+            let mut failure = 0;
+            let mut pending = 0;
+            for s in status.statuses.iter() {
+                match s.state.as_str() {
+                    "success" => success += 1,
+                    "failure" | "error" => failure += 1,
+                    "pending" => pending += 1,
+                    _ => {}
+                }
+            }
+            let total = success + failure + pending;
+            let state = if failure > 0 {
+                "failure"
+            } else if pending > 0 {
+                "pending"
+            } else if total > 0 {
+                "success"
+            } else {
+                "unknown"
+            };
+            checks = Some(GitHubChecksSummary {
+                state: state.to_string(),
+                total,
+                success,
+                failure,
+                pending,
+            });
+        }
+    }
+
+    checks
+}
+
 #[tauri::command]
 pub async fn github_pr_status(
     directory: String,
     branch: String,
     _state: State<'_, DesktopRuntime>,
-) -> Result<GitHubPullRequestStatus, String> {
+) -> Result<GitHubPullRequestStatus, CommandError> {
     let directory = directory.trim().to_string();
     let branch = branch.trim().to_string();
     if directory.is_empty() || branch.is_empty() {
-        return Err("directory and branch are required".to_string());
+        return Err(CommandError::invalid_argument(
+            "directory and branch are required",
+        ));
     }
 
-    let stored = read_auth_file().await;
+    let stored = load_stored_auth().await;
     let Some(stored) = stored else {
         return Ok(GitHubPullRequestStatus {
             connected: false,
@@ -1383,17 +2200,6 @@ pub async fn github_pr_status(
         });
     };
 
-    if stored.access_token.trim().is_empty() {
-        let _ = clear_auth_file().await;
-        return Ok(GitHubPullRequestStatus {
-            connected: false,
-            repo: None,
-            branch: Some(branch),
-            pr: None,
-            checks: None,
-            can_merge: None,
-        });
-    }
 
     let repo = resolve_repo_from_directory(&directory).await;
     let Some(repo) = repo else {
@@ -1417,8 +2223,8 @@ pub async fn github_pr_status(
     let list = github_get_json::<Vec<PrListItem>>(&list_url, &stored.access_token).await;
     let list = match list {
         Ok(v) => v,
-        Err(err) if err == "unauthorized" => {
-            let _ = clear_auth_file().await;
+        Err(err) if err.code == ErrorCode::Unauthorized => {
+            handle_auth_lost(&stored.access_token).await;
             return Ok(GitHubPullRequestStatus {
                 connected: false,
                 repo: None,
@@ -1471,96 +2277,13 @@ pub async fn github_pr_status(
         "{}/{}/{}/pulls/{}",
         API_PULLS_URL_PREFIX, repo.owner, repo.repo, first_number
     );
-    let pr = github_get_json::<PullDetailsResponse>(&pr_url, &stored.access_token).await?;
-
-    // Checks summary: prefer check-runs (Actions), fallback to classic statuses
-    let mut checks: Option<GitHubChecksSummary> = None;
-
-    let check_runs_url = format!(
-        "{}/{}/{}/commits/{}/check-runs",
-        API_PULLS_URL_PREFIX, repo.owner, repo.repo, pr.head.sha
-    );
-
-    if let Ok(runs) = github_get_json::<CheckRunsResponse>(&check_runs_url, &stored.access_token).await {
-        if !runs.check_runs.is_empty() {
-            let mut success = 0;
-            let mut failure = 0;
-            let mut pending = 0;
-
-            for run in runs.check_runs.iter() {
-                let status = run.status.as_deref().unwrap_or("");
-                let conclusion = run.conclusion.as_deref().unwrap_or("");
-                if status == "queued" || status == "in_progress" {
-                    pending += 1;
-                    continue;
-                }
-                if conclusion.is_empty() {
-                    pending += 1;
-                    continue;
-                }
-                if conclusion == "success" || conclusion == "neutral" || conclusion == "skipped" {
-                    success += 1;
-                } else {
-                    failure += 1;
-                }
-            }
-
-            let total = success + failure + pending;
-            let state = if failure > 0 {
-                "failure"
-            } else if pending > 0 {
-                "pending"
-            } else if total > 0 {
-                "success"
-            } else {
-                "unknown"
-            };
-            checks = Some(GitHubChecksSummary {
-                state: state.to_string(),
-                total,
-                success,
-                failure,
-                pending,
-            });
-        }
-    }
+    let pr = check_token(
+        &stored.access_token,
+        github_get_json::<PullDetailsResponse>(&pr_url, &stored.access_token).await,
+    )
+    .await?;
 
-    if checks.is_none() {
-        let status_url = format!(
-            "{}/{}/{}/commits/{}/status",
-            API_PULLS_URL_PREFIX, repo.owner, repo.repo, pr.head.sha
-        );
-        if let Ok(status) = github_get_json::<CombinedStatusResponse>(&status_url, &stored.access_token).await {
-            let mut success = 0;
-            let mut failure = 0;
-            let mut pending = 0;
-            for s in status.statuses.iter() {
-                match s.state.as_str() {
-                    "success" => success += 1,
-                    "failure" | "error" => failure += 1,
-                    "pending" => pending += 1,
-                    _ => {}
-                }
-            }
-            let total = success + failure + pending;
-            let state = if failure > 0 {
-                "failure"
-            } else if pending > 0 {
-                "pending"
-            } else if total > 0 {
-                "success"
-            } else {
-                "unknown"
-            };
-            checks = Some(GitHubChecksSummary {
-                state: state.to_string(),
-                total,
-                success,
-                failure,
-                pending,
-            });
-        }
-    }
+    let checks = fetch_checks_summary(&repo, &pr.head.sha, &stored.access_token).await;
 
     // Permissions (best-effort)
     let mut can_merge = None;
@@ -1609,6 +2332,160 @@ pub async fn github_pr_status(
     })
 }
 
+/// One poll of a watched PR. `Err` is the `github_get_json` error.
+async fn poll_pr_checks(
+    directory: &str,
+    repo: &GitHubRepoRef,
+    number: u64,
+    access_token: &str,
+) -> Result<GitHubPrChecksChanged, CommandError> {
+    let pr_url = format!(
+        "{}/{}/{}/pulls/{}",
+        API_PULLS_URL_PREFIX, repo.owner, repo.repo, number
+    );
+    let pr = github_get_json::<PullDetailsResponse>(&pr_url, access_token).await?;
+    let state = if pr.merged {
+        "merged"
+    } else if pr.state == "closed" {
+        "closed"
+    } else {
+        "open"
+    };
+    let checks = fetch_checks_summary(repo, &pr.head.sha, access_token).await;
+
+    Ok(GitHubPrChecksChanged {
+        directory: directory.to_string(),
+        number,
+        repo: repo.clone(),
+        state: state.to_string(),
+        head_sha: pr.head.sha,
+        checks,
+    })
+}
+
+fn pr_watch_key(repo: &GitHubRepoRef, number: u64) -> PrWatchKey {
+    (
+        repo.owner.to_ascii_lowercase(),
+        repo.repo.to_ascii_lowercase(),
+        number,
+    )
+}
+
+async fn run_pr_watch(app: AppHandle, directory: String, repo: GitHubRepoRef, number: u64) {
+    // (PR state, head sha, checks state) of the last emitted event.
+    let mut last: Option<(String, String, Option<String>)> = None;
+    loop {
+        let Some(stored) = load_stored_auth().await else {
+            break;
+        };
+        let interval = match poll_pr_checks(&directory, &repo, number, &stored.access_token).await {
+            Ok(update) => {
+                let key = (
+                    update.state.clone(),
+                    update.head_sha.clone(),
+                    update.checks.as_ref().map(|checks| checks.state.clone()),
+                );
+                let pending = key.2.as_deref() == Some("pending");
+                let settled = update.state != "open";
+                if last.as_ref() != Some(&key) {
+                    let _ = app.emit(PR_CHECKS_CHANGED_EVENT, update);
+                    last = Some(key);
+                }
+                if settled {
+                    break;
+                }
+                if pending {
+                    PR_WATCH_PENDING_INTERVAL
+                } else {
+                    PR_WATCH_SETTLED_INTERVAL
+                }
+            }
+            Err(err) if err.code == ErrorCode::Unauthorized => {
+                handle_auth_lost(&stored.access_token).await;
+                break;
+            }
+            Err(err) => {
+                log::warn!(
+                    "[desktop:github] PR #{} checks poll failed: {}",
+                    number,
+                    err
+                );
+                PR_WATCH_ERROR_INTERVAL
+            }
+        };
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Poll PR `number`'s checks in the background and emit `openchamber:pr-checks-changed` when
+/// the aggregate state changes. Watching a PR that is already watched restarts its watch.
+#[tauri::command]
+pub async fn github_pr_watch(
+    directory: String,
+    number: u64,
+    app: AppHandle,
+    _state: State<'_, DesktopRuntime>,
+) -> Result<(), CommandError> {
+    let directory = directory.trim().to_string();
+    if directory.is_empty() || number == 0 {
+        return Err(CommandError::invalid_argument(
+            "directory and number are required",
+        ));
+    }
+
+    if load_stored_auth().await.is_none() {
+        return Err(not_connected());
+    }
+    let repo = resolve_repo_from_directory(&directory)
+        .await
+        .ok_or_else(repo_not_resolved)?;
+
+    let id = NEXT_PR_WATCH_ID.fetch_add(1, Ordering::Relaxed);
+    let key = pr_watch_key(&repo, number);
+    let task_key = key.clone();
+    let mut watches = PR_WATCHES.lock();
+    let task = tauri::async_runtime::spawn(async move {
+        run_pr_watch(app, directory, repo, number).await;
+        let mut watches = PR_WATCHES.lock();
+        if watches.get(&task_key).is_some_and(|watch| watch.id == id) {
+            watches.remove(&task_key);
+        }
+    });
+    if let Some(previous) = watches.insert(key, PrWatch { id, task }) {
+        previous.task.abort();
+    }
+    Ok(())
+}
+
+/// Number of PRs with an active checks watch.
+pub(crate) fn pr_watch_count() -> usize {
+    PR_WATCHES.lock().len()
+}
+
+/// Stop watching PR `number` of `directory`'s repo. Returns `false` if it wasn't being watched.
+#[tauri::command]
+pub async fn github_pr_unwatch(
+    directory: String,
+    number: u64,
+    _state: State<'_, DesktopRuntime>,
+) -> Result<bool, CommandError> {
+    let directory = directory.trim();
+    if directory.is_empty() || number == 0 {
+        return Err(CommandError::invalid_argument(
+            "directory and number are required",
+        ));
+    }
+    let repo = resolve_repo_from_directory(directory)
+        .await
+        .ok_or_else(repo_not_resolved)?;
+
+    let Some(watch) = PR_WATCHES.lock().remove(&pr_watch_key(&repo, number)) else {
+        return Ok(false);
+    };
+    watch.task.abort();
+    Ok(true)
+}
+
 #[tauri::command]
 pub async fn github_pr_create(
     directory: String,
@@ -1618,27 +2495,25 @@ pub async fn github_pr_create(
     body: Option<String>,
     draft: Option<bool>,
     _state: State<'_, DesktopRuntime>,
-) -> Result<GitHubPullRequestSummary, String> {
+) -> Result<GitHubPullRequestSummary, CommandError> {
     let directory = directory.trim().to_string();
     let title = title.trim().to_string();
     let head = head.trim().to_string();
     let base = base.trim().to_string();
     if directory.is_empty() || title.is_empty() || head.is_empty() || base.is_empty() {
-        return Err("directory, title, head, base are required".to_string());
+        return Err(CommandError::invalid_argument(
+            "directory, title, head, base are required",
+        ));
     }
 
-    let stored = read_auth_file().await;
+    let stored = load_stored_auth().await;
     let Some(stored) = stored else {
-        return Err("GitHub not connected".to_string());
+        return Err(not_connected());
     };
-    if stored.access_token.trim().is_empty() {
-        let _ = clear_auth_file().await;
-        return Err("GitHub not connected".to_string());
-    }
 
     let repo = resolve_repo_from_directory(&directory)
         .await
-        .ok_or_else(|| "Unable to resolve GitHub repo from git remote".to_string())?;
+        .ok_or_else(repo_not_resolved)?;
 
     let url = format!("{}/{}/{}/pulls", API_PULLS_URL_PREFIX, repo.owner, repo.repo);
     let request = PullCreateRequest {
@@ -1649,7 +2524,11 @@ pub async fn github_pr_create(
         draft,
     };
 
-    let created = github_post_json::<PullCreateResponse, _>(&url, &stored.access_token, &request).await?;
+    let created = check_token(
+        &stored.access_token,
+        github_post_json::<PullCreateResponse, _>(&url, &stored.access_token, &request).await,
+    )
+    .await?;
 
     Ok(GitHubPullRequestSummary {
         number: created.number,
@@ -1669,34 +2548,50 @@ pub async fn github_pr_create(
     })
 }
 
+/// Pull request templates found in the repo containing `directory`, for prefilling the PR body.
+#[tauri::command]
+pub async fn get_pr_template(
+    directory: String,
+    _state: State<'_, DesktopRuntime>,
+) -> Result<GitHubPrTemplatesResult, CommandError> {
+    let directory = directory.trim().to_string();
+    if directory.is_empty() {
+        return Err(CommandError::invalid_argument("directory is required"));
+    }
+
+    let root = get_repo_toplevel(&directory)
+        .await
+        .ok_or_else(|| CommandError::new(ErrorCode::RepoNotFound, "Not a git repository"))?;
+
+    Ok(GitHubPrTemplatesResult {
+        templates: collect_pr_templates(&root).await,
+    })
+}
+
 #[tauri::command]
 pub async fn github_pr_merge(
     directory: String,
     number: u64,
     method: String,
     _state: State<'_, DesktopRuntime>,
-) -> Result<GitHubPullRequestMergeResult, String> {
+) -> Result<GitHubPullRequestMergeResult, CommandError> {
     let directory = directory.trim().to_string();
     let method = method.trim().to_string();
     if directory.is_empty() {
-        return Err("directory is required".to_string());
+        return Err(CommandError::invalid_argument("directory is required"));
     }
     if number == 0 {
-        return Err("number is required".to_string());
+        return Err(CommandError::invalid_argument("number is required"));
     }
 
-    let stored = read_auth_file().await;
+    let stored = load_stored_auth().await;
     let Some(stored) = stored else {
-        return Err("GitHub not connected".to_string());
+        return Err(not_connected());
     };
-    if stored.access_token.trim().is_empty() {
-        let _ = clear_auth_file().await;
-        return Err("GitHub not connected".to_string());
-    }
 
     let repo = resolve_repo_from_directory(&directory)
         .await
-        .ok_or_else(|| "Unable to resolve GitHub repo from git remote".to_string())?;
+        .ok_or_else(repo_not_resolved)?;
 
     let url = format!(
         "{}/{}/{}/pulls/{}/merge",
@@ -1717,11 +2612,14 @@ pub async fn github_pr_merge(
         .map_err(|e| e.to_string())?;
 
     if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
-        let _ = clear_auth_file().await;
-        return Err("GitHub token expired or revoked".to_string());
+        handle_auth_lost(&stored.access_token).await;
+        return Err(token_rejected());
     }
     if resp.status() == reqwest::StatusCode::FORBIDDEN {
-        return Err("Not authorized to merge this PR".to_string());
+        return Err(CommandError::new(
+            ErrorCode::Forbidden,
+            "Not authorized to merge this PR",
+        ));
     }
     if resp.status() == reqwest::StatusCode::METHOD_NOT_ALLOWED
         || resp.status() == reqwest::StatusCode::CONFLICT
@@ -1732,7 +2630,7 @@ pub async fn github_pr_merge(
         });
     }
     if !resp.status().is_success() {
-        return Err(format!("GitHub merge failed: {}", resp.status()));
+        return Err(format!("GitHub merge failed: {}", resp.status()).into());
     }
 
     let parsed = resp.json::<PullMergeResponse>().await.map_err(|e| e.to_string())?;
@@ -1747,33 +2645,33 @@ pub async fn github_pr_ready(
     directory: String,
     number: u64,
     _state: State<'_, DesktopRuntime>,
-) -> Result<GitHubPullRequestReadyResult, String> {
+) -> Result<GitHubPullRequestReadyResult, CommandError> {
     let directory = directory.trim().to_string();
     if directory.is_empty() {
-        return Err("directory is required".to_string());
+        return Err(CommandError::invalid_argument("directory is required"));
     }
     if number == 0 {
-        return Err("number is required".to_string());
+        return Err(CommandError::invalid_argument("number is required"));
     }
 
-    let stored = read_auth_file().await;
+    let stored = load_stored_auth().await;
     let Some(stored) = stored else {
-        return Err("GitHub not connected".to_string());
+        return Err(not_connected());
     };
-    if stored.access_token.trim().is_empty() {
-        let _ = clear_auth_file().await;
-        return Err("GitHub not connected".to_string());
-    }
 
     let repo = resolve_repo_from_directory(&directory)
         .await
-        .ok_or_else(|| "Unable to resolve GitHub repo from git remote".to_string())?;
+        .ok_or_else(repo_not_resolved)?;
 
     let pr_url = format!(
         "{}/{}/{}/pulls/{}",
         API_PULLS_URL_PREFIX, repo.owner, repo.repo, number
     );
-    let pr = github_get_json::<PullDetailsResponse>(&pr_url, &stored.access_token).await?;
+    let pr = check_token(
+        &stored.access_token,
+        github_get_json::<PullDetailsResponse>(&pr_url, &stored.access_token).await,
+    )
+    .await?;
     let node_id = pr
         .node_id
         .ok_or_else(|| "Failed to resolve PR node id".to_string())?;
@@ -1800,19 +2698,22 @@ pub async fn github_pr_ready(
         .map_err(|e| e.to_string())?;
 
     if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
-        let _ = clear_auth_file().await;
-        return Err("GitHub token expired or revoked".to_string());
+        handle_auth_lost(&stored.access_token).await;
+        return Err(token_rejected());
     }
     if resp.status() == reqwest::StatusCode::FORBIDDEN {
-        return Err("Not authorized to mark PR ready".to_string());
+        return Err(CommandError::new(
+            ErrorCode::Forbidden,
+            "Not authorized to mark PR ready",
+        ));
     }
     if !resp.status().is_success() {
-        return Err(format!("GitHub request failed: {}", resp.status()));
+        return Err(github_status_error(resp.status(), ""));
     }
 
     let body: serde_json::Value = resp.json().await.map_err(|e| e.to_string())?;
     if body.get("errors").is_some() {
-        return Err("GitHub GraphQL error".to_string());
+        return Err("GitHub GraphQL error".into());
     }
 
     Ok(GitHubPullRequestReadyResult { ready: true })
@@ -1822,14 +2723,16 @@ pub async fn github_pr_ready(
 pub async fn github_issues_list(
     directory: String,
     page: Option<u32>,
+    state: Option<String>,
     _state: State<'_, DesktopRuntime>,
-) -> Result<GitHubIssuesListResult, String> {
+) -> Result<GitHubIssuesListResult, CommandError> {
     let directory = directory.trim().to_string();
     if directory.is_empty() {
-        return Err("directory is required".to_string());
+        return Err(CommandError::invalid_argument("directory is required"));
     }
+    let issue_state = normalize_issue_state(state.as_deref())?;
 
-    let stored = read_auth_file().await;
+    let stored = load_stored_auth().await;
     let Some(stored) = stored else {
         return Ok(GitHubIssuesListResult {
             connected: false,
@@ -1839,16 +2742,6 @@ pub async fn github_issues_list(
             has_more: None,
         });
     };
-    if stored.access_token.trim().is_empty() {
-        let _ = clear_auth_file().await;
-        return Ok(GitHubIssuesListResult {
-            connected: false,
-            repo: None,
-            issues: None,
-            page: None,
-            has_more: None,
-        });
-    }
 
     let repo = resolve_repo_from_directory(&directory).await;
     let Some(repo) = repo else {
@@ -1863,20 +2756,24 @@ pub async fn github_issues_list(
 
     let page = page.unwrap_or(1).max(1);
     let url = format!(
-        "{}/{}/{}/issues?state=open&per_page=50&page={}",
-        API_PULLS_URL_PREFIX, repo.owner, repo.repo, page
+        "{}/{}/{}/issues?state={}&per_page=50&page={}",
+        API_PULLS_URL_PREFIX, repo.owner, repo.repo, issue_state, page
     );
 
-    let resp = reqwest::Client::new()
-        .get(url)
-        .header("Accept", "application/vnd.github+json")
-        .header("Authorization", format!("Bearer {}", stored.access_token))
-        .header("User-Agent", "OpenChamber")
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    let client = reqwest::Client::new();
+    let resp = send_github_request(|| {
+        client
+            .get(&url)
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", format!("Bearer {}", stored.access_token))
+            .header("User-Agent", "OpenChamber")
+    })
+    .await?;
+    if resp.status() == reqwest::StatusCode::GONE {
+        return Err(issues_disabled_error(&repo));
+    }
     if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
-        let _ = clear_auth_file().await;
+        handle_auth_lost(&stored.access_token).await;
         return Ok(GitHubIssuesListResult {
             connected: false,
             repo: None,
@@ -1886,7 +2783,7 @@ pub async fn github_issues_list(
         });
     }
     if !resp.status().is_success() {
-        return Err(format!("GitHub request failed: {}", resp.status()));
+        return Err(github_status_error(resp.status(), ""));
     }
     let link = resp
         .headers()
@@ -1900,14 +2797,7 @@ pub async fn github_issues_list(
     let issues = list
         .into_iter()
         .filter(|item| item.pull_request.is_none())
-        .map(|item| GitHubIssueSummary {
-            number: item.number,
-            title: item.title,
-            url: item.html_url,
-            state: item.state,
-            author: item.user.as_ref().map(map_issue_user),
-            labels: Some(map_issue_labels(item.labels)),
-        })
+        .map(map_issue_summary)
         .collect::<Vec<_>>();
 
     Ok(GitHubIssuesListResult {
@@ -1919,21 +2809,87 @@ pub async fn github_issues_list(
     })
 }
 
+#[tauri::command]
+pub async fn github_issue_create(
+    directory: String,
+    title: String,
+    body: Option<String>,
+    labels: Option<Vec<String>>,
+    _state: State<'_, DesktopRuntime>,
+) -> Result<GitHubIssueSummary, CommandError> {
+    let directory = directory.trim().to_string();
+    let title = title.trim().to_string();
+    if directory.is_empty() || title.is_empty() {
+        return Err(CommandError::invalid_argument(
+            "directory and title are required",
+        ));
+    }
+    let labels = labels
+        .unwrap_or_default()
+        .into_iter()
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty())
+        .collect::<Vec<_>>();
+
+    let stored = load_stored_auth().await;
+    let Some(stored) = stored else {
+        return Err(not_connected());
+    };
+
+    let repo = resolve_repo_from_directory(&directory)
+        .await
+        .ok_or_else(repo_not_resolved)?;
+
+    let url = format!("{}/{}/{}/issues", API_PULLS_URL_PREFIX, repo.owner, repo.repo);
+    let request = IssueCreateRequest {
+        title: &title,
+        body: body.as_deref().filter(|body| !body.trim().is_empty()),
+        labels: (!labels.is_empty()).then_some(labels.as_slice()),
+    };
+
+    let client = reqwest::Client::new();
+    let resp = send_github_request(|| {
+        client
+            .post(&url)
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", format!("Bearer {}", stored.access_token))
+            .header("User-Agent", "OpenChamber")
+            .json(&request)
+    })
+    .await?;
+
+    match resp.status() {
+        reqwest::StatusCode::UNAUTHORIZED => {
+            handle_auth_lost(&stored.access_token).await;
+            return Err(token_rejected());
+        }
+        reqwest::StatusCode::GONE => return Err(issues_disabled_error(&repo)),
+        status if !status.is_success() => {
+            let text = resp.text().await.unwrap_or_default();
+            return Err(github_status_error(status, &text));
+        }
+        _ => {}
+    }
+    let created = resp.json::<IssueListItem>().await.map_err(|e| e.to_string())?;
+
+    Ok(map_issue_summary(created))
+}
+
 #[tauri::command]
 pub async fn github_issue_get(
     directory: String,
     number: u64,
     _state: State<'_, DesktopRuntime>,
-) -> Result<GitHubIssueGetResult, String> {
+) -> Result<GitHubIssueGetResult, CommandError> {
     let directory = directory.trim().to_string();
     if directory.is_empty() {
-        return Err("directory is required".to_string());
+        return Err(CommandError::invalid_argument("directory is required"));
     }
     if number == 0 {
-        return Err("number is required".to_string());
+        return Err(CommandError::invalid_argument("number is required"));
     }
 
-    let stored = read_auth_file().await;
+    let stored = load_stored_auth().await;
     let Some(stored) = stored else {
         return Ok(GitHubIssueGetResult {
             connected: false,
@@ -1941,14 +2897,6 @@ pub async fn github_issue_get(
             issue: None,
         });
     };
-    if stored.access_token.trim().is_empty() {
-        let _ = clear_auth_file().await;
-        return Ok(GitHubIssueGetResult {
-            connected: false,
-            repo: None,
-            issue: None,
-        });
-    }
 
     let repo = resolve_repo_from_directory(&directory).await;
     let Some(repo) = repo else {
@@ -1967,8 +2915,8 @@ pub async fn github_issue_get(
     let issue = github_get_json::<IssueDetailsResponse>(&url, &stored.access_token).await;
     let issue = match issue {
         Ok(v) => v,
-        Err(err) if err == "unauthorized" => {
-            let _ = clear_auth_file().await;
+        Err(err) if err.code == ErrorCode::Unauthorized => {
+            handle_auth_lost(&stored.access_token).await;
             return Ok(GitHubIssueGetResult {
                 connected: false,
                 repo: None,
@@ -1979,7 +2927,7 @@ pub async fn github_issue_get(
     };
 
     if issue.pull_request.is_some() {
-        return Err("Not a GitHub issue".to_string());
+        return Err(CommandError::invalid_argument("Not a GitHub issue"));
     }
 
     let summary = GitHubIssueSummary {
@@ -2014,16 +2962,16 @@ pub async fn github_issue_comments(
     directory: String,
     number: u64,
     _state: State<'_, DesktopRuntime>,
-) -> Result<GitHubIssueCommentsResult, String> {
+) -> Result<GitHubIssueCommentsResult, CommandError> {
     let directory = directory.trim().to_string();
     if directory.is_empty() {
-        return Err("directory is required".to_string());
+        return Err(CommandError::invalid_argument("directory is required"));
     }
     if number == 0 {
-        return Err("number is required".to_string());
+        return Err(CommandError::invalid_argument("number is required"));
     }
 
-    let stored = read_auth_file().await;
+    let stored = load_stored_auth().await;
     let Some(stored) = stored else {
         return Ok(GitHubIssueCommentsResult {
             connected: false,
@@ -2031,14 +2979,6 @@ pub async fn github_issue_comments(
             comments: None,
         });
     };
-    if stored.access_token.trim().is_empty() {
-        let _ = clear_auth_file().await;
-        return Ok(GitHubIssueCommentsResult {
-            connected: false,
-            repo: None,
-            comments: None,
-        });
-    }
 
     let repo = resolve_repo_from_directory(&directory).await;
     let Some(repo) = repo else {
@@ -2057,8 +2997,8 @@ pub async fn github_issue_comments(
     let comments = github_get_json::<Vec<IssueCommentResponse>>(&url, &stored.access_token).await;
     let comments = match comments {
         Ok(v) => v,
-        Err(err) if err == "unauthorized" => {
-            let _ = clear_auth_file().await;
+        Err(err) if err.code == ErrorCode::Unauthorized => {
+            handle_auth_lost(&stored.access_token).await;
             return Ok(GitHubIssueCommentsResult {
                 connected: false,
                 repo: None,
@@ -2142,24 +3082,30 @@ fn map_pr_head_repo(value: &Value) -> Option<GitHubPullRequestHeadRepo> {
     })
 }
 
-async fn github_get_text(url: &str, access_token: &str, accept: &str) -> Result<String, String> {
+async fn github_get_text(
+    url: &str,
+    access_token: &str,
+    accept: &str,
+) -> Result<String, CommandError> {
     let client = reqwest::Client::new();
-    let resp = client
-        .get(url)
-        .header("Accept", accept)
-        .header("Authorization", format!("Bearer {}", access_token))
-        .header("User-Agent", "OpenChamber")
-        .send()
-        .await
-        .map_err(|e| e.to_string())?;
+    let resp = send_github_request(|| {
+        client
+            .get(url)
+            .header("Accept", accept)
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("User-Agent", "OpenChamber")
+    })
+    .await?;
 
     if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
-        return Err("unauthorized".to_string());
+        return Err(token_rejected());
     }
     if !resp.status().is_success() {
-        return Err(format!("GitHub request failed: {}", resp.status()));
+        return Err(github_status_error(resp.status(), ""));
     }
-    resp.text().await.map_err(|e| e.to_string())
+    resp.text()
+        .await
+        .map_err(|e| CommandError::from(e.to_string()))
 }
 
 #[tauri::command]
@@ -2167,13 +3113,13 @@ pub async fn github_prs_list(
     directory: String,
     page: Option<u32>,
     _state: State<'_, DesktopRuntime>,
-) -> Result<GitHubPullRequestsListResult, String> {
+) -> Result<GitHubPullRequestsListResult, CommandError> {
     let directory = directory.trim().to_string();
     if directory.is_empty() {
-        return Err("directory is required".to_string());
+        return Err(CommandError::invalid_argument("directory is required"));
     }
 
-    let stored = read_auth_file().await;
+    let stored = load_stored_auth().await;
     let Some(stored) = stored else {
         return Ok(GitHubPullRequestsListResult {
             connected: false,
@@ -2183,16 +3129,6 @@ pub async fn github_prs_list(
             has_more: None,
         });
     };
-    if stored.access_token.trim().is_empty() {
-        let _ = clear_auth_file().await;
-        return Ok(GitHubPullRequestsListResult {
-            connected: false,
-            repo: None,
-            prs: None,
-            page: None,
-            has_more: None,
-        });
-    }
 
     let repo = resolve_repo_from_directory(&directory).await;
     let Some(repo) = repo else {
@@ -2220,7 +3156,7 @@ pub async fn github_prs_list(
         .await
         .map_err(|e| e.to_string())?;
     if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
-        let _ = clear_auth_file().await;
+        handle_auth_lost(&stored.access_token).await;
         return Ok(GitHubPullRequestsListResult {
             connected: false,
             repo: None,
@@ -2230,7 +3166,7 @@ pub async fn github_prs_list(
         });
     }
     if !resp.status().is_success() {
-        return Err(format!("GitHub request failed: {}", resp.status()));
+        return Err(github_status_error(resp.status(), ""));
     }
     let link = resp
         .headers()
@@ -2302,6 +3238,147 @@ pub async fn github_prs_list(
     })
 }
 
+fn map_pull_file(file: PullFileResponse, include_patch: bool) -> GitHubPullRequestFile {
+    GitHubPullRequestFile {
+        filename: file.filename,
+        previous_filename: file.previous_filename,
+        status: file.status,
+        additions: file.additions,
+        deletions: file.deletions,
+        changes: file.changes,
+        patch: if include_patch { file.patch } else { None },
+    }
+}
+
+/// GET one page of a paginated list; the flag is whether the `link` header has a next page.
+async fn github_get_page<T: for<'de> Deserialize<'de>>(
+    url: &str,
+    access_token: &str,
+) -> Result<(T, bool), CommandError> {
+    let client = reqwest::Client::new();
+    let resp = send_github_request(|| {
+        client
+            .get(url)
+            .header("Accept", "application/vnd.github+json")
+            .header("Authorization", format!("Bearer {}", access_token))
+            .header("User-Agent", "OpenChamber")
+    })
+    .await?;
+
+    if resp.status() == reqwest::StatusCode::UNAUTHORIZED {
+        return Err(token_rejected());
+    }
+    if !resp.status().is_success() {
+        return Err(github_status_error(resp.status(), ""));
+    }
+    let has_more = resp
+        .headers()
+        .get("link")
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|link| link.contains("rel=\"next\""));
+    let body = resp.json::<T>().await.map_err(|e| e.to_string())?;
+    Ok((body, has_more))
+}
+
+/// Changed files of PR `number`, one page at a time. Pass `includePatch: false` to list files
+/// without their patches, then `filePath` to fetch a single file's patch on demand.
+#[tauri::command]
+pub async fn github_pr_files(
+    directory: String,
+    number: u64,
+    page: Option<u32>,
+    per_page: Option<u32>,
+    include_patch: Option<bool>,
+    file_path: Option<String>,
+    _state: State<'_, DesktopRuntime>,
+) -> Result<GitHubPullRequestFilesResult, CommandError> {
+    let directory = directory.trim().to_string();
+    if directory.is_empty() {
+        return Err(CommandError::invalid_argument("directory is required"));
+    }
+    if number == 0 {
+        return Err(CommandError::invalid_argument("number is required"));
+    }
+    let file_path = file_path
+        .map(|path| path.trim().to_string())
+        .filter(|path| !path.is_empty());
+
+    let disconnected = GitHubPullRequestFilesResult {
+        connected: false,
+        repo: None,
+        files: None,
+        page: None,
+        has_more: None,
+    };
+    let Some(stored) = load_stored_auth().await else {
+        return Ok(disconnected);
+    };
+
+    let repo = resolve_repo_from_directory(&directory)
+        .await
+        .ok_or_else(repo_not_resolved)?;
+
+    // A single-file lookup scans pages of the maximum size until the file turns up.
+    let (mut page, per_page) = match file_path {
+        Some(_) => (1, PR_FILES_MAX_PER_PAGE),
+        None => (
+            page.unwrap_or(1).clamp(1, PR_FILES_MAX_PAGES),
+            per_page
+                .unwrap_or(PR_FILES_DEFAULT_PER_PAGE)
+                .clamp(1, PR_FILES_MAX_PER_PAGE),
+        ),
+    };
+    let include_patch = include_patch.unwrap_or(true) || file_path.is_some();
+
+    loop {
+        let url = format!(
+            "{}/{}/{}/pulls/{}/files?per_page={}&page={}",
+            API_PULLS_URL_PREFIX, repo.owner, repo.repo, number, per_page, page
+        );
+        let result = github_get_page::<Vec<PullFileResponse>>(&url, &stored.access_token).await;
+        let (files, has_more) = match result {
+            Ok(value) => value,
+            Err(err) if err.code == ErrorCode::Unauthorized => {
+                handle_auth_lost(&stored.access_token).await;
+                return Ok(disconnected);
+            }
+            Err(err) => return Err(err),
+        };
+
+        let Some(wanted) = file_path.as_deref() else {
+            return Ok(GitHubPullRequestFilesResult {
+                connected: true,
+                repo: Some(repo),
+                files: Some(
+                    files
+                        .into_iter()
+                        .map(|file| map_pull_file(file, include_patch))
+                        .collect(),
+                ),
+                page: Some(page as u64),
+                has_more: Some(has_more),
+            });
+        };
+
+        if let Some(file) = files.into_iter().find(|file| file.filename == wanted) {
+            return Ok(GitHubPullRequestFilesResult {
+                connected: true,
+                repo: Some(repo),
+                files: Some(vec![map_pull_file(file, true)]),
+                page: Some(page as u64),
+                has_more: Some(false),
+            });
+        }
+        if !has_more || page >= PR_FILES_MAX_PAGES {
+            return Err(CommandError::not_found(format!(
+                "File not found in pull request #{}: {}",
+                number, wanted
+            )));
+        }
+        page += 1;
+    }
+}
+
 #[tauri::command]
 pub async fn github_pr_context(
     directory: String,
@@ -2311,16 +3388,16 @@ pub async fn github_pr_context(
     #[allow(non_snake_case)]
     includeCheckDetails: Option<bool>,
     _state: State<'_, DesktopRuntime>,
-) -> Result<GitHubPullRequestContextResult, String> {
+) -> Result<GitHubPullRequestContextResult, CommandError> {
     let directory = directory.trim().to_string();
     if directory.is_empty() {
-        return Err("directory is required".to_string());
+        return Err(CommandError::invalid_argument("directory is required"));
     }
     if number == 0 {
-        return Err("number is required".to_string());
+        return Err(CommandError::invalid_argument("number is required"));
     }
 
-    let stored = read_auth_file().await;
+    let stored = load_stored_auth().await;
     let Some(stored) = stored else {
         return Ok(GitHubPullRequestContextResult {
             connected: false,
@@ -2334,20 +3411,6 @@ pub async fn github_pr_context(
             check_runs: None,
         });
     };
-    if stored.access_token.trim().is_empty() {
-        let _ = clear_auth_file().await;
-        return Ok(GitHubPullRequestContextResult {
-            connected: false,
-            repo: None,
-            pr: None,
-            issue_comments: None,
-            review_comments: None,
-            files: None,
-            diff: None,
-            checks: None,
-            check_runs: None,
-        });
-    }
 
     let repo = resolve_repo_from_directory(&directory).await;
     let Some(repo) = repo else {
@@ -2368,8 +3431,8 @@ pub async fn github_pr_context(
     let pr_json = github_get_json::<Value>(&pr_url, &stored.access_token).await;
     let pr_json = match pr_json {
         Ok(v) => v,
-        Err(err) if err == "unauthorized" => {
-            let _ = clear_auth_file().await;
+        Err(err) if err.code == ErrorCode::Unauthorized => {
+            handle_auth_lost(&stored.access_token).await;
             return Ok(GitHubPullRequestContextResult {
                 connected: false,
                 repo: None,
@@ -2429,7 +3492,12 @@ pub async fn github_pr_context(
         "{}/{}/{}/issues/{}/comments?per_page=100",
         API_PULLS_URL_PREFIX, repo.owner, repo.repo, number
     );
-    let issue_comments = github_get_json::<Vec<IssueCommentResponse>>(&issue_comments_url, &stored.access_token).await?;
+    let issue_comments = check_token(
+        &stored.access_token,
+        github_get_json::<Vec<IssueCommentResponse>>(&issue_comments_url, &stored.access_token)
+            .await,
+    )
+    .await?;
     let issue_comments = issue_comments
         .into_iter()
         .map(|c| GitHubIssueComment {
@@ -2446,7 +3514,15 @@ pub async fn github_pr_context(
         "{}/{}/{}/pulls/{}/comments?per_page=100",
         API_PULLS_URL_PREFIX, repo.owner, repo.repo, number
     );
-    let review_comments = github_get_json::<Vec<PullReviewCommentResponse>>(&review_comments_url, &stored.access_token).await?;
+    let review_comments = check_token(
+        &stored.access_token,
+        github_get_json::<Vec<PullReviewCommentResponse>>(
+            &review_comments_url,
+            &stored.access_token,
+        )
+        .await,
+    )
+    .await?;
     let review_comments = review_comments
         .into_iter()
         .map(|c| GitHubPullRequestReviewComment {
@@ -2466,17 +3542,14 @@ pub async fn github_pr_context(
         "{}/{}/{}/pulls/{}/files?per_page=100",
         API_PULLS_URL_PREFIX, repo.owner, repo.repo, number
     );
-    let files = github_get_json::<Vec<PullFileResponse>>(&files_url, &stored.access_token).await?;
+    let files = check_token(
+        &stored.access_token,
+        github_get_json::<Vec<PullFileResponse>>(&files_url, &stored.access_token).await,
+    )
+    .await?;
     let files = files
         .into_iter()
-        .map(|f| GitHubPullRequestFile {
-            filename: f.filename,
-            status: f.status,
-            additions: f.additions,
-            deletions: f.deletions,
-            changes: f.changes,
-            patch: f.patch,
-        })
+        .map(|f| map_pull_file(f, true))
         .collect::<Vec<_>>();
 
     // checks summary (same as github_pr_status)
@@ -2488,229 +3561,151 @@ pub async fn github_pr_context(
     let mut jobs_by_run_id: std::collections::HashMap<u64, Vec<Value>> = std::collections::HashMap::new();
 
     if let Some(ref sha) = pr.summary.head_sha {
-        let check_runs_url = format!(
-            "{}/{}/{}/commits/{}/check-runs",
-            API_PULLS_URL_PREFIX, repo.owner, repo.repo, sha
-        );
-        if let Ok(runs) = github_get_json::<CheckRunsResponse>(&check_runs_url, &stored.access_token).await {
-            if !runs.check_runs.is_empty() {
-                let mut out: Vec<GitHubCheckRun> = Vec::new();
-
-                for run in runs.check_runs.iter() {
-                    let name = run.name.clone().unwrap_or_default();
-                    if name.trim().is_empty() {
-                        continue;
-                    }
+        let check_runs = fetch_check_runs(&repo, sha, &stored.access_token).await;
+        if !check_runs.is_empty() {
+            let mut out: Vec<GitHubCheckRun> = Vec::new();
+
+            for run in check_runs.iter() {
+                let name = run.name.clone().unwrap_or_default();
+                if name.trim().is_empty() {
+                    continue;
+                }
 
-                    let mut job: Option<GitHubCheckRunJob> = None;
-                    if include_check_details {
-                        if let Some(details_url) = &run.details_url {
-                            let (run_id, job_id) = (|| {
-                                let marker = "/actions/runs/";
-                                let idx = details_url.find(marker)?;
-                                let rest = &details_url[(idx + marker.len())..];
-                                let mut iter = rest.split('/');
-                                let run_id_str = iter.next()?;
-                                let run_id_val = run_id_str.parse::<u64>().ok()?;
-                                let mut job_id_val: Option<u64> = None;
-                                let next = iter.next().unwrap_or("");
-                                if next == "job" {
-                                    job_id_val = iter.next().and_then(|s| s.parse::<u64>().ok());
-                                }
-                                Some((run_id_val, job_id_val))
-                            })().unwrap_or((0, None));
-
-                            if run_id > 0 {
-                                if !jobs_by_run_id.contains_key(&run_id) {
-                                    let jobs_url = format!(
-                                        "{}/{}/{}/actions/runs/{}/jobs?per_page=100",
-                                        API_PULLS_URL_PREFIX, repo.owner, repo.repo, run_id
-                                    );
-                                    let jobs_json = github_get_json::<Value>(&jobs_url, &stored.access_token).await;
-                                    let jobs = jobs_json
-                                        .ok()
-                                        .and_then(|v| v.get("jobs").cloned())
-                                        .and_then(|v| v.as_array().cloned())
-                                        .unwrap_or_default();
-                                    jobs_by_run_id.insert(run_id, jobs);
-                                }
-
-                                let jobs = jobs_by_run_id.get(&run_id).cloned().unwrap_or_default();
-                                let picked = if let Some(job_id_val) = job_id {
-                                    jobs.iter()
-                                        .find(|j| j.get("id").and_then(|v| v.as_u64()) == Some(job_id_val))
-                                        .cloned()
-                                } else {
-                                    jobs.iter()
-                                        .find(|j| j.get("name").and_then(|v| v.as_str()) == Some(name.as_str()))
-                                        .cloned()
-                                };
-
-                                if let Some(picked) = picked {
-                                    let steps = picked
-                                        .get("steps")
-                                        .and_then(|v| v.as_array())
-                                        .map(|arr| {
-                                            arr.iter()
-                                                .filter_map(|s| {
-                                                    let step_name = s
-                                                        .get("name")
+                let mut job: Option<GitHubCheckRunJob> = None;
+                if include_check_details {
+                    if let Some(details_url) = &run.details_url {
+                        let (run_id, job_id) = (|| {
+                            let marker = "/actions/runs/";
+                            let idx = details_url.find(marker)?;
+                            let rest = &details_url[(idx + marker.len())..];
+                            let mut iter = rest.split('/');
+                            let run_id_str = iter.next()?;
+                            let run_id_val = run_id_str.parse::<u64>().ok()?;
+                            let mut job_id_val: Option<u64> = None;
+                            let next = iter.next().unwrap_or("");
+                            if next == "job" {
+                                job_id_val = iter.next().and_then(|s| s.parse::<u64>().ok());
+                            }
+                            Some((run_id_val, job_id_val))
+                        })().unwrap_or((0, None));
+
+                        if run_id > 0 {
+                            if !jobs_by_run_id.contains_key(&run_id) {
+                                let jobs_url = format!(
+                                    "{}/{}/{}/actions/runs/{}/jobs?per_page=100",
+                                    API_PULLS_URL_PREFIX, repo.owner, repo.repo, run_id
+                                );
+                                let jobs_json = github_get_json::<Value>(&jobs_url, &stored.access_token).await;
+                                let jobs = jobs_json
+                                    .ok()
+                                    .and_then(|v| v.get("jobs").cloned())
+                                    .and_then(|v| v.as_array().cloned())
+                                    .unwrap_or_default();
+                                jobs_by_run_id.insert(run_id, jobs);
+                            }
+
+                            let jobs = jobs_by_run_id.get(&run_id).cloned().unwrap_or_default();
+                            let picked = if let Some(job_id_val) = job_id {
+                                jobs.iter()
+                                    .find(|j| j.get("id").and_then(|v| v.as_u64()) == Some(job_id_val))
+                                    .cloned()
+                            } else {
+                                jobs.iter()
+                                    .find(|j| j.get("name").and_then(|v| v.as_str()) == Some(name.as_str()))
+                                    .cloned()
+                            };
+
+                            if let Some(picked) = picked {
+                                let steps = picked
+                                    .get("steps")
+                                    .and_then(|v| v.as_array())
+                                    .map(|arr| {
+                                        arr.iter()
+                                            .filter_map(|s| {
+                                                let step_name = s
+                                                    .get("name")
+                                                    .and_then(|v| v.as_str())
+                                                    .unwrap_or("");
+                                                if step_name.trim().is_empty() {
+                                                    return None;
+                                                }
+                                                Some(GitHubCheckRunJobStep {
+                                                    name: step_name.to_string(),
+                                                    status: s
+                                                        .get("status")
+                                                        .and_then(|v| v.as_str())
+                                                        .map(|s| s.to_string()),
+                                                    conclusion: s
+                                                        .get("conclusion")
                                                         .and_then(|v| v.as_str())
-                                                        .unwrap_or("");
-                                                    if step_name.trim().is_empty() {
-                                                        return None;
-                                                    }
-                                                    Some(GitHubCheckRunJobStep {
-                                                        name: step_name.to_string(),
-                                                        status: s
-                                                            .get("status")
-                                                            .and_then(|v| v.as_str())
-                                                            .map(|s| s.to_string()),
-                                                        conclusion: s
-                                                            .get("conclusion")
-                                                            .and_then(|v| v.as_str())
-                                                            .map(|s| s.to_string()),
-                                                        number: s.get("number").and_then(|v| v.as_u64()),
-                                                    })
+                                                        .map(|s| s.to_string()),
+                                                    number: s.get("number").and_then(|v| v.as_u64()),
                                                 })
-                                                .collect::<Vec<_>>()
-                                        });
-
-                                    job = Some(GitHubCheckRunJob {
-                                        run_id: Some(run_id),
-                                        job_id: picked.get("id").and_then(|v| v.as_u64()),
-                                        url: picked
-                                            .get("html_url")
-                                            .and_then(|v| v.as_str())
-                                            .map(|s| s.to_string()),
-                                        name: picked
-                                            .get("name")
-                                            .and_then(|v| v.as_str())
-                                            .map(|s| s.to_string()),
-                                        conclusion: picked
-                                            .get("conclusion")
-                                            .and_then(|v| v.as_str())
-                                            .map(|s| s.to_string()),
-                                        steps,
+                                            })
+                                            .collect::<Vec<_>>()
                                     });
-                                } else {
-                                    job = Some(GitHubCheckRunJob {
-                                        run_id: Some(run_id),
-                                        job_id,
-                                        url: Some(details_url.clone()),
-                                        name: None,
-                                        conclusion: None,
-                                        steps: None,
-                                    });
-                                }
+
+                                job = Some(GitHubCheckRunJob {
+                                    run_id: Some(run_id),
+                                    job_id: picked.get("id").and_then(|v| v.as_u64()),
+                                    url: picked
+                                        .get("html_url")
+                                        .and_then(|v| v.as_str())
+                                        .map(|s| s.to_string()),
+                                    name: picked
+                                        .get("name")
+                                        .and_then(|v| v.as_str())
+                                        .map(|s| s.to_string()),
+                                    conclusion: picked
+                                        .get("conclusion")
+                                        .and_then(|v| v.as_str())
+                                        .map(|s| s.to_string()),
+                                    steps,
+                                });
+                            } else {
+                                job = Some(GitHubCheckRunJob {
+                                    run_id: Some(run_id),
+                                    job_id,
+                                    url: Some(details_url.clone()),
+                                    name: None,
+                                    conclusion: None,
+                                    steps: None,
+                                });
                             }
                         }
                     }
-
-                    out.push(GitHubCheckRun {
-                        name,
-                        app: run.app.as_ref().map(|a| GitHubCheckRunApp {
-                            name: a.name.clone(),
-                            slug: a.slug.clone(),
-                        }),
-                        status: run.status.clone(),
-                        conclusion: run.conclusion.clone(),
-                        details_url: run.details_url.clone(),
-                        output: run.output.as_ref().map(|o| GitHubCheckRunOutput {
-                            title: o.title.clone(),
-                            summary: o.summary.clone(),
-                            text: o.text.clone(),
-                        }),
-                        job,
-                    });
                 }
 
-                check_runs_out = Some(out);
-
-                let mut success = 0;
-                let mut failure = 0;
-                let mut pending = 0;
-                for run in runs.check_runs.iter() {
-                    let status = run.status.as_deref().unwrap_or("");
-                    let conclusion = run.conclusion.as_deref().unwrap_or("");
-                    if status == "queued" || status == "in_progress" {
-                        pending += 1;
-                        continue;
-                    }
-                    if conclusion.is_empty() {
-                        pending += 1;
-                        continue;
-                    }
-                    if conclusion == "success" || conclusion == "neutral" || conclusion == "skipped" {
-                        success += 1;
-                    } else {
-                        failure += 1;
-                    }
-                }
-                let total = success + failure + pending;
-                let state = if failure > 0 {
-                    "failure"
-                } else if pending > 0 {
-                    "pending"
-                } else if total > 0 {
-                    "success"
-                } else {
-                    "unknown"
-                };
-                checks = Some(GitHubChecksSummary {
-                    state: state.to_string(),
-                    total,
-                    success,
-                    failure,
-                    pending,
+                out.push(GitHubCheckRun {
+                    name,
+                    app: run.app.as_ref().map(|a| GitHubCheckRunApp {
+                        name: a.name.clone(),
+                        slug: a.slug.clone(),
+                    }),
+                    status: run.status.clone(),
+                    conclusion: run.conclusion.clone(),
+                    details_url: run.details_url.clone(),
+                    output: run.output.as_ref().map(|o| GitHubCheckRunOutput {
+                        title: o.title.clone(),
+                        summary: o.summary.clone(),
+                        text: o.text.clone(),
+                    }),
+                    job,
                 });
             }
-        }
 
-        if checks.is_none() {
-            let status_url = format!(
-                "{}/{}/{}/commits/{}/status",
-                API_PULLS_URL_PREFIX, repo.owner, repo.repo, sha
-            );
-            if let Ok(status) = github_get_json::<CombinedStatusResponse>(&status_url, &stored.access_token).await {
-                let mut success = 0;
-                let mut failure = 0;
-                let mut pending = 0;
-                for s in status.statuses.iter() {
-                    match s.state.as_str() {
-                        "success" => success += 1,
-                        "failure" | "error" => failure += 1,
-                        "pending" => pending += 1,
-                        _ => {}
-                    }
-                }
-                let total = success + failure + pending;
-                let state = if failure > 0 {
-                    "failure"
-                } else if pending > 0 {
-                    "pending"
-                } else if total > 0 {
-                    "success"
-                } else {
-                    "unknown"
-                };
-                checks = Some(GitHubChecksSummary {
-                    state: state.to_string(),
-                    total,
-                    success,
-                    failure,
-                    pending,
-                });
-            }
+            check_runs_out = Some(out);
         }
+
+        checks = summarize_checks(&repo, sha, &check_runs, &stored.access_token).await;
     }
 
     let diff = if includeDiff {
         let diff_text = github_get_text(&pr_url, &stored.access_token, "application/vnd.github.v3.diff").await;
         match diff_text {
             Ok(v) => Some(v),
-            Err(err) if err == "unauthorized" => {
-                let _ = clear_auth_file().await;
+            Err(err) if err.code == ErrorCode::Unauthorized => {
+                handle_auth_lost(&stored.access_token).await;
                 return Ok(GitHubPullRequestContextResult {
                     connected: false,
                     repo: None,
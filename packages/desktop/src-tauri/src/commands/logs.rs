@@ -1,25 +1,331 @@
-use crate::logging::log_file_path;
+use crate::crash_reporter::crash_directory;
+use crate::logging::{
+    apply_log_level, detect_log_format, log_directory, log_file_path, parse_log_level,
+};
+use crate::DesktopRuntime;
 use serde::Serialize;
-use tokio::fs;
+use serde_json::{json, Value};
+use std::{
+    io::SeekFrom,
+    path::{Path, PathBuf},
+};
+use tauri::State;
+use tokio::{
+    fs,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
+
+const DEFAULT_OPENCODE_LOG_LINES: usize = 500;
+const MAX_OPENCODE_LOG_LINES: usize = 5000;
+// Only the tail of the file is read; OpenCode logs can grow large in long sessions.
+const OPENCODE_LOG_TAIL_BYTES: u64 = 2 * 1024 * 1024;
+// Reading the whole desktop log is refused above this size; page through it instead.
+const DESKTOP_LOG_FULL_READ_MAX_BYTES: u64 = 16 * 1024 * 1024;
+const DEFAULT_LOG_PAGE_BYTES: u64 = 1024 * 1024;
+const MAX_LOG_PAGE_BYTES: u64 = 8 * 1024 * 1024;
+// Per-file cap inside the diagnostics bundle; larger logs contribute only their tail.
+const DIAGNOSTICS_FILE_MAX_BYTES: u64 = 16 * 1024 * 1024;
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DesktopLogFile {
     pub file_name: String,
     pub content: String,
+    /// "text" or "json" (one object per line), per the `logFormat` setting when written.
+    pub format: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_size: Option<u64>,
+    /// Offset to pass as `fromOffset` for the next page; `None` once the end was reached.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
 }
 
+/// Read the desktop log. Without arguments the whole file is returned (refused above 16MB);
+/// with `from_offset` and/or `max_bytes` one page is returned, ending on a line boundary.
 #[tauri::command]
-pub async fn fetch_desktop_logs() -> Result<DesktopLogFile, String> {
+pub async fn fetch_desktop_logs(
+    from_offset: Option<u64>,
+    max_bytes: Option<u64>,
+) -> Result<DesktopLogFile, String> {
     let path = log_file_path().ok_or_else(|| "Log location unavailable".to_string())?;
-    let content = fs::read_to_string(&path)
+    let file_name = path
+        .file_name()
+        .and_then(|value| value.to_str())
+        .unwrap_or("openchamber.log")
+        .to_string();
+
+    let mut file = fs::File::open(&path)
+        .await
+        .map_err(|err| format!("Failed to read log file: {err}"))?;
+    let total_size = file
+        .metadata()
+        .await
+        .map_err(|err| format!("Failed to read log file: {err}"))?
+        .len();
+
+    if from_offset.is_none() && max_bytes.is_none() {
+        if total_size > DESKTOP_LOG_FULL_READ_MAX_BYTES {
+            return Err(format!(
+                "Log file is too large to load at once ({} MB); use fromOffset/maxBytes to page through it",
+                total_size / (1024 * 1024)
+            ));
+        }
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)
+            .await
+            .map_err(|err| format!("Failed to read log file: {err}"))?;
+        let content = String::from_utf8_lossy(&bytes).into_owned();
+        return Ok(DesktopLogFile {
+            file_name,
+            format: detect_log_format(&content),
+            content,
+            total_size: Some(total_size),
+            next_offset: None,
+        });
+    }
+
+    let start = from_offset.unwrap_or(0).min(total_size);
+    let page = max_bytes
+        .unwrap_or(DEFAULT_LOG_PAGE_BYTES)
+        .clamp(1, MAX_LOG_PAGE_BYTES);
+    file.seek(SeekFrom::Start(start))
+        .await
+        .map_err(|err| format!("Failed to read log file: {err}"))?;
+    let mut bytes = Vec::new();
+    (&mut file)
+        .take(page)
+        .read_to_end(&mut bytes)
         .await
         .map_err(|err| format!("Failed to read log file: {err}"))?;
+
+    let mut end = start + bytes.len() as u64;
+    if end < total_size {
+        // Stop after the last complete line so pages never split a line (or a UTF-8 char).
+        if let Some(newline) = bytes.iter().rposition(|byte| *byte == b'\n') {
+            bytes.truncate(newline + 1);
+            end = start + bytes.len() as u64;
+        }
+    }
+
+    let content = String::from_utf8_lossy(&bytes).into_owned();
+    Ok(DesktopLogFile {
+        file_name,
+        format: detect_log_format(&content),
+        content,
+        total_size: Some(total_size),
+        next_offset: (end < total_size).then_some(end),
+    })
+}
+
+/// OpenCode writes timestamped `*.log` files under `$XDG_DATA_HOME/opencode/log`
+/// (`~/.local/share/opencode/log` by default, on every platform).
+fn opencode_log_dir() -> Option<PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| dirs::home_dir().map(|home| home.join(".local").join("share")))?;
+    Some(data_home.join("opencode").join("log"))
+}
+
+async fn latest_log_file(dir: &Path) -> Option<PathBuf> {
+    let mut entries = fs::read_dir(dir).await.ok()?;
+    let mut latest: Option<(std::time::SystemTime, PathBuf)> = None;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("log") {
+            continue;
+        }
+        let Ok(modified) = entry.metadata().await.and_then(|meta| meta.modified()) else {
+            continue;
+        };
+        let is_newer = match &latest {
+            Some((time, _)) => modified > *time,
+            None => true,
+        };
+        if is_newer {
+            latest = Some((modified, path));
+        }
+    }
+    latest.map(|(_, path)| path)
+}
+
+/// Tail the most recent OpenCode log file (last `max_lines` lines, default 500).
+#[tauri::command]
+pub async fn fetch_opencode_logs(max_lines: Option<usize>) -> Result<DesktopLogFile, String> {
+    let max_lines = max_lines
+        .unwrap_or(DEFAULT_OPENCODE_LOG_LINES)
+        .clamp(1, MAX_OPENCODE_LOG_LINES);
+    let dir = opencode_log_dir().ok_or_else(|| "OpenCode log location unavailable".to_string())?;
+    let path = latest_log_file(&dir).await.ok_or_else(|| {
+        format!(
+            "No OpenCode logs found in {} (has OpenCode been started yet?)",
+            dir.display()
+        )
+    })?;
+
+    let mut file = fs::File::open(&path)
+        .await
+        .map_err(|err| format!("Failed to open OpenCode log file: {err}"))?;
+    let len = file
+        .metadata()
+        .await
+        .map_err(|err| format!("Failed to read OpenCode log file: {err}"))?
+        .len();
+    let start = len.saturating_sub(OPENCODE_LOG_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))
+        .await
+        .map_err(|err| format!("Failed to read OpenCode log file: {err}"))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)
+        .await
+        .map_err(|err| format!("Failed to read OpenCode log file: {err}"))?;
+
+    let text = String::from_utf8_lossy(&bytes);
+    let mut lines: Vec<&str> = text.lines().collect();
+    if start > 0 && !lines.is_empty() {
+        // The first line is most likely cut mid-way by the seek.
+        lines.remove(0);
+    }
+    let skip = lines.len().saturating_sub(max_lines);
+    let content = lines[skip..].join("\n");
+
     let file_name = path
         .file_name()
         .and_then(|value| value.to_str())
-        .unwrap_or("openchamber.log")
+        .unwrap_or("opencode.log")
         .to_string();
 
-    Ok(DesktopLogFile { file_name, content })
+    Ok(DesktopLogFile {
+        file_name,
+        format: detect_log_format(&content),
+        content,
+        total_size: Some(len),
+        next_offset: None,
+    })
+}
+
+/// Change the log level at runtime ("off", "error", "warn", "info", "debug" or "trace") and
+/// persist it as `logLevel` so it survives a restart.
+#[tauri::command]
+pub async fn set_log_level(
+    level: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<String, String> {
+    let normalized = level.trim().to_ascii_lowercase();
+    let filter =
+        parse_log_level(&normalized).ok_or_else(|| format!("Unknown log level: {level}"))?;
+
+    state
+        .settings()
+        .update(|mut settings| {
+            if !settings.is_object() {
+                settings = json!({});
+            }
+            if let Some(obj) = settings.as_object_mut() {
+                obj.insert("logLevel".to_string(), Value::String(normalized.clone()));
+            }
+            settings
+        })
+        .await
+        .map_err(|err| format!("Failed to save log level: {err}"))?;
+
+    let previous = log::max_level();
+    // Make sure the change itself is recorded even when moving between quiet levels.
+    apply_log_level(previous.max(filter).max(log::LevelFilter::Info));
+    log::info!("[desktop:logs] log level changed from {previous} to {filter}");
+    apply_log_level(filter);
+
+    Ok(normalized)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsBundle {
+    pub path: String,
+    pub files: Vec<String>,
+}
+
+/// Zip the desktop log, the latest OpenCode log and all crash reports into
+/// `<log dir>/diagnostics/` and return the archive path.
+#[tauri::command]
+pub async fn export_diagnostics_bundle() -> Result<DiagnosticsBundle, String> {
+    let log_dir = log_directory().ok_or_else(|| "Log location unavailable".to_string())?;
+
+    let mut sources: Vec<(String, PathBuf)> = Vec::new();
+    if let Some(path) = log_file_path().filter(|path| path.is_file()) {
+        sources.push(("openchamber.log".to_string(), path));
+    }
+    if let Some(dir) = opencode_log_dir() {
+        if let Some(path) = latest_log_file(&dir).await {
+            sources.push(("opencode.log".to_string(), path));
+        }
+    }
+    if let Some(dir) = crash_directory() {
+        if let Ok(mut entries) = fs::read_dir(&dir).await {
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                let path = entry.path();
+                if path.is_file() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    sources.push((format!("crashes/{name}"), path));
+                }
+            }
+        }
+    }
+
+    let bundle_path = log_dir.join("diagnostics").join(format!(
+        "openchamber-diagnostics-{}.zip",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    let target = bundle_path.clone();
+    let files =
+        tauri::async_runtime::spawn_blocking(move || write_diagnostics_zip(&target, &sources))
+            .await
+            .map_err(bundle_error)??;
+
+    Ok(DiagnosticsBundle {
+        path: bundle_path.to_string_lossy().to_string(),
+        files,
+    })
+}
+
+fn write_diagnostics_zip(
+    target: &Path,
+    sources: &[(String, PathBuf)],
+) -> Result<Vec<String>, String> {
+    use std::io::{Read, Seek, Write};
+    use zip::write::SimpleFileOptions;
+
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent).map_err(bundle_error)?;
+    }
+    let file = std::fs::File::create(target).map_err(bundle_error)?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let mut added = Vec::new();
+    for (name, path) in sources {
+        let Ok(mut source) = std::fs::File::open(path) else {
+            continue;
+        };
+        let len = source.metadata().map(|meta| meta.len()).unwrap_or(0);
+        if len > DIAGNOSTICS_FILE_MAX_BYTES {
+            let _ = source.seek(SeekFrom::Start(len - DIAGNOSTICS_FILE_MAX_BYTES));
+        }
+        let mut bytes = Vec::new();
+        if source.read_to_end(&mut bytes).is_err() {
+            continue;
+        }
+
+        zip.start_file(name.as_str(), options)
+            .map_err(bundle_error)?;
+        zip.write_all(&bytes).map_err(bundle_error)?;
+        added.push(name.clone());
+    }
+
+    zip.finish().map_err(bundle_error)?;
+    Ok(added)
+}
+
+fn bundle_error(err: impl std::fmt::Display) -> String {
+    format!("Failed to build diagnostics bundle: {err}")
 }
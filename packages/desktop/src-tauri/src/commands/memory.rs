@@ -0,0 +1,160 @@
+use std::time::Duration;
+
+use log::info;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::commands::github::pr_watch_count;
+use crate::commands::terminal::TerminalState;
+use crate::fs_watcher;
+use crate::session_activity::SessionActivityState;
+use crate::DesktopRuntime;
+
+const MEMORY_STATS_EVENT: &str = "openchamber:memory-stats";
+const MEMORY_STATS_INTERVAL: Duration = Duration::from_secs(2);
+
+static MEMORY_STATS_TASK: Lazy<Mutex<Option<tauri::async_runtime::JoinHandle<()>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryStats {
+    /// Resident set size of this process, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub helper_rss_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opencode_pid: Option<u32>,
+    /// Resident set size of the spawned OpenCode process, in bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub opencode_rss_bytes: Option<u64>,
+    pub terminals: usize,
+    pub directory_watchers: usize,
+    pub pr_watches: usize,
+    pub active_searches: usize,
+    pub tracked_sessions: usize,
+    pub models_metadata_cached: bool,
+    /// Unix timestamp in milliseconds.
+    pub collected_at: i64,
+}
+
+/// Resident memory of `pid` in bytes, or `None` if the process can't be inspected.
+#[cfg(target_os = "linux")]
+async fn process_rss_bytes(pid: u32) -> Option<u64> {
+    let status = tokio::fs::read_to_string(format!("/proc/{pid}/status"))
+        .await
+        .ok()?;
+    let kb = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(target_os = "macos")]
+async fn process_rss_bytes(pid: u32) -> Option<u64> {
+    let output = tokio::process::Command::new("ps")
+        .args(["-o", "rss=", "-p", &pid.to_string()])
+        .output()
+        .await
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let kb = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(windows)]
+async fn process_rss_bytes(pid: u32) -> Option<u64> {
+    // CSV row: "name","pid","session","#","12,345 K"
+    let output = tokio::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/FO", "CSV", "/NH"])
+        .output()
+        .await
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mem = stdout.lines().next()?.rsplit("\",\"").next()?;
+    let kb = mem
+        .trim_matches(|c: char| !c.is_ascii_digit())
+        .replace([',', '.', '\u{a0}'], "")
+        .parse::<u64>()
+        .ok()?;
+    Some(kb * 1024)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+async fn process_rss_bytes(_pid: u32) -> Option<u64> {
+    None
+}
+
+async fn collect_memory_stats(
+    runtime: &DesktopRuntime,
+    terminals: &TerminalState,
+    activity: &SessionActivityState,
+) -> MemoryStats {
+    let opencode_pid = runtime.opencode_manager().child_pid();
+    let opencode_rss_bytes = match opencode_pid {
+        Some(pid) => process_rss_bytes(pid).await,
+        None => None,
+    };
+
+    MemoryStats {
+        helper_rss_bytes: process_rss_bytes(std::process::id()).await,
+        opencode_pid,
+        opencode_rss_bytes,
+        terminals: terminals.sessions.lock().len(),
+        directory_watchers: fs_watcher::watch_count(),
+        pr_watches: pr_watch_count(),
+        active_searches: runtime.active_search_count(),
+        tracked_sessions: activity.snapshot().await.sessions.len(),
+        models_metadata_cached: runtime.models_metadata_cached().await,
+        collected_at: chrono::Utc::now().timestamp_millis(),
+    }
+}
+
+/// Memory usage of the helper and OpenCode processes plus counts of tracked resources.
+#[tauri::command]
+pub async fn get_memory_stats(
+    runtime: State<'_, DesktopRuntime>,
+    terminals: State<'_, TerminalState>,
+    activity: State<'_, SessionActivityState>,
+) -> Result<MemoryStats, String> {
+    Ok(collect_memory_stats(&runtime, &terminals, &activity).await)
+}
+
+/// Start or stop emitting `openchamber:memory-stats` every couple of seconds while the
+/// memory debug overlay is visible.
+#[tauri::command]
+pub async fn set_memory_stats_streaming(enabled: bool, app: AppHandle) -> Result<(), String> {
+    let mut task = MEMORY_STATS_TASK.lock();
+    if let Some(previous) = task.take() {
+        previous.abort();
+    }
+    if !enabled {
+        info!("[desktop:memory] stats streaming stopped");
+        return Ok(());
+    }
+
+    *task = Some(tauri::async_runtime::spawn(async move {
+        let runtime = app.state::<DesktopRuntime>().inner().clone();
+        let activity = app.state::<SessionActivityState>().inner().clone();
+        let mut interval = tokio::time::interval(MEMORY_STATS_INTERVAL);
+        loop {
+            interval.tick().await;
+            let terminals = app.state::<TerminalState>();
+            let stats = collect_memory_stats(&runtime, &terminals, &activity).await;
+            let _ = app.emit(MEMORY_STATS_EVENT, stats);
+        }
+    }));
+    info!("[desktop:memory] stats streaming started");
+    Ok(())
+}
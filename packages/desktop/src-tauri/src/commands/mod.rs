@@ -1,8 +1,20 @@
+pub mod badge;
+pub mod batch;
+pub mod clipboard;
+pub mod diagnostics;
+pub mod error;
+pub mod external;
 pub mod files;
 pub mod git;
 pub mod github;
 pub mod logs;
+pub mod memory;
+pub mod models;
 pub mod notifications;
 pub mod permissions;
+pub mod sessions;
 pub mod settings;
+pub mod skills;
 pub mod terminal;
+pub mod updater;
+pub mod window;
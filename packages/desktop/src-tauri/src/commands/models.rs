@@ -0,0 +1,226 @@
+use std::time::{Duration, Instant};
+
+use log::{info, warn};
+use reqwest::{Client, StatusCode};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tauri::State;
+
+use crate::DesktopRuntime;
+
+const MODEL_TEST_TIMEOUT: Duration = Duration::from_secs(20);
+const MODEL_TEST_CLEANUP_TIMEOUT: Duration = Duration::from_secs(5);
+const MODEL_TEST_PROMPT: &str = "Reply with the single word OK.";
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ModelTestErrorKind {
+    AuthFailed,
+    ModelNotFound,
+    RateLimited,
+    Timeout,
+    Unavailable,
+    Other,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelTestError {
+    pub kind: ModelTestErrorKind,
+    pub message: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelTestResult {
+    pub ok: bool,
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    /// Time from sending the prompt to receiving the reply (or the error).
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ModelTestError>,
+}
+
+impl ModelTestError {
+    fn new(kind: ModelTestErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+/// Map an OpenCode error object (`{ name, data: { message, statusCode } }`) onto a kind the
+/// model picker can explain.
+fn classify_opencode_error(error: &Value, status: Option<StatusCode>) -> ModelTestError {
+    let name = error
+        .get("name")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let data = error.get("data").unwrap_or(&Value::Null);
+    let message = data
+        .get("message")
+        .or_else(|| error.get("message"))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            if name.is_empty() {
+                "Unknown error".to_string()
+            } else {
+                name.to_string()
+            }
+        });
+    let upstream_status = data
+        .get("statusCode")
+        .and_then(Value::as_u64)
+        .or_else(|| status.map(|status| status.as_u16() as u64));
+
+    let lower = message.to_lowercase();
+    let kind = if name == "ProviderAuthError"
+        || matches!(upstream_status, Some(401 | 403))
+        || lower.contains("api key")
+        || lower.contains("unauthorized")
+    {
+        ModelTestErrorKind::AuthFailed
+    } else if name == "ProviderModelNotFoundError"
+        || name == "ModelNotFoundError"
+        || upstream_status == Some(404)
+        || lower.contains("model not found")
+    {
+        ModelTestErrorKind::ModelNotFound
+    } else if upstream_status == Some(429) || lower.contains("rate limit") {
+        ModelTestErrorKind::RateLimited
+    } else {
+        ModelTestErrorKind::Other
+    };
+    ModelTestError::new(kind, message)
+}
+
+/// Send the test prompt and return the error, if any, reported by OpenCode or the provider.
+async fn send_test_prompt(
+    client: &Client,
+    base: &str,
+    session_id: &str,
+    body: &Value,
+) -> Option<ModelTestError> {
+    let response = match client
+        .post(format!("{base}/session/{session_id}/message"))
+        .timeout(MODEL_TEST_TIMEOUT)
+        .json(body)
+        .send()
+        .await
+    {
+        Ok(response) => response,
+        Err(err) if err.is_timeout() => {
+            return Some(ModelTestError::new(
+                ModelTestErrorKind::Timeout,
+                format!("No reply within {} seconds", MODEL_TEST_TIMEOUT.as_secs()),
+            ))
+        }
+        Err(err) => {
+            return Some(ModelTestError::new(
+                ModelTestErrorKind::Unavailable,
+                format!("Failed to reach OpenCode: {err}"),
+            ))
+        }
+    };
+
+    let status = response.status();
+    let payload: Value = response.json().await.unwrap_or(Value::Null);
+    if !status.is_success() {
+        return Some(classify_opencode_error(&payload, Some(status)));
+    }
+    // A provider failure still answers 200, with the error attached to the assistant message.
+    payload
+        .pointer("/info/error")
+        .filter(|error| !error.is_null())
+        .map(|error| classify_opencode_error(error, None))
+}
+
+/// Send a one-line prompt to `model` ("provider/model") through OpenCode and report whether
+/// it answered, using a throwaway session that is deleted afterwards.
+#[tauri::command]
+pub async fn test_model(
+    model: String,
+    variant: Option<String>,
+    runtime: State<'_, DesktopRuntime>,
+) -> Result<ModelTestResult, String> {
+    let model = model.trim().to_string();
+    let Some((provider_id, model_id)) = model
+        .split_once('/')
+        .filter(|(provider, id)| !provider.is_empty() && !id.is_empty())
+    else {
+        return Err("Model must be in the form provider/model".to_string());
+    };
+    let variant = variant
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+
+    let base = runtime
+        .opencode_api_base()
+        .await
+        .map_err(|e| e.to_string())?;
+    let client = runtime.http_client();
+
+    let session: Value = client
+        .post(format!("{base}/session"))
+        .timeout(MODEL_TEST_CLEANUP_TIMEOUT)
+        .json(&json!({ "title": "OpenChamber model test" }))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| format!("Failed to create test session: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Invalid session response: {}", e))?;
+    let session_id = session
+        .get("id")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "OpenCode did not return a session id".to_string())?
+        .to_string();
+
+    let mut body = json!({
+        "model": { "providerID": provider_id, "modelID": model_id },
+        "parts": [{ "type": "text", "text": MODEL_TEST_PROMPT }],
+    });
+    if let Some(variant) = variant.as_ref() {
+        body["variant"] = json!(variant);
+    }
+
+    let started = Instant::now();
+    let error = send_test_prompt(client, &base, &session_id, &body).await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    // Abort first in case the prompt timed out while still generating.
+    let _ = client
+        .post(format!("{base}/session/{session_id}/abort"))
+        .timeout(MODEL_TEST_CLEANUP_TIMEOUT)
+        .send()
+        .await;
+    if let Err(err) = client
+        .delete(format!("{base}/session/{session_id}"))
+        .timeout(MODEL_TEST_CLEANUP_TIMEOUT)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+    {
+        warn!("[desktop:models] Failed to delete test session {session_id}: {err}");
+    }
+
+    match error.as_ref() {
+        Some(error) => warn!(
+            "[desktop:models] Test of {model} failed ({:?}): {}",
+            error.kind, error.message
+        ),
+        None => info!("[desktop:models] Test of {model} succeeded in {latency_ms}ms"),
+    }
+    Ok(ModelTestResult {
+        ok: error.is_none(),
+        model,
+        variant,
+        latency_ms,
+        error,
+    })
+}
@@ -1,18 +1,163 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use serde::Deserialize;
-use tauri::{AppHandle, Runtime};
+use serde_json::Value;
+use tauri::{AppHandle, Manager, Runtime, State};
 use tauri_plugin_notification::NotificationExt;
 
+use crate::DesktopRuntime;
+
+// Global cap across all notification sources.
+const NOTIFICATION_RATE_WINDOW: Duration = Duration::from_secs(10);
+const NOTIFICATION_RATE_LIMIT: usize = 5;
+// Repeats within the same group inside this window are folded into the next one shown.
+const NOTIFICATION_GROUP_WINDOW: Duration = Duration::from_secs(5);
+const NOTIFICATION_GROUP_RETENTION: Duration = Duration::from_secs(10 * 60);
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct NotificationPayload {
     pub title: Option<String>,
     pub body: Option<String>,
+    pub group_id: Option<String>,
+}
+
+struct NotificationGroup {
+    last_shown: Instant,
+    coalesced: usize,
+}
+
+#[derive(Default)]
+struct NotificationLimiter {
+    recent: VecDeque<Instant>,
+    groups: HashMap<String, NotificationGroup>,
+}
+
+static NOTIFICATION_LIMITER: Lazy<Mutex<NotificationLimiter>> =
+    Lazy::new(|| Mutex::new(NotificationLimiter::default()));
+
+impl NotificationLimiter {
+    fn prune_recent(&mut self, now: Instant) {
+        while let Some(front) = self.recent.front() {
+            if now.duration_since(*front) >= NOTIFICATION_RATE_WINDOW {
+                self.recent.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Count a notification that must always be shown towards the rate window.
+    fn record(&mut self, now: Instant) {
+        self.prune_recent(now);
+        self.recent.push_back(now);
+    }
+
+    /// Returns the number of earlier notifications folded into this one, or `None` when it
+    /// should be dropped.
+    fn admit(&mut self, group_id: Option<&str>, now: Instant) -> Option<usize> {
+        self.prune_recent(now);
+        self.groups.retain(|_, group| {
+            group.coalesced > 0
+                || now.duration_since(group.last_shown) < NOTIFICATION_GROUP_RETENTION
+        });
+
+        let group = group_id.filter(|id| !id.is_empty());
+
+        if let Some(id) = group {
+            if let Some(existing) = self.groups.get_mut(id) {
+                if now.duration_since(existing.last_shown) < NOTIFICATION_GROUP_WINDOW {
+                    existing.coalesced += 1;
+                    return None;
+                }
+            }
+        }
+
+        if self.recent.len() >= NOTIFICATION_RATE_LIMIT {
+            if let Some(id) = group {
+                if let Some(existing) = self.groups.get_mut(id) {
+                    existing.coalesced += 1;
+                }
+            }
+            return None;
+        }
+
+        self.recent.push_back(now);
+
+        let coalesced = match group {
+            Some(id) => {
+                let entry = self
+                    .groups
+                    .entry(id.to_string())
+                    .or_insert(NotificationGroup {
+                        last_shown: now,
+                        coalesced: 0,
+                    });
+                entry.last_shown = now;
+                std::mem::take(&mut entry.coalesced)
+            }
+            None => 0,
+        };
+
+        Some(coalesced)
+    }
+}
+
+/// Apply grouping and the global rate limit. Returns the body to show, or `None` when the
+/// notification was coalesced or rate limited.
+pub(crate) fn admit_notification(group_id: Option<&str>, body: &str) -> Option<String> {
+    let coalesced = NOTIFICATION_LIMITER
+        .lock()
+        .admit(group_id, Instant::now())?;
+
+    if coalesced == 0 {
+        return Some(body.to_string());
+    }
+
+    let suffix = if coalesced == 1 { "" } else { "s" };
+    Some(format!("{body} (+{coalesced} more update{suffix})"))
+}
+
+/// Permission and question prompts block the agent until answered, so they are never
+/// coalesced or rate limited; they still count towards the limit for other notifications.
+pub(crate) fn admit_prompt_notification() {
+    NOTIFICATION_LIMITER.lock().record(Instant::now());
+}
+
+pub(crate) fn is_main_window_hidden<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.get_webview_window("main")
+        .map(|window| {
+            let focused = window.is_focused().unwrap_or(false);
+            let minimized = window.is_minimized().unwrap_or(false);
+            !focused || minimized
+        })
+        .unwrap_or(true)
+}
+
+/// Whether a notification should be shown now, honoring the `notificationMode` setting.
+pub(crate) async fn should_notify<R: Runtime>(
+    app: &AppHandle<R>,
+    runtime: &DesktopRuntime,
+) -> bool {
+    let settings = runtime.settings().load().await.unwrap_or(Value::Null);
+    let mode = settings
+        .get("notificationMode")
+        .and_then(Value::as_str)
+        .unwrap_or("hidden-only");
+
+    mode == "always" || is_main_window_hidden(app)
 }
 
 #[tauri::command]
 pub async fn desktop_notify<R: Runtime>(
     app: AppHandle<R>,
     payload: Option<NotificationPayload>,
+    state: State<'_, DesktopRuntime>,
 ) -> Result<bool, String> {
     let title = payload
         .as_ref()
@@ -22,6 +167,15 @@ pub async fn desktop_notify<R: Runtime>(
         .as_ref()
         .and_then(|p| p.body.as_deref())
         .unwrap_or("Task completed");
+    let group_id = payload.as_ref().and_then(|p| p.group_id.as_deref());
+
+    if !should_notify(&app, &state).await {
+        return Ok(false);
+    }
+
+    let Some(body) = admit_notification(group_id, body) else {
+        return Ok(false);
+    };
 
     match app
         .notification()
@@ -1,13 +1,16 @@
 use chrono::Utc;
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
+use std::path::PathBuf;
 use tauri::AppHandle;
 use tauri::State;
+use tauri_plugin_dialog::DialogExt;
 use uuid::Uuid;
 
+use crate::commands::settings::record_recent_directory;
 use crate::path_utils::expand_tilde_path;
-use crate::DesktopRuntime;
+use crate::{DesktopRuntime, SettingsStore};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -32,6 +35,53 @@ pub struct StartAccessingResult {
     error: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirectoryBookmarkEntry {
+    path: String,
+    resolves: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoveBookmarkResult {
+    success: bool,
+    removed: bool,
+    error: Option<String>,
+}
+
+/// Approved roots are the explicitly approved directories plus known project paths,
+/// canonicalized where possible. An empty list means no restriction is configured.
+pub(crate) async fn resolve_approved_roots(settings: &SettingsStore) -> Vec<PathBuf> {
+    let current = settings.load().await.unwrap_or(Value::Null);
+    let mut roots: Vec<PathBuf> = Vec::new();
+    if let Some(entries) = current.get("approvedDirectories").and_then(Value::as_array) {
+        roots.extend(
+            entries
+                .iter()
+                .filter_map(Value::as_str)
+                .map(expand_tilde_path),
+        );
+    }
+    if let Some(projects) = current.get("projects").and_then(Value::as_array) {
+        roots.extend(
+            projects
+                .iter()
+                .filter_map(|entry| entry.get("path").and_then(Value::as_str))
+                .map(expand_tilde_path),
+        );
+    }
+
+    let mut canonical_roots: Vec<PathBuf> = Vec::with_capacity(roots.len());
+    for root in roots {
+        let root = tokio::fs::canonicalize(&root).await.unwrap_or(root);
+        if !canonical_roots.contains(&root) {
+            canonical_roots.push(root);
+        }
+    }
+    canonical_roots
+}
+
 /// Process directory selection from frontend.
 /// Updates settings (projects, activeProjectId, lastDirectory).
 #[tauri::command]
@@ -134,6 +184,7 @@ pub async fn process_directory_selection(
                     json!(normalized_path_for_update),
                 );
             }
+            record_recent_directory(&mut settings, &normalized_path_for_update, now);
 
             (settings, project_id)
         })
@@ -169,6 +220,90 @@ pub async fn pick_directory(
     })
 }
 
+/// Directory picker constrained to an approved root. The dialog opens at `root` and a
+/// selection that escapes it after canonicalization is rejected. Without a root this behaves
+/// like the default picker.
+#[tauri::command]
+pub async fn pick_directory_within(
+    root: Option<String>,
+    app_handle: AppHandle,
+    state: State<'_, DesktopRuntime>,
+) -> Result<DirectoryPermissionResult, String> {
+    let denied = || DirectoryPermissionResult {
+        success: false,
+        path: None,
+        project_id: None,
+        error: Some("Access to directory denied".to_string()),
+    };
+
+    let root = root
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+
+    let root_path = match root {
+        Some(value) => {
+            let Ok(canonical) = tokio::fs::canonicalize(expand_tilde_path(value)).await else {
+                return Ok(DirectoryPermissionResult {
+                    success: false,
+                    path: None,
+                    project_id: None,
+                    error: Some("Directory not found".to_string()),
+                });
+            };
+            let approved = resolve_approved_roots(state.settings()).await;
+            if !approved.is_empty() && !approved.iter().any(|entry| canonical.starts_with(entry)) {
+                warn!(
+                    "[permissions] Refusing picker rooted outside approved directories: {}",
+                    canonical.display()
+                );
+                return Ok(denied());
+            }
+            Some(canonical)
+        }
+        None => None,
+    };
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let mut dialog = app_handle.dialog().file();
+    if let Some(root_path) = &root_path {
+        dialog = dialog.set_directory(root_path);
+    }
+    dialog.pick_folder(move |selection| {
+        let _ = tx.send(selection);
+    });
+
+    let Some(selection) = rx.await.map_err(|e| e.to_string())? else {
+        return Ok(DirectoryPermissionResult {
+            success: false,
+            path: None,
+            project_id: None,
+            error: Some("No directory selected".to_string()),
+        });
+    };
+
+    let picked = selection.into_path().map_err(|e| e.to_string())?;
+    let picked = tokio::fs::canonicalize(&picked).await.unwrap_or(picked);
+
+    if let Some(root_path) = &root_path {
+        if !picked.starts_with(root_path) {
+            warn!(
+                "[permissions] Picked directory escapes root {}: {}",
+                root_path.display(),
+                picked.display()
+            );
+            return Ok(denied());
+        }
+    }
+
+    Ok(DirectoryPermissionResult {
+        success: true,
+        path: Some(picked.to_string_lossy().to_string()),
+        project_id: None,
+        error: None,
+    })
+}
+
 /// Request directory access (desktop implementation)
 /// For unsandboxed apps, just validates the path is accessible
 #[tauri::command]
@@ -283,3 +418,92 @@ pub async fn restore_bookmarks_on_startup(_state: State<'_, DesktopRuntime>) ->
     info!("[permissions] Bookmark restore not needed for unsandboxed app");
     Ok(())
 }
+
+/// Check whether a stored bookmark still points at an accessible directory.
+/// Unsandboxed builds store plain paths, so resolution is a filesystem check.
+fn bookmark_resolves(entry: &str) -> bool {
+    let path = expand_tilde_path(entry);
+    path.is_dir() && std::fs::read_dir(&path).is_ok()
+}
+
+/// List stored security-scoped bookmarks and whether each one still resolves.
+#[tauri::command]
+pub async fn list_directory_bookmarks(
+    state: State<'_, DesktopRuntime>,
+) -> Result<Vec<DirectoryBookmarkEntry>, String> {
+    let settings = state
+        .settings()
+        .load()
+        .await
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+
+    let entries = settings
+        .get("securityScopedBookmarks")
+        .and_then(|value| value.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str())
+                .filter(|item| !item.trim().is_empty())
+                .map(|item| DirectoryBookmarkEntry {
+                    path: item.to_string(),
+                    resolves: bookmark_resolves(item),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(entries)
+}
+
+/// Remove a stored bookmark and release its access scope.
+#[tauri::command]
+pub async fn remove_directory_bookmark(
+    path: String,
+    state: State<'_, DesktopRuntime>,
+) -> Result<RemoveBookmarkResult, String> {
+    if path.trim().is_empty() {
+        return Ok(RemoveBookmarkResult {
+            success: false,
+            removed: false,
+            error: Some("Path is required".to_string()),
+        });
+    }
+    let target = expand_tilde_path(path.trim());
+
+    let (_, removed) = state
+        .settings()
+        .update_with(|mut settings| {
+            let removed = settings
+                .get_mut("securityScopedBookmarks")
+                .and_then(|value| value.as_array_mut())
+                .map(|items| {
+                    let before = items.len();
+                    items.retain(|item| {
+                        item.as_str()
+                            .map(|value| expand_tilde_path(value) != target)
+                            .unwrap_or(false)
+                    });
+                    items.len() != before
+                })
+                .unwrap_or(false);
+            (settings, removed)
+        })
+        .await
+        .map_err(|e| format!("Failed to save updated settings: {}", e))?;
+
+    if removed {
+        // Unsandboxed builds hold no security scope, so releasing it is a no-op outside macOS.
+        #[cfg(target_os = "macos")]
+        {
+            let _ = stop_accessing_directory(path.clone(), state).await;
+        }
+        info!("[permissions] Removed directory bookmark: {}", path);
+    }
+
+    Ok(RemoveBookmarkResult {
+        success: true,
+        removed,
+        error: None,
+    })
+}
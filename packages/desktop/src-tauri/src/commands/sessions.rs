@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{State, Window};
+
+use crate::commands::settings::memory_limit;
+use crate::session_activity::{
+    abort_busy_sessions, CancelSessionsResult, SessionActivitySnapshot, SessionActivityState,
+};
+use crate::session_read_positions::{SessionReadPosition, SessionReadPositionStore};
+use crate::DesktopRuntime;
+
+const SESSION_MESSAGES_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionMessagesResult {
+    /// The most recent messages, oldest first, at most `limit` of them.
+    pub messages: Vec<Value>,
+    pub limit: usize,
+    /// Older messages were left out to stay within `limit`.
+    pub has_more: bool,
+}
+
+/// Current view of the session activity tracker (busy/cooldown/idle per session).
+#[tauri::command]
+pub async fn get_session_activity(
+    state: State<'_, SessionActivityState>,
+) -> Result<SessionActivitySnapshot, String> {
+    Ok(state.snapshot().await)
+}
+
+/// Abort every in-flight assistant run. Idempotent: with nothing running it cancels nothing.
+/// The Stop All Sessions menu item that also triggers this exists only on macOS; other
+/// platforms reach it through this command alone.
+#[tauri::command]
+pub async fn cancel_all_sessions(
+    runtime: State<'_, DesktopRuntime>,
+    activity: State<'_, SessionActivityState>,
+) -> Result<CancelSessionsResult, String> {
+    Ok(abort_busy_sessions(&runtime, &activity).await)
+}
+
+fn validate_session_id(session_id: &str) -> Result<&str, String> {
+    let session_id = session_id.trim();
+    if session_id.is_empty() || session_id.contains('/') {
+        return Err("A valid session id is required".to_string());
+    }
+    Ok(session_id)
+}
+
+/// Remember the last message read in a session. Writes are batched, so this is cheap to call
+/// while scrolling.
+#[tauri::command]
+pub async fn save_session_read_position(
+    session_id: String,
+    message_id: String,
+    positions: State<'_, SessionReadPositionStore>,
+) -> Result<(), String> {
+    let session_id = validate_session_id(&session_id)?;
+    let message_id = message_id.trim();
+    if message_id.is_empty() {
+        return Err("A message id is required".to_string());
+    }
+
+    positions.set(session_id, message_id);
+    Ok(())
+}
+
+/// Last-read message of a session, or `None` if it was never saved.
+#[tauri::command]
+pub async fn get_session_read_position(
+    session_id: String,
+    positions: State<'_, SessionReadPositionStore>,
+) -> Result<Option<SessionReadPosition>, String> {
+    let session_id = validate_session_id(&session_id)?;
+    Ok(positions.get(session_id))
+}
+
+/// Record the session this window is showing (`None` when none is open). Session cleanup
+/// waits for the first report and never deletes a reported session.
+#[tauri::command]
+pub async fn set_current_session(
+    session_id: Option<String>,
+    window: Window,
+    activity: State<'_, SessionActivityState>,
+) -> Result<(), String> {
+    let session_id = session_id.as_deref().map(validate_session_id).transpose()?;
+    activity
+        .set_viewed_session(window.label(), session_id)
+        .await;
+    Ok(())
+}
+
+/// Fetch a session's message history from OpenCode, keeping only the newest messages allowed
+/// by the memory limit settings: `memoryLimitActiveSession` while the session is running,
+/// otherwise `memoryLimitViewport` for the visible session and `memoryLimitHistorical` for
+/// background ones.
+#[tauri::command]
+pub async fn get_session_messages(
+    session_id: String,
+    viewport: bool,
+    directory: Option<String>,
+    window: Window,
+    runtime: State<'_, DesktopRuntime>,
+    activity: State<'_, SessionActivityState>,
+) -> Result<SessionMessagesResult, String> {
+    let session_id = validate_session_id(&session_id)?;
+    if viewport {
+        activity
+            .set_viewed_session(window.label(), Some(session_id))
+            .await;
+    }
+
+    let settings = runtime.settings().load().await.unwrap_or(Value::Null);
+    let busy = activity
+        .snapshot()
+        .await
+        .sessions
+        .iter()
+        .any(|session| session.session_id == session_id && session.phase != "idle");
+    let limit = if busy {
+        memory_limit(&settings, "memoryLimitActiveSession")
+    } else if viewport {
+        memory_limit(&settings, "memoryLimitViewport")
+    } else {
+        memory_limit(&settings, "memoryLimitHistorical")
+    };
+
+    let base = runtime
+        .opencode_api_base()
+        .await
+        .map_err(|e| e.to_string())?;
+    let url = format!("{base}/session/{}/message", urlencoding::encode(session_id));
+
+    // Ask for one extra message so we can tell whether anything older was left out. OpenCode
+    // versions that ignore `limit` return everything and are trimmed below.
+    let mut query = vec![("limit", (limit + 1).to_string())];
+    if let Some(directory) = directory.filter(|value| !value.trim().is_empty()) {
+        query.push(("directory", directory));
+    }
+    let response = runtime
+        .http_client()
+        .get(&url)
+        .query(&query)
+        .timeout(SESSION_MESSAGES_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch session messages: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "OpenCode returned {} for session messages",
+            response.status()
+        ));
+    }
+    let mut messages: Vec<Value> = response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid session messages response: {}", e))?;
+
+    let has_more = messages.len() > limit;
+    if has_more {
+        messages.drain(..messages.len() - limit);
+    }
+
+    Ok(SessionMessagesResult {
+        messages,
+        limit,
+        has_more,
+    })
+}
@@ -1,10 +1,12 @@
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
 use std::collections::HashSet;
-use tauri::State;
+use std::path::PathBuf;
+use tauri::{AppHandle, State};
 use uuid::Uuid;
 
+use crate::opencode_config;
 use crate::path_utils::expand_tilde_path;
 use crate::DesktopRuntime;
 
@@ -21,14 +23,40 @@ pub struct RestartResult {
     restarted: bool,
 }
 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveWorkspace {
+    /// Directory the running OpenCode instance was started in.
+    opencode_directory: String,
+    /// Active project (or last directory) from settings.
+    settings_directory: Option<String>,
+    synced: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsRejection {
+    key: String,
+    reason: String,
+}
+
 /// Load settings from disk.
 #[tauri::command]
 pub async fn load_settings(state: State<'_, DesktopRuntime>) -> Result<SettingsLoadResult, String> {
+    let current = state.settings().load().await.unwrap_or(Value::Null);
+    let opencode_defaults = discover_opencode_defaults(&current).await;
+
     let (settings, _) = state
         .settings()
         .update_with(|mut settings| {
+            migrate_settings_schema(&mut settings);
             migrate_legacy_project_settings(&mut settings);
             normalize_project_selection(&mut settings);
+            if let Some(defaults) = opencode_defaults.as_ref() {
+                seed_opencode_defaults(&mut settings, defaults);
+            }
             (settings, ())
         })
         .await
@@ -44,21 +72,268 @@ pub async fn load_settings(state: State<'_, DesktopRuntime>) -> Result<SettingsL
 #[tauri::command]
 pub async fn save_settings(
     changes: Value,
+    app: AppHandle,
     state: State<'_, DesktopRuntime>,
 ) -> Result<Value, String> {
-    let sanitized_changes = sanitize_settings_update(&changes);
+    let (sanitized_changes, rejected) = sanitize_settings_update_with_rejections(&changes);
+    if !rejected.is_empty() {
+        let keys: Vec<&str> = rejected.iter().map(|entry| entry.key.as_str()).collect();
+        log::warn!(
+            "[desktop:settings] dropped or adjusted fields: {}",
+            keys.join(", ")
+        );
+    }
 
     let (merged, _) = state
         .settings()
-        .update_with(|current| {
-            let mut merged = merge_persisted_settings(&current, &sanitized_changes);
-            normalize_project_selection(&mut merged);
-            (merged, ())
+        .update_with(|current| (apply_settings_changes(&current, &sanitized_changes), ()))
+        .await
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+    state
+        .opencode_manager()
+        .set_api_prefix_override(opencode_api_prefix_override(&merged));
+    #[cfg(target_os = "macos")]
+    crate::set_app_nap_prevention(prevent_app_nap_enabled(&merged));
+    crate::logging::apply_log_level(log_level_setting(&merged));
+    crate::global_shortcut::apply_toggle_shortcut(&app, global_toggle_shortcut(&merged).as_deref());
+    crate::tray::apply_tray_setting(&app, show_tray_icon_enabled(&merged));
+    state.set_proxy_body_limit(proxy_body_limit_bytes(
+        &merged,
+        crate::DEFAULT_PROXY_BODY_LIMIT,
+    ));
+    state.set_sse_keep_alive_secs(sse_keep_alive_secs(
+        &merged,
+        crate::DEFAULT_SSE_KEEP_ALIVE_SECS,
+    ));
+
+    Ok(build_save_response(&merged, rejected))
+}
+
+/// Run the same sanitize + merge + normalize pipeline as `save_settings` without persisting,
+/// returning the document a save would produce.
+#[tauri::command]
+pub async fn preview_settings_merge(
+    changes: Value,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Value, String> {
+    let (sanitized_changes, rejected) = sanitize_settings_update_with_rejections(&changes);
+
+    let current = state
+        .settings()
+        .load()
+        .await
+        .map_err(|e| format!("Failed to load settings: {}", e))?;
+    let merged = apply_settings_changes(&current, &sanitized_changes);
+
+    Ok(build_save_response(&merged, rejected))
+}
+
+/// Set one default field without round-tripping the whole settings document. `null` or a
+/// blank string clears it. Returns the stored value.
+#[tauri::command]
+pub async fn set_default(
+    key: String,
+    value: Value,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Value, String> {
+    if !DEFAULT_SETTING_KEYS.contains(&key.as_str()) {
+        return Err(format!("Unsupported default setting: {}", key));
+    }
+    let next = match &value {
+        Value::Null => Value::Null,
+        Value::String(s) if s.trim().is_empty() => Value::Null,
+        Value::String(s) => json!(s.trim()),
+        _ => return Err(format!("{} must be a string or null", key)),
+    };
+
+    state
+        .settings()
+        .update_with(|mut settings| {
+            if !settings.is_object() {
+                settings = json!({});
+            }
+            if let Some(obj) = settings.as_object_mut() {
+                obj.insert(key.clone(), next.clone());
+            }
+            (settings, ())
         })
         .await
         .map_err(|e| format!("Failed to save settings: {}", e))?;
 
-    Ok(format_settings_response(&merged))
+    Ok(next)
+}
+
+/// `preventAppNap` defaults to on (the historical behavior).
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub(crate) fn prevent_app_nap_enabled(settings: &Value) -> bool {
+    settings
+        .get("preventAppNap")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(true)
+}
+
+/// `showTrayIcon`, off unless explicitly enabled.
+pub(crate) fn show_tray_icon_enabled(settings: &Value) -> bool {
+    settings
+        .get("showTrayIcon")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// `logLevel` from persisted settings, defaulting to info.
+pub(crate) fn log_level_setting(settings: &Value) -> log::LevelFilter {
+    settings
+        .get("logLevel")
+        .and_then(|value| value.as_str())
+        .and_then(crate::logging::parse_log_level)
+        .unwrap_or(crate::logging::DEFAULT_LOG_LEVEL)
+}
+
+/// `logFormat` is "text" (default) or "json".
+pub(crate) fn json_log_format_enabled(settings: &Value) -> bool {
+    settings.get("logFormat").and_then(|value| value.as_str()) == Some("json")
+}
+
+/// `proxyBodyLimitMb` converted to bytes, or `default` when unset.
+pub(crate) fn proxy_body_limit_bytes(settings: &Value, default: usize) -> usize {
+    settings
+        .get("proxyBodyLimitMb")
+        .and_then(|value| value.as_u64())
+        .map(|mb| mb.clamp(1, 256) as usize * 1024 * 1024)
+        .unwrap_or(default)
+}
+
+/// `sseKeepAliveSeconds`, or `default` when unset; 0 turns keep-alive comments off.
+pub(crate) fn sse_keep_alive_secs(settings: &Value, default: u64) -> u64 {
+    settings
+        .get("sseKeepAliveSeconds")
+        .and_then(|value| value.as_u64())
+        .map(|secs| secs.min(300))
+        .unwrap_or(default)
+}
+
+/// `globalToggleShortcut` accelerator, or `None` when unset or blank.
+pub(crate) fn global_toggle_shortcut(settings: &Value) -> Option<String> {
+    settings
+        .get("globalToggleShortcut")
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// `opencodeIdleSleepMinutes`, or `None` when auto-sleep is off (unset or 0, the default).
+pub(crate) fn opencode_idle_sleep_minutes(settings: &Value) -> Option<u64> {
+    settings
+        .get("opencodeIdleSleepMinutes")
+        .and_then(|value| value.as_u64())
+        .filter(|minutes| *minutes > 0)
+        .map(|minutes| minutes.min(1440))
+}
+
+/// `autoDeleteAfterDays` (default 30, like the UI), or `None` while `autoDeleteEnabled` is off.
+pub(crate) fn auto_delete_after_days(settings: &Value) -> Option<u64> {
+    if !settings
+        .get("autoDeleteEnabled")
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    let days = settings
+        .get("autoDeleteAfterDays")
+        .and_then(|value| value.as_u64())
+        .unwrap_or(30);
+    Some(days.clamp(1, 365))
+}
+
+/// A `memoryLimit*` setting clamped to its range, or the UI's default when unset.
+pub(crate) fn memory_limit(settings: &Value, key: &str) -> usize {
+    let default = MEMORY_LIMIT_DEFAULTS
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map_or(0, |(_, value)| *value);
+    let value = settings
+        .get(key)
+        .and_then(|value| value.as_u64())
+        .unwrap_or(default);
+    let clamped = match SETTINGS_NUMBER_RANGES
+        .iter()
+        .find(|(name, _, _)| *name == key)
+    {
+        Some((_, min, max)) => value.clamp(*min, *max),
+        None => value,
+    };
+    clamped as usize
+}
+
+/// `opencodeApiPrefix` from persisted settings, if set.
+pub(crate) fn opencode_api_prefix_override(settings: &Value) -> Option<String> {
+    settings
+        .get("opencodeApiPrefix")
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|value| value.starts_with('/'))
+        .map(str::to_string)
+}
+
+/// `connectivityProbeUrl` from persisted settings, or `default_url`.
+pub(crate) fn connectivity_probe_url(settings: &Value, default_url: &str) -> String {
+    settings
+        .get("connectivityProbeUrl")
+        .and_then(|value| value.as_str())
+        .and_then(normalize_https_url)
+        .unwrap_or_else(|| default_url.to_string())
+}
+
+/// Models metadata sources in the order they should be tried: `modelsMetadataUrl` (or
+/// `default_url`) first, then each of `modelsMetadataMirrors`.
+pub(crate) fn models_metadata_sources(settings: &Value, default_url: &str) -> Vec<String> {
+    let primary = settings
+        .get("modelsMetadataUrl")
+        .and_then(|value| value.as_str())
+        .and_then(normalize_https_url)
+        .unwrap_or_else(|| default_url.to_string());
+
+    let mut sources = vec![primary];
+    let mirrors = settings
+        .get("modelsMetadataMirrors")
+        .and_then(|value| value.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|value| value.as_str())
+        .filter_map(normalize_https_url);
+    for mirror in mirrors {
+        if !sources.contains(&mirror) {
+            sources.push(mirror);
+        }
+    }
+    sources
+}
+
+fn normalize_https_url(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    let url = url::Url::parse(trimmed).ok()?;
+    if url.scheme() != "https" || !url.has_host() {
+        return None;
+    }
+    Some(trimmed.to_string())
+}
+
+fn apply_settings_changes(current: &Value, sanitized_changes: &Value) -> Value {
+    let mut merged = merge_persisted_settings(current, sanitized_changes);
+    normalize_project_selection(&mut merged);
+    merged
+}
+
+fn build_save_response(merged: &Value, rejected: Vec<SettingsRejection>) -> Value {
+    let mut response = format_settings_response(merged);
+    if !rejected.is_empty() {
+        if let Some(obj) = response.as_object_mut() {
+            obj.insert("rejected".to_string(), json!(rejected));
+        }
+    }
+    response
 }
 
 /// Restart the backend process (config reload).
@@ -73,6 +348,336 @@ pub async fn restart_opencode(state: State<'_, DesktopRuntime>) -> Result<Restar
     Ok(RestartResult { restarted: true })
 }
 
+async fn canonical_or_raw(path: PathBuf) -> PathBuf {
+    tokio::fs::canonicalize(&path).await.unwrap_or(path)
+}
+
+/// Compare OpenCode's working directory with the active project so the UI can spot and fix
+/// drift, e.g. after a failed restart left OpenCode in the previous directory.
+#[tauri::command]
+pub async fn get_active_workspace(
+    state: State<'_, DesktopRuntime>,
+) -> Result<ActiveWorkspace, String> {
+    let opencode_directory = canonical_or_raw(state.opencode.get_working_directory()).await;
+    let settings_directory =
+        match crate::session_activity::resolve_project_directory_from_settings(&state).await {
+            Some(path) => Some(canonical_or_raw(path).await),
+            None => None,
+        };
+
+    let synced = match settings_directory.as_ref() {
+        Some(path) => *path == opencode_directory,
+        // Nothing selected in settings means there is nothing to drift from.
+        None => true,
+    };
+    let hint = (!synced).then(|| {
+        "OpenCode is running in a different directory than the active project; \
+         POST the project path to /api/opencode/directory to switch it."
+            .to_string()
+    });
+
+    Ok(ActiveWorkspace {
+        opencode_directory: opencode_directory.to_string_lossy().to_string(),
+        settings_directory: settings_directory.map(|path| path.to_string_lossy().to_string()),
+        synced,
+        hint,
+    })
+}
+
+// Set once defaults have been imported from OpenCode's config so a value the user later
+// clears isn't re-seeded on the next load.
+const OPENCODE_DEFAULTS_SEEDED_KEY: &str = "opencodeDefaultsSeeded";
+
+struct OpenCodeDefaults {
+    model: Option<String>,
+    agent: Option<String>,
+}
+
+fn is_blank_setting(settings: &Value, key: &str) -> bool {
+    settings
+        .get(key)
+        .and_then(|value| value.as_str())
+        .map(|value| value.trim().is_empty())
+        .unwrap_or(true)
+}
+
+/// Read `model` / `default_agent` from OpenCode's merged config when OpenChamber has no
+/// default of its own yet. Returns `None` when there is nothing to import.
+async fn discover_opencode_defaults(settings: &Value) -> Option<OpenCodeDefaults> {
+    if settings
+        .get(OPENCODE_DEFAULTS_SEEDED_KEY)
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+    {
+        return None;
+    }
+    if !is_blank_setting(settings, "defaultModel") && !is_blank_setting(settings, "defaultAgent") {
+        return None;
+    }
+
+    let working_directory = settings
+        .get("lastDirectory")
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(expand_tilde_path);
+
+    let config = match opencode_config::read_config(working_directory.as_deref()).await {
+        Ok(config) => config,
+        Err(err) => {
+            log::debug!("[desktop:settings] OpenCode config unavailable for defaults: {err}");
+            return None;
+        }
+    };
+
+    let read_string = |key: &str| {
+        config
+            .get(key)
+            .and_then(|value| value.as_str())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let defaults = OpenCodeDefaults {
+        model: read_string("model").filter(|model| model.contains('/')),
+        agent: read_string("default_agent"),
+    };
+
+    if defaults.model.is_none() && defaults.agent.is_none() {
+        return None;
+    }
+    Some(defaults)
+}
+
+/// Fill only blank `defaultModel` / `defaultAgent` fields; user choices are never replaced.
+fn seed_opencode_defaults(settings: &mut Value, defaults: &OpenCodeDefaults) {
+    if settings
+        .get(OPENCODE_DEFAULTS_SEEDED_KEY)
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+    {
+        return;
+    }
+    if !settings.is_object() {
+        *settings = json!({});
+    }
+
+    let mut seeded = false;
+    for (key, value) in [
+        ("defaultModel", defaults.model.as_ref()),
+        ("defaultAgent", defaults.agent.as_ref()),
+    ] {
+        if let Some(value) = value {
+            if is_blank_setting(settings, key) {
+                settings[key] = json!(value);
+                seeded = true;
+            }
+        }
+    }
+
+    if seeded {
+        settings[OPENCODE_DEFAULTS_SEEDED_KEY] = json!(true);
+        log::info!("[desktop:settings] Seeded default model/agent from OpenCode config");
+    }
+}
+
+const RECENT_DIRECTORIES_LIMIT: usize = 20;
+
+/// Sanitize `recentDirectories`: entries need a path, are deduped by path,
+/// ordered most-recent-first and capped at `RECENT_DIRECTORIES_LIMIT`.
+fn sanitize_recent_directories(value: &Value) -> Option<Value> {
+    let arr = value.as_array()?;
+    let mut entries: Vec<(String, i64)> = Vec::new();
+
+    for entry in arr {
+        let Some(obj) = entry.as_object() else {
+            continue;
+        };
+        let raw_path = obj
+            .get("path")
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .trim();
+        if raw_path.is_empty() {
+            continue;
+        }
+        let path = expand_tilde_path(raw_path).to_string_lossy().to_string();
+        let last_opened_at = obj
+            .get("lastOpenedAt")
+            .and_then(|v| v.as_i64())
+            .filter(|v| *v >= 0)
+            .unwrap_or(0);
+
+        match entries.iter_mut().find(|(existing, _)| *existing == path) {
+            Some(existing) => existing.1 = existing.1.max(last_opened_at),
+            None => entries.push((path, last_opened_at)),
+        }
+    }
+
+    entries.sort_by(|a, b| b.1.cmp(&a.1));
+    entries.truncate(RECENT_DIRECTORIES_LIMIT);
+
+    Some(Value::Array(
+        entries
+            .into_iter()
+            .map(|(path, last_opened_at)| json!({ "path": path, "lastOpenedAt": last_opened_at }))
+            .collect(),
+    ))
+}
+
+/// Outcome of checking the saved directory at startup.
+pub(crate) enum StartupDirectory {
+    /// Nothing was saved (fresh install).
+    Unset,
+    /// The saved directory, or the first valid fallback when `fallback` is set.
+    Resolved { path: PathBuf, fallback: bool },
+    /// A directory was saved but neither it nor any project/recent entry still exists.
+    Missing { stale: String },
+}
+
+fn existing_directory(raw: &str) -> Option<PathBuf> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let path = expand_tilde_path(trimmed);
+    let path = std::fs::canonicalize(&path).unwrap_or(path);
+    std::fs::metadata(&path)
+        .ok()
+        .filter(|meta| meta.is_dir())
+        .map(|_| path)
+}
+
+/// Make sure the saved directory still exists. When it doesn't, point `lastDirectory` (and
+/// `activeProjectId`) at the most recent project or recent directory that does; when none
+/// do, drop both so the UI asks for a directory instead of loading a dead path. An
+/// `activeProjectId` naming a project that no longer exists is reassigned or cleared too.
+pub(crate) fn resolve_startup_directory(settings: &mut Value) -> StartupDirectory {
+    let Some(obj) = settings.as_object_mut() else {
+        return StartupDirectory::Unset;
+    };
+
+    let mut projects: Vec<(String, String, i64)> = obj
+        .get("projects")
+        .and_then(|value| value.as_array())
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    let id = entry.get("id")?.as_str()?.to_string();
+                    let path = entry.get("path")?.as_str()?.to_string();
+                    let last_opened_at = entry
+                        .get("lastOpenedAt")
+                        .and_then(|value| value.as_i64())
+                        .unwrap_or(0);
+                    Some((id, path, last_opened_at))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let active_id = obj
+        .get("activeProjectId")
+        .and_then(|value| value.as_str())
+        .map(str::to_string);
+    let last_directory = obj
+        .get("lastDirectory")
+        .and_then(|value| value.as_str())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
+
+    let active_path = active_id.as_ref().and_then(|id| {
+        projects
+            .iter()
+            .find(|(project_id, _, _)| project_id == id)
+            .map(|(_, path, _)| path.clone())
+    });
+    let active_is_dead = active_id.is_some() && active_path.is_none();
+    let Some(saved) = active_path.or_else(|| last_directory.clone()) else {
+        if active_is_dead {
+            obj.remove("activeProjectId");
+        }
+        return StartupDirectory::Unset;
+    };
+    projects.sort_by(|a, b| b.2.cmp(&a.2));
+    // The project, if any, that a directory we settle on belongs to.
+    let project_at = |dir: &PathBuf| {
+        projects
+            .iter()
+            .find(|(_, path, _)| existing_directory(path).as_ref() == Some(dir))
+            .map(|(id, _, _)| id.clone())
+    };
+    if let Some(path) = existing_directory(&saved) {
+        if active_is_dead {
+            match project_at(&path) {
+                Some(id) => obj.insert("activeProjectId".to_string(), json!(id)),
+                None => obj.remove("activeProjectId"),
+            };
+        }
+        return StartupDirectory::Resolved {
+            path,
+            fallback: false,
+        };
+    }
+
+    let project_fallback = projects
+        .iter()
+        .find_map(|(id, path, _)| existing_directory(path).map(|dir| (Some(id.clone()), dir)));
+    let recent_fallback = || {
+        obj.get("recentDirectories")
+            .and_then(|value| value.as_array())?
+            .iter()
+            .filter_map(|entry| entry.get("path").and_then(|value| value.as_str()))
+            .find_map(existing_directory)
+            .map(|dir| (None, dir))
+    };
+    let fallback = last_directory
+        .as_deref()
+        .and_then(existing_directory)
+        .map(|dir| (None, dir))
+        .or(project_fallback)
+        .or_else(recent_fallback);
+
+    match fallback {
+        Some((project_id, path)) => {
+            obj.insert(
+                "lastDirectory".to_string(),
+                json!(path.to_string_lossy().to_string()),
+            );
+            // Never leave `activeProjectId` on the project that just went missing.
+            match project_id.or_else(|| project_at(&path)) {
+                Some(id) => obj.insert("activeProjectId".to_string(), json!(id)),
+                None => obj.remove("activeProjectId"),
+            };
+            StartupDirectory::Resolved {
+                path,
+                fallback: true,
+            }
+        }
+        None => {
+            obj.remove("lastDirectory");
+            obj.remove("activeProjectId");
+            StartupDirectory::Missing { stale: saved }
+        }
+    }
+}
+
+/// Move `path` to the front of `recentDirectories`, keeping the list capped.
+pub(crate) fn record_recent_directory(settings: &mut Value, path: &str, now: i64) {
+    let Some(obj) = settings.as_object_mut() else {
+        return;
+    };
+
+    let mut recents = vec![json!({ "path": path, "lastOpenedAt": now })];
+    if let Some(Value::Array(existing)) = obj.get("recentDirectories") {
+        recents.extend(existing.iter().cloned());
+    }
+
+    if let Some(sanitized) = sanitize_recent_directories(&Value::Array(recents)) {
+        obj.insert("recentDirectories".to_string(), sanitized);
+    }
+}
+
 fn sanitize_projects(value: &Value) -> Option<Value> {
     let arr = value.as_array()?;
     let mut seen_ids = HashSet::new();
@@ -170,355 +775,512 @@ fn sanitize_projects(value: &Value) -> Option<Value> {
     }
 }
 
-/// Sanitize settings update payload (port of Express sanitizeSettingsUpdate)
-fn sanitize_settings_update(payload: &Value) -> Value {
-    let mut result = json!({});
+// Clamping ranges applied by `sanitize_settings_update`.
+const SETTINGS_NUMBER_RANGES: &[(&str, u64, u64)] = &[
+    ("autoDeleteAfterDays", 1, 365),
+    ("fontSize", 50, 200),
+    ("padding", 50, 200),
+    ("cornerRadius", 0, 32),
+    ("inputBarOffset", 0, 100),
+    ("memoryLimitHistorical", 10, 500),
+    ("memoryLimitViewport", 20, 500),
+    ("memoryLimitActiveSession", 30, 1000),
+    ("proxyBodyLimitMb", 1, 256),
+    ("opencodeIdleSleepMinutes", 0, 1440),
+    ("sseKeepAliveSeconds", 0, 300),
+];
+// Matches DEFAULT_MEMORY_LIMITS / DEFAULT_ACTIVE_SESSION_WINDOW in the UI.
+const MEMORY_LIMIT_DEFAULTS: &[(&str, u64)] = &[
+    ("memoryLimitHistorical", 90),
+    ("memoryLimitViewport", 120),
+    ("memoryLimitActiveSession", 180),
+];
+// Fields `set_default` may change; each holds an optional string.
+const DEFAULT_SETTING_KEYS: &[&str] = &[
+    "defaultModel",
+    "defaultVariant",
+    "defaultAgent",
+    "defaultGitIdentityId",
+];
+const BADGE_TRIGGERS: &[&str] = &["unread-messages", "completed-runs", "errors"];
+
+/// Non-negative integer from a JSON number; fractions are rounded and negatives become 0.
+fn parse_setting_number(n: &serde_json::Number) -> Option<u64> {
+    n.as_u64()
+        .or_else(|| {
+            n.as_i64()
+                .and_then(|v| if v >= 0 { Some(v as u64) } else { None })
+        })
+        .or_else(|| n.as_f64().map(|v| v.round().max(0.0) as u64))
+}
 
-    if let Some(obj) = payload.as_object() {
-        let result_obj = result.as_object_mut().unwrap();
+/// Collects the sanitized fields of a settings update along with the reason for every field
+/// it dropped or adjusted, so reported rejections always match what gets saved.
+struct SettingsSanitizer<'a> {
+    input: &'a Map<String, Value>,
+    output: Map<String, Value>,
+    handled: HashSet<&'static str>,
+    rejected: Vec<SettingsRejection>,
+}
 
-        // String fields
-        if let Some(Value::String(s)) = obj.get("themeId") {
-            if !s.is_empty() {
-                result_obj.insert("themeId".to_string(), json!(s));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("themeVariant") {
-            if s == "light" || s == "dark" {
-                result_obj.insert("themeVariant".to_string(), json!(s));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("lightThemeId") {
-            if !s.is_empty() {
-                result_obj.insert("lightThemeId".to_string(), json!(s));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("darkThemeId") {
-            if !s.is_empty() {
-                result_obj.insert("darkThemeId".to_string(), json!(s));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("lastDirectory") {
-            if !s.is_empty() {
-                let expanded = expand_tilde_path(s).to_string_lossy().to_string();
-                result_obj.insert("lastDirectory".to_string(), json!(expanded));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("homeDirectory") {
-            if !s.is_empty() {
-                let expanded = expand_tilde_path(s).to_string_lossy().to_string();
-                result_obj.insert("homeDirectory".to_string(), json!(expanded));
-            }
-        }
-        if let Some(projects) = obj.get("projects").and_then(sanitize_projects) {
-            result_obj.insert("projects".to_string(), projects);
-        }
-        if let Some(Value::String(s)) = obj.get("activeProjectId") {
-            if !s.is_empty() {
-                result_obj.insert("activeProjectId".to_string(), json!(s));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("uiFont") {
-            if !s.is_empty() {
-                result_obj.insert("uiFont".to_string(), json!(s));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("monoFont") {
-            if !s.is_empty() {
-                result_obj.insert("monoFont".to_string(), json!(s));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("markdownDisplayMode") {
-            if !s.is_empty() {
-                result_obj.insert("markdownDisplayMode".to_string(), json!(s));
-            }
+impl<'a> SettingsSanitizer<'a> {
+    fn new(input: &'a Map<String, Value>) -> Self {
+        Self {
+            input,
+            output: Map::new(),
+            handled: HashSet::new(),
+            rejected: Vec::new(),
         }
+    }
 
-        // GitHub OAuth config (non-secret)
-        if let Some(Value::String(s)) = obj.get("githubClientId") {
-            let trimmed = s.trim();
-            if !trimmed.is_empty() {
-                result_obj.insert("githubClientId".to_string(), json!(trimmed));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("githubScopes") {
-            let trimmed = s.trim();
-            if !trimmed.is_empty() {
-                result_obj.insert("githubScopes".to_string(), json!(trimmed));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("defaultModel") {
-            let trimmed = s.trim();
-            if trimmed.is_empty() {
-                result_obj.insert("defaultModel".to_string(), Value::Null);
-            } else {
-                result_obj.insert("defaultModel".to_string(), json!(trimmed));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("defaultVariant") {
-            let trimmed = s.trim();
-            if trimmed.is_empty() {
-                result_obj.insert("defaultVariant".to_string(), Value::Null);
-            } else {
-                result_obj.insert("defaultVariant".to_string(), json!(trimmed));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("defaultAgent") {
-            let trimmed = s.trim();
-            if trimmed.is_empty() {
-                result_obj.insert("defaultAgent".to_string(), Value::Null);
-            } else {
-                result_obj.insert("defaultAgent".to_string(), json!(trimmed));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("defaultGitIdentityId") {
-            let trimmed = s.trim();
-            if trimmed.is_empty() {
-                result_obj.insert("defaultGitIdentityId".to_string(), Value::Null);
-            } else {
-                result_obj.insert("defaultGitIdentityId".to_string(), json!(trimmed));
+    /// The incoming value for `key`, marking the key as recognized.
+    fn get(&mut self, key: &'static str) -> Option<&'a Value> {
+        self.handled.insert(key);
+        self.input.get(key)
+    }
+
+    fn reject(&mut self, key: &str, reason: String) {
+        self.rejected.push(SettingsRejection {
+            key: key.to_string(),
+            reason,
+        });
+    }
+
+    /// Keep `key` as `sanitize` returns it, or drop it with the returned reason.
+    fn field(
+        &mut self,
+        key: &'static str,
+        sanitize: impl FnOnce(&'a Value) -> Result<Value, String>,
+    ) {
+        let Some(value) = self.get(key) else {
+            return;
+        };
+        match sanitize(value) {
+            Ok(sanitized) => {
+                self.output.insert(key.to_string(), sanitized);
             }
+            Err(reason) => self.reject(key, reason),
         }
-        // Boolean fields
-        if let Some(Value::Bool(b)) = obj.get("gitmojiEnabled") {
-            result_obj.insert("gitmojiEnabled".to_string(), json!(b));
-        }
-        if let Some(Value::Bool(b)) = obj.get("useSystemTheme") {
-            result_obj.insert("useSystemTheme".to_string(), json!(b));
-        }
-        if let Some(Value::Bool(b)) = obj.get("showReasoningTraces") {
-            result_obj.insert("showReasoningTraces".to_string(), json!(b));
-        }
-        if let Some(Value::Bool(b)) = obj.get("showTextJustificationActivity") {
-            result_obj.insert("showTextJustificationActivity".to_string(), json!(b));
-        }
-        if let Some(Value::Bool(b)) = obj.get("nativeNotificationsEnabled") {
-            result_obj.insert("nativeNotificationsEnabled".to_string(), json!(b));
+    }
+
+    fn non_empty_string(&mut self, key: &'static str) {
+        self.field(key, |value| match value.as_str() {
+            Some(s) if !s.is_empty() => Ok(json!(s)),
+            _ => Err(format!("{} must be a non-empty string", key)),
+        });
+    }
+
+    fn trimmed_string(&mut self, key: &'static str) {
+        self.field(key, |value| match value.as_str().map(str::trim) {
+            Some(s) if !s.is_empty() => Ok(json!(s)),
+            _ => Err(format!("{} must be a non-empty string", key)),
+        });
+    }
+
+    /// A directory path; `~` is expanded.
+    fn path(&mut self, key: &'static str) {
+        self.field(key, |value| match value.as_str() {
+            Some(s) if !s.is_empty() => Ok(json!(expand_tilde_path(s).to_string_lossy())),
+            _ => Err(format!("{} must be a non-empty string", key)),
+        });
+    }
+
+    /// A string that a blank value clears.
+    fn optional_string(&mut self, key: &'static str) {
+        self.field(key, |value| match value.as_str().map(str::trim) {
+            Some("") => Ok(Value::Null),
+            Some(s) => Ok(json!(s)),
+            None => Err(format!("{} must be a string", key)),
+        });
+    }
+
+    fn boolean(&mut self, key: &'static str) {
+        self.field(key, |value| match value {
+            Value::Bool(b) => Ok(json!(b)),
+            _ => Err(format!("{} must be a boolean", key)),
+        });
+    }
+
+    fn one_of(&mut self, key: &'static str, allowed: &[&str]) {
+        self.field(key, |value| match value.as_str().map(str::trim) {
+            Some(s) if allowed.contains(&s) => Ok(json!(s)),
+            _ => Err(format!("{} not in [{}]", key, allowed.join(", "))),
+        });
+    }
+
+    /// A number clamped into its `SETTINGS_NUMBER_RANGES` range; clamping is reported too.
+    fn number(&mut self, key: &'static str) {
+        let Some(value) = self.get(key) else {
+            return;
+        };
+        let (min, max) = SETTINGS_NUMBER_RANGES
+            .iter()
+            .find(|(name, _, _)| *name == key)
+            .map_or((0, u64::MAX), |(_, min, max)| (*min, *max));
+        let constraint = format!("{} must be {}–{}", key, min, max);
+        let Some(parsed) = value.as_number().and_then(parse_setting_number) else {
+            self.reject(key, constraint);
+            return;
+        };
+        let clamped = parsed.clamp(min, max);
+        if value.as_f64() != Some(clamped as f64) {
+            self.reject(key, format!("{} (saved as {})", constraint, clamped));
         }
-        if let Some(Value::String(s)) = obj.get("notificationMode") {
-            let trimmed = s.trim();
-            if trimmed == "always" || trimmed == "hidden-only" {
-                result_obj.insert("notificationMode".to_string(), json!(trimmed));
+        self.output.insert(key.to_string(), json!(clamped));
+    }
+
+    /// Keys the sanitizer never looked at are unknown to this version of the app.
+    fn finish(mut self) -> (Value, Vec<SettingsRejection>) {
+        let input = self.input;
+        for key in input.keys() {
+            if !self.handled.contains(key.as_str()) {
+                self.reject(key, format!("{} is not a recognized setting", key));
             }
         }
-        if let Some(Value::Bool(b)) = obj.get("autoDeleteEnabled") {
-            result_obj.insert("autoDeleteEnabled".to_string(), json!(b));
-        }
-        if let Some(Value::Bool(b)) = obj.get("queueModeEnabled") {
-            result_obj.insert("queueModeEnabled".to_string(), json!(b));
+        self.rejected.sort_by(|a, b| a.key.cmp(&b.key));
+        (Value::Object(self.output), self.rejected)
+    }
+}
+
+/// Sanitize settings update payload (port of Express sanitizeSettingsUpdate)
+fn sanitize_settings_update(payload: &Value) -> Value {
+    sanitize_settings_update_with_rejections(payload).0
+}
+
+/// `sanitize_settings_update`, plus every field that was dropped or clamped and why.
+fn sanitize_settings_update_with_rejections(payload: &Value) -> (Value, Vec<SettingsRejection>) {
+    let Some(obj) = payload.as_object() else {
+        return (
+            json!({}),
+            vec![SettingsRejection {
+                key: String::new(),
+                reason: "settings update must be an object".to_string(),
+            }],
+        );
+    };
+    let mut s = SettingsSanitizer::new(obj);
+
+    // String fields
+    s.non_empty_string("themeId");
+    s.one_of("themeVariant", &["light", "dark"]);
+    s.non_empty_string("lightThemeId");
+    s.non_empty_string("darkThemeId");
+    s.path("lastDirectory");
+    s.path("homeDirectory");
+    s.field("projects", |value| {
+        sanitize_projects(value).ok_or_else(|| "projects has no valid entries".to_string())
+    });
+    s.non_empty_string("activeProjectId");
+    // An empty string turns the shortcut off.
+    s.field("globalToggleShortcut", |value| match value.as_str() {
+        Some(shortcut) => Ok(json!(shortcut.trim())),
+        None => Err("globalToggleShortcut must be a string".to_string()),
+    });
+    s.non_empty_string("uiFont");
+    s.non_empty_string("monoFont");
+    s.non_empty_string("markdownDisplayMode");
+
+    // GitHub OAuth config (non-secret)
+    s.trimmed_string("githubClientId");
+    s.trimmed_string("githubScopes");
+    for &key in DEFAULT_SETTING_KEYS {
+        s.optional_string(key);
+    }
+    s.field("opencodeApiPrefix", |value| {
+        let invalid = || "opencodeApiPrefix must be a path starting with /".to_string();
+        let raw = value.as_str().ok_or_else(invalid)?;
+        if raw.trim().is_empty() {
+            return Ok(Value::Null);
         }
-        if let Some(Value::Bool(b)) = obj.get("autoCreateWorktree") {
-            result_obj.insert("autoCreateWorktree".to_string(), json!(b));
+        let trimmed = raw.trim().trim_end_matches('/');
+        let valid = trimmed.starts_with('/')
+            && !trimmed
+                .chars()
+                .any(|ch| ch.is_whitespace() || ch == '?' || ch == '#');
+        if valid {
+            Ok(json!(trimmed))
+        } else {
+            Err(invalid())
         }
-        if let Some(Value::String(s)) = obj.get("toolCallExpansion") {
-            let trimmed = s.trim();
-            if trimmed == "collapsed" || trimmed == "activity" || trimmed == "detailed" {
-                result_obj.insert("toolCallExpansion".to_string(), json!(trimmed));
+    });
+    s.field("modelsMetadataUrl", |value| match value.as_str() {
+        Some(raw) if raw.trim().is_empty() => Ok(Value::Null),
+        Some(raw) => normalize_https_url(raw)
+            .map(|url| json!(url))
+            .ok_or_else(|| "modelsMetadataUrl must be an https URL".to_string()),
+        None => Err("modelsMetadataUrl must be an https URL".to_string()),
+    });
+    s.field("connectivityProbeUrl", |value| match value.as_str() {
+        Some(raw) if raw.trim().is_empty() => Ok(Value::Null),
+        Some(raw) => normalize_https_url(raw)
+            .map(|url| json!(url))
+            .ok_or_else(|| "connectivityProbeUrl must be an https URL".to_string()),
+        None => Err("connectivityProbeUrl must be an https URL".to_string()),
+    });
+    s.field("modelsMetadataMirrors", |value| {
+        let arr = value
+            .as_array()
+            .ok_or_else(|| "modelsMetadataMirrors must be an array of https URLs".to_string())?;
+        let mut mirrors: Vec<String> = vec![];
+        for url in arr
+            .iter()
+            .filter_map(|v| v.as_str())
+            .filter_map(normalize_https_url)
+        {
+            if !mirrors.contains(&url) {
+                mirrors.push(url);
             }
         }
+        Ok(json!(mirrors))
+    });
 
-        // Number fields
-        if let Some(Value::Number(n)) = obj.get("autoDeleteAfterDays") {
-            let parsed = n
-                .as_u64()
-                .or_else(|| {
-                    n.as_i64()
-                        .and_then(|value| if value >= 0 { Some(value as u64) } else { None })
-                })
-                .or_else(|| n.as_f64().map(|value| value.round().max(0.0) as u64));
-            if let Some(value) = parsed {
-                let clamped = value.max(1).min(365);
-                result_obj.insert("autoDeleteAfterDays".to_string(), json!(clamped));
-            }
-        }
+    // Boolean fields
+    for key in [
+        "gitmojiEnabled",
+        "useSystemTheme",
+        "showReasoningTraces",
+        "showTextJustificationActivity",
+        "nativeNotificationsEnabled",
+        "autoDeleteEnabled",
+        "queueModeEnabled",
+        "autoCreateWorktree",
+        "preventAppNap",
+        "showTrayIcon",
+        "directoryShowHidden",
+        "filesViewShowGitignored",
+    ] {
+        s.boolean(key);
+    }
 
-        if let Some(Value::Number(n)) = obj.get("fontSize") {
-            let parsed = n
-                .as_u64()
-                .or_else(|| n.as_i64().and_then(|v| if v >= 0 { Some(v as u64) } else { None }))
-                .or_else(|| n.as_f64().map(|v| v.round().max(0.0) as u64));
-            if let Some(value) = parsed {
-                let clamped = value.max(50).min(200);
-                result_obj.insert("fontSize".to_string(), json!(clamped));
-            }
+    // Enum fields
+    s.one_of("notificationMode", &["always", "hidden-only"]);
+    s.field("logLevel", |value| {
+        let normalized = value.as_str().map(|s| s.trim().to_ascii_lowercase());
+        match normalized {
+            Some(level) if crate::logging::parse_log_level(&level).is_some() => Ok(json!(level)),
+            _ => Err(format!(
+                "logLevel not in [{}]",
+                crate::logging::LOG_LEVELS.join(", ")
+            )),
         }
-        if let Some(Value::Number(n)) = obj.get("padding") {
-            let parsed = n
-                .as_u64()
-                .or_else(|| n.as_i64().and_then(|v| if v >= 0 { Some(v as u64) } else { None }))
-                .or_else(|| n.as_f64().map(|v| v.round().max(0.0) as u64));
-            if let Some(value) = parsed {
-                let clamped = value.max(50).min(200);
-                result_obj.insert("padding".to_string(), json!(clamped));
+    });
+    s.one_of("logFormat", &["text", "json"]);
+    s.one_of("toolCallExpansion", &["collapsed", "activity", "detailed"]);
+    s.one_of(
+        "diffLayoutPreference",
+        &["dynamic", "inline", "side-by-side"],
+    );
+    s.one_of("diffViewMode", &["single", "stacked"]);
+
+    // Number fields, including memory limits
+    for &(key, _, _) in SETTINGS_NUMBER_RANGES {
+        s.number(key);
+    }
+
+    // Array fields
+    for key in [
+        "approvedDirectories",
+        "securityScopedBookmarks",
+        "pinnedDirectories",
+    ] {
+        s.field(key, |value| Ok(normalize_string_array(value)));
+    }
+    s.field("corsAllowedOrigins", |value| {
+        let arr = value
+            .as_array()
+            .ok_or_else(|| "corsAllowedOrigins must be an array of origins".to_string())?;
+        let mut origins: Vec<String> = vec![];
+        for value in arr.iter().filter_map(|v| v.as_str()) {
+            let origin = value.trim().trim_end_matches('/');
+            let valid = url::Url::parse(origin)
+                .map(|url| url.has_host())
+                .unwrap_or(false);
+            if valid && !origins.iter().any(|existing| existing == origin) {
+                origins.push(origin.to_string());
             }
         }
-        if let Some(Value::Number(n)) = obj.get("cornerRadius") {
-            let parsed = n
-                .as_u64()
-                .or_else(|| n.as_i64().and_then(|v| if v >= 0 { Some(v as u64) } else { None }))
-                .or_else(|| n.as_f64().map(|v| v.round().max(0.0) as u64));
-            if let Some(value) = parsed {
-                let clamped = value.max(0).min(32);
-                result_obj.insert("cornerRadius".to_string(), json!(clamped));
+        Ok(json!(origins))
+    });
+    s.field("recentDirectories", |value| {
+        sanitize_recent_directories(value)
+            .ok_or_else(|| "recentDirectories has no valid entries".to_string())
+    });
+    s.field("badgeTriggers", |value| {
+        let arr = value.as_array().ok_or_else(|| {
+            format!(
+                "badgeTriggers must be an array of [{}]",
+                BADGE_TRIGGERS.join(", ")
+            )
+        })?;
+        let mut triggers: Vec<&str> = vec![];
+        for value in arr.iter().filter_map(|v| v.as_str()).map(str::trim) {
+            if BADGE_TRIGGERS.contains(&value) && !triggers.contains(&value) {
+                triggers.push(value);
             }
         }
-        if let Some(Value::Number(n)) = obj.get("inputBarOffset") {
-            let parsed = n
-                .as_u64()
-                .or_else(|| n.as_i64().and_then(|v| if v >= 0 { Some(v as u64) } else { None }))
-                .or_else(|| n.as_f64().map(|v| v.round().max(0.0) as u64));
-            if let Some(value) = parsed {
-                let clamped = value.max(0).min(100);
-                result_obj.insert("inputBarOffset".to_string(), json!(clamped));
+        Ok(json!(triggers))
+    });
+
+    // Typography sizes object (partial)
+    s.field("typographySizes", |value| {
+        sanitize_typography_sizes_partial(value)
+            .ok_or_else(|| "typographySizes has no valid entries".to_string())
+    });
+
+    // Skill catalogs (array of objects)
+    s.field("skillCatalogs", |value| {
+        let no_entries = || "skillCatalogs has no valid entries".to_string();
+        let arr = value.as_array().ok_or_else(no_entries)?;
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut catalogs: Vec<Value> = vec![];
+
+        for entry in arr {
+            let Some(obj) = entry.as_object() else {
+                continue;
+            };
+
+            let id = obj.get("id").and_then(|v| v.as_str()).unwrap_or("").trim();
+            let label = obj
+                .get("label")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .trim();
+            let source = obj
+                .get("source")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .trim();
+            let subpath = obj
+                .get("subpath")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .trim();
+            let git_identity_id = obj
+                .get("gitIdentityId")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .trim();
+
+            if id.is_empty() || label.is_empty() || source.is_empty() {
+                continue;
             }
-        }
 
-        // Memory limit fields
-        if let Some(Value::Number(n)) = obj.get("memoryLimitHistorical") {
-            let parsed = n
-                .as_u64()
-                .or_else(|| {
-                    n.as_i64()
-                        .and_then(|v| if v >= 0 { Some(v as u64) } else { None })
-                })
-                .or_else(|| n.as_f64().map(|v| v.round().max(0.0) as u64));
-            if let Some(value) = parsed {
-                let clamped = value.max(10).min(500);
-                result_obj.insert("memoryLimitHistorical".to_string(), json!(clamped));
+            if seen.contains(id) {
+                continue;
             }
-        }
-        if let Some(Value::Number(n)) = obj.get("memoryLimitViewport") {
-            let parsed = n
-                .as_u64()
-                .or_else(|| {
-                    n.as_i64()
-                        .and_then(|v| if v >= 0 { Some(v as u64) } else { None })
-                })
-                .or_else(|| n.as_f64().map(|v| v.round().max(0.0) as u64));
-            if let Some(value) = parsed {
-                let clamped = value.max(20).min(500);
-                result_obj.insert("memoryLimitViewport".to_string(), json!(clamped));
+            seen.insert(id.to_string());
+
+            let mut catalog = serde_json::Map::new();
+            catalog.insert("id".to_string(), json!(id));
+            catalog.insert("label".to_string(), json!(label));
+            catalog.insert("source".to_string(), json!(source));
+            if !subpath.is_empty() {
+                catalog.insert("subpath".to_string(), json!(subpath));
             }
-        }
-        if let Some(Value::Number(n)) = obj.get("memoryLimitActiveSession") {
-            let parsed = n
-                .as_u64()
-                .or_else(|| {
-                    n.as_i64()
-                        .and_then(|v| if v >= 0 { Some(v as u64) } else { None })
-                })
-                .or_else(|| n.as_f64().map(|v| v.round().max(0.0) as u64));
-            if let Some(value) = parsed {
-                let clamped = value.max(30).min(1000);
-                result_obj.insert("memoryLimitActiveSession".to_string(), json!(clamped));
+            if !git_identity_id.is_empty() {
+                catalog.insert("gitIdentityId".to_string(), json!(git_identity_id));
             }
-        }
 
-        if let Some(Value::String(s)) = obj.get("diffLayoutPreference") {
-            let trimmed = s.trim();
-            if trimmed == "dynamic" || trimmed == "inline" || trimmed == "side-by-side" {
-                result_obj.insert("diffLayoutPreference".to_string(), json!(trimmed));
-            }
-        }
-        if let Some(Value::String(s)) = obj.get("diffViewMode") {
-            let trimmed = s.trim();
-            if trimmed == "single" || trimmed == "stacked" {
-                result_obj.insert("diffViewMode".to_string(), json!(trimmed));
-            }
-        }
-        if let Some(Value::Bool(b)) = obj.get("directoryShowHidden") {
-            result_obj.insert("directoryShowHidden".to_string(), json!(b));
-        }
-        if let Some(Value::Bool(b)) = obj.get("filesViewShowGitignored") {
-            result_obj.insert("filesViewShowGitignored".to_string(), json!(b));
+            catalogs.push(Value::Object(catalog));
         }
 
-        // Array fields
-        if let Some(arr) = obj.get("approvedDirectories") {
-            result_obj.insert(
-                "approvedDirectories".to_string(),
-                normalize_string_array(arr),
-            );
-        }
-        if let Some(arr) = obj.get("securityScopedBookmarks") {
-            result_obj.insert(
-                "securityScopedBookmarks".to_string(),
-                normalize_string_array(arr),
-            );
-        }
-        if let Some(arr) = obj.get("pinnedDirectories") {
-            result_obj.insert("pinnedDirectories".to_string(), normalize_string_array(arr));
+        if catalogs.is_empty() {
+            Err(no_entries())
+        } else {
+            Ok(Value::Array(catalogs))
         }
+    });
 
-        // Typography sizes object (partial)
-        if let Some(typo) = obj.get("typographySizes") {
-            if let Some(sanitized) = sanitize_typography_sizes_partial(typo) {
-                result_obj.insert("typographySizes".to_string(), sanitized);
-            }
-        }
+    // Written by `migrate_settings_schema`; clients may echo it back unchanged.
+    s.get("schemaVersion");
 
-        // Skill catalogs (array of objects)
-        if let Some(Value::Array(arr)) = obj.get("skillCatalogs") {
-            let mut seen: HashSet<String> = HashSet::new();
-            let mut catalogs: Vec<Value> = vec![];
-
-            for entry in arr {
-                let Some(obj) = entry.as_object() else {
-                    continue;
-                };
-
-                let id = obj.get("id").and_then(|v| v.as_str()).unwrap_or("").trim();
-                let label = obj
-                    .get("label")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .trim();
-                let source = obj
-                    .get("source")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .trim();
-                let subpath = obj
-                    .get("subpath")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .trim();
-                let git_identity_id = obj
-                    .get("gitIdentityId")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .trim();
-
-                if id.is_empty() || label.is_empty() || source.is_empty() {
-                    continue;
-                }
+    s.finish()
+}
 
-                if seen.contains(id) {
-                    continue;
-                }
-                seen.insert(id.to_string());
-
-                let mut catalog = serde_json::Map::new();
-                catalog.insert("id".to_string(), json!(id));
-                catalog.insert("label".to_string(), json!(label));
-                catalog.insert("source".to_string(), json!(source));
-                if !subpath.is_empty() {
-                    catalog.insert("subpath".to_string(), json!(subpath));
-                }
-                if !git_identity_id.is_empty() {
-                    catalog.insert("gitIdentityId".to_string(), json!(git_identity_id));
-                }
+/// Version of the persisted settings document. Bump it together with a new entry in
+/// `SETTINGS_MIGRATIONS`.
+const SETTINGS_SCHEMA_VERSION: u64 = 1;
 
-                catalogs.push(Value::Object(catalog));
-            }
+type SettingsMigration = fn(&mut Map<String, Value>);
+
+/// Step `n` upgrades a document from `schemaVersion` n to n + 1. Documents written before
+/// versioning existed have no `schemaVersion` and start at 0.
+const SETTINGS_MIGRATIONS: [SettingsMigration; SETTINGS_SCHEMA_VERSION as usize] =
+    [migrate_settings_v0_theme];
 
-            if !catalogs.is_empty() {
-                result_obj.insert("skillCatalogs".to_string(), Value::Array(catalogs));
+/// Insert `value` under `key` unless the document already has a value there.
+fn backfill_setting(obj: &mut Map<String, Value>, key: &str, value: Value) {
+    if obj.get(key).unwrap_or(&Value::Null).is_null() {
+        obj.insert(key.to_string(), value);
+    }
+}
+
+/// v0 -> v1: the single `theme` string became `themeId`/`themeVariant`/`useSystemTheme`.
+/// Old values were "system", "light", "dark" or a full theme id such as "flexoki-dark".
+fn migrate_settings_v0_theme(obj: &mut Map<String, Value>) {
+    let Some(theme) = obj.remove("theme") else {
+        return;
+    };
+    let Some(theme) = theme
+        .as_str()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    else {
+        return;
+    };
+
+    match theme {
+        "system" => backfill_setting(obj, "useSystemTheme", json!(true)),
+        "light" | "dark" => {
+            backfill_setting(obj, "themeVariant", json!(theme));
+            backfill_setting(obj, "useSystemTheme", json!(false));
+        }
+        id => {
+            backfill_setting(obj, "themeId", json!(id));
+            if let Some(variant) = ["light", "dark"]
+                .into_iter()
+                .find(|variant| id.ends_with(&format!("-{variant}")))
+            {
+                backfill_setting(obj, "themeVariant", json!(variant));
             }
+            backfill_setting(obj, "useSystemTheme", json!(false));
         }
     }
+}
 
-    result
+/// Apply every pending migration step in order and record the resulting `schemaVersion`.
+/// Documents from a newer build are left untouched.
+fn migrate_settings_schema(settings: &mut Value) {
+    if !settings.is_object() {
+        *settings = json!({});
+    }
+    let obj = settings.as_object_mut().unwrap();
+
+    let version = obj
+        .get("schemaVersion")
+        .and_then(|value| value.as_u64())
+        .unwrap_or(0);
+    if version > SETTINGS_SCHEMA_VERSION {
+        log::warn!(
+            "[desktop:settings] settings schemaVersion {} is newer than supported {}, skipping migrations",
+            version,
+            SETTINGS_SCHEMA_VERSION
+        );
+        return;
+    }
+
+    for (step, migrate) in SETTINGS_MIGRATIONS
+        .iter()
+        .enumerate()
+        .skip(version as usize)
+    {
+        migrate(obj);
+        log::info!(
+            "[desktop:settings] migrated settings to schemaVersion {}",
+            step + 1
+        );
+    }
+    obj.insert("schemaVersion".to_string(), json!(SETTINGS_SCHEMA_VERSION));
 }
 
 fn migrate_legacy_project_settings(settings: &mut Value) {
@@ -762,6 +1524,11 @@ fn format_settings_response(settings: &Value) -> Value {
             "pinnedDirectories".to_string(),
             normalize_string_array(settings.get("pinnedDirectories").unwrap_or(&json!([]))),
         );
+        obj.insert(
+            "recentDirectories".to_string(),
+            sanitize_recent_directories(settings.get("recentDirectories").unwrap_or(&json!([])))
+                .unwrap_or_else(|| json!([])),
+        );
 
         // Typography sizes
         if let Some(sanitized_typo) = sanitize_typography_sizes_partial(
@@ -842,3 +1609,53 @@ fn extract_string_vec(value: &Value) -> Vec<String> {
         vec![]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrate_settings_schema_upgrades_unversioned_documents() {
+        let mut settings = json!({ "theme": "flexoki-dark", "themeVariant": null });
+        migrate_settings_schema(&mut settings);
+        assert_eq!(
+            settings,
+            json!({
+                "themeId": "flexoki-dark",
+                "themeVariant": "dark",
+                "useSystemTheme": false,
+                "schemaVersion": SETTINGS_SCHEMA_VERSION,
+            })
+        );
+
+        let mut settings = json!({ "theme": "system", "useSystemTheme": false });
+        migrate_settings_schema(&mut settings);
+        assert_eq!(
+            settings,
+            json!({ "useSystemTheme": false, "schemaVersion": SETTINGS_SCHEMA_VERSION })
+        );
+    }
+
+    #[test]
+    fn migrate_settings_schema_is_idempotent() {
+        let mut settings = json!({ "theme": "light", "lastDirectory": "/tmp" });
+        migrate_settings_schema(&mut settings);
+        let migrated = settings.clone();
+        migrate_settings_schema(&mut settings);
+        assert_eq!(settings, migrated);
+
+        // A current document is left alone even if it has a key an old step would rewrite.
+        let mut current = json!({ "schemaVersion": SETTINGS_SCHEMA_VERSION, "theme": "dark" });
+        let expected = current.clone();
+        migrate_settings_schema(&mut current);
+        assert_eq!(current, expected);
+    }
+
+    #[test]
+    fn migrate_settings_schema_leaves_newer_documents_untouched() {
+        let mut settings = json!({ "schemaVersion": SETTINGS_SCHEMA_VERSION + 1, "theme": "dark" });
+        let expected = settings.clone();
+        migrate_settings_schema(&mut settings);
+        assert_eq!(settings, expected);
+    }
+}
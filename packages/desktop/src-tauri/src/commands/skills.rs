@@ -0,0 +1,132 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+use serde_json::json;
+use tauri::State;
+
+use crate::commands::error::{CommandError, ErrorCode};
+use crate::opencode_config::{self, SkillConfigSources};
+use crate::path_utils::expand_tilde_path;
+use crate::session_activity::resolve_project_directory_from_settings;
+use crate::skills_catalog::{self, SkillsCatalogItem, SkillsRepoError};
+use crate::DesktopRuntime;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledCatalogSkill {
+    pub name: String,
+    pub scope: String,
+    /// Where OpenCode now finds the skill, as reported by the skill config endpoints.
+    pub sources: SkillConfigSources,
+}
+
+fn skills_error(err: SkillsRepoError) -> CommandError {
+    let code = match err.kind.as_str() {
+        "authRequired" => ErrorCode::Unauthorized,
+        "notFound" => ErrorCode::NotFound,
+        "conflicts" => ErrorCode::Conflict,
+        "invalidSource" => ErrorCode::InvalidArgument,
+        "networkError" => ErrorCode::Network,
+        _ => ErrorCode::Internal,
+    };
+    let message = err.message.clone();
+    // Keeps the identity list for auth errors and the conflicting skills for installs.
+    CommandError::new(code, message).with_details(json!(err))
+}
+
+/// Project whose installed skills are compared against the catalog: `directory` if given,
+/// otherwise the active project.
+async fn resolve_skills_directory(
+    directory: Option<String>,
+    runtime: &DesktopRuntime,
+) -> Option<PathBuf> {
+    match directory.filter(|directory| !directory.trim().is_empty()) {
+        Some(directory) => Some(expand_tilde_path(&directory)),
+        None => resolve_project_directory_from_settings(runtime).await,
+    }
+}
+
+/// Skills offered by the catalog `catalogId`. Git catalogs are cloned with the catalog's git
+/// identity and cached for a while; `refresh` re-fetches the source.
+#[tauri::command]
+pub async fn list_skill_catalog_entries(
+    catalog_id: String,
+    directory: Option<String>,
+    refresh: Option<bool>,
+    state: State<'_, DesktopRuntime>,
+) -> Result<Vec<SkillsCatalogItem>, CommandError> {
+    let catalog_id = catalog_id.trim();
+    if catalog_id.is_empty() {
+        return Err(CommandError::invalid_argument("catalogId is required"));
+    }
+
+    let working_directory = resolve_skills_directory(directory, &state).await;
+    skills_catalog::list_catalog_entries(
+        working_directory.as_deref(),
+        catalog_id,
+        refresh.unwrap_or(false),
+    )
+    .await
+    .map_err(skills_error)
+}
+
+/// Install `skillName` from the catalog `catalogId` into the user's OpenCode config, or the
+/// project's with `scope: "project"`. Refuses to replace an existing skill unless `force`
+/// is set. OpenCode is restarted shortly afterwards to load it.
+#[tauri::command]
+pub async fn install_skill(
+    catalog_id: String,
+    skill_name: String,
+    scope: Option<String>,
+    directory: Option<String>,
+    force: Option<bool>,
+    state: State<'_, DesktopRuntime>,
+) -> Result<InstalledCatalogSkill, CommandError> {
+    let catalog_id = catalog_id.trim();
+    let skill_name = skill_name.trim();
+    if catalog_id.is_empty() || skill_name.is_empty() {
+        return Err(CommandError::invalid_argument(
+            "catalogId and skillName are required",
+        ));
+    }
+    let scope = scope.as_deref().map(str::trim).unwrap_or("user");
+    if scope != "user" && scope != "project" {
+        return Err(CommandError::invalid_argument(format!(
+            "Invalid scope: {}",
+            scope
+        )));
+    }
+
+    let project_directory = resolve_skills_directory(directory, &state).await;
+    let working_directory = match (scope, project_directory.clone()) {
+        ("project", Some(directory)) => directory,
+        ("project", None) => {
+            return Err(CommandError::invalid_argument(
+                "A project directory is required for project scope",
+            ))
+        }
+        _ => dirs::home_dir().unwrap_or_else(|| PathBuf::from("/")),
+    };
+
+    let installed = skills_catalog::install_catalog_skill(
+        &working_directory,
+        catalog_id,
+        skill_name,
+        scope,
+        force.unwrap_or(false),
+    )
+    .await
+    .map_err(skills_error)?;
+
+    state.schedule_opencode_restart(&format!("installing skill {}", installed.skill_name));
+
+    let sources =
+        opencode_config::get_skill_sources(&installed.skill_name, project_directory.as_deref())
+            .await
+            .map_err(|e| format!("Skill installed, but reading it back failed: {}", e))?;
+    Ok(InstalledCatalogSkill {
+        name: installed.skill_name,
+        scope: installed.scope,
+        sources,
+    })
+}
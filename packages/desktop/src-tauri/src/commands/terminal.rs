@@ -13,7 +13,36 @@ use std::{
 };
 use tauri::{Emitter, State, Window};
 
+#[cfg(not(windows))]
 const DEFAULT_SHELL: &str = "/bin/zsh";
+#[cfg(not(windows))]
+const COMMON_SHELL_PATHS: &[&str] = &[
+    "/bin/zsh",
+    "/bin/bash",
+    "/bin/sh",
+    "/bin/dash",
+    "/bin/ksh",
+    "/usr/bin/zsh",
+    "/usr/bin/bash",
+    "/usr/bin/fish",
+    "/usr/local/bin/bash",
+    "/usr/local/bin/zsh",
+    "/usr/local/bin/fish",
+    "/opt/homebrew/bin/bash",
+    "/opt/homebrew/bin/zsh",
+    "/opt/homebrew/bin/fish",
+];
+// Shells looked up on PATH in addition to the fixed locations.
+#[cfg(not(windows))]
+const PATH_SHELLS: &[&str] = &["fish", "nu", "pwsh", "xonsh", "elvish"];
+#[cfg(windows)]
+const PATH_SHELLS: &[&str] = &[
+    "cmd.exe",
+    "powershell.exe",
+    "pwsh.exe",
+    "bash.exe",
+    "wsl.exe",
+];
 const DEFAULT_TERM: &str = "xterm-256color";
 const DEFAULT_COLORTERM: &str = "truecolor";
 const DEFAULT_LOCALE: &str = "en_US.UTF-8";
@@ -54,6 +83,24 @@ pub struct CreateTerminalResponse {
     pub session_id: String,
 }
 
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableShell {
+    pub name: String,
+    pub path: String,
+    /// False when the file exists but lacks execute permission.
+    pub executable: bool,
+    pub is_default: bool,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableShellsResponse {
+    pub shells: Vec<AvailableShell>,
+    /// Shell new terminals start with: `$SHELL`, or the platform default.
+    pub default_shell: String,
+}
+
 #[tauri::command]
 pub async fn create_terminal_session(
     payload: CreateTerminalPayload,
@@ -436,11 +483,109 @@ fn spawn_exit_watcher(
     });
 }
 
+#[cfg(not(windows))]
+fn platform_default_shell() -> String {
+    DEFAULT_SHELL.to_string()
+}
+
+#[cfg(windows)]
+fn platform_default_shell() -> String {
+    env::var("ComSpec")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| "cmd.exe".to_string())
+}
+
 fn resolve_shell() -> String {
     env::var("SHELL")
         .ok()
         .filter(|value| !value.trim().is_empty())
-        .unwrap_or_else(|| DEFAULT_SHELL.to_string())
+        .unwrap_or_else(platform_default_shell)
+}
+
+#[cfg(unix)]
+fn is_executable(metadata: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.is_file() && metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(metadata: &std::fs::Metadata) -> bool {
+    metadata.is_file()
+}
+
+fn find_on_path(name: &str) -> Option<PathBuf> {
+    let path = env::var_os("PATH")?;
+    env::split_paths(&path)
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file())
+}
+
+fn shell_candidates() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    #[cfg(not(windows))]
+    {
+        if let Ok(contents) = std::fs::read_to_string("/etc/shells") {
+            candidates.extend(
+                contents
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| line.starts_with('/'))
+                    .map(PathBuf::from),
+            );
+        }
+        candidates.extend(COMMON_SHELL_PATHS.iter().map(PathBuf::from));
+    }
+    #[cfg(windows)]
+    {
+        candidates.push(PathBuf::from(platform_default_shell()));
+        if let Some(program_files) = env::var_os("ProgramFiles") {
+            candidates.push(PathBuf::from(program_files).join(r"Git\bin\bash.exe"));
+        }
+    }
+    candidates.extend(PATH_SHELLS.iter().filter_map(|name| find_on_path(name)));
+    candidates
+}
+
+/// Shells installed on this machine, for the terminal shell picker. Paths that resolve to the
+/// same binary (e.g. `/bin/bash` and `/usr/bin/bash` on merged-/usr systems) are listed once.
+#[tauri::command]
+pub async fn list_available_shells() -> Result<AvailableShellsResponse, String> {
+    let default_shell = resolve_shell();
+    let default_resolved = find_on_path(&default_shell)
+        .unwrap_or_else(|| PathBuf::from(&default_shell))
+        .canonicalize()
+        .ok();
+
+    let mut seen = std::collections::HashSet::new();
+    let mut shells = Vec::new();
+    for candidate in shell_candidates() {
+        let Ok(metadata) = std::fs::metadata(&candidate) else {
+            continue;
+        };
+        let resolved = candidate
+            .canonicalize()
+            .unwrap_or_else(|_| candidate.clone());
+        if !seen.insert(resolved.clone()) {
+            continue;
+        }
+        let name = candidate
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+            .to_string();
+        shells.push(AvailableShell {
+            name,
+            path: candidate.to_string_lossy().to_string(),
+            executable: is_executable(&metadata),
+            is_default: default_resolved.as_ref() == Some(&resolved),
+        });
+    }
+
+    Ok(AvailableShellsResponse {
+        shells,
+        default_shell,
+    })
 }
 
 fn shell_accepts_login_flag(shell_path: &str) -> bool {
@@ -0,0 +1,160 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use log::{info, warn};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+const UPDATE_PROGRESS_EVENT: &str = "openchamber:update-progress";
+// Chunks arrive every few KB; emit at most this often while downloading.
+const UPDATE_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+static INSTALL_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCheckResult {
+    pub available: bool,
+    pub current_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Release notes from the update manifest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// RFC 3339 publish date from the update manifest.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pub_date: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct UpdateProgressPayload {
+    /// "downloading", "installing" or "finished".
+    phase: &'static str,
+    downloaded: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total: Option<u64>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInstallResult {
+    pub installed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// The new version only takes effect after the app restarts.
+    pub restart_required: bool,
+}
+
+async fn fetch_update(app: &AppHandle) -> Result<Option<Update>, String> {
+    let updater = app
+        .updater()
+        .map_err(|e| format!("Updater is unavailable: {}", e))?;
+    updater
+        .check()
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))
+}
+
+fn emit_progress(app: &AppHandle, phase: &'static str, downloaded: u64, total: Option<u64>) {
+    let payload = UpdateProgressPayload {
+        phase,
+        downloaded,
+        total,
+    };
+    let _ = app.emit(UPDATE_PROGRESS_EVENT, payload);
+}
+
+/// Query the update endpoint without downloading anything.
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> Result<UpdateCheckResult, String> {
+    let current_version = app.package_info().version.to_string();
+    let Some(update) = fetch_update(&app).await? else {
+        return Ok(UpdateCheckResult {
+            available: false,
+            current_version,
+            version: None,
+            notes: None,
+            pub_date: None,
+        });
+    };
+
+    let pub_date = update
+        .raw_json
+        .get("pub_date")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    Ok(UpdateCheckResult {
+        available: true,
+        current_version,
+        version: Some(update.version),
+        notes: update.body.filter(|notes| !notes.trim().is_empty()),
+        pub_date,
+    })
+}
+
+/// Download and install the latest update, emitting `openchamber:update-progress` along the
+/// way. The app is not restarted; the frontend decides when to relaunch.
+#[tauri::command]
+pub async fn install_update(app: AppHandle) -> Result<UpdateInstallResult, String> {
+    if INSTALL_IN_PROGRESS.swap(true, Ordering::SeqCst) {
+        return Err("An update is already being installed".to_string());
+    }
+    let result = download_and_install(&app).await;
+    INSTALL_IN_PROGRESS.store(false, Ordering::SeqCst);
+    result
+}
+
+async fn download_and_install(app: &AppHandle) -> Result<UpdateInstallResult, String> {
+    let Some(update) = fetch_update(app).await? else {
+        return Ok(UpdateInstallResult {
+            installed: false,
+            version: None,
+            restart_required: false,
+        });
+    };
+
+    info!("[desktop:updater] installing update {}", update.version);
+    let mut downloaded: u64 = 0;
+    let mut total_size: Option<u64> = None;
+    let mut last_emit: Option<Instant> = None;
+    let bytes = update
+        .download(
+            |chunk, total| {
+                downloaded += chunk as u64;
+                total_size = total;
+                let due = match last_emit {
+                    Some(at) => at.elapsed() >= UPDATE_PROGRESS_INTERVAL,
+                    None => true,
+                };
+                if due {
+                    last_emit = Some(Instant::now());
+                    emit_progress(app, "downloading", downloaded, total);
+                }
+            },
+            || {},
+        )
+        .await
+        .map_err(|e| {
+            warn!("[desktop:updater] failed to download update: {}", e);
+            format!("Failed to download update: {}", e)
+        })?;
+
+    emit_progress(app, "installing", downloaded, total_size);
+    update.install(bytes).map_err(|e| {
+        warn!("[desktop:updater] failed to install update: {}", e);
+        format!("Failed to install update: {}", e)
+    })?;
+
+    emit_progress(app, "finished", downloaded, total_size);
+    info!("[desktop:updater] update {} installed", update.version);
+    Ok(UpdateInstallResult {
+        installed: true,
+        version: Some(update.version),
+        restart_required: true,
+    })
+}
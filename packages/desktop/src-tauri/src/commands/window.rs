@@ -0,0 +1,471 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "macos")]
+use serde_json::json;
+use serde_json::Value;
+use tauri::{AppHandle, Manager, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder};
+
+use crate::commands::error::CommandError;
+use crate::commands::error::ErrorCode;
+use crate::commands::files::resolve_workspace_creatable_path;
+use crate::path_utils::expand_tilde_path;
+use crate::session_activity::resolve_project_directory_from_settings;
+use crate::window_state::{
+    self, load_project_window_state, WindowState, WindowStateManager, UI_STATE_MAX_BYTES,
+};
+use crate::{DesktopRuntime, SettingsStore};
+
+const PROJECT_WINDOW_LABEL_PREFIX: &str = "project-";
+const INSTANCE_DISPOSE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Project directory of each open project window, keyed by window label.
+static PROJECT_WINDOWS: Lazy<Mutex<HashMap<String, String>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectWindowInfo {
+    pub label: String,
+    pub project_id: String,
+    pub directory: String,
+    /// The project already had a window, which was focused instead of opening another.
+    pub already_open: bool,
+}
+
+/// Stored window geometry plus the frontend's UI state blob from the last session.
+#[tauri::command]
+pub async fn get_window_state(
+    manager: State<'_, WindowStateManager>,
+) -> Result<WindowState, String> {
+    Ok(manager.snapshot())
+}
+
+/// Replace the UI state blob. It is written to disk with the window geometry on close;
+/// `null` clears it.
+#[tauri::command]
+pub async fn save_ui_state(
+    value: Value,
+    manager: State<'_, WindowStateManager>,
+) -> Result<(), String> {
+    if value.is_null() {
+        manager.set_ui_state(None);
+        return Ok(());
+    }
+
+    let size = serde_json::to_vec(&value).map_err(|e| e.to_string())?.len();
+    if size > UI_STATE_MAX_BYTES {
+        return Err(format!(
+            "UI state is too large ({} bytes, limit {})",
+            size, UI_STATE_MAX_BYTES
+        ));
+    }
+
+    manager.set_ui_state(Some(value));
+    Ok(())
+}
+
+pub(crate) fn is_project_window(label: &str) -> bool {
+    label.starts_with(PROJECT_WINDOW_LABEL_PREFIX)
+}
+
+/// Open `project_id` in its own window. No OpenCode process is started for it: all windows
+/// share the one server, which keeps a per-directory instance for each project. The window
+/// stays on its project through the `projectId` query parameter.
+#[tauri::command]
+pub async fn open_project_window(
+    project_id: String,
+    app: AppHandle,
+    runtime: State<'_, DesktopRuntime>,
+) -> Result<ProjectWindowInfo, String> {
+    let project_id = project_id.trim().to_string();
+    // The id becomes part of the window label, which only allows a restricted character set.
+    if project_id.is_empty()
+        || !project_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err("A valid project id is required".to_string());
+    }
+
+    let settings = runtime.settings().load().await.unwrap_or(Value::Null);
+    let project = settings
+        .get("projects")
+        .and_then(Value::as_array)
+        .and_then(|projects| {
+            projects
+                .iter()
+                .find(|entry| entry.get("id").and_then(Value::as_str) == Some(&project_id))
+        })
+        .ok_or_else(|| "Project not found".to_string())?;
+    let directory = project
+        .get("path")
+        .and_then(Value::as_str)
+        .map(expand_tilde_path)
+        .ok_or_else(|| "Project has no directory".to_string())?;
+    if !directory.is_dir() {
+        return Err(format!(
+            "Project directory {} no longer exists",
+            directory.display()
+        ));
+    }
+    let directory = directory.to_string_lossy().to_string();
+
+    let label = format!("{PROJECT_WINDOW_LABEL_PREFIX}{project_id}");
+    if let Some(window) = app.get_webview_window(&label) {
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+        return Ok(ProjectWindowInfo {
+            label,
+            project_id,
+            directory,
+            already_open: true,
+        });
+    }
+
+    let name = project
+        .get("label")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            std::path::Path::new(&directory)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| directory.clone())
+        });
+    let saved = load_project_window_state(&label).await.ok().flatten();
+    let size = saved.clone().unwrap_or_default();
+
+    let url = WebviewUrl::App(format!("index.html?projectId={project_id}").into());
+    let builder = WebviewWindowBuilder::new(&app, &label, url)
+        .title(format!("OpenChamber — {name}"))
+        .inner_size(size.width, size.height)
+        .resizable(true)
+        .visible(false);
+    #[cfg(target_os = "macos")]
+    let builder = builder
+        .hidden_title(true)
+        .title_bar_style(tauri::TitleBarStyle::Overlay)
+        .traffic_light_position(tauri::LogicalPosition::new(17.0, 26.0));
+    let window = builder
+        .build()
+        .map_err(|e| format!("Failed to open project window: {}", e))?;
+
+    if let Some(saved) = saved.as_ref() {
+        let _ = window_state::apply_window_state(&window, saved);
+    }
+    let _ = window.show();
+    let _ = window.set_focus();
+
+    PROJECT_WINDOWS
+        .lock()
+        .insert(label.clone(), directory.clone());
+    info!("[desktop:window] Opened {label} for {directory}");
+    Ok(ProjectWindowInfo {
+        label,
+        project_id,
+        directory,
+        already_open: false,
+    })
+}
+
+/// Persist a closing project window's geometry and, unless another window still shows the
+/// same project, dispose of its OpenCode instance.
+pub(crate) fn handle_project_window_closed(window: &tauri::Window) {
+    let label = window.label().to_string();
+    let geometry = window_state::capture_window_state(window, WindowState::default());
+    let Some(directory) = PROJECT_WINDOWS.lock().remove(&label) else {
+        return;
+    };
+    let runtime = window.state::<DesktopRuntime>().inner().clone();
+
+    tauri::async_runtime::spawn(async move {
+        if let Err(err) = window_state::save_project_window_state(&label, &geometry).await {
+            warn!("[desktop:window] Failed to persist state of {label}: {err}");
+        }
+
+        let main_directory = resolve_project_directory_from_settings(&runtime)
+            .await
+            .map(|path| path.to_string_lossy().to_string());
+        if main_directory.as_deref() == Some(directory.as_str()) {
+            return;
+        }
+        dispose_opencode_instance(&runtime, &directory).await;
+    });
+}
+
+async fn dispose_opencode_instance(runtime: &DesktopRuntime, directory: &str) {
+    let opencode = runtime.opencode_manager();
+    let Some(port) = opencode.current_port() else {
+        return;
+    };
+    let url = format!(
+        "http://127.0.0.1:{port}{}/instance/dispose",
+        opencode.api_prefix()
+    );
+    let client = match reqwest::Client::builder()
+        .timeout(INSTANCE_DISPOSE_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(err) => {
+            warn!("[desktop:window] Failed to build client for instance dispose: {err}");
+            return;
+        }
+    };
+    match client
+        .post(&url)
+        .query(&[("directory", directory)])
+        .send()
+        .await
+    {
+        Ok(response) if response.status().is_success() => {
+            info!("[desktop:window] Disposed OpenCode instance for {directory}")
+        }
+        Ok(response) => warn!(
+            "[desktop:window] Disposing OpenCode instance for {directory} returned {}",
+            response.status()
+        ),
+        Err(err) => {
+            warn!("[desktop:window] Failed to dispose OpenCode instance for {directory}: {err}")
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowScreenshot {
+    pub path: String,
+}
+
+/// Resolve the destination of a screenshot: an absolute `.png` path for a new file in an
+/// existing directory inside the workspace or the OpenChamber config directory.
+async fn validate_screenshot_path(
+    dest_path: &str,
+    settings: &SettingsStore,
+) -> Result<PathBuf, CommandError> {
+    if dest_path.trim().is_empty() {
+        return Err(CommandError::invalid_argument(
+            "Screenshot path is required",
+        ));
+    }
+    let path = expand_tilde_path(dest_path);
+    if !path.is_absolute() {
+        return Err(CommandError::invalid_argument(
+            "Screenshot path must be absolute",
+        ));
+    }
+    let is_png = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("png"));
+    if !is_png {
+        return Err(CommandError::invalid_argument(
+            "Screenshot path must end in .png",
+        ));
+    }
+    let path = resolve_workspace_creatable_path(&path.to_string_lossy(), settings).await?;
+    let (Some(parent), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return Err(CommandError::invalid_argument(
+            "Screenshot path must end in .png",
+        ));
+    };
+
+    let parent = tokio::fs::canonicalize(parent).await.map_err(|_| {
+        CommandError::not_found(format!("Directory does not exist: {}", parent.display()))
+    })?;
+    if !parent.is_dir() {
+        return Err(CommandError::invalid_argument(format!(
+            "Not a directory: {}",
+            parent.display()
+        )));
+    }
+    let path = parent.join(file_name);
+    if tokio::fs::symlink_metadata(&path).await.is_ok() {
+        return Err(CommandError::new(
+            ErrorCode::Conflict,
+            format!("{} already exists", path.display()),
+        ));
+    }
+    Ok(path)
+}
+
+/// Translate a rect in webview coordinates (CSS pixels) to screen points, clipped to the
+/// window's content area.
+fn capture_rect_on_screen(
+    window: &WebviewWindow,
+    rect: CaptureRect,
+) -> Result<CaptureRect, CommandError> {
+    let values = [rect.x, rect.y, rect.width, rect.height];
+    if values.iter().any(|value| !value.is_finite()) || rect.width <= 0.0 || rect.height <= 0.0 {
+        return Err(CommandError::invalid_argument(
+            "Capture rect must have a positive width and height",
+        ));
+    }
+
+    let scale = window.scale_factor().map_err(|e| e.to_string())?;
+    let origin = window
+        .inner_position()
+        .map_err(|e| e.to_string())?
+        .to_logical::<f64>(scale);
+    let size = window
+        .inner_size()
+        .map_err(|e| e.to_string())?
+        .to_logical::<f64>(scale);
+
+    let left = rect.x.max(0.0);
+    let top = rect.y.max(0.0);
+    let right = (rect.x + rect.width).min(size.width);
+    let bottom = (rect.y + rect.height).min(size.height);
+    if right - left < 1.0 || bottom - top < 1.0 {
+        return Err(CommandError::invalid_argument(
+            "Capture rect is outside the window",
+        ));
+    }
+
+    Ok(CaptureRect {
+        x: origin.x + left,
+        y: origin.y + top,
+        width: right - left,
+        height: bottom - top,
+    })
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGPreflightScreenCaptureAccess() -> bool;
+    fn CGRequestScreenCaptureAccess() -> bool;
+}
+
+#[cfg(target_os = "macos")]
+fn ensure_screen_capture_access() -> Result<(), CommandError> {
+    // SAFETY: argument-less CoreGraphics queries, available since macOS 10.15.
+    if unsafe { CGPreflightScreenCaptureAccess() } {
+        return Ok(());
+    }
+    // Shows the system prompt the first time; afterwards it returns false without prompting,
+    // and a grant only takes effect once the app is restarted.
+    if unsafe { CGRequestScreenCaptureAccess() } {
+        return Ok(());
+    }
+    Err(CommandError::new(
+        ErrorCode::Forbidden,
+        "Screen recording permission is required to take screenshots. Allow OpenChamber in \
+         System Settings > Privacy & Security > Screen Recording, then restart the app.",
+    )
+    .with_details(json!({ "permission": "screenRecording" })))
+}
+
+#[cfg(target_os = "macos")]
+async fn ns_window_number(window: &WebviewWindow) -> Result<isize, CommandError> {
+    use objc2::msg_send;
+    use objc2::runtime::AnyObject;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let target = window.clone();
+    window
+        .run_on_main_thread(move || {
+            let number: Option<isize> = target.ns_window().ok().map(|ns_window| {
+                let ns_window: *mut AnyObject = ns_window.cast();
+                unsafe { msg_send![ns_window, windowNumber] }
+            });
+            let _ = tx.send(number);
+        })
+        .map_err(|e| e.to_string())?;
+    rx.await
+        .ok()
+        .flatten()
+        .ok_or_else(|| "Window is not available".into())
+}
+
+#[cfg(target_os = "macos")]
+async fn capture_png(
+    window: &WebviewWindow,
+    path: &Path,
+    screen_rect: Option<CaptureRect>,
+) -> Result<(), CommandError> {
+    ensure_screen_capture_access()?;
+
+    // -x: no shutter sound. -o: no window shadow.
+    let mut args = vec!["-x".to_string(), "-t".to_string(), "png".to_string()];
+    match screen_rect {
+        Some(rect) => args.push(format!(
+            "-R{},{},{},{}",
+            rect.x.round(),
+            rect.y.round(),
+            rect.width.round(),
+            rect.height.round()
+        )),
+        None => {
+            args.push("-o".to_string());
+            args.push(format!("-l{}", ns_window_number(window).await?));
+        }
+    }
+    args.push(path.to_string_lossy().into_owned());
+
+    let output = tokio::process::Command::new("/usr/sbin/screencapture")
+        .args(&args)
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run screencapture: {}", e))?;
+    let written = tokio::fs::metadata(path)
+        .await
+        .map(|meta| meta.len() > 0)
+        .unwrap_or(false);
+    if !output.status.success() || !written {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("screencapture failed: {}", stderr.trim()).into());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "macos"))]
+async fn capture_png(
+    _window: &WebviewWindow,
+    _path: &Path,
+    _screen_rect: Option<CaptureRect>,
+) -> Result<(), CommandError> {
+    Err("Window screenshots are only supported on macOS".into())
+}
+
+/// Save a PNG of the calling window, or of `rect` within it (webview coordinates), to
+/// `dest_path` so it can be attached to a message. Returns the path written. macOS only;
+/// elsewhere it fails without writing anything.
+#[tauri::command]
+pub async fn capture_window_screenshot(
+    window: WebviewWindow,
+    dest_path: String,
+    rect: Option<CaptureRect>,
+    runtime: State<'_, DesktopRuntime>,
+) -> Result<WindowScreenshot, CommandError> {
+    if !cfg!(target_os = "macos") {
+        return Err("Window screenshots are only supported on macOS".into());
+    }
+    let path = validate_screenshot_path(&dest_path, runtime.settings()).await?;
+    let screen_rect = rect
+        .map(|rect| capture_rect_on_screen(&window, rect))
+        .transpose()?;
+    capture_png(&window, &path, screen_rect).await?;
+
+    info!("[desktop:window] Saved screenshot to {}", path.display());
+    Ok(WindowScreenshot {
+        path: path.to_string_lossy().into_owned(),
+    })
+}
@@ -0,0 +1,248 @@
+use std::time::{Duration, Instant};
+
+use log::info;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::commands::github::resolve_auth_status;
+use crate::commands::settings::connectivity_probe_url;
+use crate::DesktopRuntime;
+
+const CONNECTIVITY_CHANGED_EVENT: &str = "openchamber:connectivity-changed";
+const CONNECTIVITY_POLL_INTERVAL: Duration = Duration::from_secs(30);
+// Validating the token is a GitHub API call; don't spend rate limit on it every poll.
+const GITHUB_CHECK_INTERVAL: Duration = Duration::from_secs(5 * 60);
+// Overridden by the `connectivityProbeUrl` setting, e.g. where this host is blocked.
+const DEFAULT_INTERNET_PROBE_URL: &str = "https://www.apple.com/library/test/success.html";
+const INTERNET_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Ordered from best to worst, so the overall state is the maximum.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum ConnectivityState {
+    Ok,
+    Degraded,
+    Down,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectivityComponent {
+    pub state: ConnectivityState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl ConnectivityComponent {
+    fn ok() -> Self {
+        Self {
+            state: ConnectivityState::Ok,
+            message: None,
+        }
+    }
+
+    fn degraded(message: impl Into<String>) -> Self {
+        Self {
+            state: ConnectivityState::Degraded,
+            message: Some(message.into()),
+        }
+    }
+
+    fn down(message: impl Into<String>) -> Self {
+        Self {
+            state: ConnectivityState::Down,
+            message: Some(message.into()),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectivityStatus {
+    /// Worst state across the components; what the offline banner shows.
+    pub overall: ConnectivityState,
+    pub opencode: ConnectivityComponent,
+    pub models_metadata: ConnectivityComponent,
+    pub github: ConnectivityComponent,
+    pub internet: ConnectivityComponent,
+    /// Unix timestamp in milliseconds.
+    pub checked_at: i64,
+}
+
+impl ConnectivityStatus {
+    fn states(&self) -> [ConnectivityState; 4] {
+        [
+            self.opencode.state,
+            self.models_metadata.state,
+            self.github.state,
+            self.internet.state,
+        ]
+    }
+}
+
+#[derive(Default)]
+struct ConnectivityCache {
+    status: Option<ConnectivityStatus>,
+    github_checked_at: Option<Instant>,
+}
+
+static CONNECTIVITY: Lazy<Mutex<ConnectivityCache>> =
+    Lazy::new(|| Mutex::new(ConnectivityCache::default()));
+
+fn opencode_component(runtime: &DesktopRuntime) -> ConnectivityComponent {
+    let manager = runtime.opencode_manager();
+    if !manager.is_cli_available() {
+        return ConnectivityComponent::down("The OpenCode CLI was not found");
+    }
+    // Sleeping is deliberate; OpenCode wakes on the next request.
+    if manager.is_asleep() || manager.is_ready() {
+        return ConnectivityComponent::ok();
+    }
+    match manager.last_error() {
+        Some(error) => {
+            ConnectivityComponent::down(format!("OpenCode failed to start: {}", error.message))
+        }
+        None => ConnectivityComponent::degraded("OpenCode is starting"),
+    }
+}
+
+/// Judged from the metadata proxy's last refresh rather than a fresh request.
+async fn models_metadata_component(runtime: &DesktopRuntime) -> ConnectivityComponent {
+    match runtime.models_metadata_health().await {
+        (_, None) => ConnectivityComponent::ok(),
+        (true, Some(error)) => ConnectivityComponent::degraded(format!(
+            "models.dev is unreachable, using cached data ({error})"
+        )),
+        (false, Some(error)) => {
+            ConnectivityComponent::down(format!("models.dev is unreachable ({error})"))
+        }
+    }
+}
+
+async fn github_component() -> ConnectivityComponent {
+    match resolve_auth_status().await {
+        Ok(status) if status.connected => ConnectivityComponent::ok(),
+        // Not using GitHub is a choice, not an outage; a rejected token is reported as
+        // `openchamber:github-auth-lost`.
+        Ok(_) => ConnectivityComponent {
+            state: ConnectivityState::Ok,
+            message: Some("GitHub is not connected".to_string()),
+        },
+        Err(err) => {
+            ConnectivityComponent::degraded(format!("Could not verify GitHub sign-in: {err}"))
+        }
+    }
+}
+
+async fn internet_component(runtime: &DesktopRuntime) -> ConnectivityComponent {
+    let settings = runtime.settings().load().await.unwrap_or(Value::Null);
+    let probe_url = connectivity_probe_url(&settings, DEFAULT_INTERNET_PROBE_URL);
+    let client = match reqwest::Client::builder()
+        .timeout(INTERNET_PROBE_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(err) => {
+            return ConnectivityComponent::down(format!("Failed to create HTTP client: {err}"))
+        }
+    };
+    // Any HTTP response proves the network path works; only transport errors count.
+    match client.head(&probe_url).send().await {
+        Ok(_) => ConnectivityComponent::ok(),
+        Err(err) => ConnectivityComponent::down(format!("No internet connection: {err}")),
+    }
+}
+
+/// Re-check every component and emit `openchamber:connectivity-changed` if any of them
+/// changed state since the previous check. GitHub is re-validated at most every few minutes.
+pub async fn refresh_connectivity(app: &AppHandle, runtime: &DesktopRuntime) -> ConnectivityStatus {
+    let (cached_github, github_due) = {
+        let cache = CONNECTIVITY.lock();
+        let due = !matches!(
+            cache.github_checked_at,
+            Some(at) if at.elapsed() < GITHUB_CHECK_INTERVAL
+        );
+        (
+            cache.status.as_ref().map(|status| status.github.clone()),
+            due,
+        )
+    };
+    let github = async {
+        match cached_github {
+            Some(github) if !github_due => github,
+            _ => github_component().await,
+        }
+    };
+
+    let opencode = opencode_component(runtime);
+    let (models_metadata, github, internet) = tokio::join!(
+        models_metadata_component(runtime),
+        github,
+        internet_component(runtime)
+    );
+    let overall = [&opencode, &models_metadata, &github, &internet]
+        .iter()
+        .map(|component| component.state)
+        .max()
+        .unwrap_or(ConnectivityState::Ok);
+    let status = ConnectivityStatus {
+        overall,
+        opencode,
+        models_metadata,
+        github,
+        internet,
+        checked_at: chrono::Utc::now().timestamp_millis(),
+    };
+
+    // The first check only establishes the baseline; events are for flips.
+    let changed = {
+        let mut cache = CONNECTIVITY.lock();
+        if github_due {
+            cache.github_checked_at = Some(Instant::now());
+        }
+        let changed = matches!(
+            &cache.status,
+            Some(previous) if previous.states() != status.states()
+        );
+        cache.status = Some(status.clone());
+        changed
+    };
+    if changed {
+        info!(
+            "[desktop:connectivity] Connectivity changed, overall {:?}",
+            status.overall
+        );
+        let _ = app.emit(CONNECTIVITY_CHANGED_EVENT, &status);
+    }
+    status
+}
+
+/// Whether any window is on screen; nobody sees the offline banner otherwise.
+fn ui_visible(app: &AppHandle) -> bool {
+    app.webview_windows().values().any(|window| {
+        window.is_visible().unwrap_or(false) && !window.is_minimized().unwrap_or(false)
+    })
+}
+
+/// Poll connectivity in the background so the UI hears about flips without polling itself.
+/// Polls are skipped while no window is visible; `get_connectivity_status` still refreshes on
+/// demand.
+pub fn spawn_connectivity_monitor(app: AppHandle, runtime: DesktopRuntime) {
+    tauri::async_runtime::spawn(async move {
+        let mut shutdown_rx = runtime.subscribe_shutdown();
+        let mut interval = tokio::time::interval(CONNECTIVITY_POLL_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.recv() => break,
+                _ = interval.tick() => {}
+            }
+            if !ui_visible(&app) {
+                continue;
+            }
+            refresh_connectivity(&app, &runtime).await;
+        }
+    });
+}
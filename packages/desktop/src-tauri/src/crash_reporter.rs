@@ -0,0 +1,99 @@
+use std::{backtrace::Backtrace, fs, io::Write, panic::PanicHookInfo, path::PathBuf};
+
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{logging, DesktopRuntime};
+
+const CRASH_EVENT: &str = "openchamber:crash";
+
+static CRASH_APP_HANDLE: OnceCell<AppHandle> = OnceCell::new();
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct CrashPayload {
+    message: String,
+    location: Option<String>,
+    file: Option<String>,
+}
+
+/// `<log dir>/crashes`, one file per panic.
+pub fn crash_directory() -> Option<PathBuf> {
+    logging::log_directory().map(|dir| dir.join("crashes"))
+}
+
+/// Install before anything else in `main` so early panics are captured too. The default hook
+/// still runs afterwards, so stderr output is unchanged.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = panic_message(info);
+        let location = info
+            .location()
+            .map(|loc| format!("{}:{}:{}", loc.file(), loc.line(), loc.column()));
+        let file = write_crash_report(&message, location.as_deref());
+
+        if let Some(app) = CRASH_APP_HANDLE.get() {
+            let _ = app.emit(
+                CRASH_EVENT,
+                CrashPayload {
+                    message,
+                    location,
+                    file: file.map(|path| path.to_string_lossy().to_string()),
+                },
+            );
+        }
+
+        default_hook(info);
+    }));
+}
+
+/// Lets the hook emit a crash event and include the OpenCode state once the app is up.
+pub fn attach_app_handle(app: AppHandle) {
+    let _ = CRASH_APP_HANDLE.set(app);
+}
+
+fn panic_message(info: &PanicHookInfo<'_>) -> String {
+    if let Some(message) = info.payload().downcast_ref::<&str>() {
+        return (*message).to_string();
+    }
+    if let Some(message) = info.payload().downcast_ref::<String>() {
+        return message.clone();
+    }
+    "Box<dyn Any>".to_string()
+}
+
+fn write_crash_report(message: &str, location: Option<&str>) -> Option<PathBuf> {
+    let dir = crash_directory()?;
+    fs::create_dir_all(&dir).ok()?;
+
+    let now = chrono::Local::now();
+    let path = dir.join(format!(
+        "crash-{}-{}.log",
+        now.format("%Y%m%d-%H%M%S"),
+        std::process::id()
+    ));
+
+    let thread = std::thread::current();
+    let opencode = CRASH_APP_HANDLE
+        .get()
+        .and_then(|app| app.try_state::<DesktopRuntime>())
+        .map(|runtime| runtime.opencode_manager().crash_snapshot())
+        .unwrap_or_else(|| "not started".to_string());
+
+    let report = format!(
+        "OpenChamber {} crash report\ntime: {}\nthread: {}\nmessage: {}\nlocation: {}\nopencode: {}\n\nbacktrace:\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        now.to_rfc3339(),
+        thread.name().unwrap_or("<unnamed>"),
+        message,
+        location.unwrap_or("<unknown>"),
+        opencode,
+        Backtrace::force_capture(),
+    );
+
+    let mut file = fs::File::create(&path).ok()?;
+    file.write_all(report.as_bytes()).ok()?;
+    Some(path)
+}
@@ -0,0 +1,222 @@
+use std::{
+    collections::HashMap,
+    io::SeekFrom,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+use log::info;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+const FILE_TAIL_EVENT: &str = "openchamber:file-tail";
+const FILE_TAIL_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const MAX_FILE_TAILS: usize = 8;
+// Bound each poll so a file growing by hundreds of MB is streamed over several ticks.
+const FILE_TAIL_MAX_READ_BYTES: u64 = 256 * 1024;
+// A partial line longer than this is emitted as-is instead of waiting for its newline.
+const FILE_TAIL_MAX_LINE_BYTES: usize = 64 * 1024;
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct FileTailPayload {
+    id: u64,
+    path: String,
+    lines: Vec<String>,
+    /// The file shrank or was replaced (truncated or rotated) and is being read again from
+    /// the start.
+    reset: bool,
+}
+
+struct FileTail {
+    path: PathBuf,
+    /// Label of the window that started the tail.
+    window: String,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+static FILE_TAILS: Lazy<Mutex<HashMap<u64, FileTail>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static NEXT_FILE_TAIL_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Identifies the file behind a path, so a rotated log that was replaced by a new file of
+/// at least the same size is still noticed.
+#[cfg(unix)]
+type FileIdentity = (u64, u64);
+#[cfg(not(unix))]
+type FileIdentity = std::time::SystemTime;
+
+#[cfg(unix)]
+fn file_identity(metadata: &std::fs::Metadata) -> Option<FileIdentity> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_identity(metadata: &std::fs::Metadata) -> Option<FileIdentity> {
+    metadata.created().ok()
+}
+
+/// Read whatever was appended after `offset`, up to the per-poll cap. Returns the new
+/// offset and whether the file shrank below `offset` or was replaced by another file.
+async fn read_appended(
+    path: &Path,
+    offset: u64,
+    identity: &mut Option<FileIdentity>,
+    buf: &mut Vec<u8>,
+) -> Option<(u64, bool)> {
+    let mut file = tokio::fs::File::open(path).await.ok()?;
+    let metadata = file.metadata().await.ok()?;
+    let len = metadata.len();
+    let current = file_identity(&metadata);
+    let replaced = identity.is_some() && current.is_some() && *identity != current;
+    if current.is_some() {
+        *identity = current;
+    }
+    let (start, reset) = if replaced || len < offset {
+        (0, true)
+    } else {
+        (offset, false)
+    };
+    if len == start {
+        return Some((start, reset));
+    }
+
+    file.seek(SeekFrom::Start(start)).await.ok()?;
+    let read = file
+        .take((len - start).min(FILE_TAIL_MAX_READ_BYTES))
+        .read_to_end(buf)
+        .await
+        .ok()?;
+    Some((start + read as u64, reset))
+}
+
+async fn run_file_tail(app: AppHandle, id: u64, path: PathBuf, mut offset: u64) {
+    let display_path = path.to_string_lossy().replace('\\', "/");
+    let mut interval = tokio::time::interval(FILE_TAIL_POLL_INTERVAL);
+    // Bytes after the last newline, held back until the line is complete.
+    let mut pending = Vec::new();
+    let mut identity = None;
+
+    loop {
+        interval.tick().await;
+        let mut chunk = Vec::new();
+        // A missing file is expected mid-rotation; keep polling until it reappears.
+        let Some((next_offset, reset)) =
+            read_appended(&path, offset, &mut identity, &mut chunk).await
+        else {
+            continue;
+        };
+        offset = next_offset;
+        if reset {
+            pending.clear();
+        }
+        pending.extend_from_slice(&chunk);
+
+        let Some(last_newline) = pending.iter().rposition(|byte| *byte == b'\n') else {
+            if pending.len() > FILE_TAIL_MAX_LINE_BYTES {
+                let line = String::from_utf8_lossy(&pending).into_owned();
+                pending.clear();
+                let _ = app.emit(
+                    FILE_TAIL_EVENT,
+                    FileTailPayload {
+                        id,
+                        path: display_path.clone(),
+                        lines: vec![line],
+                        reset,
+                    },
+                );
+            } else if reset {
+                let _ = app.emit(
+                    FILE_TAIL_EVENT,
+                    FileTailPayload {
+                        id,
+                        path: display_path.clone(),
+                        lines: Vec::new(),
+                        reset,
+                    },
+                );
+            }
+            continue;
+        };
+        let complete: Vec<u8> = pending.drain(..=last_newline).collect();
+        let lines = complete[..complete.len() - 1]
+            .split(|byte| *byte == b'\n')
+            .map(|line| {
+                String::from_utf8_lossy(line.strip_suffix(b"\r").unwrap_or(line)).into_owned()
+            })
+            .collect();
+        let _ = app.emit(
+            FILE_TAIL_EVENT,
+            FileTailPayload {
+                id,
+                path: display_path.clone(),
+                lines,
+                reset,
+            },
+        );
+    }
+}
+
+/// Start following `path` (already sandbox-checked and canonicalized) from `start_offset`,
+/// emitting appended lines as `openchamber:file-tail` events. The tail belongs to the window
+/// labelled `window` and ends with it. Returns the subscription id.
+pub fn start_tail(
+    app: AppHandle,
+    window: String,
+    path: PathBuf,
+    start_offset: u64,
+) -> Result<u64, String> {
+    let mut tails = FILE_TAILS.lock();
+    if tails.len() >= MAX_FILE_TAILS {
+        return Err(format!(
+            "Too many files are being followed (limit {})",
+            MAX_FILE_TAILS
+        ));
+    }
+
+    let id = NEXT_FILE_TAIL_ID.fetch_add(1, Ordering::Relaxed);
+    let task = tauri::async_runtime::spawn(run_file_tail(app, id, path.clone(), start_offset));
+    info!("[desktop:tail] following {} ({id})", path.display());
+    tails.insert(id, FileTail { path, window, task });
+    Ok(id)
+}
+
+/// Stop the tail with `id`. Returns `false` if it wasn't running.
+pub fn stop_tail(id: u64) -> bool {
+    let Some(tail) = FILE_TAILS.lock().remove(&id) else {
+        return false;
+    };
+    tail.task.abort();
+    info!(
+        "[desktop:tail] stopped following {} ({id})",
+        tail.path.display()
+    );
+    true
+}
+
+/// Stop every tail started by the window labelled `window`, once it reloads or closes and can
+/// no longer stop them itself.
+pub fn stop_window_tails(window: &str) {
+    let stopped: Vec<FileTail> = {
+        let mut tails = FILE_TAILS.lock();
+        let ids: Vec<u64> = tails
+            .iter()
+            .filter(|(_, tail)| tail.window == window)
+            .map(|(id, _)| *id)
+            .collect();
+        ids.iter().filter_map(|id| tails.remove(id)).collect()
+    };
+    for tail in &stopped {
+        tail.task.abort();
+    }
+    if !stopped.is_empty() {
+        info!(
+            "[desktop:tail] stopped {} tail(s) of window {window}",
+            stopped.len()
+        );
+    }
+}
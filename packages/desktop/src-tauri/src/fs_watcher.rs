@@ -0,0 +1,167 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use log::{info, warn};
+use notify::{
+    event::ModifyKind, recommended_watcher, Event, EventKind, RecommendedWatcher, RecursiveMode,
+    Watcher,
+};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::mpsc;
+
+const FS_CHANGED_EVENT: &str = "openchamber:fs-changed";
+// Editors typically emit several events per save; collapse them into one per path/kind.
+const FS_WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+const MAX_DIRECTORY_WATCHERS: usize = 8;
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct FsChangePayload {
+    root: String,
+    path: String,
+    kind: &'static str,
+}
+
+struct DirectoryWatch {
+    _watcher: RecommendedWatcher,
+    task: tauri::async_runtime::JoinHandle<()>,
+}
+
+static DIRECTORY_WATCHES: Lazy<Mutex<HashMap<PathBuf, DirectoryWatch>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn classify_event(kind: &EventKind) -> Option<&'static str> {
+    match kind {
+        EventKind::Create(_) => Some("create"),
+        EventKind::Modify(ModifyKind::Name(_)) => Some("rename"),
+        EventKind::Modify(_) => Some("modify"),
+        EventKind::Remove(_) => Some("remove"),
+        _ => None,
+    }
+}
+
+fn normalize_path(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Start watching `root` (already sandbox-checked and canonicalized). Returns `false` when
+/// the directory is already being watched.
+pub fn watch_directory(app: AppHandle, root: PathBuf, recursive: bool) -> Result<bool, String> {
+    let mut watches = DIRECTORY_WATCHES.lock();
+    if watches.contains_key(&root) {
+        return Ok(false);
+    }
+    if watches.len() >= MAX_DIRECTORY_WATCHERS {
+        return Err(format!(
+            "Too many directory watchers (limit {})",
+            MAX_DIRECTORY_WATCHERS
+        ));
+    }
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<(PathBuf, &'static str)>();
+    let mut watcher = recommended_watcher(move |result: notify::Result<Event>| match result {
+        Ok(event) => {
+            let Some(kind) = classify_event(&event.kind) else {
+                return;
+            };
+            for path in event.paths {
+                let _ = tx.send((path, kind));
+            }
+        }
+        Err(err) => warn!("[desktop:fs-watch] watcher error: {err}"),
+    })
+    .map_err(|e| format!("Failed to create directory watcher: {}", e))?;
+
+    let mode = if recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    watcher
+        .watch(&root, mode)
+        .map_err(|e| format!("Failed to watch directory: {}", e))?;
+
+    let root_label = normalize_path(&root);
+    let task = tauri::async_runtime::spawn(async move {
+        while let Some(first) = rx.recv().await {
+            let mut pending = vec![first];
+            let deadline = tokio::time::Instant::now() + FS_WATCH_DEBOUNCE;
+            let mut closed = false;
+
+            loop {
+                match tokio::time::timeout_at(deadline, rx.recv()).await {
+                    Ok(Some(change)) => {
+                        if !pending.contains(&change) {
+                            pending.push(change);
+                        }
+                    }
+                    Ok(None) => {
+                        closed = true;
+                        break;
+                    }
+                    Err(_) => break,
+                }
+            }
+
+            for (path, kind) in pending {
+                let payload = FsChangePayload {
+                    root: root_label.clone(),
+                    path: normalize_path(&path),
+                    kind,
+                };
+                let _ = app.emit(FS_CHANGED_EVENT, payload);
+            }
+
+            if closed {
+                break;
+            }
+        }
+    });
+
+    info!("[desktop:fs-watch] watching {}", root.display());
+    watches.insert(
+        root,
+        DirectoryWatch {
+            _watcher: watcher,
+            task,
+        },
+    );
+    Ok(true)
+}
+
+/// Stop watching `root`. Returns `false` if it wasn't being watched.
+pub fn unwatch_directory(root: &Path) -> bool {
+    let Some(watch) = DIRECTORY_WATCHES.lock().remove(root) else {
+        return false;
+    };
+    watch.task.abort();
+    info!("[desktop:fs-watch] stopped watching {}", root.display());
+    true
+}
+
+/// Number of directories currently being watched.
+pub fn watch_count() -> usize {
+    DIRECTORY_WATCHES.lock().len()
+}
+
+/// Stop every active watcher (e.g. when the active directory changes).
+pub fn unwatch_all() {
+    let drained: Vec<DirectoryWatch> = DIRECTORY_WATCHES
+        .lock()
+        .drain()
+        .map(|(_, watch)| watch)
+        .collect();
+    if drained.is_empty() {
+        return;
+    }
+    for watch in &drained {
+        watch.task.abort();
+    }
+    info!("[desktop:fs-watch] stopped {} watcher(s)", drained.len());
+}
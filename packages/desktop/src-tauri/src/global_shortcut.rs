@@ -0,0 +1,100 @@
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tauri::{plugin::TauriPlugin, AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+
+const GLOBAL_SHORTCUT_ERROR_EVENT: &str = "openchamber:global-shortcut-error";
+
+/// The accelerator currently registered for toggling the main window.
+static TOGGLE_SHORTCUT: Lazy<Mutex<Option<(String, Shortcut)>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+struct GlobalShortcutErrorPayload {
+    shortcut: String,
+    message: String,
+}
+
+/// Global shortcut plugin whose only shortcut toggles the main window.
+pub fn plugin<R: Runtime>() -> TauriPlugin<R> {
+    tauri_plugin_global_shortcut::Builder::new()
+        .with_handler(|app, _shortcut, event| {
+            if event.state() == ShortcutState::Pressed {
+                toggle_main_window(app);
+            }
+        })
+        .build()
+}
+
+/// Hide the main window if it is focused, otherwise show and focus it.
+pub(crate) fn toggle_main_window<R: Runtime>(app: &AppHandle<R>) {
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+    let visible = window.is_visible().unwrap_or(false);
+    let minimized = window.is_minimized().unwrap_or(false);
+    let focused = window.is_focused().unwrap_or(false);
+    if visible && !minimized && focused {
+        let _ = window.hide();
+    } else {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn report_error<R: Runtime>(app: &AppHandle<R>, shortcut: &str, message: String) {
+    warn!("[desktop:shortcut] {message}");
+    let _ = app.emit(
+        GLOBAL_SHORTCUT_ERROR_EVENT,
+        GlobalShortcutErrorPayload {
+            shortcut: shortcut.to_string(),
+            message,
+        },
+    );
+}
+
+/// Register `accelerator` (e.g. "CmdOrCtrl+Shift+Space") as the show/hide shortcut, replacing
+/// the previous one; `None` just unregisters. Failures, such as another app owning the
+/// shortcut, are logged and emitted as `openchamber:global-shortcut-error`.
+pub fn apply_toggle_shortcut<R: Runtime>(app: &AppHandle<R>, accelerator: Option<&str>) {
+    let mut current = TOGGLE_SHORTCUT.lock();
+    if current.as_ref().map(|(value, _)| value.as_str()) == accelerator {
+        return;
+    }
+
+    let global_shortcut = app.global_shortcut();
+    if let Some((value, shortcut)) = current.take() {
+        if let Err(err) = global_shortcut.unregister(shortcut) {
+            warn!("[desktop:shortcut] Failed to unregister {value}: {err}");
+        }
+    }
+    let Some(accelerator) = accelerator else {
+        return;
+    };
+
+    let shortcut = match accelerator.parse::<Shortcut>() {
+        Ok(shortcut) => shortcut,
+        Err(err) => {
+            report_error(
+                app,
+                accelerator,
+                format!("Invalid shortcut {accelerator}: {err}"),
+            );
+            return;
+        }
+    };
+    match global_shortcut.register(shortcut) {
+        Ok(()) => {
+            info!("[desktop:shortcut] Registered {accelerator} to show/hide the window");
+            *current = Some((accelerator.to_string(), shortcut));
+        }
+        Err(err) => report_error(
+            app,
+            accelerator,
+            format!("Could not register {accelerator}, it may be in use by another app: {err}"),
+        ),
+    }
+}
@@ -1,16 +1,68 @@
-use std::path::PathBuf;
+use std::{fmt::Arguments, path::PathBuf};
+
+use log::{LevelFilter, Record};
+use tauri_plugin_log::fern::FormatCallback;
 
 #[cfg(target_os = "macos")]
-const PLATFORM_LOG_SEGMENTS: &[&str] = &["Library", "Logs", "OpenChamber"];
-#[cfg(not(target_os = "macos"))]
-const PLATFORM_LOG_SEGMENTS: &[&str] = &[".config", "openchamber", "logs"];
+pub fn log_directory() -> Option<PathBuf> {
+    Some(
+        dirs::home_dir()?
+            .join("Library")
+            .join("Logs")
+            .join("OpenChamber"),
+    )
+}
 
+/// Logs live next to the settings, so they follow a relocated config directory.
+#[cfg(not(target_os = "macos"))]
 pub fn log_directory() -> Option<PathBuf> {
-    let mut path = dirs::home_dir()?;
-    for segment in PLATFORM_LOG_SEGMENTS {
-        path.push(segment);
+    Some(crate::path_utils::openchamber_config_dir()?.join("logs"))
+}
+
+/// Levels accepted by the `logLevel` setting, least to most verbose.
+pub const LOG_LEVELS: &[&str] = &["off", "error", "warn", "info", "debug", "trace"];
+pub const DEFAULT_LOG_LEVEL: LevelFilter = LevelFilter::Info;
+
+pub fn parse_log_level(value: &str) -> Option<LevelFilter> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "off" => Some(LevelFilter::Off),
+        "error" => Some(LevelFilter::Error),
+        "warn" => Some(LevelFilter::Warn),
+        "info" => Some(LevelFilter::Info),
+        "debug" => Some(LevelFilter::Debug),
+        "trace" => Some(LevelFilter::Trace),
+        _ => None,
+    }
+}
+
+/// The plugin's dispatcher is built at `Trace`, so the global max level is the effective
+/// filter and can be changed at runtime.
+pub fn apply_log_level(level: LevelFilter) {
+    if log::max_level() != level {
+        log::set_max_level(level);
+    }
+}
+
+/// `logFormat: "json"` formatter: one object per line with timestamp, level, target, message.
+/// The plugin applies a single formatter to every target, so stdout gets JSON lines as well.
+pub fn format_json_record(out: FormatCallback, message: &Arguments, record: &Record) {
+    let line = serde_json::json!({
+        "timestamp": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        "level": record.level().as_str(),
+        "target": record.target(),
+        "message": message.to_string(),
+    });
+    out.finish(format_args!("{}", line))
+}
+
+/// "json" when the log content is JSON lines, otherwise "text". Decided by the last line so
+/// a file that switched formats reports what is currently being written.
+pub fn detect_log_format(content: &str) -> &'static str {
+    let last_line = content.lines().rev().find(|line| !line.trim().is_empty());
+    match last_line {
+        Some(line) if line.trim_start().starts_with('{') => "json",
+        _ => "text",
     }
-    Some(path)
 }
 
 pub fn log_file_path() -> Option<PathBuf> {
@@ -2,19 +2,33 @@
 
 mod assistant_notifications;
 mod commands;
+mod connectivity;
+mod crash_reporter;
+mod file_tail;
+mod fs_watcher;
+mod global_shortcut;
 mod logging;
 mod opencode_auth;
 mod opencode_config;
 mod opencode_manager;
 mod path_utils;
 mod session_activity;
+mod session_cleanup;
+mod session_read_positions;
 mod skills_catalog;
+mod tray;
 mod window_state;
 
 use std::{
     collections::HashMap,
+    future::Future,
     path::PathBuf,
-    sync::Arc,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
     time::{Duration, Instant},
 };
 
@@ -24,82 +38,158 @@ use axum::{
     body::{to_bytes, Body},
     extract::{Request, State},
     http::{Method, StatusCode},
+    middleware::{self, Next},
     response::{IntoResponse, Response},
     routing::{any, get, post},
     Json, Router,
 };
+use commands::badge::{increment_badge_count, set_badge_count, BadgeState};
+use commands::batch::batch;
+use commands::clipboard::copy_to_clipboard;
+use commands::diagnostics::{get_connectivity_status, run_diagnostics};
+use commands::external::open_external;
 use commands::files::{
-    create_directory, delete_path, exec_commands, list_directory, read_file, read_file_binary,
-    rename_path, search_files, write_file,
+    cancel_search, create_directory, create_file, delete_path, detect_file_format, exec_commands,
+    get_config_disk_usage, get_disk_usage, hash_file, hash_files, list_directory, read_file,
+    read_file_binary, rename_path, search_files, stop_tail, tail_file, unwatch_directory,
+    watch_directory, write_file,
 };
 use commands::git::{
-    add_git_worktree, check_is_git_repository, checkout_branch, create_branch, create_git_commit,
-    create_git_identity, delete_git_branch, delete_git_identity, delete_remote_branch,
-    discover_git_credentials, ensure_openchamber_ignored, generate_commit_message,
-    get_commit_files, get_current_git_identity, get_git_branches, get_git_diff, get_git_file_diff,
-    get_git_identities, get_git_log, get_git_status, get_global_git_identity, get_remote_url,
-    git_fetch, git_pull, git_push, has_local_identity, is_linked_worktree, list_git_worktrees,
-    remove_git_worktree, rename_branch, revert_git_file, set_git_identity, update_git_identity,
-    generate_pr_description,
+    add_git_remote, add_git_worktree, check_is_git_repository, checkout_branch, cherry_pick_commit,
+    create_branch, create_git_commit, create_git_identity, create_git_tag, delete_git_branch,
+    delete_git_identity, delete_git_tag, delete_remote_branch, discover_git_credentials,
+    ensure_openchamber_ignored, generate_commit_message, generate_pr_description, get_commit_files,
+    get_conflict_markers, get_current_git_identity, get_file_at_revision, get_git_branches,
+    get_git_diff, get_git_file_diff, get_git_identities, get_git_log, get_git_log_graph,
+    get_git_ref_diff, get_git_reflog, get_git_status, get_git_tags, get_global_git_identity,
+    get_remote_url, get_repo_context, get_staged_diff_stat, git_fetch, git_operation_abort,
+    git_operation_continue, git_operation_state, git_pull, git_push, has_local_identity,
+    import_git_identity_from_repo, is_linked_worktree, list_git_remotes, list_git_worktrees,
+    prune_git_worktrees, remove_git_remote, remove_git_worktree, rename_branch, rename_git_remote,
+    repair_git_worktrees, reset_to, revert_commit, revert_git_file, set_git_identity,
+    set_git_remote_url, stage_git_hunk, unstage_git_hunk, update_git_identity,
+    validate_git_identity,
+};
+use commands::logs::{
+    export_diagnostics_bundle, fetch_desktop_logs, fetch_opencode_logs, set_log_level,
 };
-use commands::logs::fetch_desktop_logs;
 
 use commands::github::{
     github_auth_activate, github_auth_complete, github_auth_disconnect, github_auth_start, github_auth_status, github_me,
-    github_issue_comments, github_issue_get, github_issues_list,
+    github_auth_with_token, validate_github_client,
+    github_issue_comments, github_issue_create, github_issue_get, github_issues_list,
     github_pr_context, github_prs_list,
-    github_pr_create, github_pr_merge, github_pr_ready, github_pr_status,
+    github_pr_create, github_pr_merge, github_pr_ready, github_pr_status, get_pr_template,
+    github_pr_files, github_pr_unwatch, github_pr_watch,
 };
+use commands::memory::{get_memory_stats, set_memory_stats_streaming};
+use commands::models::test_model;
 use commands::notifications::desktop_notify;
 use commands::permissions::{
-    pick_directory, process_directory_selection, request_directory_access,
-    restore_bookmarks_on_startup, start_accessing_directory, stop_accessing_directory,
+    list_directory_bookmarks, pick_directory, pick_directory_within, process_directory_selection,
+    remove_directory_bookmark, request_directory_access, restore_bookmarks_on_startup,
+    start_accessing_directory, stop_accessing_directory,
+};
+use commands::sessions::{
+    cancel_all_sessions, get_session_activity, get_session_messages, get_session_read_position,
+    save_session_read_position, set_current_session,
 };
-use commands::settings::{load_settings, restart_opencode, save_settings};
+use commands::settings::{
+    get_active_workspace, load_settings, preview_settings_merge, restart_opencode, save_settings,
+    set_default, StartupDirectory,
+};
+use commands::skills::{install_skill, list_skill_catalog_entries};
 use commands::terminal::{
-    close_terminal, create_terminal_session, force_kill_terminal, resize_terminal,
-    restart_terminal_session, send_terminal_input, TerminalState,
+    close_terminal, create_terminal_session, force_kill_terminal, list_available_shells,
+    resize_terminal, restart_terminal_session, send_terminal_input, TerminalState,
+};
+use commands::updater::{check_for_updates, install_update};
+use commands::window::{
+    capture_window_screenshot, get_window_state, open_project_window, save_ui_state,
 };
-use futures_util::StreamExt as FuturesStreamExt;
+use connectivity::spawn_connectivity_monitor;
+use futures_util::{Stream, StreamExt as FuturesStreamExt};
 use log::{error, info, warn};
-use opencode_manager::OpenCodeManager;
+use opencode_manager::{OpenCodeManager, OpenCodeStartupError, StreamActivityGuard};
 use path_utils::expand_tilde_path;
 use portpicker::pick_unused_port;
 use reqwest::{header, Body as ReqwestBody, Client};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use session_activity::spawn_session_activity_tracker;
+use session_activity::{
+    spawn_idle_sleep_monitor, spawn_session_activity_tracker, SessionActivityState,
+};
+use session_cleanup::spawn_session_auto_cleanup;
+use session_read_positions::SessionReadPositionStore;
 #[cfg(feature = "devtools")]
 use tauri::WebviewWindow;
 use tauri::{Emitter, Manager};
-use tauri_plugin_dialog::init as dialog_plugin;
+use tauri_plugin_clipboard_manager::init as clipboard_plugin;
+use tauri_plugin_dialog::{init as dialog_plugin, DialogExt, MessageDialogKind};
 use tauri_plugin_fs::init as fs_plugin;
 use tauri_plugin_log::{Target, TargetKind};
 use tauri_plugin_notification::init as notification_plugin;
+use tauri_plugin_opener::init as opener_plugin;
 use tauri_plugin_shell::init as shell_plugin;
 use tokio::{
     fs,
     net::TcpListener,
     sync::{broadcast, Mutex},
 };
-use tower_http::cors::CorsLayer;
-use window_state::{load_window_state, persist_window_state, WindowStateManager};
-
-#[cfg(target_os = "macos")]
-use std::sync::atomic::{AtomicBool, Ordering};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use window_state::{load_window_state, persist_window_state, GeometryChange, WindowStateManager};
 
 
 
 #[cfg(target_os = "macos")]
 static NEEDS_TRAFFIC_LIGHT_FIX: AtomicBool = AtomicBool::new(false);
+#[cfg(target_os = "macos")]
+static WINDOW_IS_FULLSCREEN: AtomicBool = AtomicBool::new(false);
+
+// Traffic-light (window controls) origin on macOS < 26, in points.
+#[cfg(target_os = "macos")]
+const TRAFFIC_LIGHT_OFFSET_X: f64 = 17.0;
+#[cfg(target_os = "macos")]
+const TRAFFIC_LIGHT_OFFSET_Y: f64 = 16.0;
+// AppKit lays the buttons out again once the fullscreen animation ends, so re-apply after it.
+#[cfg(target_os = "macos")]
+const FULLSCREEN_TRANSITION_SETTLE: Duration = Duration::from_millis(600);
+
+// Overridable with the `proxyBodyLimitMb` setting.
+const DEFAULT_PROXY_BODY_LIMIT: usize = 50 * 1024 * 1024; // 50MB
+
+// Idle time before a `: keep-alive` comment is written into a proxied event stream, so
+// intermediaries don't drop it during long silent tool runs. `sseKeepAliveSeconds`; 0 disables.
+const DEFAULT_SSE_KEEP_ALIVE_SECS: u64 = 15;
+const SSE_KEEP_ALIVE_COMMENT: &[u8] = b": keep-alive\n\n";
+
+// Correlates a UI request with the proxy and OpenCode logs; echoed back on the response.
+const REQUEST_ID_HEADER: &str = "x-request-id";
+const REQUEST_ID_MAX_LEN: usize = 128;
 
-const PROXY_BODY_LIMIT: usize = 50 * 1024 * 1024; // 50MB
 const CLIENT_RELOAD_DELAY_MS: u64 = 800;
+const OPENCODE_RESTART_DEBOUNCE: Duration = Duration::from_millis(1500);
 const MODELS_DEV_API_URL: &str = "https://models.dev/api.json";
 const MODELS_METADATA_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
 const MODELS_METADATA_REQUEST_TIMEOUT: Duration = Duration::from_secs(8);
+// Network mounts can stall metadata/canonicalize calls; bound them so the directory lock is freed.
+const DIRECTORY_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+// Slightly longer than OpenCode's own SIGTERM + SIGKILL waits so a normal stop isn't cut short.
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(6);
+// Webview origins that may always call the local server. Loopback http(s) origins on any port
+// are allowed too so local tooling keeps working; anything else needs `corsAllowedOrigins`.
+const DEFAULT_CORS_ORIGINS: &[&str] = &[
+    "tauri://localhost",
+    "http://tauri.localhost",
+    "https://tauri.localhost",
+];
+const LOOPBACK_ORIGIN_HOSTS: &[&str] = &["localhost", "127.0.0.1", "[::1]"];
 
 const CHECK_FOR_UPDATES_EVENT: &str = "openchamber:check-for-updates";
+// Emitted after the macOS-only Stop All Sessions menu item; `cancel_all_sessions` callers get
+// the result directly.
+#[cfg(target_os = "macos")]
+const SESSIONS_CANCELLED_EVENT: &str = "openchamber:sessions-cancelled";
 
 #[cfg(target_os = "macos")]
 const MENU_ITEM_CHECK_FOR_UPDATES_ID: &str = "openchamber_check_for_updates";
@@ -125,6 +215,8 @@ const MENU_ITEM_NEW_SESSION_ID: &str = "openchamber_new_session";
 const MENU_ITEM_WORKTREE_CREATOR_ID: &str = "openchamber_worktree_creator";
 #[cfg(target_os = "macos")]
 const MENU_ITEM_CHANGE_WORKSPACE_ID: &str = "openchamber_change_workspace";
+#[cfg(target_os = "macos")]
+const MENU_ITEM_CANCEL_ALL_SESSIONS_ID: &str = "openchamber_cancel_all_sessions";
 
 // View menu
 #[cfg(target_os = "macos")]
@@ -162,6 +254,12 @@ pub(crate) struct DesktopRuntime {
     shutdown_tx: broadcast::Sender<()>,
     opencode: Arc<OpenCodeManager>,
     settings: Arc<SettingsStore>,
+    client: Client,
+    active_searches: Arc<parking_lot::Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    proxy_body_limit: Arc<AtomicUsize>,
+    sse_keep_alive_secs: Arc<AtomicU64>,
+    models_metadata_cache: Arc<Mutex<ModelsMetadataCache>>,
+    opencode_restart_pending: Arc<AtomicBool>,
 }
 
 impl DesktopRuntime {
@@ -172,15 +270,20 @@ impl DesktopRuntime {
         let client = Client::builder().build()?;
 
         let (shutdown_tx, shutdown_rx) = broadcast::channel(2);
+        let proxy_body_limit = Arc::new(AtomicUsize::new(DEFAULT_PROXY_BODY_LIMIT));
+        let sse_keep_alive_secs = Arc::new(AtomicU64::new(DEFAULT_SSE_KEEP_ALIVE_SECS));
+        let models_metadata_cache = Arc::new(Mutex::new(ModelsMetadataCache::default()));
         let server_port =
             pick_unused_port().ok_or_else(|| anyhow!("No free port available"))? as u16;
         let server_state = ServerState {
-            client,
+            client: client.clone(),
             opencode: opencode.clone(),
             settings: settings.clone(),
             server_port,
             directory_change_lock: Arc::new(Mutex::new(())),
-            models_metadata_cache: Arc::new(Mutex::new(ModelsMetadataCache::default())),
+            models_metadata_cache: models_metadata_cache.clone(),
+            proxy_body_limit: proxy_body_limit.clone(),
+            sse_keep_alive_secs: sse_keep_alive_secs.clone(),
         };
 
         spawn_http_server(server_port, server_state, shutdown_rx);
@@ -190,10 +293,22 @@ impl DesktopRuntime {
             shutdown_tx,
             opencode,
             settings,
+            client,
+            active_searches: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            proxy_body_limit,
+            sse_keep_alive_secs,
+            models_metadata_cache,
+            opencode_restart_pending: Arc::new(AtomicBool::new(false)),
         })
     }
 
     async fn start_opencode(&self) {
+        if let Ok(settings) = self.settings.load().await {
+            self.opencode.set_api_prefix_override(
+                commands::settings::opencode_api_prefix_override(&settings),
+            );
+        }
+
         if self.opencode.is_cli_available() {
             if let Err(e) = self.opencode.ensure_running().await {
                 warn!("[desktop] Failed to start OpenCode: {}", e);
@@ -219,6 +334,104 @@ impl DesktopRuntime {
     pub(crate) fn opencode_manager(&self) -> Arc<OpenCodeManager> {
         self.opencode.clone()
     }
+
+    /// HTTP client shared with the proxy; set a per-request timeout where one is needed.
+    pub(crate) fn http_client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Base URL of the OpenCode API (port and prefix), waking OpenCode first if it is asleep.
+    pub(crate) async fn opencode_api_base(&self) -> Result<String> {
+        self.opencode.ensure_awake().await?;
+        let port = self
+            .opencode
+            .current_port()
+            .ok_or_else(|| anyhow!("OpenCode is not running"))?;
+        Ok(format!(
+            "http://127.0.0.1:{port}{}",
+            self.opencode.api_prefix()
+        ))
+    }
+
+    /// Apply a new request body limit; the HTTP server picks it up on the next request.
+    pub(crate) fn set_proxy_body_limit(&self, bytes: usize) {
+        self.proxy_body_limit.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Apply a new SSE keep-alive interval (0 disables); used by streams opened afterwards.
+    pub(crate) fn set_sse_keep_alive_secs(&self, secs: u64) {
+        self.sse_keep_alive_secs.store(secs, Ordering::Relaxed);
+    }
+
+    /// Register a cancellation token for `search_id`, cancelling any search still running
+    /// under the same id.
+    pub(crate) fn begin_search(&self, search_id: &str) -> Arc<AtomicBool> {
+        let token = Arc::new(AtomicBool::new(false));
+        if let Some(previous) = self
+            .active_searches
+            .lock()
+            .insert(search_id.to_string(), token.clone())
+        {
+            previous.store(true, Ordering::Relaxed);
+        }
+        token
+    }
+
+    pub(crate) fn active_search_count(&self) -> usize {
+        self.active_searches.lock().len()
+    }
+
+    /// Restart OpenCode after a short delay so it picks up config changes. Changes made in
+    /// quick succession (e.g. installing several skills) share a single restart.
+    pub(crate) fn schedule_opencode_restart(&self, reason: &str) {
+        if self.opencode_restart_pending.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        info!("[desktop:config] Restarting OpenCode shortly after {reason}");
+        let runtime = self.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(OPENCODE_RESTART_DEBOUNCE).await;
+            runtime
+                .opencode_restart_pending
+                .store(false, Ordering::SeqCst);
+            if let Err(err) = runtime.opencode.restart().await {
+                warn!("[desktop:config] Failed to restart OpenCode: {err}");
+            }
+        });
+    }
+
+    /// Whether a models.dev payload is held in memory (fresh or stale).
+    pub(crate) async fn models_metadata_cached(&self) -> bool {
+        self.models_metadata_cache.lock().await.payload.is_some()
+    }
+
+    /// Whether a models.dev payload is held in memory, and why the last refresh failed
+    /// (`None` once a refresh succeeds).
+    pub(crate) async fn models_metadata_health(&self) -> (bool, Option<String>) {
+        let cache = self.models_metadata_cache.lock().await;
+        (cache.payload.is_some(), cache.last_error.clone())
+    }
+
+    /// Drop the token for `search_id` unless a newer search has already replaced it.
+    pub(crate) fn end_search(&self, search_id: &str, token: &Arc<AtomicBool>) {
+        let mut searches = self.active_searches.lock();
+        if searches
+            .get(search_id)
+            .is_some_and(|current| Arc::ptr_eq(current, token))
+        {
+            searches.remove(search_id);
+        }
+    }
+
+    pub(crate) fn cancel_search(&self, search_id: &str) -> bool {
+        match self.active_searches.lock().remove(search_id) {
+            Some(token) => {
+                token.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -229,12 +442,29 @@ struct ServerState {
     server_port: u16,
     directory_change_lock: Arc<Mutex<()>>,
     models_metadata_cache: Arc<Mutex<ModelsMetadataCache>>,
+    proxy_body_limit: Arc<AtomicUsize>,
+    sse_keep_alive_secs: Arc<AtomicU64>,
+}
+
+impl ServerState {
+    fn proxy_body_limit(&self) -> usize {
+        self.proxy_body_limit.load(Ordering::Relaxed)
+    }
+
+    fn sse_keep_alive(&self) -> Option<Duration> {
+        match self.sse_keep_alive_secs.load(Ordering::Relaxed) {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        }
+    }
 }
 
 #[derive(Default)]
 struct ModelsMetadataCache {
     payload: Option<Value>,
     fetched_at: Option<Instant>,
+    /// Why every source failed on the last refresh.
+    last_error: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -246,11 +476,24 @@ struct ConfigActionResponse {
     reload_delay_ms: u64,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigValidationResponse {
+    valid: bool,
+    problems: Vec<opencode_config::ConfigProblem>,
+}
+
 #[derive(Serialize)]
 struct ConfigErrorResponse {
     error: String,
 }
 
+#[derive(Serialize)]
+struct ProxyErrorResponse {
+    error: String,
+    code: &'static str,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct ConfigMetadataResponse {
@@ -269,6 +512,7 @@ struct HealthResponse {
     api_prefix: String,
     is_opencode_ready: bool,
     cli_available: bool,
+    last_error: Option<OpenCodeStartupError>,
 }
 
 #[derive(Serialize)]
@@ -278,6 +522,42 @@ struct ServerInfoPayload {
     api_prefix: String,
     cli_available: bool,
     has_last_directory: bool,
+    last_error: Option<OpenCodeStartupError>,
+}
+
+/// Runs after bookmarks are restored so sandboxed directories are reachable when checked.
+async fn resolve_startup_directory(app: &tauri::AppHandle, runtime: &DesktopRuntime) {
+    let result = runtime
+        .settings()
+        .update_with(|mut settings| {
+            let outcome = commands::settings::resolve_startup_directory(&mut settings);
+            (settings, outcome)
+        })
+        .await;
+    match result {
+        Ok((_, StartupDirectory::Unset)) => {}
+        Ok((_, StartupDirectory::Resolved { path, fallback })) => {
+            if fallback {
+                info!(
+                    "[desktop] Saved directory is gone, falling back to {}",
+                    path.display()
+                );
+            } else {
+                info!("[desktop] Starting in {}", path.display());
+            }
+        }
+        Ok((_, StartupDirectory::Missing { stale })) => {
+            warn!(
+                "[desktop] Saved directory {} and all recent directories are gone, waiting for directory selection",
+                stale
+            );
+            let _ = app.emit(
+                "openchamber:directory-missing",
+                serde_json::json!({ "missing": stale }),
+            );
+        }
+        Err(err) => warn!("[desktop] Failed to resolve startup directory: {}", err),
+    }
 }
 
 #[tauri::command]
@@ -290,13 +570,14 @@ async fn desktop_server_info(
         .await
         .ok()
         .flatten()
-        .is_some();
+        .is_some_and(|path| path.is_dir());
     Ok(ServerInfoPayload {
         server_port: state.server_port,
         opencode_port: state.opencode.current_port(),
         api_prefix: state.opencode.api_prefix(),
         cli_available: state.opencode.is_cli_available(),
         has_last_directory,
+        last_error: state.opencode.last_error(),
     })
 }
 
@@ -351,6 +632,50 @@ fn adjust_traffic_lights_position<R: tauri::Runtime>(
     }
 }
 
+/// Re-apply the traffic-light offset if this macOS version needs it (no-op on 26+).
+#[cfg(target_os = "macos")]
+fn reapply_traffic_lights<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+    if !NEEDS_TRAFFIC_LIGHT_FIX.load(Ordering::SeqCst) {
+        return;
+    }
+    if let Some(webview) = app.get_webview_window("main") {
+        adjust_traffic_lights_position(&webview, TRAFFIC_LIGHT_OFFSET_X, TRAFFIC_LIGHT_OFFSET_Y);
+    }
+}
+
+/// Runs once a drag or resize of the main window has settled.
+#[cfg(target_os = "macos")]
+fn on_window_geometry_settled(app: &tauri::AppHandle, resized: bool) {
+    // Re-apply the traffic-light offset once per resize rather than on every intermediate frame.
+    if resized {
+        let main_thread_handle = app.clone();
+        let _ = app.run_on_main_thread(move || reapply_traffic_lights(&main_thread_handle));
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn on_window_geometry_settled(_app: &tauri::AppHandle, _resized: bool) {}
+
+/// Fullscreen enter/exit shows up as a resize; when the fullscreen state flipped, re-apply the
+/// offset again after AppKit has finished its own layout.
+#[cfg(target_os = "macos")]
+fn handle_fullscreen_transition<R: tauri::Runtime>(window: &tauri::Window<R>) {
+    if !NEEDS_TRAFFIC_LIGHT_FIX.load(Ordering::SeqCst) {
+        return;
+    }
+    let is_fullscreen = window.is_fullscreen().unwrap_or(false);
+    if WINDOW_IS_FULLSCREEN.swap(is_fullscreen, Ordering::SeqCst) == is_fullscreen {
+        return;
+    }
+
+    let app_handle = window.app_handle().clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(FULLSCREEN_TRANSITION_SETTLE).await;
+        let main_thread_handle = app_handle.clone();
+        let _ = app_handle.run_on_main_thread(move || reapply_traffic_lights(&main_thread_handle));
+    });
+}
+
 #[cfg(target_os = "macos")]
 fn optimize_webview_layer<R: tauri::Runtime>(window: &tauri::WebviewWindow<R>) {
     use objc2::msg_send;
@@ -387,18 +712,42 @@ fn optimize_webview_layer<R: tauri::Runtime>(window: &tauri::WebviewWindow<R>) {
 }
 
 #[cfg(target_os = "macos")]
-fn prevent_app_nap() {
-    use objc2_foundation::{NSActivityOptions, NSProcessInfo, NSString};
+struct AppNapActivity(
+    objc2::rc::Retained<objc2::runtime::ProtocolObject<dyn objc2::runtime::NSObjectProtocol>>,
+);
 
-    let options = NSActivityOptions(0x00FFFFFF | 0xFF00000000);
-    let reason = NSString::from_str("Prevent App Nap");
+// SAFETY: the activity token is an opaque object that is only handed back to
+// `NSProcessInfo::endActivity`, which may be called from any thread.
+#[cfg(target_os = "macos")]
+unsafe impl Send for AppNapActivity {}
 
-    let process_info = NSProcessInfo::processInfo();
-    let activity = process_info.beginActivityWithOptions_reason(options, &reason);
+#[cfg(target_os = "macos")]
+static APP_NAP_ACTIVITY: parking_lot::Mutex<Option<AppNapActivity>> =
+    parking_lot::const_mutex(None);
 
-    std::mem::forget(activity);
+/// Begin or end the App Nap prevention activity (the `preventAppNap` setting).
+#[cfg(target_os = "macos")]
+pub(crate) fn set_app_nap_prevention(enabled: bool) {
+    use objc2_foundation::{NSActivityOptions, NSProcessInfo, NSString};
+
+    let mut current = APP_NAP_ACTIVITY.lock();
+    let process_info = NSProcessInfo::processInfo();
 
-    info!("[macos] App Nap prevention enabled via objc2");
+    match (enabled, current.take()) {
+        (true, None) => {
+            let options = NSActivityOptions(0x00FFFFFF | 0xFF00000000);
+            let reason = NSString::from_str("Prevent App Nap");
+            let activity = process_info.beginActivityWithOptions_reason(options, &reason);
+            *current = Some(AppNapActivity(activity));
+            info!("[macos] App Nap prevention enabled via objc2");
+        }
+        (false, Some(activity)) => {
+            // SAFETY: the token came from `beginActivityWithOptions_reason` above.
+            unsafe { process_info.endActivity(&activity.0) };
+            info!("[macos] App Nap prevention disabled");
+        }
+        (_, existing) => *current = existing,
+    }
 }
 
 #[cfg(target_os = "macos")]
@@ -463,6 +812,14 @@ fn build_macos_menu<R: tauri::Runtime>(
         None::<&str>,
     )?;
 
+    let cancel_all_sessions = MenuItem::with_id(
+        app,
+        MENU_ITEM_CANCEL_ALL_SESSIONS_ID,
+        "Stop All Sessions",
+        true,
+        Some("Cmd+Shift+."),
+    )?;
+
     // View menu items
     let open_git_tab =
         MenuItem::with_id(app, MENU_ITEM_OPEN_GIT_TAB_ID, "Git", true, Some("Cmd+G"))?;
@@ -627,6 +984,8 @@ fn build_macos_menu<R: tauri::Runtime>(
                     &PredefinedMenuItem::separator(app)?,
                     &change_workspace,
                     &PredefinedMenuItem::separator(app)?,
+                    &cancel_all_sessions,
+                    &PredefinedMenuItem::separator(app)?,
                     &PredefinedMenuItem::close_window(app, None)?,
                 ],
             )?,
@@ -668,12 +1027,24 @@ fn build_macos_menu<R: tauri::Runtime>(
 }
 
 fn main() {
+    crash_reporter::install_panic_hook();
+
     let mut log_builder = tauri_plugin_log::Builder::default()
-        .level(log::LevelFilter::Info)
+        // Effective level is `log::max_level`, set from the `logLevel` setting during setup.
+        .level(log::LevelFilter::Trace)
         .clear_targets()
         .target(Target::new(TargetKind::Stdout))
         .target(Target::new(TargetKind::Webview));
 
+    // The formatter is fixed once the plugin is built, so `logFormat` applies from the next launch.
+    let startup_settings = SettingsStore::new()
+        .ok()
+        .and_then(|store| tauri::async_runtime::block_on(store.load()).ok())
+        .unwrap_or(Value::Null);
+    if commands::settings::json_log_format_enabled(&startup_settings) {
+        log_builder = log_builder.format(logging::format_json_record);
+    }
+
     if let Some(dir) = logging::log_directory() {
         log_builder = log_builder.target(Target::new(TargetKind::Folder {
             path: dir,
@@ -686,6 +1057,9 @@ fn main() {
         .plugin(dialog_plugin())
         .plugin(fs_plugin())
         .plugin(notification_plugin())
+        .plugin(opener_plugin())
+        .plugin(clipboard_plugin())
+        .plugin(global_shortcut::plugin())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
         .plugin(log_builder.build())
@@ -700,10 +1074,14 @@ fn main() {
             }
         })
         .setup(|app| {
-            #[cfg(target_os = "macos")]
-            prevent_app_nap();
-
             app.manage(TerminalState::new());
+            app.manage(BadgeState::default());
+
+            let session_activity = SessionActivityState::default();
+            app.manage(session_activity.clone());
+
+            let read_positions = tauri::async_runtime::block_on(SessionReadPositionStore::load());
+            app.manage(read_positions.clone());
 
             let stored_state = tauri::async_runtime::block_on(load_window_state()).unwrap_or(None);
             let manager = WindowStateManager::new(stored_state.clone().unwrap_or_default());
@@ -717,7 +1095,11 @@ fn main() {
 
                     if macos_version < 26 {
                         NEEDS_TRAFFIC_LIGHT_FIX.store(true, Ordering::SeqCst);
-                        adjust_traffic_lights_position(&window, 17.0, 16.0);
+                        adjust_traffic_lights_position(
+                            &window,
+                            TRAFFIC_LIGHT_OFFSET_X,
+                            TRAFFIC_LIGHT_OFFSET_Y,
+                        );
                     }
 
                     // Apply layer optimizations for smoother scrolling
@@ -730,10 +1112,51 @@ fn main() {
 
                 let _ = window.show();
                 let _ = window.set_focus();
+
+                // Showing the window can lay the buttons out again.
+                #[cfg(target_os = "macos")]
+                reapply_traffic_lights(app.app_handle());
             }
 
             let runtime = DesktopRuntime::initialize_sync()?;
             app.manage(runtime.clone());
+            if let Some(dir) = runtime.settings().config_dir() {
+                if let Err(err) = path_utils::check_directory_writable(dir) {
+                    error!("[desktop] Config directory is not writable: {err}");
+                    app.dialog()
+                        .message(format!(
+                            "OpenChamber cannot save its settings.\n\n{err}\n\n\
+                             Set OPENCHAMBER_CONFIG_DIR to a writable directory and restart."
+                        ))
+                        .title("Config directory not writable")
+                        .kind(MessageDialogKind::Error)
+                        .show(|_| {});
+                }
+            }
+            crash_reporter::attach_app_handle(app.app_handle().clone());
+            commands::github::attach_app_handle(app.app_handle().clone());
+
+            let settings =
+                tauri::async_runtime::block_on(runtime.settings().load()).unwrap_or(Value::Null);
+            runtime.set_proxy_body_limit(commands::settings::proxy_body_limit_bytes(
+                &settings,
+                DEFAULT_PROXY_BODY_LIMIT,
+            ));
+            runtime.set_sse_keep_alive_secs(commands::settings::sse_keep_alive_secs(
+                &settings,
+                DEFAULT_SSE_KEEP_ALIVE_SECS,
+            ));
+            logging::apply_log_level(commands::settings::log_level_setting(&settings));
+            global_shortcut::apply_toggle_shortcut(
+                app.app_handle(),
+                commands::settings::global_toggle_shortcut(&settings).as_deref(),
+            );
+            tray::apply_tray_setting(
+                app.app_handle(),
+                commands::settings::show_tray_icon_enabled(&settings),
+            );
+            #[cfg(target_os = "macos")]
+            set_app_nap_prevention(commands::settings::prevent_app_nap_enabled(&settings));
 
             let app_handle = app.app_handle().clone();
             let runtime_clone = runtime.clone();
@@ -746,6 +1169,8 @@ fn main() {
                     warn!("Failed to restore bookmarks on startup: {}", e);
                 }
 
+                resolve_startup_directory(&app_handle, &runtime_clone).await;
+
                 let _ = app_handle.emit("openchamber:runtime-ready", ());
             });
 
@@ -759,6 +1184,11 @@ fn main() {
                         if runtime.opencode_manager().is_shutting_down() {
                             break;
                         }
+                        // Stopped on purpose by the idle auto-sleep; the proxy wakes it.
+                        if runtime.opencode_manager().is_asleep() {
+                            tokio::time::sleep(Duration::from_secs(1)).await;
+                            continue;
+                        }
 
                         let mut sleep_ms = backoff_ms;
 
@@ -767,6 +1197,7 @@ fn main() {
                                 sleep_ms = 1000;
                                 backoff_ms = 1000;
                             }
+                            Ok(false) if runtime.opencode_manager().is_asleep() => {}
                             Ok(false) => {
                                 let _ = app_handle.emit("server.instance.disposed", ());
                                 if runtime.opencode_manager().is_cli_available() {
@@ -849,43 +1280,92 @@ fn main() {
             }
 
             spawn_assistant_notifications(app.app_handle().clone(), runtime.clone());
-            spawn_session_activity_tracker(app.app_handle().clone(), runtime.clone());
+            spawn_idle_sleep_monitor(
+                app.app_handle().clone(),
+                runtime.clone(),
+                session_activity.clone(),
+            );
+            spawn_session_auto_cleanup(
+                app.app_handle().clone(),
+                runtime.clone(),
+                session_activity.clone(),
+                read_positions.clone(),
+            );
+            spawn_session_activity_tracker(
+                app.app_handle().clone(),
+                runtime.clone(),
+                session_activity,
+                read_positions,
+            );
+            spawn_connectivity_monitor(app.app_handle().clone(), runtime.clone());
 
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             desktop_server_info,
             desktop_restart_opencode,
+            batch,
             #[cfg(feature = "devtools")]
             desktop_open_devtools,
             load_settings,
             save_settings,
+            preview_settings_merge,
+            set_default,
             restart_opencode,
+            get_active_workspace,
+            list_skill_catalog_entries,
+            install_skill,
             list_directory,
             search_files,
+            cancel_search,
             create_directory,
+            create_file,
+            hash_file,
+            hash_files,
+            get_disk_usage,
+            get_config_disk_usage,
+            detect_file_format,
             delete_path,
             rename_path,
             read_file,
             read_file_binary,
             write_file,
+            watch_directory,
+            unwatch_directory,
+            tail_file,
+            stop_tail,
             exec_commands,
             request_directory_access,
             start_accessing_directory,
             stop_accessing_directory,
             pick_directory,
+            pick_directory_within,
             restore_bookmarks_on_startup,
+            list_directory_bookmarks,
+            remove_directory_bookmark,
             process_directory_selection,
             check_is_git_repository,
             get_git_status,
+            get_conflict_markers,
             get_git_diff,
+            get_staged_diff_stat,
             get_git_file_diff,
+            get_git_ref_diff,
+            get_file_at_revision,
             revert_git_file,
+            stage_git_hunk,
+            unstage_git_hunk,
             is_linked_worktree,
+            get_repo_context,
             get_git_branches,
             delete_git_branch,
             delete_remote_branch,
+            get_git_tags,
+            create_git_tag,
+            delete_git_tag,
             list_git_worktrees,
+            prune_git_worktrees,
+            repair_git_worktrees,
             add_git_worktree,
             remove_git_worktree,
             ensure_openchamber_ignored,
@@ -894,19 +1374,34 @@ fn main() {
             git_pull,
             git_fetch,
             checkout_branch,
+            cherry_pick_commit,
+            revert_commit,
+            get_git_reflog,
+            reset_to,
+            git_operation_state,
+            git_operation_abort,
+            git_operation_continue,
             create_branch,
             rename_branch,
             get_git_log,
+            get_git_log_graph,
             get_commit_files,
             get_git_identities,
             create_git_identity,
+            import_git_identity_from_repo,
             update_git_identity,
             delete_git_identity,
             get_current_git_identity,
             has_local_identity,
             get_global_git_identity,
             get_remote_url,
+            list_git_remotes,
+            add_git_remote,
+            remove_git_remote,
+            rename_git_remote,
+            set_git_remote_url,
             set_git_identity,
+            validate_git_identity,
             discover_git_credentials,
             generate_commit_message,
             generate_pr_description,
@@ -916,21 +1411,53 @@ fn main() {
             close_terminal,
             restart_terminal_session,
             force_kill_terminal,
+            list_available_shells,
             fetch_desktop_logs,
+            fetch_opencode_logs,
+            set_log_level,
+            export_diagnostics_bundle,
+            run_diagnostics,
+            get_connectivity_status,
+            open_external,
+            copy_to_clipboard,
+            check_for_updates,
+            install_update,
             desktop_notify,
+            set_badge_count,
+            increment_badge_count,
+            get_session_activity,
+            cancel_all_sessions,
+            get_session_messages,
+            save_session_read_position,
+            get_session_read_position,
+            set_current_session,
+            get_memory_stats,
+            set_memory_stats_streaming,
+            test_model,
+            get_window_state,
+            save_ui_state,
+            open_project_window,
+            capture_window_screenshot,
             github_auth_status,
             github_auth_start,
+            validate_github_client,
             github_auth_complete,
+            github_auth_with_token,
             github_auth_disconnect,
             github_auth_activate,
             github_me,
             github_pr_status,
+            github_pr_watch,
+            github_pr_unwatch,
             github_pr_create,
+            get_pr_template,
             github_pr_merge,
             github_pr_ready,
             github_prs_list,
             github_pr_context,
+            github_pr_files,
             github_issues_list,
+            github_issue_create,
             github_issue_get,
             github_issue_comments,
         ])
@@ -947,28 +1474,27 @@ fn main() {
 
                 // External links
                 if event_id == MENU_ITEM_REPORT_BUG_ID {
-                    use tauri_plugin_shell::ShellExt;
-                    #[allow(deprecated)]
+                    if let Err(err) =
+                        commands::external::open_external_url(app, GITHUB_BUG_REPORT_URL)
                     {
-                        let _ = app.shell().open(GITHUB_BUG_REPORT_URL, None);
+                        warn!("[desktop:menu] {err}");
                     }
                     return;
                 }
 
                 if event_id == MENU_ITEM_REQUEST_FEATURE_ID {
-                    use tauri_plugin_shell::ShellExt;
-                    #[allow(deprecated)]
+                    if let Err(err) =
+                        commands::external::open_external_url(app, GITHUB_FEATURE_REQUEST_URL)
                     {
-                        let _ = app.shell().open(GITHUB_FEATURE_REQUEST_URL, None);
+                        warn!("[desktop:menu] {err}");
                     }
                     return;
                 }
 
                 if event_id == MENU_ITEM_JOIN_DISCORD_ID {
-                    use tauri_plugin_shell::ShellExt;
-                    #[allow(deprecated)]
+                    if let Err(err) = commands::external::open_external_url(app, DISCORD_INVITE_URL)
                     {
-                        let _ = app.shell().open(DISCORD_INVITE_URL, None);
+                        warn!("[desktop:menu] {err}");
                     }
                     return;
                 }
@@ -1005,6 +1531,18 @@ fn main() {
                     return;
                 }
 
+                if event_id == MENU_ITEM_CANCEL_ALL_SESSIONS_ID {
+                    let app = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let runtime = app.state::<DesktopRuntime>().inner().clone();
+                        let activity = app.state::<SessionActivityState>().inner().clone();
+                        let result =
+                            session_activity::abort_busy_sessions(&runtime, &activity).await;
+                        let _ = app.emit(SESSIONS_CANCELLED_EVENT, result);
+                    });
+                    return;
+                }
+
                 // View menu actions
                 if event_id == MENU_ITEM_OPEN_GIT_TAB_ID {
                     let _ = app.emit("openchamber:menu-action", "open-git-tab");
@@ -1058,42 +1596,73 @@ fn main() {
                 }
             }
         })
+        .on_page_load(|webview, payload| {
+            // A reloaded page has lost the ids it would need to stop its own tails.
+            if payload.event() == tauri::webview::PageLoadEvent::Started {
+                file_tail::stop_window_tails(webview.label());
+            }
+        })
         .on_window_event(|window, event| {
             let window_state_manager = window.state::<WindowStateManager>().inner().clone();
 
             match event {
-                tauri::WindowEvent::Focused(true) => {
-                    // Clear dock badge and underlying badge state when the window gains focus
-                    let _ = window.set_badge_count(None);
-                    let _ = window
-                        .app_handle()
-                        .emit("openchamber:clear-badge-sessions", ());
-                }
-                tauri::WindowEvent::Moved(position) => {
-                    let is_maximized = window.is_maximized().unwrap_or(false);
-                    window_state_manager.update_position(
-                        position.x as f64,
-                        position.y as f64,
-                        is_maximized,
+                tauri::WindowEvent::Focused(focused) => {
+                    // Focus transitions can reset the window controls to their default spot.
+                    #[cfg(target_os = "macos")]
+                    reapply_traffic_lights(window.app_handle());
+
+                    if *focused {
+                        // Clear dock badge and underlying badge state when the window gains focus
+                        window.state::<BadgeState>().clear(window);
+                        let _ = window
+                            .app_handle()
+                            .emit("openchamber:clear-badge-sessions", ());
+                    }
+                }
+                #[cfg(target_os = "macos")]
+                tauri::WindowEvent::ThemeChanged(_) => {
+                    reapply_traffic_lights(window.app_handle());
+                }
+                tauri::WindowEvent::Moved(position) if window.label() == "main" => {
+                    let app_handle = window.app_handle().clone();
+                    window_state_manager.record_geometry(
+                        GeometryChange::Moved {
+                            x: position.x as f64,
+                            y: position.y as f64,
+                            is_maximized: window.is_maximized().unwrap_or(false),
+                        },
+                        move |resized| on_window_geometry_settled(&app_handle, resized),
                     );
                 }
-                tauri::WindowEvent::Resized(size) => {
-                    let is_maximized = window.is_maximized().unwrap_or(false);
-                    window_state_manager.update_size(
-                        size.width as f64,
-                        size.height as f64,
-                        is_maximized,
+                tauri::WindowEvent::Resized(size) if window.label() == "main" => {
+                    let app_handle = window.app_handle().clone();
+                    window_state_manager.record_geometry(
+                        GeometryChange::Resized {
+                            width: size.width as f64,
+                            height: size.height as f64,
+                            is_maximized: window.is_maximized().unwrap_or(false),
+                        },
+                        move |resized| on_window_geometry_settled(&app_handle, resized),
                     );
                     #[cfg(target_os = "macos")]
-                    if NEEDS_TRAFFIC_LIGHT_FIX.load(Ordering::SeqCst) {
-                        if let Some(webview) = window.app_handle().get_webview_window("main") {
-                            adjust_traffic_lights_position(&webview, 17.0, 16.0);
-                        }
-                    }
+                    handle_fullscreen_transition(window);
                 }
                 tauri::WindowEvent::CloseRequested { api, .. } => {
-                    api.prevent_close();
                     let runtime = window.state::<DesktopRuntime>().inner().clone();
+                    let others_visible =
+                        window.app_handle().webview_windows().values().any(|other| {
+                            other.label() != window.label() && other.is_visible().unwrap_or(false)
+                        });
+
+                    if commands::window::is_project_window(window.label()) {
+                        commands::window::handle_project_window_closed(window);
+                        if !others_visible {
+                            shutdown_and_exit(window.app_handle().clone(), runtime);
+                        }
+                        return;
+                    }
+
+                    api.prevent_close();
                     let window_handle = window.clone();
                     let manager_clone = window_state_manager.clone();
                     tauri::async_runtime::spawn(async move {
@@ -1101,8 +1670,21 @@ fn main() {
                         {
                             warn!("Failed to persist window state: {}", err);
                         }
-                        runtime.shutdown().await;
-                        let _ = window_handle.app_handle().exit(0);
+                        // Project windows keep the app running; the main window just hides
+                        // until the last of them closes.
+                        if others_visible {
+                            let _ = window_handle.hide();
+                            return;
+                        }
+                        shutdown_and_exit(window_handle.app_handle().clone(), runtime);
+                    });
+                }
+                tauri::WindowEvent::Destroyed => {
+                    file_tail::stop_window_tails(window.label());
+                    let activity = window.state::<SessionActivityState>().inner().clone();
+                    let label = window.label().to_string();
+                    tauri::async_runtime::spawn(async move {
+                        activity.forget_window(&label).await;
                     });
                 }
                 _ => {}
@@ -1114,6 +1696,25 @@ fn main() {
     app.run(|_app_handle, _event| {});
 }
 
+fn shutdown_and_exit(app: tauri::AppHandle, runtime: DesktopRuntime) {
+    tauri::async_runtime::spawn(async move {
+        if let Some(read_positions) = app.try_state::<SessionReadPositionStore>() {
+            read_positions.flush().await;
+        }
+        match tokio::time::timeout(SHUTDOWN_TIMEOUT, runtime.shutdown()).await {
+            Ok(()) => info!("[desktop] Shutdown completed gracefully"),
+            Err(_) => {
+                warn!(
+                    "[desktop] Shutdown did not finish within {}s; forcing OpenCode to stop",
+                    SHUTDOWN_TIMEOUT.as_secs()
+                );
+                runtime.opencode_manager().force_kill();
+            }
+        }
+        app.exit(0);
+    });
+}
+
 fn spawn_http_server(port: u16, state: ServerState, shutdown_rx: broadcast::Receiver<()>) {
     tauri::async_runtime::spawn(async move {
         if let Err(error) = run_http_server(port, state, shutdown_rx).await {
@@ -1127,6 +1728,7 @@ async fn run_http_server(
     state: ServerState,
     mut shutdown_rx: broadcast::Receiver<()>,
 ) -> Result<()> {
+    let origin_guard_state = state.clone();
     let router = Router::new()
         .route("/health", get(health_handler))
         .route(
@@ -1137,7 +1739,12 @@ async fn run_http_server(
         .route("/api", any(proxy_to_opencode))
         .route("/api/{*rest}", any(proxy_to_opencode))
         .with_state(state)
-        .layer(CorsLayer::permissive());
+        .layer(CorsLayer::permissive().allow_origin(AllowOrigin::mirror_request()))
+        // Outermost, so disallowed origins are rejected before CORS answers a preflight.
+        .layer(middleware::from_fn_with_state(
+            origin_guard_state,
+            cors_origin_guard,
+        ));
 
     let addr = format!("127.0.0.1:{port}");
     let listener = TcpListener::bind(&addr).await?;
@@ -1152,6 +1759,51 @@ async fn run_http_server(
     Ok(())
 }
 
+fn is_default_allowed_origin(origin: &str) -> bool {
+    if DEFAULT_CORS_ORIGINS.contains(&origin) {
+        return true;
+    }
+    let Ok(url) = url::Url::parse(origin) else {
+        return false;
+    };
+    matches!(url.scheme(), "http" | "https")
+        && url
+            .host_str()
+            .is_some_and(|host| LOOPBACK_ORIGIN_HOSTS.contains(&host))
+}
+
+async fn is_allowed_origin(settings: &SettingsStore, origin: &str) -> bool {
+    if is_default_allowed_origin(origin) {
+        return true;
+    }
+    let current = settings.load().await.unwrap_or(Value::Null);
+    current
+        .get("corsAllowedOrigins")
+        .and_then(Value::as_array)
+        .is_some_and(|entries| {
+            entries
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|entry| entry.trim().trim_end_matches('/') == origin)
+        })
+}
+
+/// Reject cross-origin requests (including preflights) from origins outside the allowlist.
+/// Requests without an `Origin` header (same-origin, CLI tools) pass through.
+async fn cors_origin_guard(State(state): State<ServerState>, req: Request, next: Next) -> Response {
+    if let Some(origin) = req.headers().get(axum::http::header::ORIGIN) {
+        let allowed = match origin.to_str() {
+            Ok(value) => is_allowed_origin(&state.settings, value).await,
+            Err(_) => false,
+        };
+        if !allowed {
+            warn!("[desktop:http] Rejected request from origin {:?}", origin);
+            return StatusCode::FORBIDDEN.into_response();
+        }
+    }
+    next.run(req).await
+}
+
 async fn health_handler(State(state): State<ServerState>) -> Json<HealthResponse> {
     Json(HealthResponse {
         status: "ok",
@@ -1160,6 +1812,7 @@ async fn health_handler(State(state): State<ServerState>) -> Json<HealthResponse
         api_prefix: state.opencode.api_prefix(),
         is_opencode_ready: state.opencode.is_ready(),
         cli_available: opencode_manager::check_cli_exists(),
+        last_error: state.opencode.last_error(),
     })
 }
 
@@ -1177,46 +1830,59 @@ async fn models_metadata_handler(
         cache.payload.clone()
     };
 
-    let response = state
-        .client
-        .get(MODELS_DEV_API_URL)
+    let settings = state.settings.load().await.unwrap_or(Value::Null);
+    let sources = commands::settings::models_metadata_sources(&settings, MODELS_DEV_API_URL);
+
+    let mut last_error = None;
+    for source in &sources {
+        match fetch_models_metadata(&state.client, source).await {
+            Ok(payload) => {
+                let mut cache = state.models_metadata_cache.lock().await;
+                cache.payload = Some(payload.clone());
+                cache.fetched_at = Some(Instant::now());
+                cache.last_error = None;
+                return Ok(Json(payload));
+            }
+            Err(error) => {
+                warn!("[desktop:http] Failed to fetch models metadata from {source}: {error}");
+                last_error = Some(format!("{source}: {error}"));
+            }
+        }
+    }
+    state.models_metadata_cache.lock().await.last_error = last_error;
+
+    match cached_payload {
+        Some(payload) => Ok(Json(payload)),
+        None => Err(StatusCode::BAD_GATEWAY),
+    }
+}
+
+async fn fetch_models_metadata(client: &Client, url: &str) -> Result<Value, String> {
+    let response = client
+        .get(url)
         .header(header::ACCEPT, "application/json")
         .timeout(MODELS_METADATA_REQUEST_TIMEOUT)
         .send()
         .await
-        .map_err(|error| {
-            warn!("[desktop:http] Failed to fetch models metadata: {error}");
-            StatusCode::BAD_GATEWAY
-        })?;
+        .map_err(|error| error.to_string())?;
 
     if !response.status().is_success() {
-        warn!(
-            "[desktop:http] models.dev responded with status {}",
-            response.status()
-        );
-        if let Some(payload) = cached_payload {
-            return Ok(Json(payload));
-        }
-        return Err(StatusCode::BAD_GATEWAY);
+        return Err(format!("responded with status {}", response.status()));
     }
 
-    let payload = response.json::<Value>().await.map_err(|error| {
-        warn!("[desktop:http] Failed to parse models.dev payload: {error}");
-        StatusCode::BAD_GATEWAY
-    })?;
-
-    {
-        let mut cache = state.models_metadata_cache.lock().await;
-        cache.payload = Some(payload.clone());
-        cache.fetched_at = Some(Instant::now());
-    }
-
-    Ok(Json(payload))
+    response
+        .json::<Value>()
+        .await
+        .map_err(|error| format!("invalid payload: {error}"))
 }
 
 #[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct DirectoryChangeRequest {
     path: String,
+    /// Run all checks and report the result without switching directories.
+    #[serde(default)]
+    validate_only: bool,
 }
 
 #[derive(Serialize)]
@@ -1224,12 +1890,75 @@ struct DirectoryChangeResponse {
     success: bool,
     restarted: bool,
     path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    validation: Option<DirectoryValidationReport>,
+}
+
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct DirectoryValidationReport {
+    exists: bool,
+    is_directory: bool,
+    within_approved_root: bool,
+    is_git_repository: bool,
+    errors: Vec<String>,
 }
 
 fn json_response<T: Serialize>(status: StatusCode, payload: T) -> Response {
     (status, Json(payload)).into_response()
 }
 
+fn proxy_error_response(
+    status: StatusCode,
+    code: &'static str,
+    message: impl Into<String>,
+) -> Response {
+    json_response(
+        status,
+        ProxyErrorResponse {
+            error: message.into(),
+            code,
+        },
+    )
+}
+
+/// Map a failed upstream request to a structured error so the UI can tell a timeout from
+/// an unreachable (crashed or still starting) backend.
+fn proxy_upstream_error(err: &reqwest::Error, request_id: &str) -> Response {
+    if err.is_timeout() {
+        warn!("[desktop:http] PROXY FAILED [{request_id}]: upstream timed out: {err}");
+        return proxy_error_response(
+            StatusCode::GATEWAY_TIMEOUT,
+            "timeout",
+            "OpenCode did not respond in time",
+        );
+    }
+    warn!("[desktop:http] PROXY FAILED [{request_id}]: upstream unreachable: {err}");
+    proxy_error_response(
+        StatusCode::BAD_GATEWAY,
+        "upstream_unreachable",
+        "OpenCode is not reachable",
+    )
+}
+
+/// Reuse the caller's `X-Request-Id` when it is sane, otherwise mint a new one.
+fn resolve_request_id(headers: &header::HeaderMap) -> String {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty() && value.len() <= REQUEST_ID_MAX_LEN)
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string())
+}
+
+fn with_request_id(mut response: Response, request_id: &str) -> Response {
+    if let Ok(value) = header::HeaderValue::from_str(request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
 fn config_error_response(status: StatusCode, message: impl Into<String>) -> Response {
     json_response(
         status,
@@ -1239,11 +1968,40 @@ fn config_error_response(status: StatusCode, message: impl Into<String>) -> Resp
     )
 }
 
-async fn parse_request_payload(req: &mut Request) -> Result<HashMap<String, Value>, Response> {
+/// 413 response for a request body over `limit` bytes.
+fn body_too_large_response(limit: usize) -> Response {
+    proxy_error_response(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        "request_too_large",
+        format!("Request body exceeds the {}MB limit", limit / (1024 * 1024)),
+    )
+}
+
+/// Whether a `to_bytes` failure was caused by the body limit rather than a broken stream.
+fn is_body_limit_error(err: &axum::Error) -> bool {
+    let mut source = std::error::Error::source(err);
+    while let Some(inner) = source {
+        if inner.is::<http_body_util::LengthLimitError>() {
+            return true;
+        }
+        source = inner.source();
+    }
+    false
+}
+
+async fn parse_request_payload(
+    state: &ServerState,
+    req: &mut Request,
+) -> Result<HashMap<String, Value>, Response> {
+    let limit = state.proxy_body_limit();
     let body = std::mem::take(req.body_mut());
-    let body_bytes = to_bytes(body, PROXY_BODY_LIMIT)
-        .await
-        .map_err(|_| config_error_response(StatusCode::BAD_REQUEST, "Invalid request body"))?;
+    let body_bytes = to_bytes(body, limit).await.map_err(|err| {
+        if is_body_limit_error(&err) {
+            body_too_large_response(limit)
+        } else {
+            config_error_response(StatusCode::BAD_REQUEST, "Invalid request body")
+        }
+    })?;
 
     if body_bytes.is_empty() {
         return Ok(HashMap::new());
@@ -1384,6 +2142,60 @@ async fn resolve_project_directory(
     }
 }
 
+async fn handle_agent_duplicate_route(
+    state: &ServerState,
+    mut req: Request,
+    source: String,
+) -> Result<Response, StatusCode> {
+    let working_directory =
+        match resolve_project_directory(state, extract_directory_from_request(&req)).await {
+            Ok(directory) => directory,
+            Err(response) => return Ok(response),
+        };
+
+    let payload = match parse_request_payload(state, &mut req).await {
+        Ok(data) => data,
+        Err(resp) => return Ok(resp),
+    };
+    let Some(dest) = payload
+        .get("name")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    else {
+        return Ok(config_error_response(
+            StatusCode::BAD_REQUEST,
+            "New agent name is required",
+        ));
+    };
+
+    match opencode_config::duplicate_agent(&source, dest, Some(&working_directory)).await {
+        Ok(sources) => {
+            if let Err(resp) =
+                refresh_opencode_after_config_change(state, "agent duplication").await
+            {
+                return Ok(resp);
+            }
+            Ok(json_response(StatusCode::OK, sources))
+        }
+        Err(err) => {
+            let status = match err {
+                opencode_config::DuplicateAgentError::InvalidName(_) => StatusCode::BAD_REQUEST,
+                opencode_config::DuplicateAgentError::SourceNotFound(_) => StatusCode::NOT_FOUND,
+                opencode_config::DuplicateAgentError::AlreadyExists(_) => StatusCode::CONFLICT,
+                opencode_config::DuplicateAgentError::Failed(_) => {
+                    StatusCode::INTERNAL_SERVER_ERROR
+                }
+            };
+            error!(
+                "[desktop:config] Failed to duplicate agent {} as {}: {}",
+                source, dest, err
+            );
+            Ok(config_error_response(status, err.to_string()))
+        }
+    }
+}
+
 async fn handle_agent_route(
     state: &ServerState,
     method: Method,
@@ -1430,7 +2242,7 @@ async fn handle_agent_route(
             }
         }
         Method::POST => {
-            let payload = match parse_request_payload(&mut req).await {
+            let payload = match parse_request_payload(&state, &mut req).await {
                 Ok(data) => data,
                 Err(resp) => return Ok(resp),
             };
@@ -1478,7 +2290,7 @@ async fn handle_agent_route(
             }
         }
         Method::PATCH => {
-            let payload = match parse_request_payload(&mut req).await {
+            let payload = match parse_request_payload(&state, &mut req).await {
                 Ok(data) => data,
                 Err(resp) => return Ok(resp),
             };
@@ -1674,7 +2486,7 @@ async fn handle_skill_route(
             }
             Method::PUT => {
                 // Write supporting file
-                let payload = match parse_request_payload(&mut req).await {
+                let payload = match parse_request_payload(&state, &mut req).await {
                     Ok(data) => data,
                     Err(resp) => return Ok(resp),
                 };
@@ -1789,7 +2601,7 @@ async fn handle_skill_route(
                 }
             }
             Method::POST => {
-                let payload = match parse_request_payload(&mut req).await {
+                let payload = match parse_request_payload(&state, &mut req).await {
                     Ok(data) => data,
                     Err(resp) => return Ok(resp),
                 };
@@ -1841,7 +2653,7 @@ async fn handle_skill_route(
                 }
             }
             Method::PATCH => {
-                let payload = match parse_request_payload(&mut req).await {
+                let payload = match parse_request_payload(&state, &mut req).await {
                     Ok(data) => data,
                     Err(resp) => return Ok(resp),
                 };
@@ -1961,7 +2773,7 @@ async fn handle_command_route(
             }
         }
         Method::POST => {
-            let payload = match parse_request_payload(&mut req).await {
+            let payload = match parse_request_payload(&state, &mut req).await {
                 Ok(data) => data,
                 Err(resp) => return Ok(resp),
             };
@@ -2012,7 +2824,7 @@ async fn handle_command_route(
             }
         }
         Method::PATCH => {
-            let payload = match parse_request_payload(&mut req).await {
+            let payload = match parse_request_payload(&state, &mut req).await {
                 Ok(data) => data,
                 Err(resp) => return Ok(resp),
             };
@@ -2104,6 +2916,11 @@ async fn handle_config_routes(
                 "Agent name is required",
             ));
         }
+        if let Some(source) = trimmed.strip_suffix("/duplicate") {
+            if method == Method::POST && !source.is_empty() {
+                return handle_agent_duplicate_route(&state, req, source.to_string()).await;
+            }
+        }
         return handle_agent_route(&state, method, req, trimmed.to_string()).await;
     }
 
@@ -2136,7 +2953,7 @@ async fn handle_config_routes(
     }
 
     if path == "/api/config/skills/scan" && method == Method::POST {
-        let payload_map = match parse_request_payload(&mut req).await {
+        let payload_map = match parse_request_payload(&state, &mut req).await {
             Ok(data) => data,
             Err(resp) => return Ok(resp),
         };
@@ -2176,7 +2993,7 @@ async fn handle_config_routes(
     }
 
     if path == "/api/config/skills/install" && method == Method::POST {
-        let payload_map = match parse_request_payload(&mut req).await {
+        let payload_map = match parse_request_payload(&state, &mut req).await {
             Ok(data) => data,
             Err(resp) => return Ok(resp),
         };
@@ -2279,6 +3096,29 @@ async fn handle_config_routes(
         ));
     }
 
+    if path == "/api/config/validate" && method == Method::POST {
+        let working_directory =
+            match resolve_project_directory(&state, extract_directory_from_request(&req)).await {
+                Ok(directory) => directory,
+                Err(response) => return Ok(response),
+            };
+        let problems = opencode_config::validate_config(Some(&working_directory)).await;
+        if !problems.is_empty() {
+            warn!(
+                "[desktop:config] Config validation found {} problem(s)",
+                problems.len()
+            );
+        }
+
+        return Ok(json_response(
+            StatusCode::OK,
+            ConfigValidationResponse {
+                valid: problems.is_empty(),
+                problems,
+            },
+        ));
+    }
+
     // Handle provider source lookup: GET /api/provider/:providerId/source
     if let Some(rest) = path.strip_prefix("/api/provider/") {
         if let Some(provider_id) = rest.strip_suffix("/source") {
@@ -2493,9 +3333,19 @@ async fn change_directory_handler(
         resolved_path = home.join(resolved_path);
     }
 
+    if payload.validate_only {
+        let (path, report) = validate_directory_change(&state.settings, resolved_path).await;
+        return Ok(Json(DirectoryChangeResponse {
+            success: report.errors.is_empty(),
+            restarted: false,
+            path: path.to_string_lossy().to_string(),
+            validation: Some(report),
+        }));
+    }
+
     // Validate directory exists and is accessible
-    match fs::metadata(&resolved_path).await {
-        Ok(metadata) => {
+    match tokio::time::timeout(DIRECTORY_PROBE_TIMEOUT, fs::metadata(&resolved_path)).await {
+        Ok(Ok(metadata)) => {
             if !metadata.is_dir() {
                 warn!(
                     "[desktop:http] ERROR: Path is not a directory: {:?}",
@@ -2504,21 +3354,73 @@ async fn change_directory_handler(
                 return Err(StatusCode::BAD_REQUEST);
             }
         }
-        Err(err) => {
+        Ok(Err(err)) => {
             warn!(
                 "[desktop:http] ERROR: Cannot access path: {:?} - {}",
                 resolved_path, err
             );
             return Err(StatusCode::NOT_FOUND);
         }
+        Err(_) => {
+            warn!(
+                "[desktop:http] ERROR: Timed out probing path: {:?}",
+                resolved_path
+            );
+            return Err(StatusCode::GATEWAY_TIMEOUT);
+        }
     }
 
-    if let Ok(canonicalized) = fs::canonicalize(&resolved_path).await {
-        resolved_path = canonicalized;
+    // Resolve `..` and symlinks before the approval check so neither can escape a root.
+    resolved_path =
+        match tokio::time::timeout(DIRECTORY_PROBE_TIMEOUT, fs::canonicalize(&resolved_path)).await
+        {
+            Ok(Ok(canonicalized)) => canonicalized,
+            Ok(Err(err)) => {
+                warn!(
+                    "[desktop:http] ERROR: Cannot resolve path: {:?} - {}",
+                    resolved_path, err
+                );
+                return Err(StatusCode::NOT_FOUND);
+            }
+            Err(_) => {
+                warn!(
+                    "[desktop:http] ERROR: Timed out resolving path: {:?}",
+                    resolved_path
+                );
+                return Err(StatusCode::GATEWAY_TIMEOUT);
+            }
+        };
+
+    let approved_roots = commands::permissions::resolve_approved_roots(&state.settings).await;
+    if !approved_roots.is_empty()
+        && !approved_roots
+            .iter()
+            .any(|root| resolved_path.starts_with(root))
+    {
+        warn!(
+            "[desktop:http] ERROR: Path is outside approved directories: {:?}",
+            resolved_path
+        );
+        return Err(StatusCode::FORBIDDEN);
     }
 
     let path_value = resolved_path.to_string_lossy().to_string();
 
+    // Already the active directory: nothing to persist or tear down.
+    let current_settings = state.settings.load().await.unwrap_or(Value::Null);
+    let already_active = current_settings
+        .get("lastDirectory")
+        .and_then(Value::as_str)
+        == Some(path_value.as_str());
+    if already_active {
+        return Ok(Json(DirectoryChangeResponse {
+            success: true,
+            restarted: false,
+            path: path_value,
+            validation: None,
+        }));
+    }
+
     state
         .settings
         .update(|mut settings| {
@@ -2568,19 +3470,79 @@ async fn change_directory_handler(
                 "lastDirectory".to_string(),
                 Value::String(path_value.clone()),
             );
+            commands::settings::record_recent_directory(
+                &mut settings,
+                &path_value,
+                chrono::Utc::now().timestamp_millis(),
+            );
 
             settings
         })
         .await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    // Watchers belong to the previous directory; the UI re-subscribes for the new one.
+    fs_watcher::unwatch_all();
+
     Ok(Json(DirectoryChangeResponse {
         success: true,
         restarted: false,
         path: path_value,
+        validation: None,
     }))
 }
 
+async fn validate_directory_change(
+    settings: &SettingsStore,
+    mut resolved_path: PathBuf,
+) -> (PathBuf, DirectoryValidationReport) {
+    let mut report = DirectoryValidationReport::default();
+
+    match tokio::time::timeout(DIRECTORY_PROBE_TIMEOUT, fs::metadata(&resolved_path)).await {
+        Ok(Ok(metadata)) => {
+            report.exists = true;
+            report.is_directory = metadata.is_dir();
+            if !report.is_directory {
+                report.errors.push("Path is not a directory".to_string());
+            }
+        }
+        Ok(Err(err)) => {
+            report.errors.push(format!("Cannot access path: {}", err));
+            return (resolved_path, report);
+        }
+        Err(_) => {
+            report
+                .errors
+                .push("Timed out accessing path (unresponsive mount?)".to_string());
+            return (resolved_path, report);
+        }
+    }
+
+    if let Ok(Ok(canonicalized)) =
+        tokio::time::timeout(DIRECTORY_PROBE_TIMEOUT, fs::canonicalize(&resolved_path)).await
+    {
+        resolved_path = canonicalized;
+    }
+
+    // With no approved roots configured, any directory is allowed.
+    let canonical_roots = commands::permissions::resolve_approved_roots(settings).await;
+    report.within_approved_root = canonical_roots.is_empty()
+        || canonical_roots
+            .iter()
+            .any(|root| resolved_path.starts_with(root));
+    if !report.within_approved_root {
+        report
+            .errors
+            .push("Path is outside the approved directories".to_string());
+    }
+
+    if report.is_directory {
+        report.is_git_repository = commands::git::is_git_repository(&resolved_path).await;
+    }
+
+    (resolved_path, report)
+}
+
 async fn proxy_to_opencode(
     State(state): State<ServerState>,
     req: Request,
@@ -2602,6 +3564,7 @@ async fn proxy_to_opencode(
         || origin_path.starts_with("/api/config/commands/")
         || origin_path.starts_with("/api/config/skills")
         || origin_path == "/api/config/reload"
+        || origin_path == "/api/config/validate"
         || is_provider_auth_delete
         || is_provider_source_get;
 
@@ -2609,10 +3572,27 @@ async fn proxy_to_opencode(
         return handle_config_routes(state, &origin_path, method, req).await;
     }
 
-    let port = state.opencode.current_port().ok_or_else(|| {
-        error!("[desktop:http] PROXY FAILED: OpenCode not running (no port)");
-        StatusCode::SERVICE_UNAVAILABLE
-    })?;
+    let request_id = resolve_request_id(req.headers());
+
+    if let Err(err) = state.opencode.ensure_awake().await {
+        error!("[desktop:http] PROXY FAILED [{request_id}]: could not wake OpenCode: {err}");
+        let response = proxy_error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "opencode_wake_failed",
+            "OpenCode could not be restarted after sleeping",
+        );
+        return Ok(with_request_id(response, &request_id));
+    }
+
+    let Some(port) = state.opencode.current_port() else {
+        error!("[desktop:http] PROXY FAILED [{request_id}]: OpenCode not running (no port)");
+        let response = proxy_error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "opencode_not_running",
+            "OpenCode is not running",
+        );
+        return Ok(with_request_id(response, &request_id));
+    };
 
     let query = req.uri().query();
     let rewritten_path = state.opencode.rewrite_path(&origin_path);
@@ -2628,14 +3608,17 @@ async fn proxy_to_opencode(
 
     let mut headers = parts.headers;
     headers.insert(header::HOST, format!("127.0.0.1:{port}").parse().unwrap());
-    if headers
+    let accepts_event_stream = headers
         .get(header::ACCEPT)
         .and_then(|v| v.to_str().ok())
         .map(|val| val.contains("text/event-stream"))
-        .unwrap_or(false)
-    {
+        .unwrap_or(false);
+    if accepts_event_stream {
         headers.insert(header::CONNECTION, "keep-alive".parse().unwrap());
     }
+    if let Ok(value) = header::HeaderValue::from_str(&request_id) {
+        headers.insert(REQUEST_ID_HEADER, value);
+    }
 
     for (key, value) in headers.iter() {
         if key == &header::CONTENT_LENGTH {
@@ -2644,36 +3627,178 @@ async fn proxy_to_opencode(
         builder = builder.header(key, value);
     }
 
-    let body_bytes = to_bytes(body, PROXY_BODY_LIMIT)
-        .await
-        .map_err(|_| StatusCode::BAD_GATEWAY)?;
+    let body_limit = state.proxy_body_limit();
+    let body_bytes = match to_bytes(body, body_limit).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            warn!("[desktop:http] PROXY FAILED [{request_id}]: could not read request body: {err}");
+            let response = if is_body_limit_error(&err) {
+                body_too_large_response(body_limit)
+            } else {
+                proxy_error_response(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_request_body",
+                    "Request body could not be read",
+                )
+            };
+            return Ok(with_request_id(response, &request_id));
+        }
+    };
 
-    let response = if body_bytes.is_empty() {
-        builder.send().await.map_err(|_| StatusCode::BAD_GATEWAY)?
+    let sent = if body_bytes.is_empty() {
+        builder.send().await
     } else {
-        builder
-            .body(ReqwestBody::from(body_bytes))
-            .send()
-            .await
-            .map_err(|_| StatusCode::BAD_GATEWAY)?
+        builder.body(ReqwestBody::from(body_bytes)).send().await
+    };
+    let response = match sent {
+        Ok(response) => response,
+        Err(err) => {
+            let response = proxy_upstream_error(&err, &request_id);
+            return Ok(with_request_id(response, &request_id));
+        }
     };
 
     let status = response.status();
+    let is_event_stream = accepts_event_stream
+        && response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|val| val.starts_with("text/event-stream"));
+    let (keep_alive, activity) = if is_event_stream {
+        (state.sse_keep_alive(), Some(state.opencode.hold_stream()))
+    } else {
+        (None, None)
+    };
     let mut resp_builder = Response::builder().status(status);
     for (key, value) in response.headers() {
-        if key.as_str().eq_ignore_ascii_case("connection") {
+        if key.as_str().eq_ignore_ascii_case("connection") || key == REQUEST_ID_HEADER {
             continue;
         }
         resp_builder = resp_builder.header(key, value);
     }
+    resp_builder = resp_builder.header(REQUEST_ID_HEADER, request_id.as_str());
+
+    let body = Body::from_stream(relay_upstream(
+        response,
+        rewritten_path,
+        request_id,
+        keep_alive,
+        activity,
+    ));
+    resp_builder.body(body).map_err(|_| StatusCode::BAD_GATEWAY)
+}
 
+/// Wrap an OpenCode response body for relaying to the client, with optional SSE keep-alives.
+fn relay_upstream(
+    response: reqwest::Response,
+    path: String,
+    request_id: String,
+    keep_alive: Option<Duration>,
+    activity: Option<StreamActivityGuard>,
+) -> UpstreamStream<impl Stream<Item = std::io::Result<axum::body::Bytes>>> {
     let stream = response.bytes_stream().map(|chunk| {
         chunk
             .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
             .map(axum::body::Bytes::from)
     });
-    let body = Body::from_stream(stream);
-    resp_builder.body(body).map_err(|_| StatusCode::BAD_GATEWAY)
+    UpstreamStream {
+        inner: Some(Box::pin(stream)),
+        finished: false,
+        path,
+        request_id,
+        keep_alive: keep_alive.map(|interval| SseKeepAlive {
+            interval,
+            timer: Box::pin(tokio::time::sleep(interval)),
+            at_event_boundary: true,
+        }),
+        _activity: activity,
+    }
+}
+
+/// Idle timer for a proxied event stream. Comments are only written between events, never
+/// in the middle of one that arrived in several chunks.
+struct SseKeepAlive {
+    interval: Duration,
+    timer: Pin<Box<tokio::time::Sleep>>,
+    at_event_boundary: bool,
+}
+
+impl SseKeepAlive {
+    fn reset(&mut self) {
+        let deadline = tokio::time::Instant::now() + self.interval;
+        self.timer.as_mut().reset(deadline);
+    }
+}
+
+/// Response body relayed from OpenCode. When the client goes away (e.g. the user stops a
+/// streaming response), hyper drops the body before it finishes; the unfinished upstream body
+/// is then released, which closes the connection to OpenCode so it stops generating instead
+/// of running on.
+struct UpstreamStream<S> {
+    /// `None` once the upstream body has been released.
+    inner: Option<Pin<Box<S>>>,
+    finished: bool,
+    path: String,
+    request_id: String,
+    keep_alive: Option<SseKeepAlive>,
+    /// Keeps the idle auto-sleep off while an event stream is open.
+    _activity: Option<StreamActivityGuard>,
+}
+
+impl<S> Stream for UpstreamStream<S>
+where
+    S: Stream<Item = std::io::Result<axum::body::Bytes>>,
+{
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = match self.inner.as_mut() {
+            Some(inner) => inner.as_mut().poll_next(cx),
+            None => return Poll::Ready(None),
+        };
+        match &poll {
+            Poll::Ready(None) => self.finished = true,
+            Poll::Ready(Some(chunk)) => {
+                let ends_event = chunk
+                    .as_ref()
+                    .is_ok_and(|bytes| bytes.ends_with(b"\n\n") || bytes.ends_with(b"\r\n\r\n"));
+                if let Some(keep_alive) = self.keep_alive.as_mut() {
+                    keep_alive.at_event_boundary = ends_event;
+                    keep_alive.reset();
+                }
+            }
+            Poll::Pending => {
+                if let Some(keep_alive) = self.keep_alive.as_mut() {
+                    if keep_alive.timer.as_mut().poll(cx).is_ready() {
+                        keep_alive.reset();
+                        // Poll the re-armed timer so it wakes this task again.
+                        let _ = keep_alive.timer.as_mut().poll(cx);
+                        if keep_alive.at_event_boundary {
+                            return Poll::Ready(Some(Ok(axum::body::Bytes::from_static(
+                                SSE_KEEP_ALIVE_COMMENT,
+                            ))));
+                        }
+                    }
+                }
+            }
+        }
+        poll
+    }
+}
+
+impl<S> Drop for UpstreamStream<S> {
+    fn drop(&mut self) {
+        if !self.finished {
+            info!(
+                "[desktop:http] client disconnected; aborting upstream request {} [{}]",
+                self.path, self.request_id
+            );
+            // An unfinished reqwest body is not returned to the pool; dropping it closes the
+            // connection.
+            self.inner.take();
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -2684,11 +3809,8 @@ pub(crate) struct SettingsStore {
 
 impl SettingsStore {
     pub(crate) fn new() -> Result<Self> {
-        // Use ~/.config/openchamber for consistency with Electron/web versions
-        let home = dirs::home_dir().ok_or_else(|| anyhow!("No home directory"))?;
-        let mut dir = home;
-        dir.push(".config");
-        dir.push("openchamber");
+        let mut dir =
+            path_utils::openchamber_config_dir().ok_or_else(|| anyhow!("No home directory"))?;
         std::fs::create_dir_all(&dir).ok();
         dir.push("settings.json");
         Ok(Self {
@@ -2697,6 +3819,11 @@ impl SettingsStore {
         })
     }
 
+    /// Directory holding `settings.json` and the other OpenChamber config files.
+    pub(crate) fn config_dir(&self) -> Option<&std::path::Path> {
+        self.path.parent()
+    }
+
     pub(crate) async fn load(&self) -> Result<Value> {
         let _lock = self.guard.lock().await;
         match fs::read(&self.path).await {
@@ -2761,3 +3888,48 @@ impl SettingsStore {
         Ok(candidate)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn dropping_the_client_stream_closes_the_upstream_connection() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let upstream = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await.unwrap();
+            socket
+                .write_all(
+                    b"HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\
+                      transfer-encoding: chunked\r\n\r\n7\r\ndata:\n\n\r\n",
+                )
+                .await
+                .unwrap();
+            // The proxy never writes again, so a zero-byte read means it hung up.
+            let mut rest = [0u8; 64];
+            tokio::time::timeout(Duration::from_secs(5), socket.read(&mut rest)).await
+        });
+
+        let response = reqwest::get(format!("http://{addr}/event")).await.unwrap();
+        let mut body = relay_upstream(
+            response,
+            "/event".to_string(),
+            "test".to_string(),
+            None,
+            None,
+        );
+        let first = body.next().await.unwrap().unwrap();
+        assert_eq!(first.as_ref(), b"data:\n\n");
+        drop(body);
+
+        let closed = upstream.await.unwrap();
+        assert!(
+            matches!(closed, Ok(Ok(0))),
+            "upstream connection stayed open: {closed:?}"
+        );
+    }
+}
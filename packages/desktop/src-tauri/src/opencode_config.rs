@@ -765,7 +765,6 @@ fn strip_json_comments(content: &str) -> String {
 }
 
 /// Read merged opencode.json configuration files
-#[allow(dead_code)]
 pub async fn read_config(working_directory: Option<&Path>) -> Result<Value> {
     Ok(read_config_layers(working_directory).await?.merged)
 }
@@ -936,16 +935,12 @@ pub async fn get_agent_sources(
     Ok(sources)
 }
 
-/// Create new agent as .md file
-pub async fn create_agent(
+/// Fail if an agent with this name exists as a project/user .md file or in opencode.json
+fn ensure_agent_absent(
     agent_name: &str,
-    config: &HashMap<String, Value>,
     working_directory: Option<&Path>,
-    scope: Option<AgentScope>,
+    layers: &ConfigLayers,
 ) -> Result<()> {
-    ensure_dirs().await?;
-
-    // Check if agent already exists at either level
     if let Some(wd) = working_directory {
         let project_path = get_project_agent_path(wd, agent_name);
         if project_path.exists() {
@@ -956,23 +951,37 @@ pub async fn create_agent(
         }
     }
 
-    let user_path = get_user_agent_path(agent_name);
-    if user_path.exists() {
+    if get_user_agent_path(agent_name).exists() {
         return Err(anyhow!(
             "Agent {} already exists as user-level .md file",
             agent_name
         ));
     }
 
-    let layers = read_config_layers(working_directory).await?;
-    let json_source = get_json_entry_source(&layers, "agent", agent_name);
-    if json_source.exists {
+    if get_json_entry_source(layers, "agent", agent_name).exists {
         return Err(anyhow!(
             "Agent {} already exists in opencode.json",
             agent_name
         ));
     }
 
+    Ok(())
+}
+
+/// Create new agent as .md file
+pub async fn create_agent(
+    agent_name: &str,
+    config: &HashMap<String, Value>,
+    working_directory: Option<&Path>,
+    scope: Option<AgentScope>,
+) -> Result<()> {
+    ensure_dirs().await?;
+
+    // Check if agent already exists at either level
+    let layers = read_config_layers(working_directory).await?;
+    ensure_agent_absent(agent_name, working_directory, &layers)?;
+    let user_path = get_user_agent_path(agent_name);
+
     // Determine target path based on requested scope
     let (target_scope, target_path) = if scope == Some(AgentScope::Project) {
         if let Some(wd) = working_directory {
@@ -1267,6 +1276,100 @@ pub async fn delete_agent(agent_name: &str, working_directory: Option<&Path>) ->
     Ok(())
 }
 
+/// Why `duplicate_agent` failed, so callers can tell bad input from I/O failures
+#[derive(Debug)]
+pub enum DuplicateAgentError {
+    InvalidName(String),
+    SourceNotFound(String),
+    AlreadyExists(String),
+    Failed(anyhow::Error),
+}
+
+impl std::fmt::Display for DuplicateAgentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidName(message)
+            | Self::SourceNotFound(message)
+            | Self::AlreadyExists(message) => f.write_str(message),
+            Self::Failed(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl From<anyhow::Error> for DuplicateAgentError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::Failed(err)
+    }
+}
+
+impl From<std::io::Error> for DuplicateAgentError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Failed(err.into())
+    }
+}
+
+/// Copy an agent's .md file and opencode.json section to a new name, keeping each in
+/// the scope/config file it came from
+pub async fn duplicate_agent(
+    source_name: &str,
+    dest_name: &str,
+    working_directory: Option<&Path>,
+) -> std::result::Result<ConfigSources, DuplicateAgentError> {
+    ensure_dirs().await?;
+
+    if dest_name.is_empty() || dest_name.contains(['/', '\\']) || dest_name.starts_with('.') {
+        return Err(DuplicateAgentError::InvalidName(format!(
+            "Invalid agent name \"{}\"",
+            dest_name
+        )));
+    }
+
+    let mut layers = read_config_layers(working_directory).await?;
+    ensure_agent_absent(dest_name, working_directory, &layers)
+        .map_err(|err| DuplicateAgentError::AlreadyExists(err.to_string()))?;
+
+    let (md_scope, md_path) = get_agent_scope(source_name, working_directory);
+    let json_source = get_json_entry_source(&layers, "agent", source_name);
+    if md_path.is_none() && !json_source.exists {
+        return Err(DuplicateAgentError::SourceNotFound(format!(
+            "Agent {} has no .md file or opencode.json entry to copy",
+            source_name
+        )));
+    }
+
+    if let Some(source_path) = md_path {
+        let dest_path = match (md_scope, working_directory) {
+            (Some(AgentScope::Project), Some(wd)) => {
+                ensure_project_agent_dir(wd).await?;
+                get_project_agent_path(wd, dest_name)
+            }
+            _ => get_user_agent_path(dest_name),
+        };
+        fs::copy(&source_path, &dest_path).await?;
+        info!(
+            "Duplicated agent .md file: {} -> {}",
+            source_path.display(),
+            dest_path.display()
+        );
+    }
+
+    if let (Some(json_path), Some(section)) = (json_source.path, json_source.section) {
+        let config = get_config_for_path(&mut layers, &json_path);
+        if let Some(agents) = config.get_mut("agent").and_then(|v| v.as_object_mut()) {
+            agents.insert(dest_name.to_string(), section);
+            write_config_at(config, &json_path).await?;
+            info!(
+                "Duplicated agent in {}: {} -> {}",
+                json_path.display(),
+                source_name,
+                dest_name
+            );
+        }
+    }
+
+    Ok(get_agent_sources(dest_name, working_directory).await?)
+}
+
 /// Get information about where command configuration is stored
 pub async fn get_command_sources(
     command_name: &str,
@@ -1657,6 +1760,173 @@ pub async fn delete_command(command_name: &str, working_directory: Option<&Path>
     Ok(())
 }
 
+// ============== CONFIG VALIDATION ==============
+
+/// A single problem found while validating config files
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigProblem {
+    pub path: String,
+    pub message: String,
+}
+
+fn push_problem(problems: &mut Vec<ConfigProblem>, path: &Path, message: impl Into<String>) {
+    problems.push(ConfigProblem {
+        path: path.display().to_string(),
+        message: message.into(),
+    });
+}
+
+/// Check that an "agent"/"command" section maps names to objects whose file references resolve
+fn validate_json_section(
+    config: &Value,
+    section_key: &str,
+    path: &Path,
+    problems: &mut Vec<ConfigProblem>,
+) {
+    let Some(section) = config.get(section_key) else {
+        return;
+    };
+    let Some(entries) = section.as_object() else {
+        push_problem(
+            problems,
+            path,
+            format!("\"{}\" must be an object", section_key),
+        );
+        return;
+    };
+
+    for (name, entry) in entries {
+        let Some(entry) = entry.as_object() else {
+            push_problem(
+                problems,
+                path,
+                format!("\"{}.{}\" must be an object", section_key, name),
+            );
+            continue;
+        };
+        for field in ["prompt", "template"] {
+            let Some(reference) = entry.get(field).and_then(|v| v.as_str()) else {
+                continue;
+            };
+            if !is_prompt_file_reference(reference) {
+                continue;
+            }
+            match resolve_prompt_file_path(reference) {
+                Some(file) if file.exists() => {}
+                _ => push_problem(
+                    problems,
+                    path,
+                    format!(
+                        "\"{}.{}.{}\" references a missing file: {}",
+                        section_key, name, field, reference
+                    ),
+                ),
+            }
+        }
+    }
+}
+
+async fn validate_config_file(path: &Path, problems: &mut Vec<ConfigProblem>) {
+    if !path.exists() {
+        return;
+    }
+    match read_config_file(path).await {
+        Ok(config) => {
+            if !config.is_object() {
+                push_problem(problems, path, "Config root must be an object");
+                return;
+            }
+            validate_json_section(&config, "agent", path, problems);
+            validate_json_section(&config, "command", path, problems);
+        }
+        Err(err) => push_problem(problems, path, err.to_string()),
+    }
+}
+
+/// Check the YAML frontmatter of every .md file in an agent/command directory
+async fn validate_md_dir(dir: &Path, problems: &mut Vec<ConfigProblem>) {
+    let Ok(mut entries) = fs::read_dir(dir).await else {
+        return;
+    };
+    let re = Regex::new(r"(?s)^---\r?\n(.*?)\r?\n---\r?\n").expect("valid regex");
+
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+            continue;
+        }
+        let content = match fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(err) => {
+                push_problem(problems, &path, format!("Failed to read file: {}", err));
+                continue;
+            }
+        };
+
+        let Some(captures) = re.captures(&content) else {
+            if content.starts_with("---") {
+                push_problem(problems, &path, "Frontmatter is missing its closing ---");
+            }
+            continue;
+        };
+        let yaml_str = captures.get(1).map(|m| m.as_str()).unwrap_or("");
+        match serde_yaml::from_str::<serde_yaml::Value>(yaml_str) {
+            Ok(serde_yaml::Value::Mapping(_)) | Ok(serde_yaml::Value::Null) => {}
+            Ok(_) => push_problem(problems, &path, "Frontmatter must be a YAML mapping"),
+            Err(err) => push_problem(
+                problems,
+                &path,
+                format!("Invalid YAML frontmatter: {}", err),
+            ),
+        }
+    }
+}
+
+/// Structurally validate the opencode.json layers and agent/command .md files.
+/// OpenCode has no validate-only mode, so this runs entirely in Rust and never
+/// touches the running instance.
+pub async fn validate_config(working_directory: Option<&Path>) -> Vec<ConfigProblem> {
+    let paths = get_config_paths(working_directory);
+    let mut problems = Vec::new();
+
+    validate_config_file(&paths.user, &mut problems).await;
+    if let Some(ref path) = paths.project {
+        validate_config_file(path, &mut problems).await;
+    }
+    if let Some(ref path) = paths.custom {
+        if path.exists() {
+            validate_config_file(path, &mut problems).await;
+        } else {
+            push_problem(
+                &mut problems,
+                path,
+                "OPENCODE_CONFIG points to a file that does not exist",
+            );
+        }
+    }
+
+    let mut md_dirs = vec![
+        get_agent_dir(),
+        get_legacy_agent_dir(),
+        get_command_dir(),
+        get_legacy_command_dir(),
+    ];
+    if let Some(wd) = working_directory {
+        md_dirs.extend([
+            get_project_agent_dir(wd),
+            get_legacy_project_agent_dir(wd),
+            get_project_command_dir(wd),
+            get_legacy_project_command_dir(wd),
+        ]);
+    }
+    for dir in &md_dirs {
+        validate_md_dir(dir, &mut problems).await;
+    }
+
+    problems
+}
+
 // ============== SKILL SCOPE TYPES ==============
 
 /// Skill scope types
@@ -4,11 +4,12 @@ use once_cell::sync::Lazy;
 use parking_lot::RwLock;
 use regex::Regex;
 use reqwest::Client;
+use serde::Serialize;
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicI64, AtomicUsize, Ordering},
         Arc,
     },
     time::Duration,
@@ -16,7 +17,7 @@ use std::{
 use tokio::{
     io::{AsyncBufReadExt, BufReader},
     process::{Child, Command},
-    sync::Mutex,
+    sync::{watch, Mutex},
     time::timeout,
 };
 
@@ -27,6 +28,26 @@ static URL_REGEX: Lazy<Regex> = Lazy::new(|| {
 const FIRST_SIGNAL_TIMEOUT_MS: u64 = 750;
 const READY_CHECK_TIMEOUT_MS: u64 = 20000;
 const READY_CHECK_INTERVAL_MS: u64 = 400;
+const CLI_VERSION_TIMEOUT_MS: u64 = 5000;
+
+/// Whether OpenCode has been stopped by the idle auto-sleep.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SleepState {
+    Awake,
+    Asleep,
+    /// Restarting after a request arrived while asleep.
+    Waking,
+}
+
+/// Most recent failure to start OpenCode, cleared once a start succeeds.
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenCodeStartupError {
+    pub message: String,
+    /// Unix timestamp in milliseconds.
+    pub at: i64,
+}
 
 #[derive(Clone)]
 pub struct OpenCodeManager {
@@ -38,11 +59,30 @@ pub struct OpenCodeManager {
     child: Arc<Mutex<Option<Child>>>,
     port: Arc<RwLock<Option<u16>>>,
     api_prefix: Arc<RwLock<String>>,
+    api_prefix_override: Arc<RwLock<Option<String>>>,
     is_ready: Arc<AtomicBool>,
     shutting_down: Arc<AtomicBool>,
+    last_error: Arc<RwLock<Option<OpenCodeStartupError>>>,
+    sleep_state: Arc<watch::Sender<SleepState>>,
+    // Serializes sleep/wake so concurrent requests don't spawn OpenCode twice.
+    sleep_lock: Arc<Mutex<()>>,
+    // Proxied traffic, so the idle auto-sleep leaves OpenCode alone while a client uses it.
+    last_request_at: Arc<AtomicI64>,
+    open_streams: Arc<AtomicUsize>,
     http_client: Client,
 }
 
+/// Counts an open proxied event stream as activity until dropped.
+pub struct StreamActivityGuard {
+    open_streams: Arc<AtomicUsize>,
+}
+
+impl Drop for StreamActivityGuard {
+    fn drop(&mut self) {
+        self.open_streams.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 fn normalize_api_prefix(prefix: &str) -> String {
     let trimmed = prefix.trim();
     if trimmed.is_empty() || trimmed == "/" {
@@ -105,8 +145,14 @@ impl OpenCodeManager {
             child: Arc::new(Mutex::new(None)),
             port: Arc::new(RwLock::new(None)),
             api_prefix: Arc::new(RwLock::new(String::new())),
+            api_prefix_override: Arc::new(RwLock::new(None)),
             is_ready: Arc::new(AtomicBool::new(false)),
             shutting_down: Arc::new(AtomicBool::new(false)),
+            last_error: Arc::new(RwLock::new(None)),
+            sleep_state: Arc::new(watch::channel(SleepState::Awake).0),
+            sleep_lock: Arc::new(Mutex::new(())),
+            last_request_at: Arc::new(AtomicI64::new(chrono::Utc::now().timestamp_millis())),
+            open_streams: Arc::new(AtomicUsize::new(0)),
             http_client: Client::builder()
                 .timeout(Duration::from_secs(2))
                 .build()
@@ -119,6 +165,48 @@ impl OpenCodeManager {
     }
 
     pub async fn ensure_running(&self) -> Result<()> {
+        let result = self.start_if_needed().await;
+        match &result {
+            Ok(()) => {
+                *self.last_error.write() = None;
+                self.sleep_state.send_replace(SleepState::Awake);
+            }
+            Err(err) => {
+                *self.last_error.write() = Some(OpenCodeStartupError {
+                    message: err.to_string(),
+                    at: chrono::Utc::now().timestamp_millis(),
+                });
+            }
+        }
+        result
+    }
+
+    pub fn last_error(&self) -> Option<OpenCodeStartupError> {
+        self.last_error.read().clone()
+    }
+
+    /// One-line port/readiness summary for crash reports. Never blocks: the panicking thread
+    /// may be the one holding a lock.
+    pub fn crash_snapshot(&self) -> String {
+        let port = match self.port.try_read() {
+            Some(port) => port.map_or_else(|| "none".to_string(), |port| port.to_string()),
+            None => "unavailable (locked)".to_string(),
+        };
+        let last_error = match self.last_error.try_read() {
+            Some(error) => error
+                .as_ref()
+                .map_or_else(|| "none".to_string(), |error| error.message.clone()),
+            None => "unavailable (locked)".to_string(),
+        };
+        format!(
+            "port={} ready={} last_error={}",
+            port,
+            self.is_ready(),
+            last_error
+        )
+    }
+
+    async fn start_if_needed(&self) -> Result<()> {
         if self.binary.is_none() {
             return Err(anyhow!("OpenCode CLI is not available"));
         }
@@ -177,6 +265,106 @@ impl OpenCodeManager {
         self.graceful_stop().await
     }
 
+    /// Last-resort kill used when `shutdown` doesn't finish in time. Doesn't wait on the child
+    /// lock (it may be held by the stalled shutdown) and falls back to killing by port.
+    pub fn force_kill(&self) {
+        self.shutting_down.store(true, Ordering::SeqCst);
+        self.is_ready.store(false, Ordering::SeqCst);
+
+        if let Ok(mut guard) = self.child.try_lock() {
+            if let Some(child) = guard.as_mut() {
+                if let Err(err) = child.start_kill() {
+                    warn!("[desktop:opencode] failed to force-kill child: {err}");
+                }
+            }
+        }
+        kill_process_on_port(self.current_port());
+    }
+
+    /// Pid of the spawned OpenCode process. Never blocks, so a busy child lock reads as `None`.
+    pub fn child_pid(&self) -> Option<u32> {
+        self.child.try_lock().ok()?.as_ref()?.id()
+    }
+
+    pub fn sleep_state(&self) -> SleepState {
+        *self.sleep_state.borrow()
+    }
+
+    pub fn is_asleep(&self) -> bool {
+        self.sleep_state() != SleepState::Awake
+    }
+
+    /// Wake OpenCode if the idle auto-sleep stopped it, counting the call as activity. Code
+    /// that talks to OpenCode without going through the proxy calls this first.
+    pub async fn ensure_awake(&self) -> Result<()> {
+        self.record_request();
+        if self.is_asleep() {
+            self.wake().await?;
+        }
+        Ok(())
+    }
+
+    /// Note a proxied request for the idle auto-sleep.
+    pub fn record_request(&self) {
+        self.last_request_at
+            .store(chrono::Utc::now().timestamp_millis(), Ordering::SeqCst);
+    }
+
+    /// Unix milliseconds of the last proxied request.
+    pub fn last_request_at(&self) -> i64 {
+        self.last_request_at.load(Ordering::SeqCst)
+    }
+
+    /// Keep OpenCode awake while the returned guard, held by an open event stream, lives.
+    pub fn hold_stream(&self) -> StreamActivityGuard {
+        self.open_streams.fetch_add(1, Ordering::SeqCst);
+        StreamActivityGuard {
+            open_streams: self.open_streams.clone(),
+        }
+    }
+
+    pub fn open_stream_count(&self) -> usize {
+        self.open_streams.load(Ordering::SeqCst)
+    }
+
+    pub fn subscribe_sleep_state(&self) -> watch::Receiver<SleepState> {
+        self.sleep_state.subscribe()
+    }
+
+    /// Stop a running OpenCode to free memory until the next `wake`. The watchdog leaves a
+    /// sleeping instance alone. Returns `false` if it was not running.
+    pub async fn sleep(&self) -> Result<bool> {
+        let _guard = self.sleep_lock.lock().await;
+        if self.is_asleep() || !self.is_ready() {
+            return Ok(false);
+        }
+
+        info!("[desktop:opencode] going to sleep");
+        self.sleep_state.send_replace(SleepState::Asleep);
+        self.is_ready.store(false, Ordering::SeqCst);
+        self.graceful_stop().await?;
+        *self.port.write() = None;
+        *self.api_prefix.write() = String::new();
+        Ok(true)
+    }
+
+    /// Start OpenCode again if it was put to sleep, waiting until it is ready. Returns
+    /// `false` if it was already awake.
+    pub async fn wake(&self) -> Result<bool> {
+        let _guard = self.sleep_lock.lock().await;
+        if !self.is_asleep() {
+            return Ok(false);
+        }
+
+        info!("[desktop:opencode] waking up");
+        self.sleep_state.send_replace(SleepState::Waking);
+        if let Err(err) = self.ensure_running().await {
+            self.sleep_state.send_replace(SleepState::Asleep);
+            return Err(err);
+        }
+        Ok(true)
+    }
+
     #[allow(dead_code)]
     pub async fn set_working_directory(&self, new_dir: PathBuf) -> Result<()> {
         *self.working_dir.write() = new_dir;
@@ -227,10 +415,50 @@ impl OpenCodeManager {
         *self.port.read()
     }
 
+    /// Port requested via `OPENCHAMBER_OPENCODE_PORT`, or `None` when OpenCode picks its own.
+    pub fn desired_port(&self) -> Option<u16> {
+        (self.desired_port > 0).then_some(self.desired_port)
+    }
+
+    /// Output of `opencode --version`, or `None` if the CLI is missing or doesn't answer.
+    pub async fn cli_version(&self) -> Option<String> {
+        let binary = self.binary.as_ref()?;
+        let mut cmd = Command::new(binary);
+        cmd.arg("--version")
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true);
+        for (key, value) in &self.env {
+            cmd.env(key, value);
+        }
+
+        let output = timeout(Duration::from_millis(CLI_VERSION_TIMEOUT_MS), cmd.output())
+            .await
+            .ok()?
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        (!version.is_empty()).then_some(version)
+    }
+
+    /// Effective API prefix: the `opencodeApiPrefix` override when set, else the detected one.
     pub fn api_prefix(&self) -> String {
+        if let Some(prefix) = self.api_prefix_override.read().as_ref() {
+            return prefix.clone();
+        }
         self.api_prefix.read().clone()
     }
 
+    /// Set (or clear with `None`) the prefix prepended to proxied paths. Takes effect on the
+    /// next request; no restart needed.
+    pub fn set_api_prefix_override(&self, prefix: Option<String>) {
+        let normalized = prefix
+            .map(|value| normalize_api_prefix(&value))
+            .filter(|value| !value.is_empty());
+        *self.api_prefix_override.write() = normalized;
+    }
+
     pub fn is_ready(&self) -> bool {
         self.is_ready.load(Ordering::SeqCst)
     }
@@ -261,6 +489,11 @@ impl OpenCodeManager {
             .map(|rest| if rest.is_empty() { "/" } else { rest })
             .unwrap_or(incoming_path)
             .to_string();
+        let result = match self.api_prefix_override.read().as_ref() {
+            Some(prefix) if result == "/" => prefix.clone(),
+            Some(prefix) => format!("{prefix}{result}"),
+            None => result,
+        };
 
         debug!(
             "[opencode_manager] rewrite_path: '{}' -> '{}'",
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub fn expand_tilde_path(value: &str) -> PathBuf {
     let trimmed = value.trim();
@@ -18,3 +18,40 @@ pub fn expand_tilde_path(value: &str) -> PathBuf {
 
     PathBuf::from(trimmed)
 }
+
+fn env_directory(name: &str) -> Option<PathBuf> {
+    let value = std::env::var(name).ok()?;
+    let path = expand_tilde_path(&value);
+    // Relative values are ignored, as the XDG spec asks.
+    path.is_absolute().then_some(path)
+}
+
+/// Directory for settings and the other OpenChamber config files: `$OPENCHAMBER_CONFIG_DIR`,
+/// else `$XDG_CONFIG_HOME/openchamber`, else `~/.config/openchamber` (shared with the
+/// Electron/web versions). An existing `~/.config/openchamber` keeps being used while the
+/// `$XDG_CONFIG_HOME` one doesn't exist yet, so setting the variable doesn't lose settings.
+pub fn openchamber_config_dir() -> Option<PathBuf> {
+    if let Some(dir) = env_directory("OPENCHAMBER_CONFIG_DIR") {
+        return Some(dir);
+    }
+    let legacy = dirs::home_dir().map(|home| home.join(".config").join("openchamber"));
+    if let Some(config_home) = env_directory("XDG_CONFIG_HOME") {
+        let dir = config_home.join("openchamber");
+        return match legacy {
+            Some(legacy) if !dir.exists() && legacy.is_dir() => Some(legacy),
+            _ => Some(dir),
+        };
+    }
+    legacy
+}
+
+/// Create `dir` if needed and check a file can be written in it.
+pub fn check_directory_writable(dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dir)
+        .map_err(|err| format!("Cannot create {}: {err}", dir.display()))?;
+    let probe = dir.join(format!(".write-test-{}", std::process::id()));
+    std::fs::write(&probe, b"")
+        .map_err(|err| format!("Cannot write to {}: {err}", dir.display()))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
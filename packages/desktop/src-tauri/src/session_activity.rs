@@ -1,18 +1,33 @@
-use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::Result;
 use futures_util::TryStreamExt;
 use log::{debug, info, warn};
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tauri::{AppHandle, Emitter};
 use tokio::sync::Mutex;
 use tokio_util::io::StreamReader;
 
+use crate::commands::settings::opencode_idle_sleep_minutes;
+use crate::opencode_manager::SleepState;
 use crate::path_utils::expand_tilde_path;
+use crate::session_read_positions::SessionReadPositionStore;
 use crate::DesktopRuntime;
 
+const OPENCODE_SLEEP_EVENT: &str = "openchamber:opencode-sleep";
+const IDLE_SLEEP_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const SESSION_ABORT_TIMEOUT: Duration = Duration::from_secs(10);
+// Directories are only needed for sessions that may be aborted; past this many entries the
+// idle ones are dropped.
+const SESSION_DIRECTORY_LIMIT: usize = 256;
+
 #[derive(Deserialize)]
 struct EventEnvelope {
     #[serde(rename = "type")]
@@ -35,6 +50,273 @@ pub enum ActivityPhase {
     Cooldown,
 }
 
+#[derive(Clone, Debug)]
+struct SessionActivityEntry {
+    phase: ActivityPhase,
+    /// Unix millis of the last event seen for this session.
+    last_activity_at: i64,
+    /// Unix millis of the last phase transition.
+    phase_changed_at: i64,
+}
+
+type SessionActivityMap = Arc<Mutex<HashMap<String, SessionActivityEntry>>>;
+/// Project directory each session's events came from, when the stream reports it.
+type SessionDirectoryMap = Arc<Mutex<HashMap<String, String>>>;
+
+/// Tracker state shared between the SSE listener and `get_session_activity`.
+#[derive(Clone, Default)]
+pub struct SessionActivityState {
+    sessions: SessionActivityMap,
+    directories: SessionDirectoryMap,
+    /// Session each window's UI is showing (`None` when it has none open), keyed by window
+    /// label, so cleanup never deletes it from under the user.
+    viewed: Arc<Mutex<HashMap<String, Option<String>>>>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionActivityInfo {
+    pub session_id: String,
+    pub phase: &'static str,
+    pub last_activity_at: i64,
+    pub phase_changed_at: i64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelSessionsResult {
+    pub cancelled: usize,
+    pub failed: usize,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionActivitySnapshot {
+    pub sessions: Vec<SessionActivityInfo>,
+    pub busy_count: usize,
+    /// Sessions that just finished and are waiting out the cooldown before going idle.
+    pub cooldown_count: usize,
+}
+
+impl SessionActivityState {
+    pub async fn snapshot(&self) -> SessionActivitySnapshot {
+        let map = self.sessions.lock().await;
+        let mut sessions: Vec<SessionActivityInfo> = map
+            .iter()
+            .map(|(session_id, entry)| SessionActivityInfo {
+                session_id: session_id.clone(),
+                phase: phase_name(&entry.phase),
+                last_activity_at: entry.last_activity_at,
+                phase_changed_at: entry.phase_changed_at,
+            })
+            .collect();
+        sessions.sort_by(|a, b| b.last_activity_at.cmp(&a.last_activity_at));
+
+        let busy_count = map
+            .values()
+            .filter(|entry| entry.phase == ActivityPhase::Busy)
+            .count();
+        let cooldown_count = map
+            .values()
+            .filter(|entry| entry.phase == ActivityPhase::Cooldown)
+            .count();
+
+        SessionActivitySnapshot {
+            sessions,
+            busy_count,
+            cooldown_count,
+        }
+    }
+
+    pub async fn set_viewed_session(&self, window: &str, session_id: Option<&str>) {
+        self.viewed
+            .lock()
+            .await
+            .insert(window.to_string(), session_id.map(str::to_string));
+    }
+
+    pub async fn forget_window(&self, window: &str) {
+        self.viewed.lock().await.remove(window);
+    }
+
+    /// Sessions open in any window, or `None` while no window has reported what it shows.
+    pub async fn viewed_sessions(&self) -> Option<HashSet<String>> {
+        let viewed = self.viewed.lock().await;
+        if viewed.is_empty() {
+            return None;
+        }
+        Some(viewed.values().flatten().cloned().collect())
+    }
+
+    /// Busy sessions paired with their project directory, if known.
+    async fn busy_sessions(&self) -> Vec<(String, Option<String>)> {
+        let busy: Vec<String> = self
+            .sessions
+            .lock()
+            .await
+            .iter()
+            .filter(|(_, entry)| entry.phase == ActivityPhase::Busy)
+            .map(|(session_id, _)| session_id.clone())
+            .collect();
+        let directories = self.directories.lock().await;
+        busy.into_iter()
+            .map(|session_id| {
+                let directory = directories.get(&session_id).cloned();
+                (session_id, directory)
+            })
+            .collect()
+    }
+}
+
+/// Abort every session the tracker currently sees as busy. Safe to call when nothing is
+/// running or OpenCode is down; both simply cancel nothing.
+pub async fn abort_busy_sessions(
+    runtime: &DesktopRuntime,
+    activity: &SessionActivityState,
+) -> CancelSessionsResult {
+    let mut result = CancelSessionsResult {
+        cancelled: 0,
+        failed: 0,
+    };
+    let busy = activity.busy_sessions().await;
+    if busy.is_empty() {
+        return result;
+    }
+
+    let opencode = runtime.opencode_manager();
+    let Some(port) = opencode.current_port() else {
+        return result;
+    };
+    let base = format!("http://127.0.0.1:{port}{}", opencode.api_prefix());
+    let client = match Client::builder().timeout(SESSION_ABORT_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(err) => {
+            warn!("[desktop:activity] Failed to build client for session abort: {err}");
+            result.failed = busy.len();
+            return result;
+        }
+    };
+
+    let requests = busy.iter().map(|(session_id, directory)| {
+        let mut request = client.post(format!("{base}/session/{session_id}/abort"));
+        if let Some(directory) = directory {
+            request = request.query(&[("directory", directory)]);
+        }
+        async move {
+            match request.send().await {
+                Ok(response) if response.status().is_success() => true,
+                Ok(response) => {
+                    warn!(
+                        "[desktop:activity] Abort of session {session_id} returned {}",
+                        response.status()
+                    );
+                    false
+                }
+                Err(err) => {
+                    warn!("[desktop:activity] Abort of session {session_id} failed: {err}");
+                    false
+                }
+            }
+        }
+    });
+    for ok in futures_util::future::join_all(requests).await {
+        if ok {
+            result.cancelled += 1;
+        } else {
+            result.failed += 1;
+        }
+    }
+
+    info!(
+        "[desktop:activity] Cancelled {} session(s), {} failed",
+        result.cancelled, result.failed
+    );
+    result
+}
+
+fn phase_name(phase: &ActivityPhase) -> &'static str {
+    match phase {
+        ActivityPhase::Idle => "idle",
+        ActivityPhase::Busy => "busy",
+        ActivityPhase::Cooldown => "cooldown",
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+struct OpenCodeSleepPayload {
+    state: SleepState,
+}
+
+/// Stop OpenCode once no session has been active and nothing has been proxied to it for
+/// `opencodeIdleSleepMinutes`. The proxy wakes it on the next request; every sleep/wake
+/// transition is emitted to the webview.
+pub fn spawn_idle_sleep_monitor(
+    app: AppHandle,
+    runtime: DesktopRuntime,
+    activity: SessionActivityState,
+) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let opencode = runtime.opencode_manager();
+        let mut shutdown_rx = runtime.subscribe_shutdown();
+        let mut sleep_rx = opencode.subscribe_sleep_state();
+        let mut interval = tokio::time::interval(IDLE_SLEEP_CHECK_INTERVAL);
+        // Idle time only counts while OpenCode is up and auto-sleep is enabled.
+        let mut idle_since = chrono::Utc::now().timestamp_millis();
+
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.recv() => break,
+                changed = sleep_rx.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    let state = *sleep_rx.borrow_and_update();
+                    let _ = app.emit(OPENCODE_SLEEP_EVENT, OpenCodeSleepPayload { state });
+                    idle_since = chrono::Utc::now().timestamp_millis();
+                }
+                _ = interval.tick() => {
+                    let now = chrono::Utc::now().timestamp_millis();
+                    let settings = runtime.settings().load().await.unwrap_or(Value::Null);
+                    let Some(minutes) = opencode_idle_sleep_minutes(&settings) else {
+                        idle_since = now;
+                        continue;
+                    };
+                    if opencode.is_asleep() || !opencode.is_ready() {
+                        idle_since = now;
+                        continue;
+                    }
+
+                    let snapshot = activity.snapshot().await;
+                    // A connected client keeps its event stream open; it is not idle.
+                    if snapshot.busy_count > 0
+                        || snapshot.cooldown_count > 0
+                        || opencode.open_stream_count() > 0
+                    {
+                        idle_since = now;
+                        continue;
+                    }
+                    let last_activity = snapshot
+                        .sessions
+                        .iter()
+                        .map(|session| session.last_activity_at)
+                        .chain([opencode.last_request_at(), idle_since])
+                        .max()
+                        .unwrap_or(idle_since);
+                    if now - last_activity < (minutes * 60_000) as i64 {
+                        continue;
+                    }
+
+                    info!("[desktop:activity] No session activity for {minutes} min; stopping OpenCode");
+                    if let Err(err) = opencode.sleep().await {
+                        warn!("[desktop:activity] Failed to put OpenCode to sleep: {err}");
+                    }
+                }
+            }
+        }
+    })
+}
+
 #[derive(Clone, Debug)]
 enum SseScope {
     Global,
@@ -44,6 +326,8 @@ enum SseScope {
 pub fn spawn_session_activity_tracker(
     app: AppHandle,
     runtime: DesktopRuntime,
+    activity: SessionActivityState,
+    read_positions: SessionReadPositionStore,
 ) -> tauri::async_runtime::JoinHandle<()> {
     tauri::async_runtime::spawn(async move {
         let client = Client::builder()
@@ -53,7 +337,8 @@ pub fn spawn_session_activity_tracker(
             .expect("failed to build reqwest client");
 
         let mut shutdown_rx = runtime.subscribe_shutdown();
-        let phases = Arc::new(Mutex::new(HashMap::<String, ActivityPhase>::new()));
+        let phases = activity.sessions.clone();
+        let directories = activity.directories.clone();
         let cooldowns = Arc::new(Mutex::new(HashMap::<
             String,
             tauri::async_runtime::JoinHandle<()>,
@@ -69,7 +354,7 @@ pub fn spawn_session_activity_tracker(
                     // Reset stale phases to idle before connecting so UI doesn't stay stuck on "working" after wake.
                     reset_and_emit_all_phases(&app, phases.clone(), cooldowns.clone()).await;
 
-                    if let Err(err) = run_once(&app, &runtime, &client, phases.clone(), cooldowns.clone()).await {
+                    if let Err(err) = run_once(&app, &runtime, &client, phases.clone(), directories.clone(), cooldowns.clone(), &read_positions).await {
                         warn!("[desktop:activity] SSE loop error: {err:?}");
                     }
                     tokio::time::sleep(Duration::from_secs(2)).await;
@@ -83,8 +368,10 @@ async fn run_once(
     app: &AppHandle,
     runtime: &DesktopRuntime,
     client: &Client,
-    phases: Arc<Mutex<HashMap<String, ActivityPhase>>>,
+    phases: SessionActivityMap,
+    directories: SessionDirectoryMap,
     cooldowns: Arc<Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>>,
+    read_positions: &SessionReadPositionStore,
 ) -> Result<()> {
     let opencode = runtime.opencode_manager();
 
@@ -162,8 +449,20 @@ async fn run_once(
             data_lines.clear();
 
             match parse_event_envelope(&raw) {
-                Ok((event, _directory)) => {
-                    handle_event(app, event, phases.clone(), cooldowns.clone()).await
+                Ok((event, directory)) => {
+                    if event.event_type == "session.deleted" {
+                        if let Some(id) =
+                            event.properties.pointer("/info/id").and_then(Value::as_str)
+                        {
+                            read_positions.remove(&[id.to_string()]);
+                        }
+                    }
+                    let session_id = event_session_id(&event);
+                    handle_event(app, event, phases.clone(), cooldowns.clone()).await;
+                    if let (Some(directory), Some(session_id)) = (directory, session_id) {
+                        remember_session_directory(&directories, &phases, session_id, directory)
+                            .await;
+                    }
                 }
                 Err(err) => warn!("[desktop:activity] Failed to parse SSE data: {err}; raw={raw}"),
             };
@@ -187,7 +486,36 @@ fn parse_event_envelope(raw: &str) -> Result<(EventEnvelope, Option<String>)> {
     Ok((multiplexed.payload, multiplexed.directory))
 }
 
-async fn resolve_project_directory_from_settings(runtime: &DesktopRuntime) -> Option<PathBuf> {
+async fn remember_session_directory(
+    directories: &SessionDirectoryMap,
+    phases: &SessionActivityMap,
+    session_id: String,
+    directory: String,
+) {
+    let mut directories = directories.lock().await;
+    directories.insert(session_id, directory);
+    if directories.len() > SESSION_DIRECTORY_LIMIT {
+        let phases = phases.lock().await;
+        directories.retain(|session_id, _| {
+            phases
+                .get(session_id)
+                .is_some_and(|entry| entry.phase != ActivityPhase::Idle)
+        });
+    }
+}
+
+fn event_session_id(event: &EventEnvelope) -> Option<String> {
+    event
+        .properties
+        .get("sessionID")
+        .or_else(|| event.properties.get("info")?.get("sessionID"))
+        .and_then(Value::as_str)
+        .map(|s| s.to_string())
+}
+
+pub(crate) async fn resolve_project_directory_from_settings(
+    runtime: &DesktopRuntime,
+) -> Option<PathBuf> {
     let settings = runtime.settings().load().await.ok()?;
 
     if let Some(active_id) = settings.get("activeProjectId").and_then(Value::as_str) {
@@ -286,7 +614,7 @@ async fn try_connect_sse(
 async fn handle_event(
     app: &AppHandle,
     event: EventEnvelope,
-    phases: Arc<Mutex<HashMap<String, ActivityPhase>>>,
+    phases: SessionActivityMap,
     cooldowns: Arc<Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>>,
 ) {
     match event.event_type.as_str() {
@@ -408,10 +736,16 @@ fn has_finish_stop(info: &Value) -> bool {
 async fn enter_cooldown_if_busy(
     app: &AppHandle,
     session_id: &str,
-    phases: Arc<Mutex<HashMap<String, ActivityPhase>>>,
+    phases: SessionActivityMap,
     cooldowns: Arc<Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>>,
 ) {
-    let current = { phases.lock().await.get(session_id).cloned() };
+    let current = {
+        phases
+            .lock()
+            .await
+            .get(session_id)
+            .map(|entry| entry.phase.clone())
+    };
     if !matches!(current, Some(ActivityPhase::Busy)) {
         return;
     }
@@ -431,7 +765,13 @@ async fn enter_cooldown_if_busy(
     let id_clone = session_id.to_string();
     let handle = tauri::async_runtime::spawn(async move {
         tokio::time::sleep(Duration::from_secs(2)).await;
-        let current = { phases_clone.lock().await.get(&id_clone).cloned() };
+        let current = {
+            phases_clone
+                .lock()
+                .await
+                .get(&id_clone)
+                .map(|entry| entry.phase.clone())
+        };
         if matches!(current, Some(ActivityPhase::Cooldown)) {
             set_phase(
                 &app_clone,
@@ -455,16 +795,29 @@ async fn set_phase(
     app: &AppHandle,
     session_id: &str,
     phase: ActivityPhase,
-    phases: Arc<Mutex<HashMap<String, ActivityPhase>>>,
+    phases: SessionActivityMap,
     cooldowns: Arc<Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>>,
 ) {
     {
         let mut map = phases.lock().await;
-        let current = map.get(session_id);
-        if current == Some(&phase) {
-            return;
+        let now = chrono::Utc::now().timestamp_millis();
+        if let Some(entry) = map.get_mut(session_id) {
+            entry.last_activity_at = now;
+            if entry.phase == phase {
+                return;
+            }
+            entry.phase = phase.clone();
+            entry.phase_changed_at = now;
+        } else {
+            map.insert(
+                session_id.to_string(),
+                SessionActivityEntry {
+                    phase: phase.clone(),
+                    last_activity_at: now,
+                    phase_changed_at: now,
+                },
+            );
         }
-        map.insert(session_id.to_string(), phase.clone());
 
         // Cancel cooldown timer when leaving cooldown
         if !matches!(phase, ActivityPhase::Cooldown) {
@@ -477,11 +830,7 @@ async fn set_phase(
     // Emit to webview so UI stays in sync
     let payload = serde_json::json!({
         "sessionId": session_id,
-        "phase": match phase {
-            ActivityPhase::Idle => "idle",
-            ActivityPhase::Busy => "busy",
-            ActivityPhase::Cooldown => "cooldown",
-        }
+        "phase": phase_name(&phase),
     });
 
     let _ = app.emit("openchamber:session-activity", payload);
@@ -489,7 +838,7 @@ async fn set_phase(
 
 async fn reset_and_emit_all_phases(
     app: &AppHandle,
-    phases: Arc<Mutex<HashMap<String, ActivityPhase>>>,
+    phases: SessionActivityMap,
     cooldowns: Arc<Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>>,
 ) {
     // Cancel any cooldown timers and set all phases to idle to avoid stale "busy" after wake.
@@ -501,26 +850,26 @@ async fn reset_and_emit_all_phases(
         cd.clear();
     }
 
-    let snapshot = {
+    let snapshot: Vec<String> = {
         let mut guard = phases.lock().await;
-        for value in guard.values_mut() {
-            *value = ActivityPhase::Idle;
+        let now = chrono::Utc::now().timestamp_millis();
+        for entry in guard.values_mut() {
+            if entry.phase != ActivityPhase::Idle {
+                entry.phase = ActivityPhase::Idle;
+                entry.phase_changed_at = now;
+            }
         }
-        guard.clone()
+        guard.keys().cloned().collect()
     };
 
     if snapshot.is_empty() {
         return;
     }
 
-    for (session_id, phase) in snapshot {
+    for session_id in snapshot {
         let payload = serde_json::json!({
             "sessionId": session_id,
-            "phase": match phase {
-                ActivityPhase::Idle => "idle",
-                ActivityPhase::Busy => "busy",
-                ActivityPhase::Cooldown => "cooldown",
-            }
+            "phase": phase_name(&ActivityPhase::Idle),
         });
         let _ = app.emit("openchamber:session-activity", payload);
     }
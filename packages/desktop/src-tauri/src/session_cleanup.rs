@@ -0,0 +1,268 @@
+use std::{collections::HashSet, time::Duration};
+
+use log::{info, warn};
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::commands::settings::auto_delete_after_days;
+use crate::session_activity::SessionActivityState;
+use crate::session_read_positions::SessionReadPositionStore;
+use crate::DesktopRuntime;
+
+const SESSIONS_AUTO_DELETED_EVENT: &str = "openchamber:sessions-auto-deleted";
+// Give OpenCode time to come up before the launch run.
+const SESSION_CLEANUP_STARTUP_DELAY: Duration = Duration::from_secs(120);
+const SESSION_CLEANUP_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+// Retry delay when OpenCode or the UI was not ready for a run.
+const SESSION_CLEANUP_RETRY_DELAY: Duration = Duration::from_secs(5 * 60);
+const SESSION_CLEANUP_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+// Matches AUTO_DELETE_KEEP_RECENT in the UI: the newest sessions of each project directory
+// are never deleted.
+const SESSION_CLEANUP_KEEP_RECENT: usize = 5;
+const DAY_MS: i64 = 24 * 60 * 60 * 1000;
+
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionCleanupSummary {
+    pub deleted_ids: Vec<String>,
+    pub failed_ids: Vec<String>,
+    pub cutoff_days: u64,
+}
+
+/// Why a cleanup run did not happen.
+enum CleanupSkipped {
+    /// Auto-delete is turned off.
+    Disabled,
+    /// OpenCode is not running or no window has reported its current session yet.
+    NotReady,
+}
+
+struct CleanupCandidate {
+    id: String,
+    directory: Option<String>,
+    last_activity: i64,
+    shared: bool,
+}
+
+fn session_last_activity(session: &Value) -> i64 {
+    session
+        .pointer("/time/updated")
+        .or_else(|| session.pointer("/time/created"))
+        .and_then(Value::as_i64)
+        .unwrap_or(0)
+}
+
+fn project_directories(settings: &Value) -> Vec<Option<String>> {
+    let directories: Vec<Option<String>> = settings
+        .get("projects")
+        .and_then(Value::as_array)
+        .map(|projects| {
+            projects
+                .iter()
+                .filter_map(|project| project.get("path").and_then(Value::as_str))
+                .map(|path| Some(path.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    if directories.is_empty() {
+        // No projects yet: list whatever OpenCode serves from its own working directory.
+        vec![None]
+    } else {
+        directories
+    }
+}
+
+async fn list_sessions(
+    client: &Client,
+    base: &str,
+    directory: Option<&str>,
+) -> Result<Vec<Value>, String> {
+    let mut request = client.get(format!("{base}/session"));
+    if let Some(directory) = directory {
+        request = request.query(&[("directory", directory)]);
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to list sessions: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "OpenCode returned {} for session list",
+            response.status()
+        ));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Invalid session list response: {}", e))
+}
+
+/// Delete sessions idle longer than `autoDeleteAfterDays`, keeping the newest few of each
+/// project directory, shared sessions, the sessions open in any window and anything the
+/// activity tracker sees as running or cooling down. Reading a session counts as activity.
+async fn run_session_cleanup(
+    runtime: &DesktopRuntime,
+    activity: &SessionActivityState,
+    read_positions: &SessionReadPositionStore,
+) -> Result<SessionCleanupSummary, CleanupSkipped> {
+    let settings = runtime.settings().load().await.unwrap_or(Value::Null);
+    let cutoff_days = auto_delete_after_days(&settings).ok_or(CleanupSkipped::Disabled)?;
+
+    let opencode = runtime.opencode_manager();
+    let port = match opencode.current_port() {
+        Some(port) if opencode.is_ready() && !opencode.is_asleep() => port,
+        _ => {
+            info!("[desktop:cleanup] OpenCode is not running, postponing session cleanup");
+            return Err(CleanupSkipped::NotReady);
+        }
+    };
+    // Without a report the session on screen is unknown, so nothing is safe to delete.
+    let Some(viewed) = activity.viewed_sessions().await else {
+        info!("[desktop:cleanup] Current session not reported yet, postponing session cleanup");
+        return Err(CleanupSkipped::NotReady);
+    };
+    let base = format!("http://127.0.0.1:{port}{}", opencode.api_prefix());
+    let client = match Client::builder()
+        .timeout(SESSION_CLEANUP_REQUEST_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(err) => {
+            warn!("[desktop:cleanup] Failed to build client for session cleanup: {err}");
+            return Err(CleanupSkipped::NotReady);
+        }
+    };
+
+    let mut seen = HashSet::new();
+    let mut protected = HashSet::new();
+    let mut candidates = Vec::new();
+    for directory in project_directories(&settings) {
+        let sessions = match list_sessions(&client, &base, directory.as_deref()).await {
+            Ok(sessions) => sessions,
+            Err(err) => {
+                warn!("[desktop:cleanup] {err}");
+                continue;
+            }
+        };
+        let mut listed: Vec<CleanupCandidate> = sessions
+            .iter()
+            .filter_map(|session| {
+                let id = session.get("id").and_then(Value::as_str)?;
+                Some(CleanupCandidate {
+                    id: id.to_string(),
+                    directory: directory.clone(),
+                    last_activity: read_positions
+                        .get(id)
+                        .map_or(0, |position| position.updated_at)
+                        .max(session_last_activity(session)),
+                    shared: session.get("share").is_some_and(|share| !share.is_null()),
+                })
+            })
+            .collect();
+        listed.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+        for (index, candidate) in listed.into_iter().enumerate() {
+            if index < SESSION_CLEANUP_KEEP_RECENT {
+                protected.insert(candidate.id.clone());
+            }
+            if seen.insert(candidate.id.clone()) {
+                candidates.push(candidate);
+            }
+        }
+    }
+
+    let mut active: HashSet<String> = activity
+        .snapshot()
+        .await
+        .sessions
+        .into_iter()
+        .filter(|session| session.phase != "idle")
+        .map(|session| session.session_id)
+        .collect();
+    active.extend(viewed);
+    let cutoff = chrono::Utc::now().timestamp_millis() - cutoff_days as i64 * DAY_MS;
+
+    let mut summary = SessionCleanupSummary {
+        cutoff_days,
+        ..Default::default()
+    };
+    for candidate in &candidates {
+        if candidate.shared
+            || protected.contains(&candidate.id)
+            || active.contains(&candidate.id)
+            || candidate.last_activity == 0
+            || candidate.last_activity >= cutoff
+        {
+            continue;
+        }
+
+        let mut request = client.delete(format!("{base}/session/{}", candidate.id));
+        if let Some(directory) = candidate.directory.as_deref() {
+            request = request.query(&[("directory", directory)]);
+        }
+        match request.send().await {
+            Ok(response) if response.status().is_success() => {
+                info!("[desktop:cleanup] Deleted session {}", candidate.id);
+                summary.deleted_ids.push(candidate.id.clone());
+            }
+            Ok(response) => {
+                warn!(
+                    "[desktop:cleanup] Deleting session {} returned {}",
+                    candidate.id,
+                    response.status()
+                );
+                summary.failed_ids.push(candidate.id.clone());
+            }
+            Err(err) => {
+                warn!(
+                    "[desktop:cleanup] Deleting session {} failed: {err}",
+                    candidate.id
+                );
+                summary.failed_ids.push(candidate.id.clone());
+            }
+        }
+    }
+
+    info!(
+        "[desktop:cleanup] Deleted {} session(s) idle for more than {} day(s), {} failed",
+        summary.deleted_ids.len(),
+        cutoff_days,
+        summary.failed_ids.len()
+    );
+    Ok(summary)
+}
+
+/// Run session auto-delete once shortly after launch and then daily, while
+/// `autoDeleteEnabled` is on. A run that finds OpenCode or the UI not ready is retried after a
+/// few minutes instead of waiting a day.
+pub fn spawn_session_auto_cleanup(
+    app: AppHandle,
+    runtime: DesktopRuntime,
+    activity: SessionActivityState,
+    read_positions: SessionReadPositionStore,
+) -> tauri::async_runtime::JoinHandle<()> {
+    tauri::async_runtime::spawn(async move {
+        let mut shutdown_rx = runtime.subscribe_shutdown();
+        let mut delay = SESSION_CLEANUP_STARTUP_DELAY;
+
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.recv() => break,
+                _ = tokio::time::sleep(delay) => {}
+            }
+
+            delay = match run_session_cleanup(&runtime, &activity, &read_positions).await {
+                Ok(summary) => {
+                    read_positions.remove(&summary.deleted_ids);
+                    if !summary.deleted_ids.is_empty() || !summary.failed_ids.is_empty() {
+                        let _ = app.emit(SESSIONS_AUTO_DELETED_EVENT, summary);
+                    }
+                    SESSION_CLEANUP_INTERVAL
+                }
+                Err(CleanupSkipped::NotReady) => SESSION_CLEANUP_RETRY_DELAY,
+                Err(CleanupSkipped::Disabled) => SESSION_CLEANUP_INTERVAL,
+            };
+        }
+    })
+}
@@ -0,0 +1,163 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use log::warn;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use tokio::fs as async_fs;
+
+const READ_POSITIONS_FILE: &str = "session-read-positions.json";
+// Scrolling reports positions many times a second; write once it has been quiet this long.
+const READ_POSITIONS_FLUSH_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionReadPosition {
+    pub message_id: String,
+    /// Unix timestamp in milliseconds.
+    pub updated_at: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ReadPositionsFile {
+    #[serde(default)]
+    positions: HashMap<String, SessionReadPosition>,
+}
+
+struct ReadPositionsInner {
+    positions: HashMap<String, SessionReadPosition>,
+    /// When the pending write is due; every change pushes it back.
+    flush_due: Option<Instant>,
+}
+
+/// Last-read message per session, kept in memory and written to disk once changes settle.
+#[derive(Clone)]
+pub struct SessionReadPositionStore {
+    inner: Arc<Mutex<ReadPositionsInner>>,
+    // Held across a write so `flush` on shutdown waits for one already in progress.
+    write_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+fn positions_file_path() -> Result<PathBuf> {
+    let mut path =
+        crate::path_utils::openchamber_config_dir().ok_or_else(|| anyhow!("No home directory"))?;
+    path.push(READ_POSITIONS_FILE);
+    Ok(path)
+}
+
+async fn load_positions() -> Result<HashMap<String, SessionReadPosition>> {
+    let path = positions_file_path()?;
+    match async_fs::read(&path).await {
+        Ok(bytes) => {
+            let file: ReadPositionsFile = serde_json::from_slice(&bytes)?;
+            Ok(file.positions)
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(HashMap::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+async fn write_positions(positions: HashMap<String, SessionReadPosition>) -> Result<()> {
+    let path = positions_file_path()?;
+    if let Some(parent) = path.parent() {
+        async_fs::create_dir_all(parent).await?;
+    }
+    let data = serde_json::to_vec_pretty(&ReadPositionsFile { positions })?;
+    let tmp = path.with_extension("json.tmp");
+    async_fs::write(&tmp, data).await?;
+    async_fs::rename(&tmp, &path).await?;
+    Ok(())
+}
+
+impl SessionReadPositionStore {
+    /// Load stored positions; a missing or unreadable file starts empty.
+    pub async fn load() -> Self {
+        let positions = load_positions().await.unwrap_or_else(|err| {
+            warn!("[desktop:read-positions] Failed to load read positions: {err}");
+            HashMap::new()
+        });
+        Self {
+            inner: Arc::new(Mutex::new(ReadPositionsInner {
+                positions,
+                flush_due: None,
+            })),
+            write_lock: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    pub fn get(&self, session_id: &str) -> Option<SessionReadPosition> {
+        self.inner.lock().positions.get(session_id).cloned()
+    }
+
+    pub fn set(&self, session_id: &str, message_id: &str) {
+        let position = SessionReadPosition {
+            message_id: message_id.to_string(),
+            updated_at: chrono::Utc::now().timestamp_millis(),
+        };
+        self.inner
+            .lock()
+            .positions
+            .insert(session_id.to_string(), position);
+        self.schedule_flush();
+    }
+
+    /// Forget the positions of deleted sessions.
+    pub fn remove(&self, session_ids: &[String]) {
+        let removed = {
+            let mut inner = self.inner.lock();
+            session_ids
+                .iter()
+                .filter(|id| inner.positions.remove(id.as_str()).is_some())
+                .count()
+        };
+        if removed > 0 {
+            self.schedule_flush();
+        }
+    }
+
+    fn schedule_flush(&self) {
+        let already_scheduled = {
+            let mut inner = self.inner.lock();
+            let scheduled = inner.flush_due.is_some();
+            inner.flush_due = Some(Instant::now() + READ_POSITIONS_FLUSH_DELAY);
+            scheduled
+        };
+        if already_scheduled {
+            return;
+        }
+
+        let store = self.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                let due = store.inner.lock().flush_due;
+                match due {
+                    Some(due) if due > Instant::now() => tokio::time::sleep_until(due.into()).await,
+                    Some(_) => break,
+                    // Already written by `flush`.
+                    None => return,
+                }
+            }
+            store.flush().await;
+        });
+    }
+
+    /// Write pending changes now. Called on shutdown so the last positions aren't lost.
+    pub async fn flush(&self) {
+        let _guard = self.write_lock.lock().await;
+        let snapshot = {
+            let mut inner = self.inner.lock();
+            if inner.flush_due.take().is_none() {
+                return;
+            }
+            inner.positions.clone()
+        };
+        if let Err(err) = write_positions(snapshot).await {
+            warn!("[desktop:read-positions] Failed to save read positions: {err}");
+        }
+    }
+}
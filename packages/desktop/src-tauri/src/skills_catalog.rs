@@ -20,6 +20,11 @@ static AUTH_ERROR_RE: Lazy<Regex> = Lazy::new(|| {
 });
 
 const CACHE_TTL: Duration = Duration::from_secs(30 * 60);
+// Catalogs track the default branch of their repository.
+const CATALOG_CLONE_REF: &str = "HEAD";
+
+/// Serializes git operations on the persistent catalog clones.
+static CATALOG_CLONE_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -181,9 +186,8 @@ struct GitIdentityProfile {
 }
 
 fn identities_storage_path() -> Result<PathBuf> {
-    let mut path = dirs::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))?;
-    path.push(".config");
-    path.push("openchamber");
+    let mut path = crate::path_utils::openchamber_config_dir()
+        .ok_or_else(|| anyhow!("Could not find home directory"))?;
     path.push("git-identities.json");
     Ok(path)
 }
@@ -444,11 +448,83 @@ async fn safe_rm(dir: &Path) {
     let _ = tokio::fs::remove_dir_all(dir).await;
 }
 
+/// Persistent clone of a catalog repository, keyed by repository and ref.
+fn catalog_clone_dir(normalized_repo: &str, git_ref: &str) -> Option<PathBuf> {
+    let name = format!("{}@{}", normalized_repo, git_ref).replace(['/', '\\', ':'], "_");
+    Some(
+        crate::path_utils::openchamber_config_dir()?
+            .join("cache")
+            .join("skill-catalogs")
+            .join(name),
+    )
+}
+
+/// Bring the clone in `dir` up to date with `git_ref`, cloning it first if missing. A clone
+/// that cannot be updated for reasons other than authentication is cloned again.
+async fn sync_catalog_clone(
+    clone_url: &str,
+    dir: &Path,
+    git_ref: &str,
+    ssh_key: Option<&str>,
+) -> Result<()> {
+    if dir.join(".git").is_dir() {
+        let repo_dir = dir.display().to_string();
+        let in_repo = |args: &[&str]| -> Vec<String> {
+            ["-C", repo_dir.as_str()]
+                .iter()
+                .chain(args)
+                .map(|arg| arg.to_string())
+                .collect()
+        };
+        let cwd = std::env::temp_dir();
+        let updated = async {
+            run_git(
+                &in_repo(&["remote", "set-url", "origin", clone_url]),
+                &cwd,
+                ssh_key,
+                Duration::from_secs(15),
+            )
+            .await?;
+            run_git(
+                &in_repo(&["fetch", "--depth", "1", "origin", git_ref]),
+                &cwd,
+                ssh_key,
+                Duration::from_secs(60),
+            )
+            .await?;
+            run_git(
+                &in_repo(&["reset", "--hard", "FETCH_HEAD"]),
+                &cwd,
+                ssh_key,
+                Duration::from_secs(60),
+            )
+            .await
+        }
+        .await;
+
+        match updated {
+            Ok(_) => return Ok(()),
+            Err(err) if AUTH_ERROR_RE.is_match(&err.to_string()) => return Err(err),
+            Err(_) => safe_rm(dir).await,
+        }
+    }
+
+    if let Some(parent) = dir.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .context("Failed to create catalog cache directory")?;
+    }
+    clone_repo(clone_url, dir, ssh_key).await
+}
+
+/// Enumerate the skills of a repository. With `cached`, the persistent clone from
+/// `catalog_clone_dir` is updated and kept; otherwise a temporary clone is made and removed.
 async fn scan_repo_items(
     source: &str,
     subpath: Option<&str>,
     default_subpath: Option<&str>,
     ssh_key: Option<&str>,
+    cached: bool,
 ) -> Result<(
     String,
     Option<String>,
@@ -474,22 +550,38 @@ async fn scan_repo_items(
         parsed.clone_https.clone()
     };
 
-    let temp_base = std::env::temp_dir().join(format!(
-        "openchamber-desktop-skills-scan-{}",
-        Uuid::new_v4()
-    ));
-
-    // Clone into temp_base (directory must not exist for git clone target)
-    let _ = tokio::fs::remove_dir_all(&temp_base).await;
-
-    let clone_res = clone_repo(&clone_url, &temp_base, ssh_key).await;
-    if let Err(err) = clone_res {
-        let msg = err.to_string();
-        if AUTH_ERROR_RE.is_match(&msg) {
-            return Err(anyhow!("AUTH_REQUIRED"));
+    let _clone_guard = if cached {
+        Some(CATALOG_CLONE_LOCK.lock().await)
+    } else {
+        None
+    };
+    let clone_res = if cached {
+        let repo_dir = catalog_clone_dir(&parsed.normalized_repo, CATALOG_CLONE_REF)
+            .ok_or_else(|| anyhow!("Config directory unavailable"))?;
+        sync_catalog_clone(&clone_url, &repo_dir, CATALOG_CLONE_REF, ssh_key)
+            .await
+            .map(|_| repo_dir)
+    } else {
+        let repo_dir = std::env::temp_dir().join(format!(
+            "openchamber-desktop-skills-scan-{}",
+            Uuid::new_v4()
+        ));
+        // The clone target must not exist yet.
+        safe_rm(&repo_dir).await;
+        clone_repo(&clone_url, &repo_dir, ssh_key)
+            .await
+            .map(|_| repo_dir)
+    };
+    let repo_dir = match clone_res {
+        Ok(repo_dir) => repo_dir,
+        Err(err) => {
+            let msg = err.to_string();
+            if AUTH_ERROR_RE.is_match(&msg) {
+                return Err(anyhow!("AUTH_REQUIRED"));
+            }
+            return Err(anyhow!(msg));
         }
-        return Err(anyhow!(msg));
-    }
+    };
 
     // Fast path: sparse checkout only SKILL.md files, then read them from disk.
     // This avoids spawning `git show` per skill.
@@ -502,7 +594,7 @@ async fn scan_repo_items(
     let sparse_init = run_git(
         &vec![
             "-C".to_string(),
-            temp_base.display().to_string(),
+            repo_dir.display().to_string(),
             "sparse-checkout".to_string(),
             "init".to_string(),
             "--no-cone".to_string(),
@@ -518,7 +610,7 @@ async fn scan_repo_items(
     if sparse_init.is_ok() {
         let mut set_args = vec![
             "-C".to_string(),
-            temp_base.display().to_string(),
+            repo_dir.display().to_string(),
             "sparse-checkout".to_string(),
             "set".to_string(),
         ];
@@ -535,7 +627,7 @@ async fn scan_repo_items(
             let checkout = run_git(
                 &vec![
                     "-C".to_string(),
-                    temp_base.display().to_string(),
+                    repo_dir.display().to_string(),
                     "checkout".to_string(),
                     "--force".to_string(),
                     "HEAD".to_string(),
@@ -550,7 +642,7 @@ async fn scan_repo_items(
                 let ls_files = run_git(
                     &vec![
                         "-C".to_string(),
-                        temp_base.display().to_string(),
+                        repo_dir.display().to_string(),
                         "ls-files".to_string(),
                     ],
                     &std::env::temp_dir(),
@@ -575,7 +667,7 @@ async fn scan_repo_items(
     if skill_md_paths.is_empty() {
         let mut list_args = vec![
             "-C".to_string(),
-            temp_base.display().to_string(),
+            repo_dir.display().to_string(),
             "ls-tree".to_string(),
             "-r".to_string(),
             "--name-only".to_string(),
@@ -597,7 +689,9 @@ async fn scan_repo_items(
         let stdout = match list_out {
             Ok((out, _)) => out,
             Err(_) => {
-                safe_rm(&temp_base).await;
+                if !cached {
+                    safe_rm(&repo_dir).await;
+                }
                 return Ok((parsed.normalized_repo, effective_subpath, vec![]));
             }
         };
@@ -647,14 +741,14 @@ async fn scan_repo_items(
             format!("{}/SKILL.md", skill_dir)
         };
 
-        let skill_md_fs_path = repo_path_to_fs(&temp_base, &skill_md_repo_path);
+        let skill_md_fs_path = repo_path_to_fs(&repo_dir, &skill_md_repo_path);
         let contents = match tokio::fs::read_to_string(&skill_md_fs_path).await {
             Ok(text) => text,
             Err(_) => {
                 // Fallback to git show if the file is not present in working tree.
                 let show_args = vec![
                     "-C".to_string(),
-                    temp_base.display().to_string(),
+                    repo_dir.display().to_string(),
                     "show".to_string(),
                     format!("HEAD:{}", skill_md_repo_path),
                 ];
@@ -695,7 +789,9 @@ async fn scan_repo_items(
         ));
     }
 
-    safe_rm(&temp_base).await;
+    if !cached {
+        safe_rm(&repo_dir).await;
+    }
 
     Ok((parsed.normalized_repo, effective_subpath, items))
 }
@@ -932,12 +1028,8 @@ async fn fetch_clawdhub_skill_info(slug: &str) -> Result<ClawdHubSkillInfoRespon
 }
 
 fn load_custom_catalog_sources() -> Vec<SkillsCatalogSource> {
-    let settings_path = dirs::home_dir().map(|mut home| {
-        home.push(".config");
-        home.push("openchamber");
-        home.push("settings.json");
-        home
-    });
+    let settings_path =
+        crate::path_utils::openchamber_config_dir().map(|dir| dir.join("settings.json"));
 
     let Some(path) = settings_path else {
         return vec![];
@@ -1039,200 +1131,140 @@ pub async fn get_curated_sources() -> Vec<SkillsCatalogSource> {
     sources
 }
 
-pub async fn get_catalog(working_directory: &Path, refresh: bool) -> SkillsCatalogResponse {
-    let sources = get_curated_sources().await;
+fn installed_badge(
+    installed_by_name: &HashMap<String, opencode_config::DiscoveredSkill>,
+    skill_name: &str,
+) -> SkillsCatalogInstalledBadge {
+    let installed = installed_by_name.get(skill_name);
+    SkillsCatalogInstalledBadge {
+        is_installed: installed.is_some(),
+        scope: installed.map(|s| match s.scope {
+            opencode_config::Scope::User => "user".to_string(),
+            opencode_config::Scope::Project => "project".to_string(),
+        }),
+    }
+}
 
-    let discovered = opencode_config::discover_skills(Some(working_directory));
-    let installed_by_name: HashMap<String, opencode_config::DiscoveredSkill> = discovered
+fn installed_skills_by_name(
+    working_directory: Option<&Path>,
+) -> HashMap<String, opencode_config::DiscoveredSkill> {
+    opencode_config::discover_skills(working_directory)
         .into_iter()
         .map(|s| (s.name.clone(), s))
-        .collect();
-
-    let mut items_by_source: HashMap<String, Vec<SkillsCatalogItem>> = HashMap::new();
-
-    for src in &sources {
-        // Handle ClawdHub sources separately (API-based, not git-based)
-        if is_clawdhub_source(&src.source) {
-            let key = "clawdhub:registry".to_string();
-
-            let maybe_cached = if refresh {
-                None
-            } else {
-                let cache = CATALOG_CACHE.lock().await;
-                cache.get(&key).cloned()
-            };
-
-            let cached_items = maybe_cached.and_then(|entry| {
-                if entry.created_at.elapsed() < CACHE_TTL {
-                    Some(entry.items)
-                } else {
-                    None
-                }
-            });
-
-            let scanned_items = if let Some(items) = cached_items {
-                items
-            } else {
-                let items = match scan_clawdhub().await {
-                    Ok(items) => items,
-                    Err(_) => {
-                        items_by_source.insert(src.id.clone(), vec![]);
-                        continue;
-                    }
-                };
-
-                let mut cache = CATALOG_CACHE.lock().await;
-                cache.insert(
-                    key,
-                    CacheEntry {
-                        created_at: Instant::now(),
-                        items: items.clone(),
-                    },
-                );
-
-                items
-            };
-
-            // Update installed badges
-            let enriched: Vec<SkillsCatalogItem> = scanned_items
-                .into_iter()
-                .map(|mut item| {
-                    let installed = installed_by_name.get(&item.skill_name);
-                    item.installed = SkillsCatalogInstalledBadge {
-                        is_installed: installed.is_some(),
-                        scope: installed.map(|s| match s.scope {
-                            opencode_config::Scope::User => "user".to_string(),
-                            opencode_config::Scope::Project => "project".to_string(),
-                        }),
-                    };
-                    item
-                })
-                .collect();
-
-            items_by_source.insert(src.id.clone(), enriched);
-            continue;
-        }
-
-        // Handle GitHub sources (git clone based)
-        let parsed = match parse_repo_source(&src.source, None) {
-            Ok(p) => p,
-            Err(_) => {
-                items_by_source.insert(src.id.clone(), vec![]);
-                continue;
-            }
-        };
+        .collect()
+}
 
+/// Skills offered by one catalog source, served from `CATALOG_CACHE` unless `refresh` is set
+/// or the entry is older than `CACHE_TTL`. Installed badges are left for the caller to fill.
+async fn scan_catalog_source(
+    src: &SkillsCatalogSource,
+    refresh: bool,
+) -> Result<Vec<SkillsCatalogItem>> {
+    // ClawdHub sources are API-based, everything else is a git repository.
+    let key = if is_clawdhub_source(&src.source) {
+        "clawdhub:registry".to_string()
+    } else {
+        let parsed = parse_repo_source(&src.source, None)?;
         let effective_subpath = src
             .default_subpath
             .as_deref()
             .or(parsed.effective_subpath.as_deref())
             .unwrap_or("");
-
-        let key = cache_key(
+        cache_key(
             &parsed.normalized_repo,
             Some(effective_subpath),
             src.git_identity_id.as_deref(),
-        );
-
-        let maybe_cached = if refresh {
-            None
-        } else {
-            let cache = CATALOG_CACHE.lock().await;
-            cache.get(&key).cloned()
-        };
+        )
+    };
 
-        let cached_items = maybe_cached.and_then(|entry| {
+    if !refresh {
+        let cache = CATALOG_CACHE.lock().await;
+        if let Some(entry) = cache.get(&key) {
             if entry.created_at.elapsed() < CACHE_TTL {
-                Some(entry.items)
-            } else {
-                None
+                return Ok(entry.items.clone());
             }
-        });
-
-        let scanned_items = if let Some(items) = cached_items {
-            items
-        } else {
-            let ssh_key = resolve_identity_ssh_key(src.git_identity_id.as_deref());
-            let scan = scan_repo_items(
-                &src.source,
-                None,
-                src.default_subpath.as_deref(),
-                ssh_key.as_deref(),
-            )
-            .await;
+        }
+    }
 
-            let (_, _, raw_items) = match scan {
-                Ok(v) => v,
-                Err(_) => {
-                    items_by_source.insert(src.id.clone(), vec![]);
-                    continue;
-                }
-            };
+    let items = if is_clawdhub_source(&src.source) {
+        scan_clawdhub().await?
+    } else {
+        let ssh_key = resolve_identity_ssh_key(src.git_identity_id.as_deref());
+        let (_, _, raw_items) = scan_repo_items(
+            &src.source,
+            None,
+            src.default_subpath.as_deref(),
+            ssh_key.as_deref(),
+            true,
+        )
+        .await?;
+
+        let mut items: Vec<SkillsCatalogItem> = vec![];
+        for (repo_source, skill_dir, fm_name, desc, warnings, installable) in raw_items {
+            let skill_name = skill_dir
+                .split('/')
+                .filter(|s| !s.is_empty())
+                .last()
+                .unwrap_or("")
+                .to_string();
+
+            items.push(SkillsCatalogItem {
+                source_id: src.id.clone(),
+                repo_source,
+                repo_subpath: src.default_subpath.clone(),
+                git_identity_id: src.git_identity_id.clone(),
+                skill_dir,
+                skill_name,
+                frontmatter_name: fm_name,
+                description: desc,
+                installable,
+                warnings: if warnings.is_empty() {
+                    None
+                } else {
+                    Some(warnings)
+                },
+                installed: SkillsCatalogInstalledBadge {
+                    is_installed: false,
+                    scope: None,
+                },
+                clawdhub: None,
+            });
+        }
 
-            let mut items: Vec<SkillsCatalogItem> = vec![];
-            for (repo_source, skill_dir, fm_name, desc, warnings, installable) in raw_items {
-                let skill_name = skill_dir
-                    .split('/')
-                    .filter(|s| !s.is_empty())
-                    .last()
-                    .unwrap_or("")
-                    .to_string();
+        items.sort_by(|a, b| a.skill_name.cmp(&b.skill_name));
+        items
+    };
 
-                let installed = installed_by_name.get(&skill_name);
+    let mut cache = CATALOG_CACHE.lock().await;
+    cache.insert(
+        key,
+        CacheEntry {
+            created_at: Instant::now(),
+            items: items.clone(),
+        },
+    );
 
-                items.push(SkillsCatalogItem {
-                    source_id: src.id.clone(),
-                    repo_source,
-                    repo_subpath: src.default_subpath.clone(),
-                    git_identity_id: src.git_identity_id.clone(),
-                    skill_dir,
-                    skill_name,
-                    frontmatter_name: fm_name,
-                    description: desc,
-                    installable,
-                    warnings: if warnings.is_empty() {
-                        None
-                    } else {
-                        Some(warnings)
-                    },
-                    installed: SkillsCatalogInstalledBadge {
-                        is_installed: installed.is_some(),
-                        scope: installed.map(|s| match s.scope {
-                            opencode_config::Scope::User => "user".to_string(),
-                            opencode_config::Scope::Project => "project".to_string(),
-                        }),
-                    },
-                    clawdhub: None,
-                });
-            }
+    Ok(items)
+}
 
-            items.sort_by(|a, b| a.skill_name.cmp(&b.skill_name));
+pub async fn get_catalog(working_directory: &Path, refresh: bool) -> SkillsCatalogResponse {
+    let sources = get_curated_sources().await;
+    let installed_by_name = installed_skills_by_name(Some(working_directory));
 
-            let mut cache = CATALOG_CACHE.lock().await;
-            cache.insert(
-                key,
-                CacheEntry {
-                    created_at: Instant::now(),
-                    items: items.clone(),
-                },
-            );
+    let mut items_by_source: HashMap<String, Vec<SkillsCatalogItem>> = HashMap::new();
 
-            items
-        };
+    for src in &sources {
+        // A source that fails to scan shows up empty rather than failing the whole catalog.
+        let items = scan_catalog_source(src, refresh).await.unwrap_or_default();
 
         // Update installed badges at request time (cache may be stale for installs)
-        let mut enriched = vec![];
-        for mut item in scanned_items {
-            let installed = installed_by_name.get(&item.skill_name);
-            item.installed = SkillsCatalogInstalledBadge {
-                is_installed: installed.is_some(),
-                scope: installed.map(|s| match s.scope {
-                    opencode_config::Scope::User => "user".to_string(),
-                    opencode_config::Scope::Project => "project".to_string(),
-                }),
-            };
-            enriched.push(item);
-        }
+        let enriched = items
+            .into_iter()
+            .map(|mut item| {
+                item.installed = installed_badge(&installed_by_name, &item.skill_name);
+                item
+            })
+            .collect();
 
         items_by_source.insert(src.id.clone(), enriched);
     }
@@ -1245,6 +1277,40 @@ pub async fn get_catalog(working_directory: &Path, refresh: bool) -> SkillsCatal
     }
 }
 
+/// Skills of the catalog `catalog_id` (curated or from `skillCatalogs` in settings), with
+/// installed badges resolved against `working_directory`. Unlike `get_catalog`, scan
+/// failures are reported instead of showing up as an empty catalog.
+pub async fn list_catalog_entries(
+    working_directory: Option<&Path>,
+    catalog_id: &str,
+    refresh: bool,
+) -> std::result::Result<Vec<SkillsCatalogItem>, SkillsRepoError> {
+    let sources = get_curated_sources().await;
+    let Some(src) = sources.iter().find(|src| src.id == catalog_id) else {
+        return Err(simple_error(
+            "notFound",
+            &format!("Skill catalog not found: {}", catalog_id),
+        ));
+    };
+
+    let items = scan_catalog_source(src, refresh).await.map_err(|err| {
+        if err.to_string().contains("AUTH_REQUIRED") {
+            auth_required_error("Authentication required to access this repository")
+        } else {
+            simple_error("networkError", &err.to_string())
+        }
+    })?;
+
+    let installed_by_name = installed_skills_by_name(working_directory);
+    Ok(items
+        .into_iter()
+        .map(|mut item| {
+            item.installed = installed_badge(&installed_by_name, &item.skill_name);
+            item
+        })
+        .collect())
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SkillsScanRequest {
@@ -1261,6 +1327,7 @@ pub async fn scan_repository(req: SkillsScanRequest) -> SkillsRepoScanResponse {
         req.subpath.as_deref(),
         None,
         ssh_key.as_deref(),
+        false,
     )
     .await
     {
@@ -2039,3 +2106,80 @@ pub async fn install_skills(
         error: None,
     }
 }
+
+/// Install one skill from the catalog `catalog_id` into `scope` ("user" or "project").
+/// An existing skill with the same name is a `conflicts` error unless `force` is set.
+pub async fn install_catalog_skill(
+    working_directory: &Path,
+    catalog_id: &str,
+    skill_name: &str,
+    scope: &str,
+    force: bool,
+) -> std::result::Result<InstalledSkill, SkillsRepoError> {
+    let sources = get_curated_sources().await;
+    let Some(src) = sources.iter().find(|src| src.id == catalog_id) else {
+        return Err(simple_error(
+            "notFound",
+            &format!("Skill catalog not found: {}", catalog_id),
+        ));
+    };
+
+    let items = list_catalog_entries(Some(working_directory), catalog_id, false).await?;
+    let Some(item) = items.into_iter().find(|item| item.skill_name == skill_name) else {
+        return Err(simple_error(
+            "notFound",
+            &format!("Skill {} is not in catalog {}", skill_name, src.label),
+        ));
+    };
+
+    // Check the SKILL.md read during the scan before anything is written.
+    if !item.installable {
+        return Err(simple_error(
+            "invalidSource",
+            "Skill directory name is not a valid OpenCode skill name",
+        ));
+    }
+    if let Some(warning) =
+        item.warnings.iter().flatten().find(|warning| {
+            warning.starts_with("Invalid SKILL.md") || warning.starts_with("Failed")
+        })
+    {
+        return Err(simple_error("invalidSource", warning));
+    }
+    if item.clawdhub.is_none() && item.description.is_none() {
+        return Err(simple_error(
+            "invalidSource",
+            "Invalid SKILL.md: the frontmatter has no description",
+        ));
+    }
+
+    let request = SkillsInstallRequest {
+        source: src.source.clone(),
+        subpath: src.default_subpath.clone(),
+        git_identity_id: src.git_identity_id.clone(),
+        scope: scope.to_string(),
+        selections: vec![SkillsInstallSelection {
+            skill_dir: item.skill_dir,
+            clawdhub: item.clawdhub.map(|meta| ClawdHubInstallMeta {
+                slug: meta.slug,
+                version: meta.version,
+            }),
+        }],
+        conflict_policy: Some(if force { "overwriteAll" } else { "prompt" }.to_string()),
+        conflict_decisions: None,
+    };
+
+    let response = install_skills(working_directory, request).await;
+    if let Some(error) = response.error {
+        return Err(error);
+    }
+    if let Some(installed) = response.installed.and_then(|list| list.into_iter().next()) {
+        return Ok(installed);
+    }
+    let reason = response
+        .skipped
+        .and_then(|list| list.into_iter().next())
+        .map(|skipped| skipped.reason)
+        .unwrap_or_else(|| "Skill was not installed".to_string());
+    Err(simple_error("invalidSource", &reason))
+}
@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use tauri::{
+    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
+    AppHandle, Emitter, Manager, Runtime, Wry,
+};
+
+use crate::global_shortcut::toggle_main_window;
+use crate::DesktopRuntime;
+
+const TRAY_ID: &str = "openchamber-tray";
+const TRAY_TOOLTIP: &str = "OpenChamber";
+const TRAY_NEW_SESSION_ID: &str = "tray_new_session";
+const TRAY_CHANGE_WORKSPACE_ID: &str = "tray_change_workspace";
+const TRAY_OPENCODE_STATUS_ID: &str = "tray_opencode_status";
+const TRAY_RESTART_OPENCODE_ID: &str = "tray_restart_opencode";
+const TRAY_QUIT_ID: &str = "tray_quit";
+const TRAY_STATUS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Refreshes the OpenCode status item while the tray icon exists.
+static TRAY_STATUS_TASK: Lazy<Mutex<Option<tauri::async_runtime::JoinHandle<()>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+fn opencode_status_text(runtime: &DesktopRuntime) -> String {
+    let opencode = runtime.opencode_manager();
+    let status = if opencode.is_asleep() {
+        "Sleeping"
+    } else if opencode.is_ready() {
+        "Running"
+    } else if opencode.last_error().is_some() {
+        "Failed to start"
+    } else {
+        "Starting…"
+    };
+    format!("OpenCode: {status}")
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn handle_tray_menu_event(app: &AppHandle, event: MenuEvent) {
+    match event.id().as_ref() {
+        TRAY_NEW_SESSION_ID => {
+            show_main_window(app);
+            let _ = app.emit("openchamber:menu-action", "new-session");
+        }
+        TRAY_CHANGE_WORKSPACE_ID => {
+            show_main_window(app);
+            let _ = app.emit("openchamber:menu-action", "change-workspace");
+        }
+        TRAY_RESTART_OPENCODE_ID => {
+            let runtime = app.state::<DesktopRuntime>().inner().clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(err) = runtime.opencode_manager().restart().await {
+                    warn!("[desktop:tray] Failed to restart OpenCode: {err}");
+                }
+            });
+        }
+        TRAY_QUIT_ID => {
+            let runtime = app.state::<DesktopRuntime>().inner().clone();
+            crate::shutdown_and_exit(app.clone(), runtime);
+        }
+        _ => {}
+    }
+}
+
+fn build_tray(app: &AppHandle) -> tauri::Result<()> {
+    let runtime = app.state::<DesktopRuntime>().inner().clone();
+    let status_item = MenuItem::with_id(
+        app,
+        TRAY_OPENCODE_STATUS_ID,
+        opencode_status_text(&runtime),
+        false,
+        None::<&str>,
+    )?;
+    let menu = Menu::with_items(
+        app,
+        &[
+            &MenuItem::with_id(app, TRAY_NEW_SESSION_ID, "New Session", true, None::<&str>)?,
+            &MenuItem::with_id(
+                app,
+                TRAY_CHANGE_WORKSPACE_ID,
+                "Change Workspace…",
+                true,
+                None::<&str>,
+            )?,
+            &PredefinedMenuItem::separator(app)?,
+            &status_item,
+            &MenuItem::with_id(
+                app,
+                TRAY_RESTART_OPENCODE_ID,
+                "Restart OpenCode",
+                true,
+                None::<&str>,
+            )?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, TRAY_QUIT_ID, "Quit OpenChamber", true, None::<&str>)?,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(TRAY_TOOLTIP)
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(handle_tray_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                toggle_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    // The tray menu has no "about to open" hook on every platform, so poll the status.
+    let status: MenuItem<Wry> = status_item.clone();
+    let status_task = tauri::async_runtime::spawn(async move {
+        let mut interval = tokio::time::interval(TRAY_STATUS_INTERVAL);
+        loop {
+            interval.tick().await;
+            let _ = status.set_text(opencode_status_text(&runtime));
+        }
+    });
+    if let Some(previous) = TRAY_STATUS_TASK.lock().replace(status_task) {
+        previous.abort();
+    }
+    Ok(())
+}
+
+/// Create or remove the tray icon to match the `showTrayIcon` setting.
+pub fn apply_tray_setting(app: &AppHandle, enabled: bool) {
+    let exists = app.tray_by_id(TRAY_ID).is_some();
+    if enabled && !exists {
+        match build_tray(app) {
+            Ok(()) => info!("[desktop:tray] Tray icon shown"),
+            Err(err) => warn!("[desktop:tray] Failed to create tray icon: {err}"),
+        }
+    } else if !enabled && exists {
+        if let Some(task) = TRAY_STATUS_TASK.lock().take() {
+            task.abort();
+        }
+        let _ = app.remove_tray_by_id(TRAY_ID);
+        info!("[desktop:tray] Tray icon removed");
+    }
+}
+
+/// Mirror the dock badge on the tray icon: as the title on macOS, in the tooltip elsewhere.
+pub fn set_badge_count<R: Runtime>(app: &AppHandle<R>, total: u32) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    let count = (total > 0).then(|| total.to_string());
+    #[cfg(target_os = "macos")]
+    let _ = tray.set_title(count.as_deref());
+    let tooltip = match count {
+        Some(count) => format!("{TRAY_TOOLTIP} ({count} unread)"),
+        None => TRAY_TOOLTIP.to_string(),
+    };
+    let _ = tray.set_tooltip(Some(tooltip));
+}
@@ -1,13 +1,26 @@
 use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::{
+    collections::HashMap,
     path::PathBuf,
     sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 use tauri::{LogicalPosition, LogicalSize, WebviewWindow, Window};
 use tokio::fs as async_fs;
 
 const WINDOW_STATE_FILE: &str = "window-state.json";
+// The UI state blob is opaque to us; cap its serialized size so it can't bloat the state file.
+pub const UI_STATE_MAX_BYTES: usize = 64 * 1024;
+// Moves and resizes arrive dozens of times per drag; only the value after this quiet period
+// is committed.
+const WINDOW_GEOMETRY_SETTLE: Duration = Duration::from_millis(250);
+
+/// Held across each read-modify-write of the state file, which the main window and every
+/// project window update independently.
+static STATE_FILE_LOCK: Lazy<tokio::sync::Mutex<()>> = Lazy::new(|| tokio::sync::Mutex::new(()));
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -17,6 +30,9 @@ pub struct WindowState {
     pub x: f64,
     pub y: f64,
     pub is_maximized: bool,
+    /// Frontend-owned view state (open tab, session, ...), restored as-is on startup.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ui_state: Option<Value>,
 }
 
 impl Default for WindowState {
@@ -27,6 +43,7 @@ impl Default for WindowState {
             x: 0.0,
             y: 0.0,
             is_maximized: false,
+            ui_state: None,
         }
     }
 }
@@ -35,18 +52,120 @@ impl Default for WindowState {
 struct WindowStateFile {
     #[serde(rename = "windowState")]
     pub window_state: WindowState,
+    /// Geometry of secondary project windows, keyed by window label.
+    #[serde(
+        rename = "projectWindows",
+        default,
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub project_windows: HashMap<String, WindowState>,
+}
+
+pub enum GeometryChange {
+    Moved {
+        x: f64,
+        y: f64,
+        is_maximized: bool,
+    },
+    Resized {
+        width: f64,
+        height: f64,
+        is_maximized: bool,
+    },
+}
+
+#[derive(Default)]
+struct PendingGeometry {
+    position: Option<(f64, f64, bool)>,
+    size: Option<(f64, f64, bool)>,
+    last_event: Option<Instant>,
+    flush_scheduled: bool,
 }
 
 #[derive(Clone)]
 pub struct WindowStateManager {
     inner: Arc<Mutex<WindowState>>,
+    pending: Arc<Mutex<PendingGeometry>>,
 }
 
 impl WindowStateManager {
     pub fn new(initial: WindowState) -> Self {
         Self {
             inner: Arc::new(Mutex::new(initial)),
+            pending: Arc::new(Mutex::new(PendingGeometry::default())),
+        }
+    }
+
+    /// Record a move/resize and commit the latest one once events stop for a moment.
+    /// `on_settled` runs after that commit with whether the size changed; only the callback
+    /// of the event that started a burst is kept.
+    pub fn record_geometry<F>(&self, change: GeometryChange, on_settled: F)
+    where
+        F: FnOnce(bool) + Send + 'static,
+    {
+        {
+            let Ok(mut pending) = self.pending.lock() else {
+                return;
+            };
+            match change {
+                GeometryChange::Moved { x, y, is_maximized } => {
+                    pending.position = Some((x, y, is_maximized));
+                }
+                GeometryChange::Resized {
+                    width,
+                    height,
+                    is_maximized,
+                } => {
+                    pending.size = Some((width, height, is_maximized));
+                }
+            }
+            pending.last_event = Some(Instant::now());
+            if pending.flush_scheduled {
+                return;
+            }
+            pending.flush_scheduled = true;
         }
+
+        let manager = self.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                let wait = manager
+                    .pending
+                    .lock()
+                    .ok()
+                    .and_then(|pending| pending.last_event)
+                    .map(|last| {
+                        (last + WINDOW_GEOMETRY_SETTLE).saturating_duration_since(Instant::now())
+                    })
+                    .unwrap_or_default();
+                if wait.is_zero() {
+                    break;
+                }
+                tokio::time::sleep(wait).await;
+            }
+            let resized = manager.commit_pending_geometry();
+            on_settled(resized);
+        });
+    }
+
+    /// Apply a move/resize still waiting to settle. Returns whether the size changed.
+    pub fn commit_pending_geometry(&self) -> bool {
+        let (position, size) = match self.pending.lock() {
+            Ok(mut pending) => {
+                pending.flush_scheduled = false;
+                pending.last_event = None;
+                (pending.position.take(), pending.size.take())
+            }
+            Err(_) => return false,
+        };
+        // Size first: it updates the maximized flag that position updates check.
+        if let Some((width, height, is_maximized)) = size {
+            self.update_size(width, height, is_maximized);
+        }
+        if let Some((x, y, is_maximized)) = position {
+            self.update_position(x, y, is_maximized);
+        }
+        size.is_some()
     }
 
     pub fn snapshot(&self) -> WindowState {
@@ -74,39 +193,80 @@ impl WindowStateManager {
             state.is_maximized = is_maximized;
         }
     }
+
+    pub fn set_ui_state(&self, ui_state: Option<Value>) {
+        if let Ok(mut state) = self.inner.lock() {
+            state.ui_state = ui_state;
+        }
+    }
 }
 
 fn state_file_path() -> Result<PathBuf> {
-    let mut path = dirs::home_dir().ok_or_else(|| anyhow!("No home directory"))?;
-    path.push(".config");
-    path.push("openchamber");
+    let mut path =
+        crate::path_utils::openchamber_config_dir().ok_or_else(|| anyhow!("No home directory"))?;
     path.push(WINDOW_STATE_FILE);
     Ok(path)
 }
 
-pub async fn load_window_state() -> Result<Option<WindowState>> {
+async fn read_state_file() -> Result<Option<WindowStateFile>> {
     let path = state_file_path()?;
     match async_fs::read(&path).await {
-        Ok(bytes) => {
-            let file: WindowStateFile = serde_json::from_slice(&bytes)?;
-            Ok(Some(file.window_state))
-        }
+        Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
         Err(err) => Err(err.into()),
     }
 }
 
-pub async fn save_window_state(state: &WindowState) -> Result<()> {
+async fn write_state_file(file: &WindowStateFile) -> Result<()> {
     let path = state_file_path()?;
     if let Some(parent) = path.parent() {
         async_fs::create_dir_all(parent).await?;
     }
-    let payload = WindowStateFile {
+    let data = serde_json::to_vec_pretty(file)?;
+    let tmp = path.with_extension("json.tmp");
+    async_fs::write(&tmp, data).await?;
+    async_fs::rename(&tmp, &path).await?;
+    Ok(())
+}
+
+pub async fn load_window_state() -> Result<Option<WindowState>> {
+    Ok(read_state_file().await?.map(|file| file.window_state))
+}
+
+pub async fn load_project_window_state(label: &str) -> Result<Option<WindowState>> {
+    Ok(read_state_file()
+        .await?
+        .and_then(|mut file| file.project_windows.remove(label)))
+}
+
+pub async fn save_window_state(state: &WindowState) -> Result<()> {
+    let _guard = STATE_FILE_LOCK.lock().await;
+    let project_windows = read_state_file()
+        .await
+        .ok()
+        .flatten()
+        .map(|file| file.project_windows)
+        .unwrap_or_default();
+    write_state_file(&WindowStateFile {
         window_state: state.clone(),
+        project_windows,
+    })
+    .await
+}
+
+/// Store the geometry of a secondary window under its label, keeping the main window's entry.
+pub async fn save_project_window_state(label: &str, state: &WindowState) -> Result<()> {
+    let _guard = STATE_FILE_LOCK.lock().await;
+    let mut file = match read_state_file().await.ok().flatten() {
+        Some(file) => file,
+        None => WindowStateFile {
+            window_state: WindowState::default(),
+            project_windows: HashMap::new(),
+        },
     };
-    let data = serde_json::to_vec_pretty(&payload)?;
-    async_fs::write(&path, data).await?;
-    Ok(())
+    file.project_windows
+        .insert(label.to_string(), state.clone());
+    write_state_file(&file).await
 }
 
 pub fn apply_window_state(window: &WebviewWindow, state: &WindowState) -> Result<()> {
@@ -126,7 +286,14 @@ pub fn apply_window_state(window: &WebviewWindow, state: &WindowState) -> Result
 }
 
 pub async fn persist_window_state(window: &Window, manager: &WindowStateManager) -> Result<()> {
-    let mut snapshot = manager.snapshot();
+    manager.commit_pending_geometry();
+    let snapshot = capture_window_state(window, manager.snapshot());
+    save_window_state(&snapshot).await
+}
+
+/// `base` updated with the window's current logical size, position and maximized flag.
+pub fn capture_window_state(window: &Window, base: WindowState) -> WindowState {
+    let mut snapshot = base;
     let is_maximized = window.is_maximized().unwrap_or(snapshot.is_maximized);
     snapshot.is_maximized = is_maximized;
 
@@ -144,7 +311,7 @@ pub async fn persist_window_state(window: &Window, manager: &WindowStateManager)
         }
     }
 
-    save_window_state(&snapshot).await
+    snapshot
 }
 
 fn clamp_to_visible_region(window: &WebviewWindow, state: &mut WindowState) {
@@ -1,5 +1,5 @@
 
-import { safeInvoke } from '../lib/tauriCallbackManager';
+import { safeInvoke, toDesktopCommandError } from '../lib/tauriCallbackManager';
 import type { DirectoryListResult, FileSearchQuery, FileSearchResult, FilesAPI, ListDirectoryOptions } from '@openchamber/ui/lib/api/types';
 
 type ReadFileBinaryResponse = {
@@ -28,8 +28,23 @@ type SearchFilesResponse = {
   }>;
 };
 
+type ReadFileResponse = {
+  content: string;
+  path: string;
+  encoding?: string;
+  hasBom?: boolean;
+};
+
+type TextFormat = {
+  encoding: string;
+  hasBom: boolean;
+};
+
 const normalizePath = (path: string): string => path.replace(/\\/g, '/');
 
+// Non-UTF-8 files seen by readFile, so writeFile saves them back in the same encoding.
+const textFormats = new Map<string, TextFormat>();
+
 const normalizeDirectoryPayload = (result: ListDirectoryResponse): DirectoryListResult => ({
   directory: normalizePath(result.directory || result.path || ''),
   entries: Array.isArray(result.entries)
@@ -60,8 +75,7 @@ export const createDesktopFilesAPI = (): FilesAPI => ({
 
       return normalizeDirectoryPayload(result);
     } catch (error) {
-      const message = error instanceof Error ? error.message : String(error);
-      throw new Error(message || 'Failed to list directory');
+      throw toDesktopCommandError(error, { fallback: 'Failed to list directory' });
     }
   },
 
@@ -75,13 +89,11 @@ export const createDesktopFilesAPI = (): FilesAPI => ({
       const result = await safeInvoke<SearchFilesResponse>('search_files', {
         directory: normalizedDirectory,
         query: payload.query,
-        // NOTE: pass both casings; Tauri arg casing differs across commands
-        maxResults: payload.maxResults || 100,
-        includeHidden: payload.includeHidden ?? false,
-        respectGitignore: payload.respectGitignore ?? true,
-        max_results: payload.maxResults || 100,
-        include_hidden: payload.includeHidden ?? false,
-        respect_gitignore: payload.respectGitignore ?? true,
+        options: {
+          maxResults: payload.maxResults || 100,
+          includeHidden: payload.includeHidden ?? false,
+          respectGitignore: payload.respectGitignore ?? true,
+        },
       }, {
         timeout: 15000,
         onCancel: () => {
@@ -98,8 +110,7 @@ export const createDesktopFilesAPI = (): FilesAPI => ({
         preview: file.relativePath ? [normalizePath(file.relativePath)] : undefined,
       }));
     } catch (error) {
-      const message = error instanceof Error ? error.message : String(error);
-      throw new Error(message || 'Failed to search files');
+      throw toDesktopCommandError(error, { fallback: 'Failed to search files' });
     }
   },
 
@@ -120,15 +131,14 @@ export const createDesktopFilesAPI = (): FilesAPI => ({
         path: result?.path ? normalizePath(result.path) : normalizedPath,
       };
     } catch (error) {
-      const message = error instanceof Error ? error.message : String(error);
-      throw new Error(message || 'Failed to create directory');
+      throw toDesktopCommandError(error, { fallback: 'Failed to create directory' });
     }
   },
 
   async readFile(path: string): Promise<{ content: string; path: string }> {
     try {
       const normalizedPath = normalizePath(path);
-      const result = await safeInvoke<{ content: string; path: string }>('read_file', {
+      const result = await safeInvoke<ReadFileResponse>('read_file', {
         path: normalizedPath
       }, {
         timeout: 10000,
@@ -137,13 +147,18 @@ export const createDesktopFilesAPI = (): FilesAPI => ({
         }
       });
 
+      if (result?.encoding) {
+        textFormats.set(normalizedPath, { encoding: result.encoding, hasBom: Boolean(result.hasBom) });
+      } else {
+        textFormats.delete(normalizedPath);
+      }
+
       return {
         content: result?.content ?? '',
         path: result?.path ? normalizePath(result.path) : normalizedPath,
       };
     } catch (error) {
-      const message = error instanceof Error ? error.message : String(error);
-      throw new Error(message || 'Failed to read file');
+      throw toDesktopCommandError(error, { fallback: 'Failed to read file' });
     }
   },
 
@@ -164,17 +179,19 @@ export const createDesktopFilesAPI = (): FilesAPI => ({
         path: result?.path ? normalizePath(result.path) : normalizedPath,
       };
     } catch (error) {
-      const message = error instanceof Error ? error.message : String(error);
-      throw new Error(message || 'Failed to read file');
+      throw toDesktopCommandError(error, { fallback: 'Failed to read file' });
     }
   },
 
   async writeFile(path: string, content: string): Promise<{ success: boolean; path: string }> {
     try {
       const normalizedPath = normalizePath(path);
+      const format = textFormats.get(normalizedPath);
       const result = await safeInvoke<{ success: boolean; path: string }>('write_file', {
         path: normalizedPath,
-        content
+        content,
+        encoding: format?.encoding,
+        hasBom: format?.hasBom,
       }, {
         timeout: 10000,
         onCancel: () => {
@@ -187,8 +204,7 @@ export const createDesktopFilesAPI = (): FilesAPI => ({
         path: result?.path ? normalizePath(result.path) : normalizedPath,
       };
     } catch (error) {
-      const message = error instanceof Error ? error.message : String(error);
-      throw new Error(message || 'Failed to write file');
+      throw toDesktopCommandError(error, { fallback: 'Failed to write file' });
     }
   },
 
@@ -208,8 +224,7 @@ export const createDesktopFilesAPI = (): FilesAPI => ({
         success: Boolean(result?.success),
       };
     } catch (error) {
-      const message = error instanceof Error ? error.message : String(error);
-      throw new Error(message || 'Failed to delete path');
+      throw toDesktopCommandError(error, { fallback: 'Failed to delete path' });
     }
   },
 
@@ -230,8 +245,7 @@ export const createDesktopFilesAPI = (): FilesAPI => ({
         path: result?.path ? normalizePath(result.path) : normalizePath(newPath),
       };
     } catch (error) {
-      const message = error instanceof Error ? error.message : String(error);
-      throw new Error(message || 'Failed to rename path');
+      throw toDesktopCommandError(error, { fallback: 'Failed to rename path' });
     }
   },
 
@@ -273,8 +287,7 @@ export const createDesktopFilesAPI = (): FilesAPI => ({
         results: result?.results ?? [],
       };
     } catch (error) {
-      const message = error instanceof Error ? error.message : String(error);
-      throw new Error(message || 'Failed to execute commands');
+      throw toDesktopCommandError(error, { fallback: 'Failed to execute commands' });
     }
   },
 });
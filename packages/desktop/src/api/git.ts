@@ -1,5 +1,5 @@
 
-import { safeInvoke } from '../lib/tauriCallbackManager';
+import { safeInvoke, toDesktopCommandError } from '../lib/tauriCallbackManager';
 import type {
   GitAPI,
   GitStatus,
@@ -35,8 +35,7 @@ async function safeGitInvoke<T>(command: string, args?: Record<string, unknown>)
       }
     });
   } catch (error) {
-    const message = typeof error === 'string' ? error : (error as Error).message || 'Unknown error';
-    throw new Error(message);
+    throw toDesktopCommandError(error);
   }
 }
 
@@ -176,11 +175,12 @@ export const createDesktopGitAPI = (): GitAPI => ({
   },
 
   async gitFetch(directory: string, options?: { remote?: string; branch?: string }): Promise<{ success: boolean }> {
-    await safeGitInvoke<void>('git_fetch', {
+    // The command also reports the refs it updated and its progress operation id.
+    const result = await safeGitInvoke<Record<string, unknown>>('git_fetch', {
       directory,
       remote: options?.remote
     });
-    return { success: true };
+    return { ...result, success: true };
   },
 
   async checkoutBranch(directory: string, branch: string): Promise<{ success: boolean; branch: string }> {
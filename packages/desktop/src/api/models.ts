@@ -0,0 +1,6 @@
+import { invoke } from '@tauri-apps/api/core';
+import type { ModelTestResult } from '@openchamber/ui/lib/desktop';
+
+export async function testModel(model: string, variant?: string): Promise<ModelTestResult> {
+  return invoke<ModelTestResult>('test_model', { model, variant: variant ?? null });
+}
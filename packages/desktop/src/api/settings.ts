@@ -1,4 +1,5 @@
 import type { SettingsAPI, SettingsLoadResult, SettingsPayload } from '@openchamber/ui/lib/api/types';
+import { toDesktopCommandError } from '../lib/tauriCallbackManager';
 
 const sanitizePayload = (data: unknown): SettingsPayload => {
   if (!data || typeof data !== 'object') {
@@ -22,7 +23,7 @@ export const createDesktopSettingsAPI = (): SettingsAPI => ({
         source: result.source,
       };
     } catch (error) {
-      throw new Error(`Failed to load settings: ${error instanceof Error ? error.message : String(error)}`);
+      throw toDesktopCommandError(error, { prefix: 'Failed to load settings: ' });
     }
   },
 
@@ -37,7 +38,7 @@ export const createDesktopSettingsAPI = (): SettingsAPI => ({
       });
       return sanitizePayload(result);
     } catch (error) {
-      throw new Error(`Failed to save settings: ${error instanceof Error ? error.message : String(error)}`);
+      throw toDesktopCommandError(error, { prefix: 'Failed to save settings: ' });
     }
   },
 
@@ -52,7 +53,7 @@ export const createDesktopSettingsAPI = (): SettingsAPI => ({
       });
       return { restarted: result.restarted };
     } catch (error) {
-      throw new Error(`Failed to restart OpenCode: ${error instanceof Error ? error.message : String(error)}`);
+      throw toDesktopCommandError(error, { prefix: 'Failed to restart OpenCode: ' });
     }
   },
 });
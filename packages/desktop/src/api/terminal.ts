@@ -1,4 +1,4 @@
-import { safeInvoke, safeListen } from '../lib/tauriCallbackManager';
+import { safeInvoke, safeListen, toDesktopCommandError } from '../lib/tauriCallbackManager';
 import type {
   TerminalAPI,
   TerminalHandlers,
@@ -17,8 +17,7 @@ async function safeTerminalInvoke<T>(command: string, args?: Record<string, unkn
       }
     });
   } catch (error) {
-    const message = typeof error === 'string' ? error : (error as Error).message || 'Unknown error';
-    throw new Error(message);
+    throw toDesktopCommandError(error);
   }
 }
 
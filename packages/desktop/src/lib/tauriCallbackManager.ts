@@ -183,6 +183,60 @@ export function getTauriCallbackManager(config?: CallbackManagerConfig): TauriCa
   return globalCallbackManager;
 }
 
+/**
+ * Error thrown for a failed desktop command. Commands returning `CommandError` reject with a
+ * plain `{ code, message, details }` object; this keeps `code` and `details` on a real Error
+ * so callers can branch on them instead of showing "[object Object]".
+ */
+export class DesktopCommandError extends Error {
+  readonly code?: string;
+  readonly details?: unknown;
+
+  constructor(message: string, code?: string, details?: unknown) {
+    super(message);
+    this.name = 'DesktopCommandError';
+    this.code = code;
+    this.details = details;
+  }
+}
+
+/**
+ * Normalize a command rejection (string, `CommandError` object or Error) into a
+ * DesktopCommandError. `fallback` replaces an empty message; `prefix` is prepended to it.
+ */
+export function toDesktopCommandError(
+  error: unknown,
+  options: { fallback?: string; prefix?: string } = {}
+): DesktopCommandError {
+  let message = '';
+  let code: string | undefined;
+  let details: unknown;
+
+  if (error instanceof DesktopCommandError) {
+    ({ message, code, details } = error);
+  } else if (error instanceof Error) {
+    message = error.message;
+  } else if (typeof error === 'string') {
+    message = error;
+  } else if (error && typeof error === 'object') {
+    const payload = error as { code?: unknown; message?: unknown; details?: unknown };
+    message = typeof payload.message === 'string' ? payload.message : '';
+    code = typeof payload.code === 'string' ? payload.code : undefined;
+    details = payload.details;
+  } else if (error !== undefined && error !== null) {
+    message = String(error);
+  }
+
+  message = message || options.fallback || 'Unknown error';
+  if (options.prefix) {
+    message = `${options.prefix}${message}`;
+  }
+  if (error instanceof DesktopCommandError && message === error.message) {
+    return error;
+  }
+  return new DesktopCommandError(message, code, details);
+}
+
 export async function safeInvoke<T>(
   command: string,
   args?: Record<string, unknown>,
@@ -217,7 +271,7 @@ export async function safeInvoke<T>(
       return result;
     } catch (error) {
       clearAndUnregister();
-      throw error;
+      throw toDesktopCommandError(error);
     }
   }
 
@@ -255,7 +309,7 @@ export async function safeInvoke<T>(
         }
         settled = true;
         clearAndUnregister();
-        reject(error);
+        reject(toDesktopCommandError(error));
       });
   });
 }
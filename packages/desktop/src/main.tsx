@@ -1,12 +1,13 @@
 import { createDesktopAPIs } from './api';
 import { requestInitialNotificationPermission } from './api/notifications';
 import { checkForUpdates, downloadUpdate, restartToUpdate, type UpdateInfo, type UpdateProgress } from './api/updater';
+import { testModel } from './api/models';
 import { initializeDesktopBridge } from './lib/bridge';
 
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
 import type { RuntimeAPIs } from '@openchamber/ui/lib/api/types';
-import type { DesktopApi, DesktopSettings } from '@openchamber/ui/lib/desktop';
+import type { DesktopApi, DesktopSettings, ModelTestResult } from '@openchamber/ui/lib/desktop';
 import '@openchamber/ui/index.css';
 import '@openchamber/ui/styles/fonts';
 
@@ -259,6 +260,12 @@ window.opencodeDesktop = {
   },
   async restartToUpdate(): Promise<void> {
     return restartToUpdate();
+  },
+  async testModel(model: string, variant?: string): Promise<ModelTestResult> {
+    return testModel(model, variant);
+  },
+  async setCurrentSession(sessionId: string | null): Promise<void> {
+    await invoke('set_current_session', { sessionId });
   }
 };
 
@@ -4,8 +4,10 @@ import { Tooltip, TooltipContent, TooltipTrigger } from '@/components/ui/tooltip
 import { ModelSelector } from '@/components/sections/agents/ModelSelector';
 import { AgentSelector } from '@/components/sections/commands/AgentSelector';
 import { Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '@/components/ui/select';
+import { Button } from '@/components/ui/button';
 import { updateDesktopSettings } from '@/lib/persistence';
-import { getDesktopSettings, isDesktopRuntime, isVSCodeRuntime } from '@/lib/desktop';
+import { getDesktopSettings, isDesktopRuntime, isVSCodeRuntime, testDesktopModel } from '@/lib/desktop';
+import type { ModelTestErrorKind, ModelTestResult } from '@/lib/desktop';
 import { useConfigStore } from '@/stores/useConfigStore';
 import { getRegisteredRuntimeAPIs } from '@/contexts/runtimeAPIRegistry';
 import { getModifierLabel } from '@/lib/utils';
@@ -13,6 +15,15 @@ import { getModifierLabel } from '@/lib/utils';
 const FALLBACK_PROVIDER_ID = 'opencode';
 const FALLBACK_MODEL_ID = 'big-pickle';
 
+const MODEL_TEST_ERROR_LABELS: Record<ModelTestErrorKind, string> = {
+  authFailed: 'Authentication failed',
+  modelNotFound: 'Model not found',
+  rateLimited: 'Rate limited',
+  timeout: 'Timed out',
+  unavailable: 'OpenCode unavailable',
+  other: 'Failed',
+};
+
 const getDisplayModel = (
   storedModel: string | undefined,
   providers: Array<{ id: string; models: Array<{ id: string }> }>
@@ -53,12 +64,15 @@ export const DefaultsSettings: React.FC = () => {
   const [defaultVariant, setDefaultVariant] = React.useState<string | undefined>();
   const [defaultAgent, setDefaultAgent] = React.useState<string | undefined>();
   const [isLoading, setIsLoading] = React.useState(true);
+  const [isTestingModel, setIsTestingModel] = React.useState(false);
+  const [modelTestResult, setModelTestResult] = React.useState<ModelTestResult | null>(null);
 
   const parsedModel = React.useMemo(() => {
     return getDisplayModel(defaultModel, providers);
   }, [defaultModel, providers]);
 
   const isVSCode = React.useMemo(() => isVSCodeRuntime(), []);
+  const isDesktop = React.useMemo(() => isDesktopRuntime(), []);
 
   // Load current settings
   React.useEffect(() => {
@@ -127,6 +141,7 @@ export const DefaultsSettings: React.FC = () => {
   const handleModelChange = React.useCallback(async (providerId: string, modelId: string) => {
     const newValue = providerId && modelId ? `${providerId}/${modelId}` : undefined;
     setDefaultModel(newValue);
+    setModelTestResult(null);
 
     // Reset variant when model changes (model-specific)
     setDefaultVariant(undefined);
@@ -173,6 +188,7 @@ export const DefaultsSettings: React.FC = () => {
     setDefaultVariant(newValue);
     setSettingsDefaultVariant(newValue);
     setCurrentVariant(newValue);
+    setModelTestResult(null);
 
     try {
       await updateDesktopSettings({
@@ -183,6 +199,20 @@ export const DefaultsSettings: React.FC = () => {
     }
   }, [setCurrentVariant, setSettingsDefaultVariant]);
 
+  const handleTestModel = React.useCallback(async () => {
+    if (!parsedModel.providerId || !parsedModel.modelId) {
+      return;
+    }
+    setIsTestingModel(true);
+    setModelTestResult(null);
+    try {
+      const result = await testDesktopModel(`${parsedModel.providerId}/${parsedModel.modelId}`, defaultVariant);
+      setModelTestResult(result);
+    } finally {
+      setIsTestingModel(false);
+    }
+  }, [defaultVariant, parsedModel.modelId, parsedModel.providerId]);
+
   const handleAgentChange = React.useCallback(async (agentName: string) => {
     const newValue = agentName || undefined;
     setDefaultAgent(newValue);
@@ -265,11 +295,30 @@ export const DefaultsSettings: React.FC = () => {
         <div className="space-y-3">
          <div className="flex flex-col gap-1.5">
            <label className="typography-ui-label text-muted-foreground">Default model</label>
-           <ModelSelector
-             providerId={parsedModel.providerId}
-             modelId={parsedModel.modelId}
-             onChange={handleModelChange}
-           />
+           <div className="flex items-center gap-2">
+             <ModelSelector
+               providerId={parsedModel.providerId}
+               modelId={parsedModel.modelId}
+               onChange={handleModelChange}
+             />
+             {isDesktop && (
+               <Button
+                 variant="outline"
+                 size="sm"
+                 onClick={handleTestModel}
+                 disabled={isTestingModel || !parsedModel.providerId || !parsedModel.modelId}
+               >
+                 {isTestingModel ? 'Testing…' : 'Test'}
+               </Button>
+             )}
+           </div>
+           {modelTestResult && (
+             <p className={modelTestResult.ok ? 'typography-meta text-muted-foreground' : 'typography-meta text-destructive'}>
+               {modelTestResult.ok
+                 ? `Model responded in ${modelTestResult.latencyMs} ms`
+                 : `${MODEL_TEST_ERROR_LABELS[modelTestResult.error?.kind ?? 'other']}: ${modelTestResult.error?.message ?? 'Unknown error'}`}
+             </p>
+           )}
          </div>
 
          {supportsVariants && (
@@ -53,7 +53,7 @@ export const SessionRetentionSettings: React.FC = () => {
             <TooltipContent sideOffset={8} className="max-w-xs">
               Automatically delete inactive sessions based on their last activity.<br />
               You can also run a one-time cleanup without enabling auto-cleanup.<br />
-              Keeps the most recent 5 sessions, and never deletes shared sessions.
+              Keeps the most recent 5 sessions of each project, and never deletes shared sessions.
             </TooltipContent>
           </Tooltip>
         </div>
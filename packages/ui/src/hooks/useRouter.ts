@@ -1,6 +1,7 @@
 import React from 'react';
 import { useSessionStore } from '@/stores/useSessionStore';
 import { useUIStore } from '@/stores/useUIStore';
+import { getWindowProjectId } from '@/stores/useProjectsStore';
 import { parseRoute, updateBrowserURL, hasRouteParams } from '@/lib/router';
 import type { RouteState, AppRouteState } from '@/lib/router';
 import type { SidebarSection } from '@/constants/sidebar';
@@ -106,6 +107,7 @@ export function useRouter(): void {
       isSettingsOpen: uiState.isSettingsDialogOpen,
       settingsSection: uiState.sidebarSection,
       diffFile: uiState.pendingDiffFile,
+      projectId: getWindowProjectId(),
     };
   }, []);
 
@@ -278,6 +280,11 @@ export function navigateToRoute(route: Partial<RouteState>): void {
   // Build URL and navigate
   const params = new URLSearchParams();
 
+  const projectId = getWindowProjectId();
+  if (projectId) {
+    params.set('projectId', projectId);
+  }
+
   if (route.sessionId) {
     params.set('session', route.sessionId);
   }
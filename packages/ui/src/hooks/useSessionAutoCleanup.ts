@@ -2,8 +2,10 @@ import React from 'react';
 import type { Session } from '@opencode-ai/sdk/v2';
 import { useSessionStore } from '@/stores/useSessionStore';
 import { useUIStore } from '@/stores/useUIStore';
+import { isDesktopRuntime, reportDesktopCurrentSession } from '@/lib/desktop';
 
 const DAY_MS = 24 * 60 * 60 * 1000;
+// Kept per project directory; matches SESSION_CLEANUP_KEEP_RECENT in the desktop backend.
 const AUTO_DELETE_KEEP_RECENT = 5;
 const AUTO_DELETE_INTERVAL_MS = 24 * 60 * 60 * 1000;
 
@@ -34,7 +36,16 @@ export const buildAutoDeleteCandidates = ({
   const sorted = [...sessions].sort(
     (a, b) => getSessionLastActivity(b) - getSessionLastActivity(a)
   );
-  const protectedIds = new Set(sorted.slice(0, keepRecent).map((session) => session.id));
+  const keptPerDirectory = new Map<string, number>();
+  const protectedIds = new Set<string>();
+  for (const session of sorted) {
+    const directory = session.directory ?? '';
+    const kept = keptPerDirectory.get(directory) ?? 0;
+    if (kept < keepRecent) {
+      protectedIds.add(session.id);
+      keptPerDirectory.set(directory, kept + 1);
+    }
+  }
 
   return sorted
     .filter((session) => {
@@ -60,7 +71,8 @@ type CleanupOptions = {
 };
 
 export const useSessionAutoCleanup = (options?: CleanupOptions) => {
-  const autoRun = options?.autoRun !== false;
+  // On desktop the backend runs auto-delete with the same rules; only manual runs happen here.
+  const autoRun = options?.autoRun !== false && !isDesktopRuntime();
 
   const sessions = useSessionStore((state) => state.sessions);
   const currentSessionId = useSessionStore((state) => state.currentSessionId);
@@ -178,3 +190,18 @@ export const useSessionAutoCleanup = (options?: CleanupOptions) => {
     keepRecentCount: AUTO_DELETE_KEEP_RECENT,
   };
 };
+
+/**
+ * Tells the desktop backend which session this window shows, so its auto-delete never
+ * removes it. The backend postpones cleanup until the first report arrives.
+ */
+export const useReportCurrentSession = () => {
+  const currentSessionId = useSessionStore((state) => state.currentSessionId);
+
+  React.useEffect(() => {
+    if (!isDesktopRuntime()) {
+      return;
+    }
+    void reportDesktopCurrentSession(currentSessionId);
+  }, [currentSessionId]);
+};
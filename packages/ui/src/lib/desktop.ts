@@ -21,6 +21,22 @@ export type UpdateProgress = {
   total?: number;
 };
 
+export type ModelTestErrorKind =
+  | 'authFailed'
+  | 'modelNotFound'
+  | 'rateLimited'
+  | 'timeout'
+  | 'unavailable'
+  | 'other';
+
+export type ModelTestResult = {
+  ok: boolean;
+  model: string;
+  variant?: string;
+  latencyMs: number;
+  error?: { kind: ModelTestErrorKind; message: string };
+};
+
 export type DesktopServerInfo = {
   webPort: number | null;
   openCodePort: number | null;
@@ -105,6 +121,8 @@ export type DesktopApi = {
   downloadUpdate?: (onProgress?: (progress: UpdateProgress) => void) => Promise<void>;
   restartToUpdate?: () => Promise<void>;
   openExternal?: (url: string) => Promise<{ success: boolean; error?: string }>;
+  testModel?: (model: string, variant?: string) => Promise<ModelTestResult>;
+  setCurrentSession?: (sessionId: string | null) => Promise<void>;
 };
 
 export const isDesktopRuntime = (): boolean =>
@@ -331,3 +349,37 @@ export const restartToApplyUpdate = async (): Promise<boolean> => {
     return false;
   }
 };
+
+export const reportDesktopCurrentSession = async (sessionId: string | null): Promise<void> => {
+  const api = getDesktopApi();
+  if (!api || !api.setCurrentSession) {
+    return;
+  }
+  try {
+    await api.setCurrentSession(sessionId);
+  } catch (error) {
+    console.warn('Failed to report current session', error);
+  }
+};
+
+export const testDesktopModel = async (
+  model: string,
+  variant?: string
+): Promise<ModelTestResult | null> => {
+  const api = getDesktopApi();
+  if (!api || !api.testModel) {
+    return null;
+  }
+  try {
+    return await api.testModel(model, variant);
+  } catch (error) {
+    console.warn('Failed to test model', error);
+    return {
+      ok: false,
+      model,
+      variant,
+      latencyMs: 0,
+      error: { kind: 'other', message: error instanceof Error ? error.message : String(error) },
+    };
+  }
+};
@@ -11,6 +11,7 @@ export interface AppRouteState {
   isSettingsOpen: boolean;
   settingsSection: SidebarSection;
   diffFile: string | null;
+  projectId: string | null;
 }
 
 /**
@@ -25,6 +26,11 @@ const DEFAULT_TAB: MainTab = 'chat';
 export function serializeRoute(state: AppRouteState): URLSearchParams {
   const params = new URLSearchParams();
 
+  // Project ID - kept so a project window reloads into its own project
+  if (state.projectId && state.projectId.trim().length > 0) {
+    params.set(ROUTE_PARAMS.PROJECT, state.projectId);
+  }
+
   // Session ID - always include if present
   if (state.sessionId && state.sessionId.trim().length > 0) {
     params.set(ROUTE_PARAMS.SESSION, state.sessionId);
@@ -14,6 +14,8 @@ export interface RouteState {
   settingsSection: SidebarSection | null;
   /** File path for diff view */
   diffFile: string | null;
+  /** Project a desktop project window is pinned to */
+  projectId: string | null;
 }
 
 /**
@@ -51,4 +53,5 @@ export const ROUTE_PARAMS = {
   TAB: 'tab',
   SETTINGS: 'settings',
   FILE: 'file',
+  PROJECT: 'projectId',
 } as const;
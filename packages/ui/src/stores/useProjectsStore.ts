@@ -7,6 +7,7 @@ import { updateDesktopSettings } from '@/lib/persistence';
 import { getSafeStorage } from './utils/safeStorage';
 import { useDirectoryStore } from './useDirectoryStore';
 import { streamDebugEnabled } from '@/stores/utils/streamDebug';
+import { parseRoute } from '@/lib/router/parseRoute';
 
 interface ProjectPathValidationResult {
   ok: boolean;
@@ -184,8 +185,10 @@ const cacheProjects = (projects: ProjectEntry[], activeProjectId: string | null)
 };
 
 const persistProjects = (projects: ProjectEntry[], activeProjectId: string | null) => {
-  cacheProjects(projects, activeProjectId);
-  void updateDesktopSettings({ projects, activeProjectId: activeProjectId ?? undefined });
+  // A project window shows its own project; the shared active project belongs to the main window.
+  const sharedActiveProjectId = windowProjectId ? readPersistedActiveProjectId() : activeProjectId;
+  cacheProjects(projects, sharedActiveProjectId);
+  void updateDesktopSettings({ projects, activeProjectId: sharedActiveProjectId ?? undefined });
 };
 
 const initialProjects = readPersistedProjects();
@@ -230,7 +233,17 @@ const getVSCodeWorkspaceProject = (): { projects: ProjectEntry[]; activeProjectI
 // Always prefer the workspace project over any persisted multi-project registry.
 const vscodeWorkspace = getVSCodeWorkspaceProject();
 const effectiveInitialProjects = vscodeWorkspace?.projects ?? initialProjects;
+
+// Desktop project windows are opened with `?projectId=` and stay on that project.
+let windowProjectId: string | null = vscodeWorkspace ? null : parseRoute().projectId;
+
+/**
+ * Project this window is pinned to, or null for the main window.
+ */
+export const getWindowProjectId = (): string | null => windowProjectId;
+
 const initialActiveProjectId = vscodeWorkspace?.activeProjectId
+  ?? windowProjectId
   ?? readPersistedActiveProjectId()
   ?? effectiveInitialProjects[0]?.id
   ?? null;
@@ -343,6 +356,9 @@ export const useProjectsStore = create<ProjectsStore>()(
         project.id === id ? { ...project, lastOpenedAt: now } : project
       );
 
+      if (windowProjectId) {
+        windowProjectId = id;
+      }
       set({ projects: nextProjects, activeProjectId: id });
       persistProjects(nextProjects, id);
 
@@ -368,6 +384,9 @@ export const useProjectsStore = create<ProjectsStore>()(
         project.id === id ? { ...project, lastOpenedAt: now } : project
       );
 
+      if (windowProjectId) {
+        windowProjectId = id;
+      }
       set({ projects: nextProjects, activeProjectId: id });
       persistProjects(nextProjects, id);
     },
@@ -417,9 +436,10 @@ export const useProjectsStore = create<ProjectsStore>()(
         return;
       }
       const incomingProjects = sanitizeProjects(settings.projects ?? []);
-      const incomingActive = typeof settings.activeProjectId === 'string' && settings.activeProjectId.trim()
-        ? settings.activeProjectId.trim()
-        : null;
+      const incomingActive = windowProjectId
+        ?? (typeof settings.activeProjectId === 'string' && settings.activeProjectId.trim()
+          ? settings.activeProjectId.trim()
+          : null);
 
       const current = get();
       const projectsChanged = JSON.stringify(current.projects) !== JSON.stringify(incomingProjects);